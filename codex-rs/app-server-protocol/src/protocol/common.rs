@@ -115,6 +115,12 @@ client_request_definitions! {
         params: v2::ThreadResumeParams,
         response: v2::ThreadResumeResponse,
     },
+    #[serde(rename = "thread/fork")]
+    #[ts(rename = "thread/fork")]
+    ThreadFork {
+        params: v2::ThreadForkParams,
+        response: v2::ThreadForkResponse,
+    },
     #[serde(rename = "thread/archive")]
     #[ts(rename = "thread/archive")]
     ThreadArchive {
@@ -133,6 +139,12 @@ client_request_definitions! {
         params: v2::ThreadCompactParams,
         response: v2::ThreadCompactResponse,
     },
+    #[serde(rename = "history/search")]
+    #[ts(rename = "history/search")]
+    HistorySearch {
+        params: v2::HistorySearchParams,
+        response: v2::HistorySearchResponse,
+    },
     #[serde(rename = "turn/start")]
     #[ts(rename = "turn/start")]
     TurnStart {
@@ -188,6 +200,81 @@ client_request_definitions! {
         response: v2::FeedbackUploadResponse,
     },
 
+    #[serde(rename = "memory/list")]
+    #[ts(rename = "memory/list")]
+    MemoryList {
+        params: v2::MemoryListParams,
+        response: v2::MemoryListResponse,
+    },
+    #[serde(rename = "memory/search")]
+    #[ts(rename = "memory/search")]
+    MemorySearch {
+        params: v2::MemorySearchParams,
+        response: v2::MemorySearchResponse,
+    },
+    #[serde(rename = "memory/create")]
+    #[ts(rename = "memory/create")]
+    MemoryCreate {
+        params: v2::MemoryCreateParams,
+        response: v2::MemoryCreateResponse,
+    },
+    #[serde(rename = "memory/update")]
+    #[ts(rename = "memory/update")]
+    MemoryUpdate {
+        params: v2::MemoryUpdateParams,
+        response: v2::MemoryUpdateResponse,
+    },
+    #[serde(rename = "memory/delete")]
+    #[ts(rename = "memory/delete")]
+    MemoryDelete {
+        params: v2::MemoryDeleteParams,
+        response: v2::MemoryDeleteResponse,
+    },
+
+    #[serde(rename = "index/build")]
+    #[ts(rename = "index/build")]
+    IndexBuild {
+        params: v2::IndexBuildParams,
+        response: v2::IndexBuildResponse,
+    },
+    #[serde(rename = "index/status")]
+    #[ts(rename = "index/status")]
+    IndexStatus {
+        params: v2::IndexStatusParams,
+        response: v2::IndexStatusResponse,
+    },
+    #[serde(rename = "index/query")]
+    #[ts(rename = "index/query")]
+    IndexQuery {
+        params: v2::IndexQueryParams,
+        response: v2::IndexQueryResponse,
+    },
+
+    #[serde(rename = "agent/list")]
+    #[ts(rename = "agent/list")]
+    AgentList {
+        params: v2::AgentListParams,
+        response: v2::AgentListResponse,
+    },
+    #[serde(rename = "agent/get")]
+    #[ts(rename = "agent/get")]
+    AgentGet {
+        params: v2::AgentGetParams,
+        response: v2::AgentGetResponse,
+    },
+    #[serde(rename = "agent/upsert")]
+    #[ts(rename = "agent/upsert")]
+    AgentUpsert {
+        params: v2::AgentUpsertParams,
+        response: v2::AgentUpsertResponse,
+    },
+    #[serde(rename = "agent/run")]
+    #[ts(rename = "agent/run")]
+    AgentRun {
+        params: v2::AgentRunParams,
+        response: v2::AgentRunResponse,
+    },
+
     #[serde(rename = "account/read")]
     #[ts(rename = "account/read")]
     GetAccount {
@@ -533,6 +620,7 @@ server_notification_definitions! {
     McpToolCallProgress => "item/mcpToolCall/progress" (v2::McpToolCallProgressNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
+    IndexProgress => "index/progress" (v2::IndexProgressNotification),
 
     #[serde(rename = "account/login/completed")]
     #[ts(rename = "account/login/completed")]
@@ -818,4 +906,61 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn serialize_memory_search() -> Result<()> {
+        let request = ClientRequest::MemorySearch {
+            request_id: RequestId::Integer(7),
+            params: v2::MemorySearchParams {
+                query: "release checklist".to_string(),
+                limit: Some(3),
+                min_confidence: None,
+            },
+        };
+        assert_eq!(
+            json!({
+                "method": "memory/search",
+                "id": 7,
+                "params": {
+                    "query": "release checklist",
+                    "limit": 3,
+                    "minConfidence": null
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_index_progress_notification() -> Result<()> {
+        let notification = ServerNotification::IndexProgress(v2::IndexProgressNotification {
+            project_root: PathBuf::from("/repo"),
+            event: v2::IndexProgressEvent::Progress {
+                processed_files: 1,
+                total_files: 4,
+                processed_chunks: 3,
+                total_chunks: 12,
+                current_path: "src/lib.rs".to_string(),
+            },
+        });
+        assert_eq!(
+            json!({
+                "method": "index/progress",
+                "params": {
+                    "projectRoot": "/repo",
+                    "event": {
+                        "type": "progress",
+                        "processedFiles": 1,
+                        "totalFiles": 4,
+                        "processedChunks": 3,
+                        "totalChunks": 12,
+                        "currentPath": "src/lib.rs"
+                    }
+                }
+            }),
+            serde_json::to_value(&notification)?,
+        );
+        Ok(())
+    }
 }
@@ -296,6 +296,28 @@ pub struct ThreadResumeResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadForkParams {
+    pub thread_id: String,
+    /// 1-based user turn to branch from. The new thread keeps every earlier
+    /// turn and drops this turn and everything after it.
+    pub turn: u32,
+    /// Reset the working tree to the ghost snapshot captured at the start of
+    /// `turn`, when one was recorded.
+    pub restore_snapshot: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadForkResponse {
+    pub thread: Thread,
+    /// Id of the ghost commit the workspace was restored to, if any.
+    pub restored_snapshot: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -350,6 +372,360 @@ pub struct Thread {
     pub id: String,
 }
 
+// History search APIs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct HistorySearchParams {
+    /// Whitespace-separated terms that must all appear in the same turn.
+    pub query: Option<String>,
+    /// Only turns run in this directory or below it.
+    pub cwd: Option<PathBuf>,
+    /// Substring of the model slug used for the turn.
+    pub model: Option<String>,
+    /// Inclusive lower bound on the turn date (`YYYY-MM-DD`).
+    pub since: Option<String>,
+    /// Inclusive upper bound on the turn date (`YYYY-MM-DD`).
+    pub until: Option<String>,
+    /// Only turns that called this tool.
+    pub tool: Option<String>,
+    /// Optional result cap; defaults to a reasonable server-side value.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct HistorySearchResponse {
+    /// Matching turns, newest first.
+    pub data: Vec<HistorySearchHit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct HistorySearchHit {
+    pub thread_id: String,
+    pub path: PathBuf,
+    /// 1-based index of the matching user turn within the thread.
+    pub turn_index: u32,
+    pub timestamp: String,
+    pub cwd: Option<PathBuf>,
+    pub model: Option<String>,
+    pub tools: Vec<String>,
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum MemorySource {
+    UserMessage,
+    AssistantMessage,
+    ToolOutput,
+    FileDiff,
+    SystemMessage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryRecord {
+    pub id: String,
+    pub summary: String,
+    /// Confidence in the range 0-1.
+    pub confidence: f32,
+    pub tags: Vec<String>,
+    pub source: MemorySource,
+    /// Thread the memory was distilled from, if any.
+    pub thread_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryListParams {
+    /// Only records at or above this confidence (0-1); all records when omitted.
+    pub min_confidence: Option<f32>,
+    /// Optional result cap.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryListResponse {
+    /// Records, most recently updated first.
+    pub data: Vec<MemoryRecord>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemorySearchParams {
+    pub query: String,
+    /// Optional result cap; defaults to a reasonable server-side value.
+    pub limit: Option<u32>,
+    /// Only records at or above this confidence (0-1); defaults to the
+    /// configured memory threshold.
+    pub min_confidence: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemorySearchResponse {
+    /// Matches, best first.
+    pub data: Vec<MemorySearchHit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemorySearchHit {
+    pub score: f32,
+    pub record: MemoryRecord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryCreateParams {
+    pub summary: String,
+    /// Defaults to the configured memory threshold.
+    pub confidence: Option<f32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Defaults to `userMessage`.
+    pub source: Option<MemorySource>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryCreateResponse {
+    pub record: MemoryRecord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryUpdateParams {
+    pub id: String,
+    pub summary: Option<String>,
+    pub confidence: Option<f32>,
+    /// Replaces the existing tags when present.
+    pub tags: Option<Vec<String>>,
+    pub source: Option<MemorySource>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryUpdateResponse {
+    pub record: MemoryRecord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryDeleteParams {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryDeleteResponse {
+    /// False when no record had the given id.
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexBuildParams {
+    /// Project root to index; defaults to the server's working directory.
+    pub cwd: Option<PathBuf>,
+    /// Embedding model override; defaults to the model of the existing index.
+    pub model: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexBuildResponse {
+    pub summary: IndexSummary,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexSummary {
+    pub project_root: PathBuf,
+    pub total_files: u32,
+    pub total_chunks: u32,
+    pub embedding_model: String,
+    pub embedding_dim: u32,
+    pub duration_ms: i64,
+    /// Chunks carried over unchanged from the previous build.
+    pub reused_chunks: u32,
+    pub new_chunks: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexStatusParams {
+    /// Project root; defaults to the server's working directory.
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexStatusResponse {
+    pub project_root: PathBuf,
+    /// False until the index has been built once; the remaining fields are
+    /// empty in that case.
+    pub built: bool,
+    pub embedding_model: Option<String>,
+    pub embedding_dim: Option<u32>,
+    pub total_files: u32,
+    pub total_chunks: u32,
+    pub updated_at: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexQueryParams {
+    pub query: String,
+    /// Project root; defaults to the server's working directory.
+    pub cwd: Option<PathBuf>,
+    /// Optional result cap; defaults to a reasonable server-side value.
+    pub top_k: Option<u32>,
+    /// Drop hits scoring below this (0-1); every hit is returned when omitted.
+    pub min_confidence: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexQueryResponse {
+    /// Matching chunks, best first.
+    pub data: Vec<IndexQueryHit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexQueryHit {
+    /// 1-based rank.
+    pub rank: u32,
+    pub score: f32,
+    /// Path relative to the project root.
+    pub file_path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentProfile {
+    pub name: String,
+    /// Derived from the name when empty on upsert.
+    #[serde(default)]
+    pub slug: String,
+    pub description: Option<String>,
+    pub priming_prompt: Option<String>,
+    #[serde(default)]
+    pub default_command: Vec<String>,
+    #[serde(default)]
+    pub enabled_tools: Vec<String>,
+    /// Approval policy for runs, e.g. `on-request` or `never`.
+    pub approval_mode: Option<String>,
+    /// Sandbox mode for runs, e.g. `read-only` or `workspace-write`.
+    pub sandbox_mode: Option<String>,
+    pub default_flags: Option<HashMap<String, bool>>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub last_run_at: Option<String>,
+    pub last_run_summary: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentListParams {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentListResponse {
+    /// Profiles sorted by name.
+    pub data: Vec<AgentProfile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentGetParams {
+    /// Slug or display name of the agent.
+    pub agent: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentGetResponse {
+    pub agent: AgentProfile,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentUpsertParams {
+    /// Creates a new agent when `slug` is empty, otherwise replaces the
+    /// profile stored under that slug.
+    pub agent: AgentProfile,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentUpsertResponse {
+    pub agent: AgentProfile,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentRunParams {
+    /// Slug or display name of the agent.
+    pub agent: String,
+    pub prompt: String,
+    pub cwd: Option<String>,
+    pub model: Option<String>,
+}
+
+/// The run happens on a new thread whose events stream like any other
+/// thread's; the run record is finalized when its turn ends.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentRunResponse {
+    pub run_id: String,
+    pub thread: Thread,
+    pub turn: Turn,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -644,6 +1020,41 @@ pub struct McpToolCallProgressNotification {
     pub message: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct IndexProgressNotification {
+    pub project_root: PathBuf,
+    pub event: IndexProgressEvent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum IndexProgressEvent {
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Started {
+        total_files: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Progress {
+        processed_files: u32,
+        total_files: u32,
+        processed_chunks: u32,
+        total_chunks: u32,
+        current_path: String,
+    },
+    Completed {
+        summary: IndexSummary,
+    },
+    Error {
+        message: String,
+    },
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -0,0 +1,242 @@
+//! Request handling for the v2 `agent/*` methods, backed by the same
+//! [`AgentStore`] profiles the TUI and `codex exec --agent` use.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::error_code::internal_error;
+use crate::error_code::invalid_request;
+use codex_agentic_core::AgentProfile as CoreAgentProfile;
+use codex_agentic_core::AgentRunContext;
+use codex_agentic_core::AgentStore;
+use codex_app_server_protocol::AgentGetParams;
+use codex_app_server_protocol::AgentGetResponse;
+use codex_app_server_protocol::AgentListResponse;
+use codex_app_server_protocol::AgentProfile;
+use codex_app_server_protocol::AgentUpsertParams;
+use codex_app_server_protocol::AgentUpsertResponse;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_core::config::ConfigOverrides;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::AskForApproval;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+const WEB_SEARCH_TOOL: &str = "web_search_request";
+
+/// Agent runs whose turn has not finished yet, keyed by the thread they run on.
+pub(crate) type PendingAgentRuns = Arc<Mutex<HashMap<ConversationId, PendingAgentRun>>>;
+
+pub(crate) struct PendingAgentRun {
+    pub(crate) store: AgentStore,
+    pub(crate) agent_slug: String,
+    pub(crate) run_id: String,
+}
+
+/// How the turn driving an agent run ended.
+pub(crate) enum AgentRunOutcome {
+    Completed { last_agent_message: Option<String> },
+    Failed,
+    Cancelled,
+}
+
+pub(crate) fn open_store() -> Result<AgentStore, JSONRPCErrorError> {
+    AgentStore::new()
+        .map_err(|err| internal_error(format!("failed to open agents directory: {err:#}")))
+}
+
+pub(crate) fn list_agents(store: &AgentStore) -> Result<AgentListResponse, JSONRPCErrorError> {
+    let profiles = store
+        .list_profiles()
+        .map_err(|err| internal_error(format!("failed to list agents: {err:#}")))?;
+    Ok(AgentListResponse {
+        data: profiles.into_iter().map(profile_to_v2).collect(),
+    })
+}
+
+pub(crate) fn load_agent(
+    store: &AgentStore,
+    selector: &str,
+) -> Result<CoreAgentProfile, JSONRPCErrorError> {
+    store
+        .load_profile_by_selector(selector)
+        .map_err(|err| invalid_request(format!("{err:#}")))
+}
+
+pub(crate) fn get_agent(
+    store: &AgentStore,
+    params: AgentGetParams,
+) -> Result<AgentGetResponse, JSONRPCErrorError> {
+    let profile = load_agent(store, &params.agent)?;
+    Ok(AgentGetResponse {
+        agent: profile_to_v2(profile),
+    })
+}
+
+pub(crate) fn upsert_agent(
+    store: &AgentStore,
+    params: AgentUpsertParams,
+) -> Result<AgentUpsertResponse, JSONRPCErrorError> {
+    let mut profile = profile_from_v2(params.agent);
+    if profile.name.trim().is_empty() {
+        return Err(invalid_request("agent name cannot be empty".to_string()));
+    }
+    // Clients that only edit the persona should not reset the bookkeeping
+    // fields the server maintains.
+    if !profile.slug.is_empty()
+        && let Ok(existing) = store.load_profile(&profile.slug)
+    {
+        profile.created_at = profile.created_at.or(existing.created_at);
+        profile.budget = existing.budget;
+        profile.last_run_at = profile.last_run_at.or(existing.last_run_at);
+        profile.last_run_summary = profile.last_run_summary.or(existing.last_run_summary);
+    }
+    let profile = store
+        .upsert_profile(profile)
+        .map_err(|err| invalid_request(format!("failed to save agent: {err:#}")))?;
+    Ok(AgentUpsertResponse {
+        agent: profile_to_v2(profile),
+    })
+}
+
+/// Config overrides for a thread running `profile`, mirroring the defaults
+/// `codex exec --agent` applies.
+pub(crate) fn run_overrides(
+    profile: &CoreAgentProfile,
+    cwd: Option<String>,
+    model: Option<String>,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> Result<ConfigOverrides, JSONRPCErrorError> {
+    let approval_policy = profile
+        .approval_mode
+        .as_deref()
+        .map(|mode| parse_mode::<AskForApproval>("approval mode", mode))
+        .transpose()?;
+    let sandbox_mode = profile
+        .sandbox_mode
+        .as_deref()
+        .map(|mode| parse_mode::<SandboxMode>("sandbox mode", mode))
+        .transpose()?;
+    let tools_web_search_request = profile
+        .enabled_tools
+        .iter()
+        .any(|tool| tool == WEB_SEARCH_TOOL)
+        .then_some(true);
+    Ok(ConfigOverrides {
+        model,
+        cwd: cwd.map(PathBuf::from),
+        approval_policy,
+        sandbox_mode,
+        codex_linux_sandbox_exe,
+        tools_web_search_request,
+        ..Default::default()
+    })
+}
+
+/// The first user message of a run: the agent's priming prompt, if any,
+/// followed by the caller's prompt.
+pub(crate) fn primed_prompt(profile: &CoreAgentProfile, prompt: &str) -> String {
+    let priming = profile.priming_prompt().trim();
+    if priming.is_empty() {
+        prompt.to_string()
+    } else {
+        format!("{priming}\n\n{prompt}")
+    }
+}
+
+pub(crate) fn begin_run(
+    store: &AgentStore,
+    profile: &CoreAgentProfile,
+    prompt: &str,
+) -> Result<String, JSONRPCErrorError> {
+    let run_id = uuid::Uuid::now_v7().to_string();
+    let context = AgentRunContext::from(profile);
+    store
+        .begin_run(
+            &profile.slug,
+            &run_id,
+            Some(prompt.to_string()),
+            Some(context),
+        )
+        .map_err(|err| internal_error(format!("failed to register agent run: {err:#}")))?;
+    Ok(run_id)
+}
+
+/// Record the outcome of the agent run on `conversation_id`, if there is one.
+pub(crate) async fn finish_agent_run(
+    pending_runs: &PendingAgentRuns,
+    conversation_id: ConversationId,
+    outcome: AgentRunOutcome,
+) {
+    let Some(run) = pending_runs.lock().await.remove(&conversation_id) else {
+        return;
+    };
+    let PendingAgentRun {
+        store,
+        agent_slug,
+        run_id,
+    } = run;
+    let result = match outcome {
+        AgentRunOutcome::Completed { last_agent_message } => store
+            .complete_run(&agent_slug, &run_id, None, false)
+            .and_then(|_| match last_agent_message {
+                Some(message) => store
+                    .annotate_run_summary(&agent_slug, &run_id, Some(message.clone()))
+                    .and_then(|_| store.update_profile_summary(&agent_slug, Some(message)))
+                    .map(|_| ()),
+                None => Ok(()),
+            }),
+        AgentRunOutcome::Failed => store
+            .complete_run(&agent_slug, &run_id, None, true)
+            .map(|_| ()),
+        AgentRunOutcome::Cancelled => store.cancel_run(&agent_slug, &run_id).map(|_| ()),
+    };
+    if let Err(err) = result {
+        warn!("failed to finalize agent run {run_id}: {err:#}");
+    }
+}
+
+fn parse_mode<T: DeserializeOwned>(kind: &str, value: &str) -> Result<T, JSONRPCErrorError> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| invalid_request(format!("agent has an unknown {kind} '{value}'")))
+}
+
+fn profile_to_v2(profile: CoreAgentProfile) -> AgentProfile {
+    AgentProfile {
+        name: profile.name,
+        slug: profile.slug,
+        description: profile.description,
+        priming_prompt: profile.priming_prompt,
+        default_command: profile.default_command,
+        enabled_tools: profile.enabled_tools,
+        approval_mode: profile.approval_mode,
+        sandbox_mode: profile.sandbox_mode,
+        default_flags: profile.default_flags,
+        created_at: profile.created_at,
+        updated_at: profile.updated_at,
+        last_run_at: profile.last_run_at,
+        last_run_summary: profile.last_run_summary,
+    }
+}
+
+fn profile_from_v2(profile: AgentProfile) -> CoreAgentProfile {
+    CoreAgentProfile {
+        name: profile.name,
+        slug: profile.slug,
+        description: profile.description,
+        priming_prompt: profile.priming_prompt,
+        default_command: profile.default_command,
+        enabled_tools: profile.enabled_tools,
+        approval_mode: profile.approval_mode,
+        sandbox_mode: profile.sandbox_mode,
+        default_flags: profile.default_flags,
+        created_at: profile.created_at,
+        updated_at: profile.updated_at,
+        last_run_at: profile.last_run_at,
+        last_run_summary: profile.last_run_summary,
+        budget: None,
+    }
+}
@@ -1,14 +1,26 @@
+use crate::agents;
+use crate::agents::AgentRunOutcome;
+use crate::agents::PendingAgentRun;
+use crate::agents::PendingAgentRuns;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+use crate::error_code::internal_error;
+use crate::error_code::invalid_request;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
+use crate::memory;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
+use crate::semantic_index;
 use codex_app_server_protocol::AccountLoginCompletedNotification;
 use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
 use codex_app_server_protocol::AccountUpdatedNotification;
 use codex_app_server_protocol::AddConversationListenerParams;
 use codex_app_server_protocol::AddConversationSubscriptionResponse;
+use codex_app_server_protocol::AgentGetParams;
+use codex_app_server_protocol::AgentRunParams;
+use codex_app_server_protocol::AgentRunResponse;
+use codex_app_server_protocol::AgentUpsertParams;
 use codex_app_server_protocol::ApplyPatchApprovalParams;
 use codex_app_server_protocol::ApplyPatchApprovalResponse;
 use codex_app_server_protocol::ArchiveConversationParams;
@@ -38,6 +50,13 @@ use codex_app_server_protocol::GetConversationSummaryResponse;
 use codex_app_server_protocol::GetUserAgentResponse;
 use codex_app_server_protocol::GetUserSavedConfigResponse;
 use codex_app_server_protocol::GitDiffToRemoteResponse;
+use codex_app_server_protocol::HistorySearchHit;
+use codex_app_server_protocol::HistorySearchParams;
+use codex_app_server_protocol::HistorySearchResponse;
+use codex_app_server_protocol::IndexBuildParams;
+use codex_app_server_protocol::IndexProgressNotification;
+use codex_app_server_protocol::IndexQueryParams;
+use codex_app_server_protocol::IndexStatusParams;
 use codex_app_server_protocol::InputItem as WireInputItem;
 use codex_app_server_protocol::InterruptConversationParams;
 use codex_app_server_protocol::InterruptConversationResponse;
@@ -51,6 +70,11 @@ use codex_app_server_protocol::LoginChatGptCompleteNotification;
 use codex_app_server_protocol::LoginChatGptResponse;
 use codex_app_server_protocol::LogoutAccountResponse;
 use codex_app_server_protocol::LogoutChatGptResponse;
+use codex_app_server_protocol::MemoryCreateParams;
+use codex_app_server_protocol::MemoryDeleteParams;
+use codex_app_server_protocol::MemoryListParams;
+use codex_app_server_protocol::MemorySearchParams;
+use codex_app_server_protocol::MemoryUpdateParams;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
 use codex_app_server_protocol::NewConversationParams;
@@ -74,6 +98,8 @@ use codex_app_server_protocol::SetDefaultModelResponse;
 use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
+use codex_app_server_protocol::ThreadForkParams;
+use codex_app_server_protocol::ThreadForkResponse;
 use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadListResponse;
@@ -97,6 +123,7 @@ use codex_core::AuthManager;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::Cursor as RolloutCursor;
+use codex_core::HistorySearchQuery;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::InitialHistory;
 use codex_core::NewConversation;
@@ -110,11 +137,13 @@ use codex_core::config::ConfigToml;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config_loader::load_config_as_toml;
 use codex_core::default_client::get_codex_user_agent;
+use codex_core::error::CodexErr;
 use codex_core::exec::ExecParams;
 use codex_core::exec_env::create_env;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
 use codex_core::git_info::git_diff_to_remote;
+use codex_core::memory::MemoryRuntime;
 use codex_core::parse_cursor;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::Event;
@@ -123,6 +152,7 @@ use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_core::read_head_for_summary;
+use codex_core::search_history;
 use codex_feedback::CodexFeedback;
 use codex_login::ServerOptions as LoginServerOptions;
 use codex_login::ShutdownHandle;
@@ -149,6 +179,8 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::error;
 use tracing::info;
@@ -183,6 +215,10 @@ pub(crate) struct CodexMessageProcessor {
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    // Agent runs started via `agent/run`, finalized when their turn ends.
+    pending_agent_runs: PendingAgentRuns,
+    // Loaded on the first `memory/*` request.
+    memory_runtime: OnceCell<MemoryRuntime>,
     feedback: CodexFeedback,
 }
 
@@ -211,6 +247,8 @@ impl CodexMessageProcessor {
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
+            pending_agent_runs: Arc::new(Mutex::new(HashMap::new())),
+            memory_runtime: OnceCell::new(),
             feedback,
         }
     }
@@ -227,12 +265,18 @@ impl CodexMessageProcessor {
             ClientRequest::ThreadResume { request_id, params } => {
                 self.thread_resume(request_id, params).await;
             }
+            ClientRequest::ThreadFork { request_id, params } => {
+                self.thread_fork(request_id, params).await;
+            }
             ClientRequest::ThreadArchive { request_id, params } => {
                 self.thread_archive(request_id, params).await;
             }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(request_id, params).await;
             }
+            ClientRequest::HistorySearch { request_id, params } => {
+                self.history_search(request_id, params).await;
+            }
             ClientRequest::ThreadCompact {
                 request_id,
                 params: _,
@@ -361,6 +405,46 @@ impl CodexMessageProcessor {
             ClientRequest::FeedbackUpload { request_id, params } => {
                 self.upload_feedback(request_id, params).await;
             }
+            ClientRequest::MemoryList { request_id, params } => {
+                self.memory_list(request_id, params).await;
+            }
+            ClientRequest::MemorySearch { request_id, params } => {
+                self.memory_search(request_id, params).await;
+            }
+            ClientRequest::MemoryCreate { request_id, params } => {
+                self.memory_create(request_id, params).await;
+            }
+            ClientRequest::MemoryUpdate { request_id, params } => {
+                self.memory_update(request_id, params).await;
+            }
+            ClientRequest::MemoryDelete { request_id, params } => {
+                self.memory_delete(request_id, params).await;
+            }
+            ClientRequest::IndexBuild { request_id, params } => {
+                self.index_build(request_id, params);
+            }
+            ClientRequest::IndexStatus { request_id, params } => {
+                self.index_status(request_id, params);
+            }
+            ClientRequest::IndexQuery { request_id, params } => {
+                self.index_query(request_id, params);
+            }
+            ClientRequest::AgentList {
+                request_id,
+                params: _,
+            } => {
+                let result = agents::open_store().and_then(|store| agents::list_agents(&store));
+                self.outgoing.send_result(request_id, result).await;
+            }
+            ClientRequest::AgentGet { request_id, params } => {
+                self.agent_get(request_id, params).await;
+            }
+            ClientRequest::AgentUpsert { request_id, params } => {
+                self.agent_upsert(request_id, params).await;
+            }
+            ClientRequest::AgentRun { request_id, params } => {
+                self.agent_run(request_id, params).await;
+            }
         }
     }
 
@@ -1186,6 +1270,113 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_fork(&mut self, request_id: RequestId, params: ThreadForkParams) {
+        let ThreadForkParams {
+            thread_id,
+            turn,
+            restore_snapshot,
+        } = params;
+        let conversation_id = match ConversationId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("invalid thread id: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let path = match find_conversation_path_by_id_str(
+            &self.config.codex_home,
+            &conversation_id.to_string(),
+        )
+        .await
+        {
+            Ok(Some(p)) => p,
+            Ok(None) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("no rollout found for conversation id {conversation_id}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("failed to locate conversation id {conversation_id}: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match self
+            .conversation_manager
+            .fork_conversation_at_turn(
+                turn as usize,
+                self.config.as_ref().clone(),
+                path,
+                restore_snapshot.unwrap_or(false),
+            )
+            .await
+        {
+            Ok(forked) => {
+                let new_conversation_id = forked.new_conversation.conversation_id;
+                let thread = Thread {
+                    id: new_conversation_id.to_string(),
+                };
+
+                // Auto-attach a conversation listener, as for thread/start.
+                if let Err(err) = self
+                    .attach_conversation_listener(new_conversation_id, false)
+                    .await
+                {
+                    tracing::warn!(
+                        "failed to attach listener for conversation {}: {}",
+                        new_conversation_id,
+                        err.message
+                    );
+                }
+
+                let response = ThreadForkResponse {
+                    thread: thread.clone(),
+                    restored_snapshot: forked.restored_snapshot,
+                };
+                self.outgoing.send_response(request_id, response).await;
+
+                let notif = ThreadStartedNotification { thread };
+                self.outgoing
+                    .send_server_notification(ServerNotification::ThreadStarted(notif))
+                    .await;
+            }
+            Err(
+                err
+                @ (CodexErr::ForkTurnOutOfRange { .. } | CodexErr::ForkWorkspaceMismatch { .. }),
+            ) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: err.to_string(),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("error forking thread: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     async fn thread_archive(&mut self, request_id: RequestId, params: ThreadArchiveParams) {
         let conversation_id = match ConversationId::from_string(&params.thread_id) {
             Ok(id) => id,
@@ -1272,6 +1463,329 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn history_search(&self, request_id: RequestId, params: HistorySearchParams) {
+        let HistorySearchParams {
+            query,
+            cwd,
+            model,
+            since,
+            until,
+            tool,
+            limit,
+        } = params;
+        if let Err(message) = validate_history_dates(since.as_deref(), until.as_deref()) {
+            self.send_invalid_request_error(request_id, message).await;
+            return;
+        }
+
+        let query = HistorySearchQuery {
+            text: query,
+            cwd,
+            model,
+            since,
+            until,
+            tool,
+            limit: limit.map(|limit| limit.max(1) as usize),
+        };
+
+        match search_history(&self.config.codex_home, &query).await {
+            Ok(hits) => {
+                let data = hits
+                    .into_iter()
+                    .map(|hit| HistorySearchHit {
+                        thread_id: hit.conversation_id.to_string(),
+                        path: hit.path,
+                        turn_index: hit.turn_index as u32,
+                        timestamp: hit.timestamp,
+                        cwd: hit.cwd,
+                        model: hit.model,
+                        tools: hit.tools,
+                        snippet: hit.snippet,
+                    })
+                    .collect();
+                let response = HistorySearchResponse { data };
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to search history: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn memory_runtime(&self) -> Result<&MemoryRuntime, JSONRPCErrorError> {
+        let memory_root = self.config.codex_home.join("memory");
+        self.memory_runtime
+            .get_or_try_init(|| MemoryRuntime::load(memory_root))
+            .await
+            .map_err(|err| internal_error(format!("failed to load memory store: {err:#}")))
+    }
+
+    async fn memory_list(&self, request_id: RequestId, params: MemoryListParams) {
+        let result = async {
+            let runtime = self.memory_runtime().await?;
+            memory::list_memories(runtime, params).await
+        }
+        .await;
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    async fn memory_search(&self, request_id: RequestId, params: MemorySearchParams) {
+        let result = async {
+            let runtime = self.memory_runtime().await?;
+            memory::search_memories(runtime, params).await
+        }
+        .await;
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    async fn memory_create(&self, request_id: RequestId, params: MemoryCreateParams) {
+        let result = async {
+            let runtime = self.memory_runtime().await?;
+            memory::create_memory(runtime, params).await
+        }
+        .await;
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    async fn memory_update(&self, request_id: RequestId, params: MemoryUpdateParams) {
+        let result = async {
+            let runtime = self.memory_runtime().await?;
+            memory::update_memory(runtime, params).await
+        }
+        .await;
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    async fn memory_delete(&self, request_id: RequestId, params: MemoryDeleteParams) {
+        let result = async {
+            let runtime = self.memory_runtime().await?;
+            memory::delete_memory(runtime, params).await
+        }
+        .await;
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    /// Builds run on a blocking thread so the processor keeps serving other
+    /// requests; progress is streamed as `index/progress` notifications and
+    /// the response is sent once the build finishes.
+    fn index_build(&self, request_id: RequestId, params: IndexBuildParams) {
+        let IndexBuildParams { cwd, model } = params;
+        let project_root = cwd.unwrap_or_else(|| self.config.cwd.clone());
+        let outgoing = self.outgoing.clone();
+        tokio::spawn(async move {
+            let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+            let build_root = project_root.clone();
+            let build = tokio::task::spawn_blocking(move || {
+                semantic_index::build_index(build_root, model, |event| {
+                    let _ = event_tx.send(event);
+                })
+            });
+            while let Some(event) = event_rx.recv().await {
+                let notification = IndexProgressNotification {
+                    project_root: project_root.clone(),
+                    event,
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::IndexProgress(notification))
+                    .await;
+            }
+            let result = build.await.unwrap_or_else(|err| {
+                Err(internal_error(format!("index build task failed: {err}")))
+            });
+            outgoing.send_result(request_id, result).await;
+        });
+    }
+
+    fn index_status(&self, request_id: RequestId, params: IndexStatusParams) {
+        let project_root = params.cwd.unwrap_or_else(|| self.config.cwd.clone());
+        let outgoing = self.outgoing.clone();
+        tokio::spawn(async move {
+            let result =
+                tokio::task::spawn_blocking(move || semantic_index::index_status(project_root))
+                    .await
+                    .unwrap_or_else(|err| {
+                        Err(internal_error(format!("index status task failed: {err}")))
+                    });
+            outgoing.send_result(request_id, result).await;
+        });
+    }
+
+    fn index_query(&self, request_id: RequestId, params: IndexQueryParams) {
+        let project_root = params
+            .cwd
+            .clone()
+            .unwrap_or_else(|| self.config.cwd.clone());
+        let outgoing = self.outgoing.clone();
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                semantic_index::query_index(project_root, params)
+            })
+            .await
+            .unwrap_or_else(|err| Err(internal_error(format!("index query task failed: {err}"))));
+            outgoing.send_result(request_id, result).await;
+        });
+    }
+
+    async fn agent_get(&self, request_id: RequestId, params: AgentGetParams) {
+        let result = agents::open_store().and_then(|store| agents::get_agent(&store, params));
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    async fn agent_upsert(&self, request_id: RequestId, params: AgentUpsertParams) {
+        let result = agents::open_store().and_then(|store| agents::upsert_agent(&store, params));
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    /// Runs an agent on a new thread: the thread is configured from the
+    /// agent profile, its first turn is the primed prompt, and the run record
+    /// is finalized when that turn ends.
+    async fn agent_run(&mut self, request_id: RequestId, params: AgentRunParams) {
+        let AgentRunParams {
+            agent,
+            prompt,
+            cwd,
+            model,
+        } = params;
+        if prompt.trim().is_empty() {
+            self.outgoing
+                .send_error(
+                    request_id,
+                    invalid_request("agent prompt cannot be empty".to_string()),
+                )
+                .await;
+            return;
+        }
+        let codex_linux_sandbox_exe = self.codex_linux_sandbox_exe.clone();
+        let prepared = agents::open_store().and_then(|store| {
+            let profile = agents::load_agent(&store, &agent)?;
+            let overrides = agents::run_overrides(&profile, cwd, model, codex_linux_sandbox_exe)?;
+            Ok((store, profile, overrides))
+        });
+        let (store, profile, overrides) = match prepared {
+            Ok(prepared) => prepared,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let mut config = match derive_config_from_params(overrides, None).await {
+            Ok(config) => config,
+            Err(err) => {
+                self.outgoing
+                    .send_error(
+                        request_id,
+                        invalid_request(format!("error deriving config: {err}")),
+                    )
+                    .await;
+                return;
+            }
+        };
+        if let Some(budget) = profile.budget.clone() {
+            config.budget = budget;
+        }
+
+        let run_id = match agents::begin_run(&store, &profile, &prompt) {
+            Ok(run_id) => run_id,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let new_conv = match self.conversation_manager.new_conversation(config).await {
+            Ok(new_conv) => new_conv,
+            Err(err) => {
+                if let Err(err) = store.complete_run(&profile.slug, &run_id, None, true) {
+                    warn!("failed to finalize agent run {run_id}: {err:#}");
+                }
+                self.outgoing
+                    .send_error(
+                        request_id,
+                        internal_error(format!("error creating thread: {err}")),
+                    )
+                    .await;
+                return;
+            }
+        };
+        let conversation_id = new_conv.conversation_id;
+        self.pending_agent_runs.lock().await.insert(
+            conversation_id,
+            PendingAgentRun {
+                store,
+                agent_slug: profile.slug.clone(),
+                run_id: run_id.clone(),
+            },
+        );
+        if let Err(err) = self
+            .attach_conversation_listener(conversation_id, false)
+            .await
+        {
+            warn!(
+                "failed to attach listener for conversation {conversation_id}: {}",
+                err.message
+            );
+        }
+
+        let text = agents::primed_prompt(&profile, &prompt);
+        let turn_id = match new_conv
+            .conversation
+            .submit(Op::UserInput {
+                items: vec![CoreInputItem::Text { text: text.clone() }],
+            })
+            .await
+        {
+            Ok(turn_id) => turn_id,
+            Err(err) => {
+                agents::finish_agent_run(
+                    &self.pending_agent_runs,
+                    conversation_id,
+                    AgentRunOutcome::Failed,
+                )
+                .await;
+                self.outgoing
+                    .send_error(
+                        request_id,
+                        internal_error(format!("failed to start turn: {err}")),
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        let thread = Thread {
+            id: conversation_id.to_string(),
+        };
+        let turn = Turn {
+            id: turn_id.clone(),
+            items: vec![ThreadItem::UserMessage {
+                id: turn_id,
+                content: vec![V2UserInput::Text { text }],
+            }],
+            status: TurnStatus::InProgress,
+            error: None,
+        };
+        let response = AgentRunResponse {
+            run_id,
+            thread: thread.clone(),
+            turn: turn.clone(),
+        };
+        self.outgoing.send_response(request_id, response).await;
+        self.outgoing
+            .send_server_notification(ServerNotification::ThreadStarted(
+                ThreadStartedNotification { thread },
+            ))
+            .await;
+        self.outgoing
+            .send_server_notification(ServerNotification::TurnStarted(TurnStartedNotification {
+                turn,
+            }))
+            .await;
+    }
+
     async fn thread_resume(&mut self, request_id: RequestId, params: ThreadResumeParams) {
         let conversation_id = match ConversationId::from_string(&params.thread_id) {
             Ok(id) => id,
@@ -2287,6 +2801,7 @@ impl CodexMessageProcessor {
 
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
+        let pending_agent_runs = self.pending_agent_runs.clone();
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -2342,6 +2857,7 @@ impl CodexMessageProcessor {
                             conversation.clone(),
                             outgoing_for_task.clone(),
                             pending_interrupts.clone(),
+                            pending_agent_runs.clone(),
                         )
                         .await;
                     }
@@ -2490,6 +3006,7 @@ async fn apply_bespoke_event_handling(
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: PendingInterrupts,
+    pending_agent_runs: PendingAgentRuns,
 ) {
     let Event { id: event_id, msg } = event;
     match msg {
@@ -2551,8 +3068,32 @@ async fn apply_bespoke_event_handling(
                     .await;
             }
         }
+        EventMsg::TaskComplete(task_complete_event) => {
+            agents::finish_agent_run(
+                &pending_agent_runs,
+                conversation_id,
+                AgentRunOutcome::Completed {
+                    last_agent_message: task_complete_event.last_agent_message,
+                },
+            )
+            .await;
+        }
+        EventMsg::Error(_) => {
+            agents::finish_agent_run(
+                &pending_agent_runs,
+                conversation_id,
+                AgentRunOutcome::Failed,
+            )
+            .await;
+        }
         // If this is a TurnAborted, reply to any pending interrupt requests.
         EventMsg::TurnAborted(turn_aborted_event) => {
+            agents::finish_agent_run(
+                &pending_agent_runs,
+                conversation_id,
+                AgentRunOutcome::Cancelled,
+            )
+            .await;
             let pending = {
                 let mut map = pending_interrupts.lock().await;
                 map.remove(&conversation_id).unwrap_or_default()
@@ -2782,6 +3323,24 @@ fn map_git_info(git_info: &GitInfo) -> ConversationGitInfo {
     }
 }
 
+/// Check the `since`/`until` bounds of a history search: both must be
+/// `YYYY-MM-DD` dates and the range must not be empty.
+fn validate_history_dates(since: Option<&str>, until: Option<&str>) -> Result<(), String> {
+    let parse = |date: Option<&str>| {
+        date.map(|date| {
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("invalid date `{date}`; expected YYYY-MM-DD"))
+        })
+        .transpose()
+    };
+    if let (Some(since), Some(until)) = (parse(since)?, parse(until)?)
+        && since > until
+    {
+        return Err(format!("`since` ({since}) is after `until` ({until})"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,12 +28,15 @@ use tracing_subscriber::filter::Targets;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
+mod agents;
 mod codex_message_processor;
 mod error_code;
 mod fuzzy_file_search;
+mod memory;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod semantic_index;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
@@ -0,0 +1,192 @@
+//! Request handling for the v2 `memory/*` methods, backed by the global
+//! memory store under `$CODEX_HOME/memory`.
+
+use crate::error_code::internal_error;
+use crate::error_code::invalid_request;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::MemoryCreateParams;
+use codex_app_server_protocol::MemoryCreateResponse;
+use codex_app_server_protocol::MemoryDeleteParams;
+use codex_app_server_protocol::MemoryDeleteResponse;
+use codex_app_server_protocol::MemoryListParams;
+use codex_app_server_protocol::MemoryListResponse;
+use codex_app_server_protocol::MemoryRecord;
+use codex_app_server_protocol::MemorySearchHit;
+use codex_app_server_protocol::MemorySearchParams;
+use codex_app_server_protocol::MemorySearchResponse;
+use codex_app_server_protocol::MemorySource;
+use codex_app_server_protocol::MemoryUpdateParams;
+use codex_app_server_protocol::MemoryUpdateResponse;
+use codex_core::memory::MemoryMetadata;
+use codex_core::memory::MemoryRecord as CoreMemoryRecord;
+use codex_core::memory::MemoryRecordUpdate;
+use codex_core::memory::MemoryRuntime;
+use codex_core::memory::MemorySource as CoreMemorySource;
+use uuid::Uuid;
+
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+pub(crate) async fn list_memories(
+    runtime: &MemoryRuntime,
+    params: MemoryListParams,
+) -> Result<MemoryListResponse, JSONRPCErrorError> {
+    let mut records = runtime
+        .list_records()
+        .await
+        .map_err(|err| internal_error(format!("failed to list memories: {err:#}")))?;
+    records.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    if let Some(min_confidence) = params.min_confidence {
+        let threshold = min_confidence.clamp(0.0, 1.0);
+        records.retain(|record| record.confidence >= threshold);
+    }
+    if let Some(limit) = params.limit {
+        records.truncate(limit as usize);
+    }
+    Ok(MemoryListResponse {
+        data: records.iter().map(record_to_v2).collect(),
+    })
+}
+
+pub(crate) async fn search_memories(
+    runtime: &MemoryRuntime,
+    params: MemorySearchParams,
+) -> Result<MemorySearchResponse, JSONRPCErrorError> {
+    let threshold = match params.min_confidence {
+        Some(min_confidence) => min_confidence.clamp(0.0, 1.0),
+        None => runtime.settings.get().await.min_confidence,
+    };
+    let limit = params
+        .limit
+        .map(|limit| limit.max(1) as usize)
+        .unwrap_or(DEFAULT_SEARCH_LIMIT);
+    let hits = runtime
+        .search_records(&params.query, limit, Some(threshold))
+        .await
+        .map_err(|err| internal_error(format!("memory search failed: {err:#}")))?;
+    Ok(MemorySearchResponse {
+        data: hits
+            .iter()
+            .map(|hit| MemorySearchHit {
+                score: hit.score,
+                record: record_to_v2(&hit.record),
+            })
+            .collect(),
+    })
+}
+
+pub(crate) async fn create_memory(
+    runtime: &MemoryRuntime,
+    params: MemoryCreateParams,
+) -> Result<MemoryCreateResponse, JSONRPCErrorError> {
+    let summary = params.summary.trim();
+    if summary.is_empty() {
+        return Err(invalid_request("summary cannot be empty".to_string()));
+    }
+    let confidence = match params.confidence {
+        Some(confidence) => confidence,
+        None => runtime.settings.get().await.min_confidence,
+    };
+    let metadata = MemoryMetadata {
+        tags: params.tags,
+        ..Default::default()
+    };
+    let source = params
+        .source
+        .map(source_to_core)
+        .unwrap_or(CoreMemorySource::UserMessage);
+    let record = runtime
+        .create_record(summary.to_string(), metadata, confidence, source)
+        .await
+        .map_err(|err| internal_error(format!("failed to create memory: {err:#}")))?;
+    Ok(MemoryCreateResponse {
+        record: record_to_v2(&record),
+    })
+}
+
+pub(crate) async fn update_memory(
+    runtime: &MemoryRuntime,
+    params: MemoryUpdateParams,
+) -> Result<MemoryUpdateResponse, JSONRPCErrorError> {
+    let id = parse_record_id(&params.id)?;
+    let current = runtime
+        .fetch_records(&[id])
+        .await
+        .map_err(|err| internal_error(format!("failed to fetch memory: {err:#}")))?
+        .pop()
+        .ok_or_else(|| invalid_request(format!("memory {id} not found")))?;
+
+    let mut update = MemoryRecordUpdate::default();
+    if let Some(summary) = params.summary {
+        let summary = summary.trim();
+        if summary.is_empty() {
+            return Err(invalid_request("summary cannot be empty".to_string()));
+        }
+        update.summary = Some(summary.to_string());
+    }
+    update.confidence = params.confidence;
+    if let Some(tags) = params.tags {
+        let mut metadata = current.metadata;
+        metadata.tags = tags;
+        update.metadata = Some(metadata);
+    }
+    update.source = params.source.map(source_to_core);
+
+    let record = runtime
+        .update_record(id, update)
+        .await
+        .map_err(|err| internal_error(format!("failed to update memory: {err:#}")))?;
+    Ok(MemoryUpdateResponse {
+        record: record_to_v2(&record),
+    })
+}
+
+pub(crate) async fn delete_memory(
+    runtime: &MemoryRuntime,
+    params: MemoryDeleteParams,
+) -> Result<MemoryDeleteResponse, JSONRPCErrorError> {
+    let id = parse_record_id(&params.id)?;
+    let deleted = runtime
+        .delete_record(id)
+        .await
+        .map_err(|err| internal_error(format!("failed to delete memory: {err:#}")))?;
+    Ok(MemoryDeleteResponse {
+        deleted: deleted.is_some(),
+    })
+}
+
+fn record_to_v2(record: &CoreMemoryRecord) -> MemoryRecord {
+    MemoryRecord {
+        id: record.record_id.to_string(),
+        summary: record.summary.clone(),
+        confidence: record.confidence,
+        tags: record.metadata.tags.clone(),
+        source: source_from_core(&record.source),
+        thread_id: record.metadata.conversation_id.clone(),
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
+
+fn source_from_core(source: &CoreMemorySource) -> MemorySource {
+    match source {
+        CoreMemorySource::UserMessage => MemorySource::UserMessage,
+        CoreMemorySource::AssistantMessage => MemorySource::AssistantMessage,
+        CoreMemorySource::ToolOutput => MemorySource::ToolOutput,
+        CoreMemorySource::FileDiff => MemorySource::FileDiff,
+        CoreMemorySource::SystemMessage => MemorySource::SystemMessage,
+    }
+}
+
+fn source_to_core(source: MemorySource) -> CoreMemorySource {
+    match source {
+        MemorySource::UserMessage => CoreMemorySource::UserMessage,
+        MemorySource::AssistantMessage => CoreMemorySource::AssistantMessage,
+        MemorySource::ToolOutput => CoreMemorySource::ToolOutput,
+        MemorySource::FileDiff => CoreMemorySource::FileDiff,
+        MemorySource::SystemMessage => CoreMemorySource::SystemMessage,
+    }
+}
+
+fn parse_record_id(id: &str) -> Result<Uuid, JSONRPCErrorError> {
+    Uuid::parse_str(id).map_err(|err| invalid_request(format!("invalid memory id '{id}': {err}")))
+}
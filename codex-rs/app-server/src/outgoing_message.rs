@@ -88,6 +88,18 @@ impl OutgoingMessageSender {
         }
     }
 
+    /// Reply with `result`, as a response on success or an error otherwise.
+    pub(crate) async fn send_result<T: Serialize>(
+        &self,
+        id: RequestId,
+        result: Result<T, JSONRPCErrorError>,
+    ) {
+        match result {
+            Ok(response) => self.send_response(id, response).await,
+            Err(error) => self.send_error(id, error).await,
+        }
+    }
+
     pub(crate) async fn send_server_notification(&self, notification: ServerNotification) {
         let _ = self
             .sender
@@ -0,0 +1,157 @@
+//! Request handling for the v2 `index/*` methods, backed by the per-project
+//! semantic index under `<project>/.codex/index`. Every function here blocks
+//! on disk and embedding work and must run on a blocking thread.
+
+use std::path::PathBuf;
+
+use crate::error_code::internal_error;
+use crate::error_code::invalid_request;
+use codex_agentic_core::BuildOptions;
+use codex_agentic_core::IndexEvent;
+use codex_agentic_core::IndexSummary as CoreIndexSummary;
+use codex_agentic_core::index::analytics::load_analytics;
+use codex_agentic_core::index::analytics::load_manifest;
+use codex_agentic_core::index::build_with_progress;
+use codex_agentic_core::index::paths::IndexPaths;
+use codex_agentic_core::index::query_index as core_query_index;
+use codex_app_server_protocol::IndexBuildResponse;
+use codex_app_server_protocol::IndexProgressEvent;
+use codex_app_server_protocol::IndexQueryHit;
+use codex_app_server_protocol::IndexQueryParams;
+use codex_app_server_protocol::IndexQueryResponse;
+use codex_app_server_protocol::IndexStatusResponse;
+use codex_app_server_protocol::IndexSummary;
+use codex_app_server_protocol::JSONRPCErrorError;
+
+const DEFAULT_QUERY_TOP_K: usize = 8;
+
+pub(crate) fn build_index(
+    project_root: PathBuf,
+    model: Option<String>,
+    mut on_event: impl FnMut(IndexProgressEvent),
+) -> Result<IndexBuildResponse, JSONRPCErrorError> {
+    let options = BuildOptions {
+        project_root,
+        requested_model: model,
+        ..Default::default()
+    };
+    let summary = build_with_progress(options, |event| on_event(event_to_v2(event)))
+        .map_err(|err| internal_error(format!("failed to build index: {err:#}")))?;
+    Ok(IndexBuildResponse {
+        summary: summary_to_v2(summary),
+    })
+}
+
+pub(crate) fn index_status(
+    project_root: PathBuf,
+) -> Result<IndexStatusResponse, JSONRPCErrorError> {
+    let paths = IndexPaths::from_root(project_root.clone());
+    if !paths.manifest_path.exists() {
+        return Ok(IndexStatusResponse {
+            project_root,
+            built: false,
+            embedding_model: None,
+            embedding_dim: None,
+            total_files: 0,
+            total_chunks: 0,
+            updated_at: None,
+            last_success_at: None,
+            last_error: None,
+        });
+    }
+    let manifest = load_manifest(&paths.manifest_path)
+        .map_err(|err| internal_error(format!("failed to read index manifest: {err:#}")))?;
+    let analytics = load_analytics(&paths.analytics_path).unwrap_or_default();
+    Ok(IndexStatusResponse {
+        project_root,
+        built: true,
+        embedding_model: Some(manifest.embedding_model),
+        embedding_dim: Some(count(manifest.embedding_dim)),
+        total_files: count(manifest.total_files),
+        total_chunks: count(manifest.total_chunks),
+        updated_at: Some(manifest.updated_at.to_rfc3339()),
+        last_success_at: analytics.last_success_ts.map(|ts| ts.to_rfc3339()),
+        last_error: analytics.last_error,
+    })
+}
+
+pub(crate) fn query_index(
+    project_root: PathBuf,
+    params: IndexQueryParams,
+) -> Result<IndexQueryResponse, JSONRPCErrorError> {
+    if params.query.trim().is_empty() {
+        return Err(invalid_request("query cannot be empty".to_string()));
+    }
+    let paths = IndexPaths::from_root(project_root.clone());
+    if !paths.manifest_path.exists() {
+        return Err(invalid_request(format!(
+            "no index has been built for {}",
+            project_root.display()
+        )));
+    }
+    let top_k = params
+        .top_k
+        .map(|top_k| top_k.max(1) as usize)
+        .unwrap_or(DEFAULT_QUERY_TOP_K);
+    let mut response = core_query_index(&project_root, &params.query, top_k, None)
+        .map_err(|err| internal_error(format!("index query failed: {err:#}")))?;
+    if let Some(min_confidence) = params.min_confidence {
+        response = response.with_confidence_min(min_confidence);
+    }
+    Ok(IndexQueryResponse {
+        data: response
+            .hits
+            .into_iter()
+            .map(|hit| IndexQueryHit {
+                rank: count(hit.rank),
+                score: hit.score,
+                file_path: hit.file_path,
+                start_line: count(hit.start_line),
+                end_line: count(hit.end_line),
+                snippet: hit.snippet,
+            })
+            .collect(),
+    })
+}
+
+fn event_to_v2(event: IndexEvent) -> IndexProgressEvent {
+    match event {
+        IndexEvent::Started { total_files } => IndexProgressEvent::Started {
+            total_files: count(total_files),
+        },
+        IndexEvent::Progress {
+            processed_files,
+            total_files,
+            processed_chunks,
+            total_chunks,
+            current_path,
+        } => IndexProgressEvent::Progress {
+            processed_files: count(processed_files),
+            total_files: count(total_files),
+            processed_chunks: count(processed_chunks),
+            total_chunks: count(total_chunks),
+            current_path,
+        },
+        IndexEvent::Completed(summary) => IndexProgressEvent::Completed {
+            summary: summary_to_v2(summary),
+        },
+        IndexEvent::Error { message } => IndexProgressEvent::Error { message },
+    }
+}
+
+fn summary_to_v2(summary: CoreIndexSummary) -> IndexSummary {
+    IndexSummary {
+        project_root: summary.project_root,
+        total_files: count(summary.total_files),
+        total_chunks: count(summary.total_chunks),
+        embedding_model: summary.embedding_model,
+        embedding_dim: count(summary.embedding_dim),
+        duration_ms: i64::try_from(summary.duration_ms).unwrap_or(i64::MAX),
+        reused_chunks: count(summary.reused_chunks),
+        new_chunks: count(summary.new_chunks),
+    }
+}
+
+fn count(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
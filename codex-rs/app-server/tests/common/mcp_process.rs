@@ -13,6 +13,9 @@ use tokio::process::ChildStdout;
 use anyhow::Context;
 use assert_cmd::prelude::*;
 use codex_app_server_protocol::AddConversationListenerParams;
+use codex_app_server_protocol::AgentGetParams;
+use codex_app_server_protocol::AgentListParams;
+use codex_app_server_protocol::AgentUpsertParams;
 use codex_app_server_protocol::ArchiveConversationParams;
 use codex_app_server_protocol::CancelLoginAccountParams;
 use codex_app_server_protocol::CancelLoginChatGptParams;
@@ -20,6 +23,9 @@ use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::GetAuthStatusParams;
+use codex_app_server_protocol::HistorySearchParams;
+use codex_app_server_protocol::IndexQueryParams;
+use codex_app_server_protocol::IndexStatusParams;
 use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::InterruptConversationParams;
 use codex_app_server_protocol::JSONRPCError;
@@ -29,6 +35,8 @@ use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::ListConversationsParams;
 use codex_app_server_protocol::LoginApiKeyParams;
+use codex_app_server_protocol::MemoryDeleteParams;
+use codex_app_server_protocol::MemoryListParams;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::RemoveConversationListenerParams;
@@ -39,6 +47,7 @@ use codex_app_server_protocol::SendUserTurnParams;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::SetDefaultModelParams;
 use codex_app_server_protocol::ThreadArchiveParams;
+use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadStartParams;
@@ -299,6 +308,15 @@ impl McpProcess {
         self.send_request("thread/resume", params).await
     }
 
+    /// Send a `thread/fork` JSON-RPC request.
+    pub async fn send_thread_fork_request(
+        &mut self,
+        params: ThreadForkParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/fork", params).await
+    }
+
     /// Send a `thread/archive` JSON-RPC request.
     pub async fn send_thread_archive_request(
         &mut self,
@@ -317,6 +335,75 @@ impl McpProcess {
         self.send_request("thread/list", params).await
     }
 
+    /// Send a `history/search` JSON-RPC request.
+    pub async fn send_history_search_request(
+        &mut self,
+        params: HistorySearchParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("history/search", params).await
+    }
+
+    /// Send a `memory/list` JSON-RPC request.
+    pub async fn send_memory_list_request(
+        &mut self,
+        params: MemoryListParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("memory/list", params).await
+    }
+
+    /// Send a `memory/delete` JSON-RPC request.
+    pub async fn send_memory_delete_request(
+        &mut self,
+        params: MemoryDeleteParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("memory/delete", params).await
+    }
+
+    /// Send a `index/status` JSON-RPC request.
+    pub async fn send_index_status_request(
+        &mut self,
+        params: IndexStatusParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("index/status", params).await
+    }
+
+    /// Send a `index/query` JSON-RPC request.
+    pub async fn send_index_query_request(
+        &mut self,
+        params: IndexQueryParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("index/query", params).await
+    }
+
+    /// Send a `agent/list` JSON-RPC request.
+    pub async fn send_agent_list_request(
+        &mut self,
+        params: AgentListParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("agent/list", params).await
+    }
+
+    /// Send a `agent/get` JSON-RPC request.
+    pub async fn send_agent_get_request(&mut self, params: AgentGetParams) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("agent/get", params).await
+    }
+
+    /// Send a `agent/upsert` JSON-RPC request.
+    pub async fn send_agent_upsert_request(
+        &mut self,
+        params: AgentUpsertParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("agent/upsert", params).await
+    }
+
     /// Send a `model/list` JSON-RPC request.
     pub async fn send_list_models_request(
         &mut self,
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::AgentGetParams;
+use codex_app_server_protocol::AgentGetResponse;
+use codex_app_server_protocol::AgentListParams;
+use codex_app_server_protocol::AgentListResponse;
+use codex_app_server_protocol::AgentProfile;
+use codex_app_server_protocol::AgentUpsertParams;
+use codex_app_server_protocol::AgentUpsertResponse;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+
+fn reviewer_profile() -> AgentProfile {
+    AgentProfile {
+        name: "Code Reviewer".to_string(),
+        slug: String::new(),
+        description: Some("Reviews diffs".to_string()),
+        priming_prompt: Some("You review code carefully.".to_string()),
+        default_command: Vec::new(),
+        enabled_tools: Vec::new(),
+        approval_mode: Some("never".to_string()),
+        sandbox_mode: Some("read-only".to_string()),
+        default_flags: None,
+        created_at: None,
+        updated_at: None,
+        last_run_at: None,
+        last_run_summary: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_upsert_list_and_get() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let agents_dir = codex_home.path().join("agents");
+    let agents_dir = agents_dir.to_string_lossy().to_string();
+    let mut mcp = McpProcess::new_with_env(
+        codex_home.path(),
+        &[("CODEX_AGENTS_PATH", Some(agents_dir.as_str()))],
+    )
+    .await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_agent_upsert_request(AgentUpsertParams {
+            agent: reviewer_profile(),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let AgentUpsertResponse { agent } = to_response::<AgentUpsertResponse>(response)?;
+    assert_eq!(agent.slug, "code-reviewer");
+    assert!(agent.created_at.is_some());
+
+    let request_id = mcp
+        .send_agent_list_request(AgentListParams::default())
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let AgentListResponse { data } = to_response::<AgentListResponse>(response)?;
+    assert_eq!(data, vec![agent.clone()]);
+
+    let request_id = mcp
+        .send_agent_get_request(AgentGetParams {
+            agent: "Code Reviewer".to_string(),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let AgentGetResponse { agent: fetched } = to_response::<AgentGetResponse>(response)?;
+    assert_eq!(fetched, agent);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn agent_get_rejects_unknown_agent() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let agents_dir = codex_home.path().join("agents");
+    let agents_dir = agents_dir.to_string_lossy().to_string();
+    let mut mcp = McpProcess::new_with_env(
+        codex_home.path(),
+        &[("CODEX_AGENTS_PATH", Some(agents_dir.as_str()))],
+    )
+    .await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_agent_get_request(AgentGetParams {
+            agent: "missing".to_string(),
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::HistorySearchParams;
+use codex_app_server_protocol::HistorySearchResponse;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+
+#[tokio::test]
+async fn history_search_matches_user_turns() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+"#,
+    )?;
+
+    let matching_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Refactor the tokenizer cache",
+        Some("mock_provider"),
+    )?;
+    let _other = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-01T12-00-00",
+        "2025-01-01T12:00:00Z",
+        "Write release notes",
+        Some("mock_provider"),
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let search_id = mcp
+        .send_history_search_request(HistorySearchParams {
+            query: Some("tokenizer cache".to_string()),
+            cwd: None,
+            model: None,
+            since: None,
+            until: None,
+            tool: None,
+            limit: Some(10),
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(search_id)),
+    )
+    .await??;
+    let HistorySearchResponse { data } = to_response::<HistorySearchResponse>(resp)?;
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].thread_id, matching_id);
+    assert_eq!(data[0].turn_index, 1);
+    assert!(data[0].snippet.contains("tokenizer"));
+
+    // Date filters exclude turns outside the window.
+    let search_id = mcp
+        .send_history_search_request(HistorySearchParams {
+            query: Some("tokenizer".to_string()),
+            cwd: None,
+            model: None,
+            since: Some("2025-01-03".to_string()),
+            until: None,
+            tool: None,
+            limit: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(search_id)),
+    )
+    .await??;
+    let HistorySearchResponse { data } = to_response::<HistorySearchResponse>(resp)?;
+    assert!(data.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn history_search_rejects_invalid_date_ranges() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    for (since, until) in [
+        (Some("2025-13-01"), None),
+        (None, Some("yesterday")),
+        (Some("2025-02-01"), Some("2025-01-01")),
+    ] {
+        let search_id = mcp
+            .send_history_search_request(HistorySearchParams {
+                query: Some("anything".to_string()),
+                cwd: None,
+                model: None,
+                since: since.map(str::to_string),
+                until: until.map(str::to_string),
+                tool: None,
+                limit: None,
+            })
+            .await?;
+        let error: JSONRPCError = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_error_message(RequestId::Integer(search_id)),
+        )
+        .await??;
+        assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    }
+
+    Ok(())
+}
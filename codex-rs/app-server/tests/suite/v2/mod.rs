@@ -1,7 +1,12 @@
 mod account;
+mod agent;
+mod history_search;
+mod index;
+mod memory;
 mod model_list;
 mod rate_limits;
 mod thread_archive;
+mod thread_fork;
 mod thread_list;
 mod thread_resume;
 mod thread_start;
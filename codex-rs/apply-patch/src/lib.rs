@@ -1,6 +1,7 @@
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod unified_diff;
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -10,6 +11,7 @@ use std::sync::LazyLock;
 
 use anyhow::Context;
 use anyhow::Result;
+pub use parser::FileMode;
 pub use parser::Hunk;
 pub use parser::ParseError;
 use parser::ParseError::*;
@@ -160,6 +162,9 @@ pub enum MaybeApplyPatchVerified {
 pub struct ApplyPatchAction {
     changes: HashMap<PathBuf, ApplyPatchFileChange>,
 
+    /// Mode changes (from git diff headers) applied after `changes`.
+    mode_changes: HashMap<PathBuf, FileMode>,
+
     /// The raw patch argument that can be used with `apply_patch` as an exec
     /// call. i.e., if the original arg was parsed in "lenient" mode with a
     /// heredoc, this should be the value without the heredoc wrapper.
@@ -171,7 +176,7 @@ pub struct ApplyPatchAction {
 
 impl ApplyPatchAction {
     pub fn is_empty(&self) -> bool {
-        self.changes.is_empty()
+        self.changes.is_empty() && self.mode_changes.is_empty()
     }
 
     /// Returns the changes that would be made by applying the patch.
@@ -179,6 +184,11 @@ impl ApplyPatchAction {
         &self.changes
     }
 
+    /// Returns the file mode changes that would be made by applying the patch.
+    pub fn mode_changes(&self) -> &HashMap<PathBuf, FileMode> {
+        &self.mode_changes
+    }
+
     /// Should be used exclusively for testing. (Not worth the overhead of
     /// creating a feature flag for this.)
     pub fn new_add_for_test(path: &Path, content: String) -> Self {
@@ -202,6 +212,7 @@ impl ApplyPatchAction {
         #[expect(clippy::expect_used)]
         Self {
             changes,
+            mode_changes: HashMap::new(),
             cwd: path
                 .parent()
                 .expect("path should have parent")
@@ -252,6 +263,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                 })
                 .unwrap_or_else(|| cwd.to_path_buf());
             let mut changes = HashMap::new();
+            let mut mode_changes = HashMap::new();
             for hunk in hunks {
                 let path = hunk.resolve_path(&effective_cwd);
                 match hunk {
@@ -259,7 +271,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         changes.insert(path, ApplyPatchFileChange::Add { content: contents });
                     }
                     Hunk::DeleteFile { .. } => {
-                        let content = match std::fs::read_to_string(&path) {
+                        let content = match read_file_lossy(&path) {
                             Ok(content) => content,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(
@@ -272,6 +284,31 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         };
                         changes.insert(path, ApplyPatchFileChange::Delete { content });
                     }
+                    Hunk::UpdateFile {
+                        move_path, chunks, ..
+                    } if chunks.is_empty() => {
+                        // Pure rename: the content is moved as-is, which also
+                        // works for binary files.
+                        let content = match read_file_lossy(&path) {
+                            Ok(content) => content,
+                            Err(e) => {
+                                return MaybeApplyPatchVerified::CorrectnessError(
+                                    ApplyPatchError::IoError(IoError {
+                                        context: format!("Failed to read {}", path.display()),
+                                        source: e,
+                                    }),
+                                );
+                            }
+                        };
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Update {
+                                unified_diff: String::new(),
+                                move_path: move_path.map(|p| cwd.join(p)),
+                                new_content: content,
+                            },
+                        );
+                    }
                     Hunk::UpdateFile {
                         move_path, chunks, ..
                     } => {
@@ -293,10 +330,14 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                             },
                         );
                     }
+                    Hunk::SetMode { mode, .. } => {
+                        mode_changes.insert(path, mode);
+                    }
                 }
             }
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
                 changes,
+                mode_changes,
                 patch,
                 cwd: effective_cwd,
             })
@@ -530,6 +571,7 @@ pub fn apply_hunks(
                 }
                 None => Some(path.as_path()),
             },
+            Hunk::SetMode { path, .. } => Some(path.as_path()),
         })
         .collect::<Vec<&Path>>();
 
@@ -592,6 +634,28 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                     .with_context(|| format!("Failed to delete file {}", path.display()))?;
                 deleted.push(path.clone());
             }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } if chunks.is_empty() => {
+                // Pure rename: move the file without reading it so that binary
+                // files and symlinks are preserved byte-for-byte.
+                let Some(dest) = move_path else {
+                    continue;
+                };
+                if let Some(parent) = dest.parent()
+                    && !parent.as_os_str().is_empty()
+                {
+                    std::fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create parent directories for {}", dest.display())
+                    })?;
+                }
+                std::fs::rename(path, dest).with_context(|| {
+                    format!("Failed to move {} to {}", path.display(), dest.display())
+                })?;
+                modified.push(dest.clone());
+            }
             Hunk::UpdateFile {
                 path,
                 move_path,
@@ -618,6 +682,13 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                     modified.push(path.clone());
                 }
             }
+            Hunk::SetMode { path, mode } => {
+                set_file_mode(path, *mode)
+                    .with_context(|| format!("Failed to set mode of {}", path.display()))?;
+                if !added.contains(path) && !modified.contains(path) {
+                    modified.push(path.clone());
+                }
+            }
         }
     }
     Ok(AffectedPaths {
@@ -627,6 +698,64 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     })
 }
 
+/// Applies `mode` to the file at `path`. Switching to [`FileMode::Symlink`]
+/// turns the file into a link whose target is the file's current content;
+/// switching away from it writes the link target out as a regular file.
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: FileMode) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    let is_symlink = std::fs::symlink_metadata(path)?.file_type().is_symlink();
+    match mode {
+        FileMode::Symlink => {
+            if is_symlink {
+                return Ok(());
+            }
+            let target = std::fs::read_to_string(path)?;
+            std::fs::remove_file(path)?;
+            std::os::unix::fs::symlink(target.trim_end_matches('\n'), path)
+        }
+        FileMode::Regular | FileMode::Executable => {
+            if is_symlink {
+                let target = std::fs::read_link(path)?;
+                std::fs::remove_file(path)?;
+                std::fs::write(path, target.as_os_str().as_bytes())?;
+            }
+            // Like git, only toggle the executable bits and keep the rest.
+            let current = std::fs::metadata(path)?.permissions().mode();
+            let bits = if mode == FileMode::Executable {
+                current | 0o111
+            } else {
+                current & !0o111
+            };
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(bits))
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, mode: FileMode) -> std::io::Result<()> {
+    match mode {
+        // There is no executable bit to toggle on this platform.
+        FileMode::Regular | FileMode::Executable => Ok(()),
+        FileMode::Symlink => Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "symlinks from patches are only supported on Unix",
+        )),
+    }
+}
+
+/// Reads a file for display purposes without failing on non-UTF-8 content.
+/// Symlinks are reported as their target, matching how git diffs them.
+fn read_file_lossy(path: &Path) -> std::io::Result<String> {
+    if std::fs::symlink_metadata(path)?.file_type().is_symlink() {
+        return Ok(std::fs::read_link(path)?.to_string_lossy().into_owned());
+    }
+    let bytes = std::fs::read(path)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -701,12 +830,25 @@ fn compute_replacements(
         }
 
         if chunk.old_lines.is_empty() {
-            // Pure addition (no old lines). We'll add them at the end or just
-            // before the final empty line if one exists.
-            let insertion_idx = if original_lines.last().is_some_and(String::is_empty) {
-                original_lines.len() - 1
-            } else {
-                original_lines.len()
+            // Pure addition (no old lines). Unified diff hunks record where the
+            // lines go; otherwise we add them at the end or just before the
+            // final empty line if one exists.
+            let insertion_idx = match chunk.orig_index {
+                Some(idx) if idx > original_lines.len() => {
+                    return Err(ApplyPatchError::ComputeReplacements(format!(
+                        "Insertion at line {} is past the end of {}",
+                        idx + 1,
+                        path.display()
+                    )));
+                }
+                Some(idx) => {
+                    line_index = idx;
+                    idx
+                }
+                None if original_lines.last().is_some_and(String::is_empty) => {
+                    original_lines.len() - 1
+                }
+                None => original_lines.len(),
             };
             replacements.push((insertion_idx, 0, chunk.new_lines.clone()));
             continue;
@@ -1293,6 +1435,39 @@ PATCH"#,
         );
     }
 
+    #[test]
+    fn test_zero_context_unified_diff_inserts_at_hunk_start() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("list.txt");
+        fs::write(&path, "one\ntwo\nthree\n").unwrap();
+        // `diff -U0` output: insertions carry no context lines, only the
+        // position recorded in the hunk header.
+        let patch = format!(
+            "--- a/{path}\n+++ b/{path}\n@@ -0,0 +1 @@\n+zero\n@@ -1,0 +3 @@\n+one-and-a-half\n@@ -3 +4 @@\n-three\n+THREE\n",
+            path = path.display()
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        let contents = fs::read_to_string(path).unwrap();
+        assert_eq!(contents, "zero\none\none-and-a-half\ntwo\nTHREE\n");
+    }
+
+    #[test]
+    fn test_zero_context_unified_diff_rejects_insertion_past_eof() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("short.txt");
+        fs::write(&path, "one\n").unwrap();
+        let patch = format!(
+            "--- a/{path}\n+++ b/{path}\n@@ -5,0 +6 @@\n+late\n",
+            path = path.display()
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        assert!(apply_patch(&patch, &mut stdout, &mut stderr).is_err());
+        assert_eq!(fs::read_to_string(path).unwrap(), "one\n");
+    }
+
     /// Ensure that patches authored with ASCII characters can update lines that
     /// contain typographic Unicode punctuation (e.g. EN DASH, NON-BREAKING
     /// HYPHEN). Historically `git apply` succeeds in such scenarios but our
@@ -1597,12 +1772,55 @@ g
                         new_content: "updated session directory content\n".to_string(),
                     },
                 )]),
+                mode_changes: HashMap::new(),
                 patch: argv[1].clone(),
                 cwd: session_dir.path().to_path_buf(),
             })
         );
     }
 
+    #[test]
+    fn test_unified_diff_verified_reports_rename_and_mode_change() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("run.sh"), "echo old\n").unwrap();
+        let argv = vec![
+            "apply_patch".to_string(),
+            "diff --git a/run.sh b/bin/run.sh\n\
+old mode 100644\n\
+new mode 100755\n\
+rename from run.sh\n\
+rename to bin/run.sh\n\
+--- a/run.sh\n\
++++ b/bin/run.sh\n\
+@@ -1 +1 @@\n\
+-echo old\n\
++echo new\n"
+                .to_string(),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+
+        assert_eq!(
+            result,
+            MaybeApplyPatchVerified::Body(ApplyPatchAction {
+                changes: HashMap::from([(
+                    session_dir.path().join("run.sh"),
+                    ApplyPatchFileChange::Update {
+                        unified_diff: "@@ -1 +1 @@\n-echo old\n+echo new\n".to_string(),
+                        move_path: Some(session_dir.path().join("bin/run.sh")),
+                        new_content: "echo new\n".to_string(),
+                    },
+                )]),
+                mode_changes: HashMap::from([(
+                    session_dir.path().join("bin/run.sh"),
+                    FileMode::Executable
+                )]),
+                patch: argv[1].trim().to_string(),
+                cwd: session_dir.path().to_path_buf(),
+            })
+        );
+    }
+
     #[test]
     fn test_apply_patch_fails_on_write_error() {
         let dir = tempdir().unwrap();
@@ -22,7 +22,11 @@
 //!
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
+//!
+//! Standard unified diffs (`diff --git ...` or `--- a/...`/`+++ b/...`) are also
+//! accepted; see [`crate::unified_diff`] for how they are lowered into hunks.
 use crate::ApplyPatchArgs;
+use crate::unified_diff;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -73,6 +77,12 @@ pub enum Hunk {
         /// should occur later in the file than the previous chunk.
         chunks: Vec<UpdateFileChunk>,
     },
+    /// Sets the mode of `path` after any content changes for it have been
+    /// applied. Only produced by unified diffs carrying git mode headers.
+    SetMode {
+        path: PathBuf,
+        mode: FileMode,
+    },
 }
 
 impl Hunk {
@@ -81,6 +91,37 @@ impl Hunk {
             Hunk::AddFile { path, .. } => cwd.join(path),
             Hunk::DeleteFile { path } => cwd.join(path),
             Hunk::UpdateFile { path, .. } => cwd.join(path),
+            Hunk::SetMode { path, .. } => cwd.join(path),
+        }
+    }
+}
+
+/// File modes that can be expressed in git diff headers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FileMode {
+    /// `100644`
+    Regular,
+    /// `100755`
+    Executable,
+    /// `120000`: the file content is the link target.
+    Symlink,
+}
+
+impl FileMode {
+    pub fn from_git_mode(mode: &str) -> Option<Self> {
+        match mode {
+            "100644" | "100664" => Some(FileMode::Regular),
+            "100755" => Some(FileMode::Executable),
+            "120000" => Some(FileMode::Symlink),
+            _ => None,
+        }
+    }
+
+    pub fn as_git_mode(self) -> &'static str {
+        match self {
+            FileMode::Regular => "100644",
+            FileMode::Executable => "100755",
+            FileMode::Symlink => "120000",
         }
     }
 }
@@ -101,9 +142,17 @@ pub struct UpdateFileChunk {
     /// If set to true, `old_lines` must occur at the end of the source file.
     /// (Tolerance around trailing newlines should be encouraged.)
     pub is_end_of_file: bool,
+
+    /// 0-based line in the original file where the chunk starts, when the
+    /// patch format records it (unified diff hunk headers). Used to place
+    /// chunks without any `old_lines`, which have nothing to match on.
+    pub orig_index: Option<usize>,
 }
 
 pub fn parse_patch(patch: &str) -> Result<ApplyPatchArgs, ParseError> {
+    if unified_diff::is_unified_diff(patch) {
+        return unified_diff::parse_unified_diff(patch);
+    }
     let mode = if PARSE_IN_STRICT_MODE {
         ParseMode::Strict
     } else {
@@ -377,6 +426,7 @@ fn parse_update_file_chunk(
         old_lines: Vec::new(),
         new_lines: Vec::new(),
         is_end_of_file: false,
+        orig_index: None,
     };
     let mut parsed_lines = 0;
     for line in &lines[start_index..] {
@@ -498,7 +548,8 @@ fn test_parse_patch() {
                     change_context: Some("def f():".to_string()),
                     old_lines: vec!["    pass".to_string()],
                     new_lines: vec!["    return 123".to_string()],
-                    is_end_of_file: false
+                    is_end_of_file: false,
+                    orig_index: None
                 }]
             }
         ]
@@ -525,7 +576,8 @@ fn test_parse_patch() {
                     change_context: None,
                     old_lines: vec![],
                     new_lines: vec!["line".to_string()],
-                    is_end_of_file: false
+                    is_end_of_file: false,
+                    orig_index: None
                 }],
             },
             AddFile {
@@ -556,6 +608,7 @@ fn test_parse_patch() {
                 old_lines: vec!["import foo".to_string()],
                 new_lines: vec!["import foo".to_string(), "bar".to_string()],
                 is_end_of_file: false,
+                orig_index: None,
             }],
         }]
     );
@@ -576,6 +629,7 @@ fn test_parse_patch_lenient() {
             old_lines: vec!["import foo".to_string()],
             new_lines: vec!["import foo".to_string(), "bar".to_string()],
             is_end_of_file: false,
+            orig_index: None,
         }],
     }];
     let expected_error =
@@ -721,7 +775,8 @@ fn test_update_file_chunk() {
                     "add".to_string(),
                     "context2".to_string()
                 ],
-                is_end_of_file: false
+                is_end_of_file: false,
+                orig_index: None
             }),
             6
         ))
@@ -733,7 +788,8 @@ fn test_update_file_chunk() {
                 change_context: None,
                 old_lines: vec![],
                 new_lines: vec!["line".to_string()],
-                is_end_of_file: true
+                is_end_of_file: true,
+                orig_index: None
             }),
             3
         ))
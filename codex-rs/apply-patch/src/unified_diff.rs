@@ -0,0 +1,701 @@
+//! Front-end that lowers standard unified diffs (as produced by `diff -u` or
+//! `git diff`) into the same [`Hunk`]s produced by the `*** Begin Patch`
+//! parser, so both formats share one application and verification path.
+//!
+//! In addition to plain `---`/`+++`/`@@` sections, the git extended headers
+//! are understood:
+//!
+//! - `new file mode` / `deleted file mode` turn a section into an add/delete.
+//! - `old mode` / `new mode` (and the mode on `new file mode`) produce a
+//!   [`Hunk::SetMode`] after any content change.
+//! - `rename from` / `rename to` produce an update with a `move_path`, with or
+//!   without content edits.
+//! - Mode `120000` marks a symlink whose content is the link target.
+//!
+//! Binary files can be deleted, renamed, or have their mode changed, but
+//! binary content changes (`GIT binary patch`, `Binary files ... differ`) are
+//! rejected since there is no textual content to apply.
+use std::path::PathBuf;
+
+use crate::ApplyPatchArgs;
+use crate::parser::FileMode;
+use crate::parser::Hunk;
+use crate::parser::ParseError;
+use crate::parser::ParseError::*;
+use crate::parser::UpdateFileChunk;
+
+const DIFF_GIT_PREFIX: &str = "diff --git ";
+const OLD_FILE_PREFIX: &str = "--- ";
+const NEW_FILE_PREFIX: &str = "+++ ";
+const HUNK_PREFIX: &str = "@@ -";
+const NO_NEWLINE_MARKER: &str = "\\ No newline at end of file";
+const DEV_NULL: &str = "/dev/null";
+
+/// Returns true when `patch` looks like a unified diff rather than the
+/// `*** Begin Patch` envelope.
+pub(crate) fn is_unified_diff(patch: &str) -> bool {
+    let mut lines = patch.trim_start().lines();
+    match lines.next() {
+        Some(first) if first.starts_with(DIFF_GIT_PREFIX) => true,
+        Some(first) if first.starts_with(OLD_FILE_PREFIX) => lines
+            .next()
+            .is_some_and(|second| second.starts_with(NEW_FILE_PREFIX)),
+        _ => false,
+    }
+}
+
+pub(crate) fn parse_unified_diff(patch: &str) -> Result<ApplyPatchArgs, ParseError> {
+    let lines: Vec<&str> = patch.trim().lines().collect();
+    let mut hunks = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].trim().is_empty() {
+            index += 1;
+            continue;
+        }
+        let parsed_lines = parse_file_section(&lines[index..], index + 1, &mut hunks)?;
+        index += parsed_lines;
+    }
+    if hunks.is_empty() {
+        return Err(InvalidPatchError(
+            "The unified diff does not contain any file changes".to_string(),
+        ));
+    }
+    Ok(ApplyPatchArgs {
+        hunks,
+        patch: lines.join("\n"),
+        workdir: None,
+    })
+}
+
+/// Everything we learn about a single file from its headers.
+#[derive(Default)]
+struct FileHeader {
+    git_old_path: Option<String>,
+    git_new_path: Option<String>,
+    old_path: Option<String>,
+    new_path: Option<String>,
+    rename_from: Option<String>,
+    rename_to: Option<String>,
+    old_mode: Option<FileMode>,
+    new_mode: Option<FileMode>,
+    /// Mode reported on the `index <a>..<b> <mode>` line for unchanged modes.
+    index_mode: Option<FileMode>,
+    is_new_file: bool,
+    is_deleted_file: bool,
+    is_binary: bool,
+}
+
+/// Parses one file section starting at `lines[0]`, appending the resulting
+/// hunks to `hunks`. Returns the number of lines consumed.
+fn parse_file_section(
+    lines: &[&str],
+    line_number: usize,
+    hunks: &mut Vec<Hunk>,
+) -> Result<usize, ParseError> {
+    let mut header = FileHeader::default();
+    let mut index = 0;
+    let is_git = lines[0].starts_with(DIFF_GIT_PREFIX);
+
+    if let Some(rest) = lines[0].strip_prefix(DIFF_GIT_PREFIX) {
+        let (old, new) = split_git_paths(rest).ok_or_else(|| InvalidHunkError {
+            message: format!("Could not parse paths from git header: '{}'", lines[0]),
+            line_number,
+        })?;
+        header.git_old_path = Some(old);
+        header.git_new_path = Some(new);
+        index += 1;
+    }
+
+    // Extended git headers and the `---`/`+++` pair.
+    while index < lines.len() {
+        let line = lines[index];
+        let current_line_number = line_number + index;
+        if line.starts_with(HUNK_PREFIX) || line.starts_with(DIFF_GIT_PREFIX) {
+            break;
+        }
+        if let Some(path) = line.strip_prefix(OLD_FILE_PREFIX) {
+            header.old_path = parse_header_path(path, is_git);
+        } else if let Some(path) = line.strip_prefix(NEW_FILE_PREFIX) {
+            header.new_path = parse_header_path(path, is_git);
+        } else if let Some(mode) = line.strip_prefix("old mode ") {
+            header.old_mode = Some(parse_mode(mode, current_line_number)?);
+        } else if let Some(mode) = line.strip_prefix("new mode ") {
+            header.new_mode = Some(parse_mode(mode, current_line_number)?);
+        } else if let Some(mode) = line.strip_prefix("new file mode ") {
+            header.is_new_file = true;
+            header.new_mode = Some(parse_mode(mode, current_line_number)?);
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+            header.is_deleted_file = true;
+            header.old_mode = Some(parse_mode(mode, current_line_number)?);
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            header.rename_from = Some(unquote_path(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            header.rename_to = Some(unquote_path(path));
+        } else if let Some(rest) = line.strip_prefix("index ") {
+            if let Some((_, mode)) = rest.split_once(' ') {
+                header.index_mode = Some(parse_mode(mode, current_line_number)?);
+            }
+        } else if line.starts_with("similarity index ")
+            || line.starts_with("dissimilarity index ")
+            || (!is_git
+                && (line.starts_with("diff ")
+                    || line.starts_with("Index: ")
+                    || line.starts_with("===")))
+        {
+            // Informational only.
+        } else if line.starts_with("copy from ") || line.starts_with("copy to ") {
+            return Err(InvalidHunkError {
+                message: "Copies are not supported; add the new file explicitly instead"
+                    .to_string(),
+                line_number: current_line_number,
+            });
+        } else if line == "GIT binary patch"
+            || (line.starts_with("Binary files ") && line.ends_with(" differ"))
+        {
+            header.is_binary = true;
+        } else if is_git && !header.is_binary {
+            return Err(InvalidHunkError {
+                message: format!("Unexpected line in git diff header: '{line}'"),
+                line_number: current_line_number,
+            });
+        } else if header.is_binary {
+            // Skip over the base85 payload of a binary patch; it is rejected below
+            // unless the section only deletes the file.
+        } else {
+            return Err(InvalidHunkError {
+                message: format!("Unexpected line in unified diff header: '{line}'"),
+                line_number: current_line_number,
+            });
+        }
+        index += 1;
+    }
+
+    let old_path = header
+        .rename_from
+        .clone()
+        .or_else(|| header.old_path.clone().filter(|p| p != DEV_NULL))
+        .or_else(|| header.git_old_path.clone());
+    let new_path = header
+        .rename_to
+        .clone()
+        .or_else(|| header.new_path.clone().filter(|p| p != DEV_NULL))
+        .or_else(|| header.git_new_path.clone());
+    let is_new_file = header.is_new_file || header.old_path.as_deref() == Some(DEV_NULL);
+    let is_deleted_file = header.is_deleted_file || header.new_path.as_deref() == Some(DEV_NULL);
+
+    let (Some(old_path), Some(new_path)) = (old_path, new_path) else {
+        return Err(InvalidHunkError {
+            message: "Unified diff section is missing '---'/'+++' file names".to_string(),
+            line_number,
+        });
+    };
+
+    // Git only emits a binary marker when the file content itself differs.
+    if header.is_binary && !is_deleted_file {
+        return Err(InvalidHunkError {
+            message: format!(
+                "Binary content changes for '{new_path}' are not supported; only deleting, renaming, or changing the mode of binary files can be applied"
+            ),
+            line_number,
+        });
+    }
+
+    let mut chunks = Vec::new();
+    let mut missing_final_newline = false;
+    while index < lines.len() && lines[index].starts_with(HUNK_PREFIX) {
+        let (chunk, chunk_lines, no_newline) =
+            parse_unified_hunk(&lines[index..], line_number + index)?;
+        chunks.push(chunk);
+        missing_final_newline = no_newline;
+        index += chunk_lines;
+    }
+    if header.is_binary {
+        // Skip any remaining binary payload for this file.
+        while index < lines.len() && !lines[index].starts_with(DIFF_GIT_PREFIX) {
+            index += 1;
+        }
+    }
+
+    let symlink_mode = header
+        .new_mode
+        .or(header.index_mode)
+        .filter(|mode| *mode == FileMode::Symlink);
+
+    if is_deleted_file {
+        hunks.push(Hunk::DeleteFile {
+            path: PathBuf::from(old_path),
+        });
+        return Ok(index);
+    }
+
+    if is_new_file {
+        let mut contents = chunks
+            .iter()
+            .flat_map(|chunk| chunk.new_lines.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !contents.is_empty() && !missing_final_newline {
+            contents.push('\n');
+        }
+        hunks.push(Hunk::AddFile {
+            path: PathBuf::from(&new_path),
+            contents,
+        });
+        if let Some(mode) = header.new_mode.filter(|mode| *mode != FileMode::Regular) {
+            hunks.push(Hunk::SetMode {
+                path: PathBuf::from(new_path),
+                mode,
+            });
+        }
+        return Ok(index);
+    }
+
+    let move_path = (old_path != new_path).then(|| PathBuf::from(&new_path));
+
+    if let Some(mode) = symlink_mode
+        && !chunks.is_empty()
+    {
+        // The content of a symlink is its target; retargeting the link is
+        // expressed as replacing it rather than editing it in place.
+        let target = chunks
+            .iter()
+            .flat_map(|chunk| chunk.new_lines.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        hunks.push(Hunk::DeleteFile {
+            path: PathBuf::from(&old_path),
+        });
+        hunks.push(Hunk::AddFile {
+            path: PathBuf::from(&new_path),
+            contents: target,
+        });
+        hunks.push(Hunk::SetMode {
+            path: PathBuf::from(new_path),
+            mode,
+        });
+        return Ok(index);
+    }
+
+    if !chunks.is_empty() || move_path.is_some() {
+        hunks.push(Hunk::UpdateFile {
+            path: PathBuf::from(&old_path),
+            move_path,
+            chunks,
+        });
+    }
+    if let Some(mode) = header.new_mode
+        && header.old_mode != Some(mode)
+    {
+        hunks.push(Hunk::SetMode {
+            path: PathBuf::from(new_path),
+            mode,
+        });
+    }
+    Ok(index)
+}
+
+/// Parses a single `@@ -a,b +c,d @@` hunk. Returns the chunk, the number of
+/// lines consumed and whether the new side ends without a trailing newline.
+fn parse_unified_hunk(
+    lines: &[&str],
+    line_number: usize,
+) -> Result<(UpdateFileChunk, usize, bool), ParseError> {
+    let header = lines[0];
+    let (old_start, mut old_remaining, mut new_remaining) =
+        parse_hunk_range(header).ok_or_else(|| InvalidHunkError {
+            message: format!("Invalid unified diff hunk header: '{header}'"),
+            line_number,
+        })?;
+
+    // `-a,0` means "after line a"; otherwise the hunk starts at line a.
+    let orig_index = if old_remaining == 0 {
+        old_start
+    } else {
+        old_start.saturating_sub(1)
+    };
+    let mut chunk = UpdateFileChunk {
+        change_context: None,
+        old_lines: Vec::new(),
+        new_lines: Vec::new(),
+        is_end_of_file: false,
+        orig_index: Some(orig_index),
+    };
+    let mut index = 1;
+    let mut last_side: Option<char> = None;
+    let mut new_missing_newline = false;
+    while index < lines.len() && (old_remaining > 0 || new_remaining > 0) {
+        let line = lines[index];
+        match line.chars().next() {
+            // Editors commonly strip the single space from blank context lines.
+            None => {
+                chunk.old_lines.push(String::new());
+                chunk.new_lines.push(String::new());
+                old_remaining = old_remaining.saturating_sub(1);
+                new_remaining = new_remaining.saturating_sub(1);
+                last_side = Some(' ');
+            }
+            Some(' ') => {
+                chunk.old_lines.push(line[1..].to_string());
+                chunk.new_lines.push(line[1..].to_string());
+                old_remaining = old_remaining.saturating_sub(1);
+                new_remaining = new_remaining.saturating_sub(1);
+                last_side = Some(' ');
+            }
+            Some('-') => {
+                chunk.old_lines.push(line[1..].to_string());
+                old_remaining = old_remaining.saturating_sub(1);
+                last_side = Some('-');
+            }
+            Some('+') => {
+                chunk.new_lines.push(line[1..].to_string());
+                new_remaining = new_remaining.saturating_sub(1);
+                last_side = Some('+');
+            }
+            Some('\\') => {}
+            _ => {
+                return Err(InvalidHunkError {
+                    message: format!(
+                        "Unexpected line found in unified diff hunk: '{line}'. Every line should start with ' ' (context line), '+' (added line), or '-' (removed line)"
+                    ),
+                    line_number: line_number + index,
+                });
+            }
+        }
+        index += 1;
+    }
+    if old_remaining > 0 || new_remaining > 0 {
+        return Err(InvalidHunkError {
+            message: format!("Unified diff hunk '{header}' ended before all lines were read"),
+            line_number,
+        });
+    }
+    // A trailing "\ No newline at end of file" belongs to the last line read.
+    if lines
+        .get(index)
+        .is_some_and(|line| line.starts_with(NO_NEWLINE_MARKER))
+    {
+        new_missing_newline = matches!(last_side, Some('+') | Some(' '));
+        chunk.is_end_of_file = true;
+        index += 1;
+    }
+    Ok((chunk, index, new_missing_newline))
+}
+
+/// Parses `@@ -a[,b] +c[,d] @@ ...` into the old start line and the old and
+/// new line counts.
+fn parse_hunk_range(header: &str) -> Option<(usize, usize, usize)> {
+    let rest = header.strip_prefix(HUNK_PREFIX)?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let (old_start, old_len) = parse_range(old)?;
+    let (_, new_len) = parse_range(new)?;
+    Some((old_start, old_len, new_len))
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+fn parse_mode(mode: &str, line_number: usize) -> Result<FileMode, ParseError> {
+    FileMode::from_git_mode(mode.trim()).ok_or_else(|| InvalidHunkError {
+        message: format!("Unsupported file mode '{}'", mode.trim()),
+        line_number,
+    })
+}
+
+/// Parses a `---`/`+++` path, dropping any trailing timestamp and, for git
+/// diffs, the `a/`/`b/` prefix.
+fn parse_header_path(raw: &str, is_git: bool) -> Option<String> {
+    let raw = raw.split('\t').next().unwrap_or(raw).trim_end();
+    let path = unquote_path(raw);
+    if path == DEV_NULL {
+        return Some(path);
+    }
+    if is_git || path.starts_with("a/") || path.starts_with("b/") {
+        return Some(strip_git_prefix(&path).to_string());
+    }
+    Some(path)
+}
+
+/// Splits the `a/<old> b/<new>` portion of a `diff --git` line.
+fn split_git_paths(rest: &str) -> Option<(String, String)> {
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let end = quoted.find('"')? + 2;
+        let old = unquote_path(&rest[..end]);
+        let new = unquote_path(rest[end..].trim_start());
+        return Some((
+            strip_git_prefix(&old).to_string(),
+            strip_git_prefix(&new).to_string(),
+        ));
+    }
+    // Without quoting the split is ambiguous when paths contain " b/"; prefer
+    // the split where both sides name the same file, as git does.
+    let candidates: Vec<usize> = rest.match_indices(" b/").map(|(idx, _)| idx).collect();
+    let split = candidates
+        .iter()
+        .copied()
+        .find(|idx| strip_git_prefix(&rest[..*idx]) == &rest[idx + 3..])
+        .or_else(|| candidates.first().copied())?;
+    let old = strip_git_prefix(&rest[..split]).to_string();
+    let new = unquote_path(&rest[split + 1..]);
+    Some((old, strip_git_prefix(&new).to_string()))
+}
+
+fn strip_git_prefix(path: &str) -> &str {
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+}
+
+/// Git quotes paths containing special characters using C-style escapes.
+fn unquote_path(path: &str) -> String {
+    let Some(inner) = path
+        .strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+    else {
+        return path.to_string();
+    };
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('"') => bytes.push(b'"'),
+            Some('\\') => bytes.push(b'\\'),
+            Some(d) if d.is_digit(8) => {
+                let mut value = d.to_digit(8).unwrap_or(0);
+                for _ in 0..2 {
+                    if let Some(next) = chars.peek().and_then(|c| c.to_digit(8)) {
+                        value = value * 8 + next;
+                        chars.next();
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => {
+                bytes.push(b'\\');
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+            }
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn hunks(patch: &str) -> Vec<Hunk> {
+        parse_unified_diff(patch).unwrap().hunks
+    }
+
+    #[test]
+    fn detects_unified_diffs() {
+        assert!(is_unified_diff("diff --git a/x b/x\n"));
+        assert!(is_unified_diff("--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n"));
+        assert!(!is_unified_diff("*** Begin Patch\n*** End Patch"));
+        assert!(!is_unified_diff("--- just a horizontal rule\n"));
+    }
+
+    #[test]
+    fn parses_plain_update() {
+        let patch = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,3 @@ fn main()\n one\n-two\n+TWO\n three\n";
+        assert_eq!(
+            hunks(patch),
+            vec![Hunk::UpdateFile {
+                path: PathBuf::from("src/lib.rs"),
+                move_path: None,
+                chunks: vec![UpdateFileChunk {
+                    change_context: None,
+                    old_lines: vec!["one".into(), "two".into(), "three".into()],
+                    new_lines: vec!["one".into(), "TWO".into(), "three".into()],
+                    is_end_of_file: false,
+                    orig_index: Some(0),
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_new_and_deleted_files() {
+        let patch = "diff --git a/new.txt b/new.txt\n\
+new file mode 100755\n\
+index 0000000..e69de29\n\
+--- /dev/null\n\
++++ b/new.txt\n\
+@@ -0,0 +1,2 @@\n\
++#!/bin/sh\n\
++echo hi\n\
+diff --git a/old.txt b/old.txt\n\
+deleted file mode 100644\n\
+index e69de29..0000000\n\
+--- a/old.txt\n\
++++ /dev/null\n\
+@@ -1 +0,0 @@\n\
+-bye\n";
+        assert_eq!(
+            hunks(patch),
+            vec![
+                Hunk::AddFile {
+                    path: PathBuf::from("new.txt"),
+                    contents: "#!/bin/sh\necho hi\n".to_string(),
+                },
+                Hunk::SetMode {
+                    path: PathBuf::from("new.txt"),
+                    mode: FileMode::Executable,
+                },
+                Hunk::DeleteFile {
+                    path: PathBuf::from("old.txt"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_rename_with_edit_and_mode_change() {
+        let patch = "diff --git a/run.sh b/bin/run.sh\n\
+old mode 100644\n\
+new mode 100755\n\
+similarity index 90%\n\
+rename from run.sh\n\
+rename to bin/run.sh\n\
+index 1111111..2222222\n\
+--- a/run.sh\n\
++++ b/bin/run.sh\n\
+@@ -1,2 +1,2 @@\n \
+#!/bin/sh\n\
+-echo old\n\
++echo new\n";
+        assert_eq!(
+            hunks(patch),
+            vec![
+                Hunk::UpdateFile {
+                    path: PathBuf::from("run.sh"),
+                    move_path: Some(PathBuf::from("bin/run.sh")),
+                    chunks: vec![UpdateFileChunk {
+                        change_context: None,
+                        old_lines: vec!["#!/bin/sh".into(), "echo old".into()],
+                        new_lines: vec!["#!/bin/sh".into(), "echo new".into()],
+                        is_end_of_file: false,
+                        orig_index: Some(0),
+                    }],
+                },
+                Hunk::SetMode {
+                    path: PathBuf::from("bin/run.sh"),
+                    mode: FileMode::Executable,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_pure_rename_and_mode_only_change() {
+        let patch = "diff --git a/logo.png b/assets/logo.png\n\
+similarity index 100%\n\
+rename from logo.png\n\
+rename to assets/logo.png\n\
+diff --git a/tool b/tool\n\
+old mode 100644\n\
+new mode 100755\n";
+        assert_eq!(
+            hunks(patch),
+            vec![
+                Hunk::UpdateFile {
+                    path: PathBuf::from("logo.png"),
+                    move_path: Some(PathBuf::from("assets/logo.png")),
+                    chunks: Vec::new(),
+                },
+                Hunk::SetMode {
+                    path: PathBuf::from("tool"),
+                    mode: FileMode::Executable,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_new_symlink() {
+        let patch = "diff --git a/link b/link\n\
+new file mode 120000\n\
+index 0000000..1111111\n\
+--- /dev/null\n\
++++ b/link\n\
+@@ -0,0 +1 @@\n\
++target.txt\n\
+\\ No newline at end of file\n";
+        assert_eq!(
+            hunks(patch),
+            vec![
+                Hunk::AddFile {
+                    path: PathBuf::from("link"),
+                    contents: "target.txt".to_string(),
+                },
+                Hunk::SetMode {
+                    path: PathBuf::from("link"),
+                    mode: FileMode::Symlink,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn allows_binary_delete_but_rejects_binary_edit() {
+        let delete = "diff --git a/img.png b/img.png\n\
+deleted file mode 100644\n\
+index 1111111..0000000\n\
+Binary files a/img.png and /dev/null differ\n";
+        assert_eq!(
+            hunks(delete),
+            vec![Hunk::DeleteFile {
+                path: PathBuf::from("img.png"),
+            }]
+        );
+
+        let edit = "diff --git a/img.png b/img.png\n\
+index 1111111..2222222 100644\n\
+GIT binary patch\n\
+literal 3\n\
+KcmZ?wWB>pF\n";
+        assert!(matches!(
+            parse_unified_diff(edit),
+            Err(InvalidHunkError { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_hunk() {
+        let patch = "--- a/x\n+++ b/x\n@@ -1,3 +1,3 @@\n a\n-b\n";
+        assert_eq!(
+            parse_unified_diff(patch),
+            Err(InvalidHunkError {
+                message: "Unified diff hunk '@@ -1,3 +1,3 @@' ended before all lines were read"
+                    .to_string(),
+                line_number: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn unquotes_git_paths() {
+        assert_eq!(unquote_path("\"caf\\303\\251.txt\""), "café.txt");
+        assert_eq!(
+            split_git_paths("a/dir b/x.txt b/dir b/x.txt"),
+            Some(("dir b/x.txt".to_string(), "dir b/x.txt".to_string()))
+        );
+    }
+}
@@ -255,3 +255,56 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
 
     Ok(())
 }
+
+#[test]
+fn test_apply_patch_cli_accepts_git_unified_diff() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    fs::write(tmp.path().join("modify.txt"), "line1\nline2\nline3\n")?;
+    fs::write(tmp.path().join("delete.txt"), "obsolete\n")?;
+
+    let patch = "diff --git a/modify.txt b/modify.txt\nindex 1111111..2222222 100644\n--- a/modify.txt\n+++ b/modify.txt\n@@ -1,3 +1,3 @@\n line1\n-line2\n+changed\n line3\ndiff --git a/nested/new.txt b/nested/new.txt\nnew file mode 100644\nindex 0000000..3333333\n--- /dev/null\n+++ b/nested/new.txt\n@@ -0,0 +1 @@\n+created\ndiff --git a/delete.txt b/delete.txt\ndeleted file mode 100644\nindex 4444444..0000000\n--- a/delete.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-obsolete\n";
+
+    run_apply_patch_in_dir(tmp.path(), patch)?.success().stdout(
+        "Success. Updated the following files:\nA nested/new.txt\nM modify.txt\nD delete.txt\n",
+    );
+
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("modify.txt"))?,
+        "line1\nchanged\nline3\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("nested/new.txt"))?,
+        "created\n"
+    );
+    assert!(!tmp.path().join("delete.txt").exists());
+
+    Ok(())
+}
+
+// Symlinks and mode bits from patches are only applied on Unix.
+#[cfg(unix)]
+#[test]
+fn test_apply_patch_cli_git_diff_renames_binary_and_sets_modes() -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempdir()?;
+    let binary = [0u8, 159, 146, 150, 255];
+    fs::write(tmp.path().join("logo.bin"), binary)?;
+    fs::write(tmp.path().join("run.sh"), "echo old\n")?;
+    fs::set_permissions(tmp.path().join("run.sh"), fs::Permissions::from_mode(0o664))?;
+
+    let patch = "diff --git a/logo.bin b/assets/logo.bin\nsimilarity index 100%\nrename from logo.bin\nrename to assets/logo.bin\ndiff --git a/run.sh b/run.sh\nold mode 100644\nnew mode 100755\nindex 1111111..2222222\n--- a/run.sh\n+++ b/run.sh\n@@ -1 +1 @@\n-echo old\n+echo new\ndiff --git a/link b/link\nnew file mode 120000\nindex 0000000..3333333\n--- /dev/null\n+++ b/link\n@@ -0,0 +1 @@\n+run.sh\n\\ No newline at end of file\n";
+
+    run_apply_patch_in_dir(tmp.path(), patch)?
+        .success()
+        .stdout("Success. Updated the following files:\nA link\nM assets/logo.bin\nM run.sh\n");
+
+    assert_eq!(fs::read(tmp.path().join("assets/logo.bin"))?, binary);
+    assert!(!tmp.path().join("logo.bin").exists());
+    let run_sh = tmp.path().join("run.sh");
+    assert_eq!(fs::read_to_string(&run_sh)?, "echo new\n");
+    assert_eq!(fs::metadata(&run_sh)?.permissions().mode() & 0o777, 0o775);
+    assert_eq!(fs::read_link(tmp.path().join("link"))?, Path::new("run.sh"));
+
+    Ok(())
+}
@@ -141,6 +141,8 @@ pub async fn run(
     refresh_model_metadata(&mut config);
     codex_agentic_core::provider::sanitize_reasoning_overrides(&mut config);
     codex_agentic_core::provider::sanitize_tool_overrides(&mut config);
+    codex_agentic_core::provider::apply_provider_fallbacks(&mut config);
+    codex_agentic_core::provider::discover_model_limits(&mut config).await;
 
     if let Err(err) = init_global_prompt(&command_ctx.settings) {
         eprintln!("warning: failed to load system prompt overlay while starting ACP: {err}");
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::anyhow;
+use clap::Parser;
+use clap::Subcommand;
+use codex_common::CliConfigOverrides;
+use codex_core::HistorySearchHit;
+use codex_core::HistorySearchQuery;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::enforce_retention;
+use codex_core::search_history;
+
+#[derive(Debug, Parser)]
+pub struct HistoryCli {
+    #[command(subcommand)]
+    action: HistoryAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum HistoryAction {
+    /// Search previous sessions by text, directory, model, date, or tool.
+    Search(SearchArgs),
+    /// Apply the session retention policy: delete, compress, and trim rollouts.
+    Prune(PruneArgs),
+}
+
+#[derive(Debug, Parser)]
+struct SearchArgs {
+    /// Terms that must all appear in the same turn.
+    query: Option<String>,
+    /// Only turns run in this directory or below it.
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+    /// Only turns whose model slug contains this value.
+    #[arg(long)]
+    model: Option<String>,
+    /// Only turns on or after this date (YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+    /// Only turns on or before this date (YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+    /// Only turns that called this tool (e.g. `shell`, `apply_patch`).
+    #[arg(long)]
+    tool: Option<String>,
+    /// Maximum number of results to display.
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Output as JSON for scripting.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Parser)]
+struct PruneArgs {
+    /// Report what would change without touching any files.
+    #[arg(long)]
+    dry_run: bool,
+    /// Delete sessions idle for more than this many days.
+    /// Overrides `session_retention.max_age_days`.
+    #[arg(long, value_name = "DAYS")]
+    max_age_days: Option<u64>,
+    /// Delete the oldest sessions until all rollouts fit in this size
+    /// (e.g. `500M`, `2G`). Overrides `session_retention.max_total_bytes`.
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    max_total_size: Option<u64>,
+    /// Compress sessions idle for more than this many days.
+    /// Overrides `session_retention.compress_after_days`.
+    #[arg(long, value_name = "DAYS")]
+    compress_after_days: Option<u64>,
+    /// Output the report as JSON for scripting.
+    #[arg(long)]
+    json: bool,
+}
+
+pub async fn run(
+    history_cli: HistoryCli,
+    root_overrides: CliConfigOverrides,
+) -> anyhow::Result<()> {
+    let overrides = root_overrides
+        .parse_overrides()
+        .map_err(|err| anyhow!("invalid config override: {err}"))?;
+    let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default()).await?;
+
+    match history_cli.action {
+        HistoryAction::Search(args) => search(config, args).await?,
+        HistoryAction::Prune(args) => prune(config, args).await?,
+    }
+
+    Ok(())
+}
+
+async fn search(config: Config, args: SearchArgs) -> anyhow::Result<()> {
+    for date in [&args.since, &args.until].into_iter().flatten() {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .with_context(|| format!("invalid date `{date}`; expected YYYY-MM-DD"))?;
+    }
+    let cwd = match args.cwd {
+        Some(cwd) => Some(std::path::absolute(&cwd).unwrap_or(cwd)),
+        None => None,
+    };
+    let query = HistorySearchQuery {
+        text: args.query,
+        cwd,
+        model: args.model,
+        since: args.since,
+        until: args.until,
+        tool: args.tool,
+        limit: args.limit,
+    };
+    let hits = search_history(&config.codex_home, &query)
+        .await
+        .context("history search failed")?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+    } else {
+        print_hits(&hits);
+    }
+    Ok(())
+}
+
+fn print_hits(hits: &[HistorySearchHit]) {
+    if hits.is_empty() {
+        println!("No matching turns found.");
+        return;
+    }
+    for hit in hits {
+        let timestamp = hit.timestamp.get(..19).unwrap_or(&hit.timestamp);
+        let model = hit.model.as_deref().unwrap_or("-");
+        println!(
+            "{timestamp}  {}  turn {}  {model}",
+            hit.conversation_id, hit.turn_index
+        );
+        if let Some(cwd) = &hit.cwd {
+            println!("    cwd: {}", cwd.display());
+        }
+        if !hit.tools.is_empty() {
+            println!("    tools: {}", hit.tools.join(", "));
+        }
+        println!("    {}", hit.snippet);
+        println!(
+            "    resume: codex resume {} --turn {}",
+            hit.conversation_id, hit.turn_index
+        );
+        println!();
+    }
+}
+
+async fn prune(config: Config, args: PruneArgs) -> anyhow::Result<()> {
+    let mut policy = config.session_retention.clone();
+    if args.max_age_days.is_some() {
+        policy.max_age_days = args.max_age_days;
+    }
+    if args.max_total_size.is_some() {
+        policy.max_total_bytes = args.max_total_size;
+    }
+    if args.compress_after_days.is_some() {
+        policy.compress_after_days = args.compress_after_days;
+    }
+    if policy.max_age_days.is_none()
+        && policy.max_total_bytes.is_none()
+        && policy.compress_after_days.is_none()
+    {
+        anyhow::bail!(
+            "no retention policy configured; set [session_retention] in config.toml or pass --max-age-days, --max-total-size, or --compress-after-days"
+        );
+    }
+
+    let report = enforce_retention(&config.codex_home, &policy, args.dry_run)
+        .await
+        .context("failed to apply retention policy")?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    let verb = if args.dry_run {
+        "Would delete"
+    } else {
+        "Deleted"
+    };
+    for path in &report.deleted {
+        println!("{verb} {}", path.display());
+    }
+    let verb = if args.dry_run {
+        "Would compress"
+    } else {
+        "Compressed"
+    };
+    for path in &report.compressed {
+        println!("{verb} {}", path.display());
+    }
+    println!(
+        "{} deleted, {} compressed; sessions use {} (was {}).",
+        report.deleted.len(),
+        report.compressed.len(),
+        format_size(report.bytes_after),
+        format_size(report.bytes_before)
+    );
+    Ok(())
+}
+
+/// Parse a byte size with an optional `K`, `M`, or `G` suffix (powers of 1024).
+fn parse_size(raw: &str) -> Result<u64, String> {
+    let trimmed = raw.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let digits = upper.trim_end_matches('B');
+    let (number, multiplier) = match digits.chars().last() {
+        Some('K') => (&digits[..digits.len() - 1], 1024),
+        Some('M') => (&digits[..digits.len() - 1], 1024 * 1024),
+        Some('G') => (&digits[..digits.len() - 1], 1024 * 1024 * 1024),
+        _ => (digits, 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|value| value.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size `{trimmed}`; expected e.g. 500M or 2G"))
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_size_accepts_suffixes() {
+        assert_eq!(parse_size("1024"), Ok(1024));
+        assert_eq!(parse_size("2K"), Ok(2048));
+        assert_eq!(parse_size("500m"), Ok(500 * 1024 * 1024));
+        assert_eq!(parse_size("1GB"), Ok(1024 * 1024 * 1024));
+        assert!(parse_size("lots").is_err());
+    }
+}
@@ -33,12 +33,16 @@ use supports_color::Stream;
 
 mod acp_cmd;
 mod agentic_commands;
+mod history_cmd;
 mod mcp_cmd;
 mod memory_cmd;
+mod oss_cmd;
 
 use crate::acp_cmd::AcpCli;
+use crate::history_cmd::HistoryCli;
 use crate::mcp_cmd::McpCli;
 use crate::memory_cmd::MemoryCli;
+use crate::oss_cmd::OssCli;
 use agentic_commands::build_cli_registry;
 use agentic_commands::command_output_to_string;
 use codex_core::config::Config;
@@ -153,6 +157,12 @@ enum Subcommand {
 
     /// Manage the global memory store.
     Memory(MemoryCli),
+
+    /// Search or prune recorded session history.
+    History(HistoryCli),
+
+    /// Manage local models served by Ollama (`--oss`).
+    Oss(OssCli),
 }
 
 #[derive(Debug, Parser)]
@@ -173,6 +183,26 @@ struct ResumeCommand {
     #[arg(long = "last", default_value_t = false, conflicts_with = "session_id")]
     last: bool,
 
+    /// Branch into a new session that starts at this user turn (1-based) of
+    /// the selected session. Earlier turns are kept; the original session is
+    /// left unchanged.
+    #[arg(long = "fork-at", value_name = "TURN", conflicts_with = "turn")]
+    fork_at: Option<usize>,
+
+    /// Open the transcript at this user turn (1-based) after resuming, e.g.
+    /// a turn reported by `codex history search`.
+    #[arg(long = "turn", value_name = "TURN")]
+    turn: Option<usize>,
+
+    /// With --fork-at, reset the working tree to the ghost snapshot captured
+    /// at the start of that turn, if one was recorded.
+    #[arg(
+        long = "restore-snapshot",
+        default_value_t = false,
+        requires = "fork_at"
+    )]
+    restore_snapshot: bool,
+
     #[clap(flatten)]
     config_overrides: TuiCli,
 }
@@ -529,20 +559,20 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         Some(Subcommand::Memory(memory_cli)) => {
             memory_cmd::run(memory_cli, root_config_overrides.clone()).await?;
         }
+        Some(Subcommand::History(history_cli)) => {
+            history_cmd::run(history_cli, root_config_overrides.clone()).await?;
+        }
+        Some(Subcommand::Oss(oss_cli)) => {
+            oss_cmd::run(oss_cli, root_config_overrides.clone()).await?;
+        }
         Some(Subcommand::AppServer) => {
             codex_app_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
-        Some(Subcommand::Resume(ResumeCommand {
-            session_id,
-            last,
-            config_overrides,
-        })) => {
+        Some(Subcommand::Resume(resume_command)) => {
             interactive = finalize_resume_interactive(
                 interactive,
                 root_config_overrides.clone(),
-                session_id,
-                last,
-                config_overrides,
+                resume_command,
             );
             let exit_info = codex_tui::run_main(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
@@ -727,16 +757,25 @@ fn prepend_config_flags(
 fn finalize_resume_interactive(
     mut interactive: TuiCli,
     root_config_overrides: CliConfigOverrides,
-    session_id: Option<String>,
-    last: bool,
-    resume_cli: TuiCli,
+    resume_command: ResumeCommand,
 ) -> TuiCli {
+    let ResumeCommand {
+        session_id,
+        last,
+        fork_at,
+        turn,
+        restore_snapshot,
+        config_overrides: resume_cli,
+    } = resume_command;
     // Start with the parsed interactive CLI so resume shares the same
     // configuration surface area as `codex` without additional flags.
     let resume_session_id = session_id;
     interactive.resume_picker = resume_session_id.is_none() && !last;
     interactive.resume_last = last;
     interactive.resume_session_id = resume_session_id;
+    interactive.resume_fork_at = fork_at;
+    interactive.resume_turn = turn;
+    interactive.resume_restore_snapshot = restore_snapshot;
 
     // Merge resume-scoped flags and overrides with highest precedence.
     merge_resume_cli_flags(&mut interactive, resume_cli);
@@ -897,16 +936,11 @@ mod tests {
             subcommand,
         } = cli;
 
-        let Subcommand::Resume(ResumeCommand {
-            session_id,
-            last,
-            config_overrides: resume_cli,
-        }) = subcommand.expect("resume present")
-        else {
+        let Subcommand::Resume(resume_command) = subcommand.expect("resume present") else {
             unreachable!()
         };
 
-        finalize_resume_interactive(interactive, root_overrides, session_id, last, resume_cli)
+        finalize_resume_interactive(interactive, root_overrides, resume_command)
     }
 
     fn sample_exit_info(conversation: Option<&str>) -> AppExitInfo {
@@ -983,6 +1017,50 @@ mod tests {
         assert_eq!(interactive.resume_session_id, None);
     }
 
+    #[test]
+    fn resume_fork_at_sets_fork_fields() {
+        let interactive = finalize_from_args(
+            [
+                "codex",
+                "resume",
+                "1234",
+                "--fork-at",
+                "3",
+                "--restore-snapshot",
+            ]
+            .as_ref(),
+        );
+        assert_eq!(interactive.resume_session_id.as_deref(), Some("1234"));
+        assert_eq!(interactive.resume_fork_at, Some(3));
+        assert!(interactive.resume_restore_snapshot);
+    }
+
+    #[test]
+    fn resume_turn_sets_jump_target() {
+        let interactive = finalize_from_args(["codex", "resume", "1234", "--turn", "2"].as_ref());
+        assert_eq!(interactive.resume_session_id.as_deref(), Some("1234"));
+        assert_eq!(interactive.resume_turn, Some(2));
+        assert_eq!(interactive.resume_fork_at, None);
+
+        let result = MultitoolCli::try_parse_from([
+            "codex",
+            "resume",
+            "1234",
+            "--turn",
+            "2",
+            "--fork-at",
+            "2",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn resume_restore_snapshot_requires_fork_at() {
+        let result =
+            MultitoolCli::try_parse_from(["codex", "resume", "1234", "--restore-snapshot"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn resume_picker_logic_with_session_id() {
         let interactive = finalize_from_args(["codex", "resume", "1234"].as_ref());
@@ -0,0 +1,211 @@
+use anyhow::Context;
+use anyhow::anyhow;
+use clap::Parser;
+use clap::Subcommand;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_ollama::CliProgressReporter;
+use codex_ollama::LocalModel;
+use codex_ollama::OllamaClient;
+use codex_ollama::RunningModel;
+
+use crate::history_cmd::format_size;
+
+#[derive(Debug, Parser)]
+pub struct OssCli {
+    #[command(subcommand)]
+    action: OssAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum OssAction {
+    /// Manage the models stored by the local Ollama server.
+    Models(ModelsCli),
+}
+
+#[derive(Debug, Parser)]
+struct ModelsCli {
+    #[command(subcommand)]
+    action: ModelsAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum ModelsAction {
+    /// List local models with their disk usage and quantization.
+    List(ListArgs),
+    /// Download a model from the Ollama library.
+    Pull(ModelArg),
+    /// Delete a local model.
+    Rm(ModelArg),
+    /// Show a model's family, quantization, context window and parameters.
+    Show(ModelArg),
+}
+
+#[derive(Debug, Parser)]
+struct ListArgs {
+    /// Output as JSON for scripting.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Parser)]
+struct ModelArg {
+    /// Model name, e.g. `gpt-oss:20b`.
+    model: String,
+}
+
+pub async fn run(oss_cli: OssCli, root_overrides: CliConfigOverrides) -> anyhow::Result<()> {
+    let overrides = root_overrides
+        .parse_overrides()
+        .map_err(|err| anyhow!("invalid config override: {err}"))?;
+    let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default()).await?;
+    let client = OllamaClient::try_from_oss_provider(&config).await?;
+
+    let OssAction::Models(models_cli) = oss_cli.action;
+    match models_cli.action {
+        ModelsAction::List(args) => list(&client, args).await?,
+        ModelsAction::Pull(ModelArg { model }) => {
+            let mut reporter = CliProgressReporter::new();
+            client.pull_with_reporter(&model, &mut reporter).await?;
+            println!("Pulled {model}.");
+        }
+        ModelsAction::Rm(ModelArg { model }) => {
+            client.delete_model(&model).await?;
+            println!("Removed {model}.");
+        }
+        ModelsAction::Show(ModelArg { model }) => show(&client, &model).await?,
+    }
+
+    Ok(())
+}
+
+async fn list(client: &OllamaClient, args: ListArgs) -> anyhow::Result<()> {
+    let models = client
+        .fetch_local_models()
+        .await
+        .context("failed to list local models")?;
+    // Loaded state is informational; older servers do not expose `/api/ps`.
+    let running = client.fetch_running_models().await.unwrap_or_default();
+
+    if args.json {
+        let rows: Vec<_> = models
+            .iter()
+            .map(|model| {
+                let loaded = running_entry(&running, &model.name);
+                serde_json::json!({
+                    "name": model.name,
+                    "size": model.size,
+                    "modified_at": model.modified_at,
+                    "digest": model.digest,
+                    "family": model.spec.family,
+                    "parameter_size": model.spec.parameter_size,
+                    "quantization_level": model.spec.quantization_level,
+                    "format": model.spec.format,
+                    "loaded": loaded.is_some(),
+                    "expires_at": loaded.and_then(|running| running.expires_at.clone()),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if models.is_empty() {
+        println!("No local models. Download one with `codex oss models pull <model>`.");
+        return Ok(());
+    }
+    print_models(&models, &running);
+    Ok(())
+}
+
+fn print_models(models: &[LocalModel], running: &[RunningModel]) {
+    let name_width = models
+        .iter()
+        .map(|model| model.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+    println!(
+        "{:<name_width$}  {:>10}  {:>7}  {:<10}  LOADED",
+        "NAME", "SIZE", "PARAMS", "QUANT"
+    );
+    for model in models {
+        let loaded = if running_entry(running, &model.name).is_some() {
+            "yes"
+        } else {
+            "-"
+        };
+        println!(
+            "{:<name_width$}  {:>10}  {:>7}  {:<10}  {loaded}",
+            model.name,
+            format_size(model.size),
+            model.spec.parameter_size.as_deref().unwrap_or("-"),
+            model.spec.quantization_level.as_deref().unwrap_or("-"),
+        );
+    }
+    let total: u64 = models.iter().map(|model| model.size).sum();
+    println!();
+    println!(
+        "{} model(s) using {} of disk.",
+        models.len(),
+        format_size(total)
+    );
+}
+
+async fn show(client: &OllamaClient, model: &str) -> anyhow::Result<()> {
+    let details = client.fetch_model_details(model).await?;
+    let local = client
+        .fetch_local_models()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|local| local.name == model);
+    let running = client.fetch_running_models().await.unwrap_or_default();
+
+    println!("{model}");
+    let spec = &details.spec;
+    for (label, value) in [
+        ("family", spec.family.as_deref()),
+        ("parameters", spec.parameter_size.as_deref()),
+        ("quantization", spec.quantization_level.as_deref()),
+        ("format", spec.format.as_deref()),
+    ] {
+        if let Some(value) = value {
+            println!("  {label:<14}{value}");
+        }
+    }
+    if let Some(local) = &local {
+        println!("  {:<14}{}", "disk usage", format_size(local.size));
+    }
+    if let Some(context_window) = details.context_window() {
+        println!("  {:<14}{context_window} tokens", "context");
+    }
+    if let Some(max_output_tokens) = details.max_output_tokens() {
+        println!("  {:<14}{max_output_tokens} tokens", "max output");
+    }
+    match running_entry(&running, model) {
+        Some(running) => println!(
+            "  {:<14}yes ({} in memory{})",
+            "loaded",
+            format_size(running.size),
+            running
+                .expires_at
+                .as_deref()
+                .map(|expires| format!(", until {expires}"))
+                .unwrap_or_default()
+        ),
+        None => println!("  {:<14}no", "loaded"),
+    }
+    if !details.parameters.is_empty() {
+        println!("  modelfile parameters:");
+        for (key, value) in &details.parameters {
+            println!("    {key} {value}");
+        }
+    }
+    Ok(())
+}
+
+fn running_entry<'a>(running: &'a [RunningModel], model: &str) -> Option<&'a RunningModel> {
+    running.iter().find(|entry| entry.name == model)
+}
@@ -1295,7 +1295,9 @@ impl RuntimeState {
                     stop_reason = "end_turn".to_string();
                     break;
                 }
-                EventMsg::TokenCount(TokenCountEvent { info, rate_limits }) => {
+                EventMsg::TokenCount(TokenCountEvent {
+                    info, rate_limits, ..
+                }) => {
                     session_state.last_usage = info.clone();
                     if let Some(snapshot) = rate_limits {
                         session_state.rate_limits = Some(snapshot);
@@ -1327,6 +1329,7 @@ impl RuntimeState {
                         TurnAbortReason::Interrupted => "cancelled".to_string(),
                         TurnAbortReason::Replaced => "replaced".to_string(),
                         TurnAbortReason::ReviewEnded => "review-ended".to_string(),
+                        TurnAbortReason::BudgetExceeded => "budget-exceeded".to_string(),
                     };
                     self.send_agent_message(
                         session_id,
@@ -2,6 +2,7 @@ use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
 use chrono::Utc;
+use codex_core::config::types::Budget;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -18,7 +19,7 @@ const INSTANCES_DIR: &str = "instances";
 const RUN_FILE: &str = "run.json";
 
 /// Primary data structure describing an agent persona.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 #[derive(Default)]
 pub struct AgentProfile {
@@ -46,6 +47,10 @@ pub struct AgentProfile {
     pub last_run_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_run_summary: Option<String>,
+    /// Token and cost limits applied to runs of this agent instead of the
+    /// `[budget]` table in `config.toml`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Budget>,
 }
 
 impl AgentProfile {
@@ -632,6 +637,7 @@ mod tests {
             updated_at: None,
             last_run_at: None,
             last_run_summary: None,
+            budget: None,
         };
 
         profile = store.upsert_profile(profile).unwrap();
@@ -38,6 +38,7 @@ pub use prompt::init_global_from_settings as init_global_prompt;
 pub use prompt::overlay_from_settings;
 pub use provider::DEFAULT_OLLAMA_ENDPOINT;
 pub use provider::OSS_PROVIDER_ID;
+pub use provider::ProviderModels;
 pub use provider::custom_provider_model_info;
 pub use provider::custom_providers;
 pub use provider::fetch_custom_provider_models;
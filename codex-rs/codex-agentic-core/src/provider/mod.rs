@@ -6,12 +6,15 @@ use codex_core::config_types::ProviderKind;
 use codex_core::default_client::create_client;
 use codex_core::features::Feature;
 use codex_core::features::Features;
+use codex_core::get_model_info;
 use codex_core::model_family::find_family_for_model;
 use codex_core::oss_model_supports_tools;
+use once_cell::sync::Lazy;
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::sync::Mutex;
 use tokio::runtime::Builder;
 use tokio::runtime::Handle;
 use tokio::task::block_in_place;
@@ -421,6 +424,125 @@ pub fn sanitize_reasoning_overrides(config: &mut Config) {
     }
 }
 
+/// Use the fallback chain declared in `settings.json` by the active custom
+/// provider, if any, instead of the one from `config.toml`.
+pub fn apply_provider_fallbacks(config: &mut Config) {
+    config.model_failover.provider_fallbacks = crate::settings::global()
+        .custom_provider(&config.model_provider_id)
+        .map(|custom| custom.fallbacks.clone())
+        .unwrap_or_default();
+}
+
+/// Context limits served by Ollama, keyed by provider base URL and model.
+static PROBED_LIMITS: Lazy<Mutex<HashMap<String, ModelLimits>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ModelLimits {
+    context_window: Option<i64>,
+    max_output_tokens: Option<i64>,
+}
+
+fn limits_cache_key(config: &Config) -> String {
+    format!(
+        "{}#{}",
+        config
+            .model_provider
+            .base_url
+            .as_deref()
+            .unwrap_or_default(),
+        config.model
+    )
+}
+
+fn serves_from_ollama(config: &Config) -> bool {
+    config.model_provider_id == OSS_PROVIDER_ID
+        || crate::settings::global()
+            .custom_provider(&config.model_provider_id)
+            .is_some_and(|custom| matches!(custom.provider_kind, ProviderKind::Ollama))
+}
+
+/// Apply the context limits known for the active model: values declared by
+/// the custom provider in `settings.json` (or probed from its `/models`
+/// listing) win over limits discovered from Ollama, which in turn win over the
+/// built-in model table. Limits the user set in `config.toml` or with `-c`
+/// (anything other than the built-in table's value) are left alone.
+pub fn apply_model_limits(config: &mut Config) {
+    let probed = PROBED_LIMITS
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&limits_cache_key(config)).copied())
+        .unwrap_or_default();
+    let declared = crate::settings::global()
+        .custom_provider(&config.model_provider_id)
+        .map(|custom| ModelLimits {
+            context_window: custom
+                .context_window
+                .or_else(|| custom.model_context_windows.get(&config.model).copied()),
+            max_output_tokens: custom.max_output_tokens,
+        })
+        .unwrap_or_default();
+    let builtin = get_model_info(&config.model_family);
+    let builtin_window = builtin.as_ref().map(|info| info.context_window());
+    let builtin_max_output = builtin.as_ref().map(|info| info.max_output_tokens());
+
+    if let Some(window) = declared.context_window.or(probed.context_window)
+        && (config.model_context_window.is_none() || config.model_context_window == builtin_window)
+    {
+        config.model_context_window = Some(window);
+        // Compact before the window fills up, even when the built-in table
+        // assumed a larger window for this model.
+        let compact_limit = window.saturating_mul(9) / 10;
+        config.model_auto_compact_token_limit = Some(
+            config
+                .model_auto_compact_token_limit
+                .map_or(compact_limit, |limit| limit.min(compact_limit)),
+        );
+    }
+    if let Some(max_output) = declared.max_output_tokens.or(probed.max_output_tokens)
+        && (config.model_max_output_tokens.is_none()
+            || config.model_max_output_tokens == builtin_max_output)
+    {
+        config.model_max_output_tokens = Some(max_output);
+    }
+}
+
+/// Ask Ollama for the context window it serves the active model with
+/// (`/api/show`), remember the answer for later model switches, and apply it
+/// with [`apply_model_limits`]. Providers other than Ollama are left alone.
+pub async fn discover_model_limits(config: &mut Config) {
+    if serves_from_ollama(config) && config.model_provider.base_url.is_some() {
+        let key = limits_cache_key(config);
+        let cached = PROBED_LIMITS
+            .lock()
+            .is_ok_and(|cache| cache.contains_key(&key));
+        if !cached {
+            let details =
+                match codex_ollama::OllamaClient::try_from_provider_info(&config.model_provider)
+                    .await
+                {
+                    Ok(client) => client.fetch_model_details(&config.model).await,
+                    Err(err) => Err(err),
+                };
+            match details {
+                Ok(details) => {
+                    let limits = ModelLimits {
+                        context_window: details.context_window(),
+                        max_output_tokens: details.max_output_tokens(),
+                    };
+                    if let Ok(mut cache) = PROBED_LIMITS.lock() {
+                        cache.insert(key, limits);
+                    }
+                }
+                Err(err) => {
+                    tracing::debug!("could not read model details for {}: {err}", config.model);
+                }
+            }
+        }
+    }
+    apply_model_limits(config);
+}
+
 /// Disable tool surfaces that the active provider cannot support.
 pub fn sanitize_tool_overrides(config: &mut Config) {
     let provider_allows_tools = config.provider_allows_tool_calls();
@@ -474,13 +596,38 @@ struct ListModelsResponse {
 #[derive(Deserialize)]
 struct ModelEntry {
     id: String,
+    /// OpenRouter, LM Studio and most gateways.
+    #[serde(default)]
+    context_length: Option<i64>,
+    /// vLLM.
+    #[serde(default)]
+    max_model_len: Option<i64>,
+    #[serde(default)]
+    context_window: Option<i64>,
+}
+
+impl ModelEntry {
+    fn context_window(&self) -> Option<i64> {
+        self.context_length
+            .or(self.max_model_len)
+            .or(self.context_window)
+            .filter(|window| *window > 0)
+    }
+}
+
+/// Models listed by a custom provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderModels {
+    pub models: Vec<String>,
+    /// Context windows for the models whose listing entry reports one.
+    pub context_windows: BTreeMap<String, i64>,
 }
 
 pub async fn fetch_custom_provider_models(
     provider_id: &str,
     provider: &CustomProvider,
     api_key: Option<&str>,
-) -> Result<Vec<String>> {
+) -> Result<ProviderModels> {
     let client = create_client();
 
     if matches!(provider.provider_kind, ProviderKind::Ollama) {
@@ -537,7 +684,10 @@ pub async fn fetch_custom_provider_models(
 
         models.sort();
         models.dedup();
-        return Ok(models);
+        return Ok(ProviderModels {
+            models,
+            context_windows: BTreeMap::new(),
+        });
     }
 
     let api_key = api_key.ok_or_else(|| {
@@ -574,16 +724,22 @@ pub async fn fetch_custom_provider_models(
         ));
     }
 
-    let mut models: Vec<String> = response
+    let entries = response
         .json::<ListModelsResponse>()
         .await
         .with_context(|| format!("failed to parse /models response for provider {provider_id}"))?
-        .data
-        .into_iter()
-        .map(|entry| entry.id)
+        .data;
+
+    let context_windows = entries
+        .iter()
+        .filter_map(|entry| Some((entry.id.clone(), entry.context_window()?)))
         .collect();
+    let mut models: Vec<String> = entries.into_iter().map(|entry| entry.id).collect();
 
     models.sort();
     models.dedup();
-    Ok(models)
+    Ok(ProviderModels {
+        models,
+        context_windows,
+    })
 }
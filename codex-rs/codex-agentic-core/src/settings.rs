@@ -2,6 +2,7 @@ use crate::provider::DEFAULT_OLLAMA_ENDPOINT;
 use anyhow::Context;
 use anyhow::Result;
 use codex_core::WireApi;
+use codex_core::config_types::ModelFallback;
 use codex_core::config_types::ProviderKind;
 use codex_core::config_types::ProviderReasoningControls;
 use codex_ollama::DEFAULT_OSS_MODEL;
@@ -163,6 +164,20 @@ pub struct CustomProvider {
     pub provider_kind: ProviderKind,
     #[serde(default, skip_serializing_if = "ProviderReasoningControls::is_default")]
     pub reasoning_controls: ProviderReasoningControls,
+    /// Ordered fallback chain used while this provider is active.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<ModelFallback>,
+    /// Context window of this provider's models, in tokens. Wins over
+    /// probed and built-in values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<i64>,
+    /// Maximum output tokens of this provider's models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+    /// Context windows reported by the provider's `/models` listing, keyed by
+    /// model id. Refreshed together with `cached_models`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub model_context_windows: BTreeMap<String, i64>,
 }
 
 impl Default for CustomProvider {
@@ -179,6 +194,10 @@ impl Default for CustomProvider {
             plan_tool_enabled: false,
             provider_kind: ProviderKind::default(),
             reasoning_controls: ProviderReasoningControls::default(),
+            fallbacks: Vec::new(),
+            context_window: None,
+            max_output_tokens: None,
+            model_context_windows: BTreeMap::new(),
         }
     }
 }
@@ -603,6 +622,7 @@ impl Settings {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_core::protocol_config_types::FailoverTrigger;
     use std::collections::BTreeMap;
 
     #[test]
@@ -669,6 +689,65 @@ mod tests {
         assert!(!custom.reasoning_controls.think_enabled);
     }
 
+    #[test]
+    fn custom_provider_can_select_messages_wire_api() {
+        let mut settings: Settings = serde_json::from_value(serde_json::json!({
+            "providers": {
+                "custom": {
+                    "anthropic": {
+                        "name": "Anthropic",
+                        "base_url": "https://api.anthropic.com/v1",
+                        "wire_api": "messages",
+                    }
+                }
+            }
+        }))
+        .expect("settings parse");
+
+        super::run_migrations(&mut settings);
+
+        let custom = settings
+            .custom_provider("anthropic")
+            .expect("anthropic provider present");
+        assert_eq!(custom.wire_api(), WireApi::Messages);
+        assert_eq!(custom.provider_kind, ProviderKind::AnthropicClaude);
+    }
+
+    #[test]
+    fn custom_provider_fallbacks_round_trip() {
+        let settings: Settings = serde_json::from_value(serde_json::json!({
+            "providers": {
+                "custom": {
+                    "gateway": {
+                        "name": "Gateway",
+                        "base_url": "https://gateway.example.com/v1",
+                        "fallbacks": [
+                            { "provider": "anthropic", "model": "claude-sonnet-4-5", "on": ["server_error"] },
+                            { "model": "gateway-long-context", "on": ["context_window"] }
+                        ]
+                    }
+                }
+            }
+        }))
+        .expect("settings parse");
+
+        let custom = settings
+            .custom_provider("gateway")
+            .expect("gateway provider present");
+        assert_eq!(custom.fallbacks.len(), 2);
+        assert_eq!(custom.fallbacks[0].provider.as_deref(), Some("anthropic"));
+        assert_eq!(custom.fallbacks[0].on, vec![FailoverTrigger::ServerError]);
+        assert_eq!(custom.fallbacks[1].provider, None);
+
+        let value = serde_json::to_value(custom).expect("serialize provider");
+        assert_eq!(
+            value["fallbacks"][1],
+            serde_json::json!({ "model": "gateway-long-context", "on": ["context_window"] })
+        );
+        let plain = serde_json::to_value(CustomProvider::default()).expect("serialize default");
+        assert!(plain.get("fallbacks").is_none());
+    }
+
     #[test]
     fn auto_build_index_defaults_to_true_and_respects_override() {
         let settings = Settings::default();
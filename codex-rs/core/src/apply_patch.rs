@@ -7,7 +7,9 @@ use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
@@ -31,6 +33,18 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// Set when the user approved only some hunks; appended to the tool
+    /// output so the model knows what was left out.
+    pub(crate) rejected_hunks_note: Option<String>,
+}
+
+impl ApplyPatchExec {
+    pub(crate) fn annotate_output(&self, content: String) -> String {
+        match &self.rejected_hunks_note {
+            Some(note) => format!("{content}\n\n{note}"),
+            None => content,
+        }
+    }
 }
 
 pub(crate) async fn apply_patch(
@@ -51,6 +65,7 @@ pub(crate) async fn apply_patch(
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            rejected_hunks_note: None,
         }),
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
@@ -60,21 +75,44 @@ pub(crate) async fn apply_patch(
             // give the user the option to expand the set of writable roots so
             // that similar patches can be auto-approved in the future during
             // this session.
+            let changes = convert_apply_patch_to_protocol(&action);
             let rx_approve = sess
                 .request_patch_approval(
                     turn_context,
                     call_id.to_owned(),
-                    convert_apply_patch_to_protocol(&action),
+                    changes.clone(),
                     None,
                     None,
                 )
                 .await;
             match rx_approve.await.unwrap_or_default() {
                 ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
-                    InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
-                        action,
-                        user_explicitly_approved_this_action: true,
-                    })
+                    let rejected_hunks = sess.take_rejected_patch_hunks(&turn_context.sub_id).await;
+                    if rejected_hunks.values().all(Vec::is_empty) {
+                        return InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                            action,
+                            user_explicitly_approved_this_action: true,
+                            rejected_hunks_note: None,
+                        });
+                    }
+                    let note = rejected_hunks_note(&changes, &rejected_hunks, &action.cwd);
+                    match retain_accepted_hunks(&action, &changes, &rejected_hunks) {
+                        Ok(Some(accepted)) => {
+                            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                                action: accepted,
+                                user_explicitly_approved_this_action: true,
+                                rejected_hunks_note: Some(note),
+                            })
+                        }
+                        Ok(None) => InternalApplyPatchInvocation::Output(Err(
+                            FunctionCallError::RespondToModel("patch rejected by user".to_string()),
+                        )),
+                        Err(err) => InternalApplyPatchInvocation::Output(Err(
+                            FunctionCallError::RespondToModel(format!(
+                                "failed to apply the hunks approved by the user: {err}\n\n{note}"
+                            )),
+                        )),
+                    }
                 }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
@@ -113,9 +151,193 @@ pub(crate) fn convert_apply_patch_to_protocol(
         };
         result.insert(path.clone(), protocol_change);
     }
+    // Mode-only changes have no content diff; surface the new mode so the
+    // change is still visible in approvals and the turn diff.
+    for (path, mode) in action.mode_changes() {
+        result
+            .entry(path.clone())
+            .or_insert_with(|| FileChange::Update {
+                unified_diff: format!("new mode {}\n", mode.as_git_mode()),
+                move_path: None,
+            });
+    }
     result
 }
 
+/// Rebuild `action` as a git-style diff containing only the hunks the user
+/// accepted, numbered per file as in [`FileChange::hunks`]. A file's rename and
+/// mode change are kept when any of its hunks is. Returns `Ok(None)` when
+/// every hunk was rejected.
+fn retain_accepted_hunks(
+    action: &ApplyPatchAction,
+    changes: &HashMap<PathBuf, FileChange>,
+    rejected_hunks: &HashMap<PathBuf, Vec<usize>>,
+) -> Result<Option<ApplyPatchAction>, String> {
+    let mut paths: Vec<&PathBuf> = changes.keys().collect();
+    paths.sort();
+
+    let mut patch = String::new();
+    for path in paths {
+        let change = &changes[path];
+        let rejected = rejected_hunks.get(path).map(Vec::as_slice).unwrap_or(&[]);
+        let accepted: Vec<&str> = change
+            .hunks()
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !rejected.contains(index))
+            .map(|(_, hunk)| hunk)
+            .collect();
+        if accepted.is_empty() {
+            continue;
+        }
+
+        let old_path = diff_path(path, &action.cwd);
+        match change {
+            FileChange::Add { content } => {
+                let mode = action
+                    .mode_changes()
+                    .get(path)
+                    .map_or("100644", |mode| mode.as_git_mode());
+                patch.push_str(&format!(
+                    "diff --git {} {}\nnew file mode {mode}\n--- /dev/null\n+++ {}\n",
+                    quote_diff_path("a/", &old_path),
+                    quote_diff_path("b/", &old_path),
+                    quote_diff_path("b/", &old_path),
+                ));
+                if !content.is_empty() {
+                    let lines: Vec<&str> = content.lines().collect();
+                    patch.push_str(&format!("@@ -0,0 +1,{} @@\n", lines.len()));
+                    for line in lines {
+                        patch.push_str(&format!("+{line}\n"));
+                    }
+                    if !content.ends_with('\n') {
+                        patch.push_str("\\ No newline at end of file\n");
+                    }
+                }
+            }
+            FileChange::Delete { .. } => {
+                patch.push_str(&format!(
+                    "diff --git {} {}\ndeleted file mode 100644\n--- {}\n+++ /dev/null\n",
+                    quote_diff_path("a/", &old_path),
+                    quote_diff_path("b/", &old_path),
+                    quote_diff_path("a/", &old_path),
+                ));
+            }
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                let new_path = move_path
+                    .as_deref()
+                    .map_or_else(|| old_path.clone(), |dest| diff_path(dest, &action.cwd));
+                patch.push_str(&format!(
+                    "diff --git {} {}\n",
+                    quote_diff_path("a/", &old_path),
+                    quote_diff_path("b/", &new_path),
+                ));
+                let mode = action.mode_changes().get(path).or_else(|| {
+                    move_path
+                        .as_ref()
+                        .and_then(|dest| action.mode_changes().get(dest))
+                });
+                if let Some(mode) = mode {
+                    patch.push_str(&format!("new mode {}\n", mode.as_git_mode()));
+                }
+                if move_path.is_some() {
+                    patch.push_str(&format!("rename from {old_path}\nrename to {new_path}\n"));
+                }
+                if unified_diff.lines().any(|line| line.starts_with("@@")) {
+                    patch.push_str(&format!(
+                        "--- {}\n+++ {}\n",
+                        quote_diff_path("a/", &old_path),
+                        quote_diff_path("b/", &new_path),
+                    ));
+                    for hunk in accepted {
+                        patch.push_str(hunk);
+                        if !hunk.ends_with('\n') {
+                            patch.push('\n');
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if patch.is_empty() {
+        return Ok(None);
+    }
+    let argv = vec!["apply_patch".to_string(), patch];
+    match codex_apply_patch::maybe_parse_apply_patch_verified(&argv, &action.cwd) {
+        MaybeApplyPatchVerified::Body(accepted) => Ok(Some(accepted)),
+        MaybeApplyPatchVerified::CorrectnessError(err) => Err(err.to_string()),
+        MaybeApplyPatchVerified::ShellParseError(err) => Err(format!("{err:?}")),
+        MaybeApplyPatchVerified::NotApplyPatch => {
+            Err("the accepted hunks do not form a valid patch".to_string())
+        }
+    }
+}
+
+/// Tool-output note listing the hunks the user rejected.
+fn rejected_hunks_note(
+    changes: &HashMap<PathBuf, FileChange>,
+    rejected_hunks: &HashMap<PathBuf, Vec<usize>>,
+    cwd: &Path,
+) -> String {
+    let mut paths: Vec<&PathBuf> = rejected_hunks.keys().collect();
+    paths.sort();
+
+    let mut note =
+        String::from("The user rejected part of this patch; these hunks were not applied:\n");
+    for path in paths {
+        let Some(change) = changes.get(path) else {
+            continue;
+        };
+        let hunks = change.hunks();
+        let mut rejected = rejected_hunks[path].clone();
+        rejected.sort_unstable();
+        rejected.dedup();
+        let display = diff_path(path, cwd);
+        if rejected.len() >= hunks.len() {
+            note.push_str(&format!("- {display}: entire change\n"));
+            continue;
+        }
+        for index in rejected {
+            let Some(hunk) = hunks.get(index) else {
+                continue;
+            };
+            let header = hunk.lines().next().unwrap_or_default();
+            note.push_str(&format!(
+                "- {display}: hunk {} of {} ({header})\n",
+                index + 1,
+                hunks.len()
+            ));
+        }
+    }
+    note.push_str("Do not reapply the rejected hunks unless the user asks for them.");
+    note
+}
+
+/// Path as written in the rebuilt diff: relative to `cwd` when possible.
+fn diff_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Prefix `path` git-style, quoting it when it would be ambiguous unquoted.
+fn quote_diff_path(prefix: &str, path: &str) -> String {
+    let path = format!("{prefix}{path}");
+    if !path.contains([' ', '"', '\\', '\t']) {
+        return path;
+    }
+    let escaped = path
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +361,66 @@ mod tests {
             })
         );
     }
+
+    fn two_hunk_action(dir: &Path) -> ApplyPatchAction {
+        let contents: String = (1..=10).map(|n| format!("line {n}\n")).collect();
+        std::fs::write(dir.join("notes.txt"), contents).expect("write notes");
+        std::fs::write(dir.join("old.txt"), "stale\n").expect("write old");
+        let patch = "*** Begin Patch\n*** Update File: notes.txt\n@@\n line 1\n-line 2\n+LINE 2\n@@\n line 8\n-line 9\n+LINE 9\n*** Delete File: old.txt\n*** End Patch";
+        let argv = vec!["apply_patch".to_string(), patch.to_string()];
+        match codex_apply_patch::maybe_parse_apply_patch_verified(&argv, dir) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected a patch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn retain_accepted_hunks_drops_rejected_hunks() {
+        let tmp = tempdir().expect("tmp");
+        let action = two_hunk_action(tmp.path());
+        let changes = convert_apply_patch_to_protocol(&action);
+        let notes = tmp.path().join("notes.txt");
+        let old = tmp.path().join("old.txt");
+        assert_eq!(changes[&notes].hunks().len(), 2);
+
+        let rejected = HashMap::from([(notes.clone(), vec![1]), (old, vec![0])]);
+        let accepted = retain_accepted_hunks(&action, &changes, &rejected)
+            .expect("rebuild patch")
+            .expect("some hunks remain");
+        assert_eq!(accepted.changes().len(), 1);
+        let Some(ApplyPatchFileChange::Update { new_content, .. }) = accepted.changes().get(&notes)
+        else {
+            panic!("expected an update of notes.txt");
+        };
+        let expected: String = (1..=10)
+            .map(|n| match n {
+                2 => "LINE 2\n".to_string(),
+                n => format!("line {n}\n"),
+            })
+            .collect();
+        assert_eq!(new_content, &expected);
+
+        assert_eq!(
+            rejected_hunks_note(&changes, &rejected, tmp.path()),
+            "The user rejected part of this patch; these hunks were not applied:\n\
+             - notes.txt: hunk 2 of 2 (@@ -8,3 +8,3 @@)\n\
+             - old.txt: entire change\n\
+             Do not reapply the rejected hunks unless the user asks for them."
+        );
+    }
+
+    #[test]
+    fn retain_accepted_hunks_returns_none_when_everything_is_rejected() {
+        let tmp = tempdir().expect("tmp");
+        let action = two_hunk_action(tmp.path());
+        let changes = convert_apply_patch_to_protocol(&action);
+        let rejected = HashMap::from([
+            (tmp.path().join("notes.txt"), vec![0, 1]),
+            (tmp.path().join("old.txt"), vec![0]),
+        ]);
+        assert_eq!(
+            retain_accepted_hunks(&action, &changes, &rejected).map(|action| action.is_some()),
+            Ok(false)
+        );
+    }
 }
@@ -0,0 +1,491 @@
+//! Token and cost budgets.
+//!
+//! Every model response adds its token usage (and, for priced models, its
+//! estimated cost) to the session totals and, when a daily budget is
+//! configured, to a per-day ledger under `~/.codex/usage`. Crossing a soft limit produces a one-time warning;
+//! reaching a hard limit makes the session end its turns with
+//! [`TurnAbortReason::BudgetExceeded`](crate::protocol::TurnAbortReason).
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Local;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::types::Budget;
+use crate::config::types::BudgetLimits;
+use crate::config::types::ModelPricing;
+use crate::model_family::ModelFamily;
+use crate::protocol::TokenUsage;
+use crate::protocol::UsageCost;
+
+const USAGE_SUBDIR: &str = "usage";
+
+/// Attempts to take the ledger lock before giving up on an update.
+const LOCK_RETRIES: usize = 10;
+const LOCK_RETRY_SLEEP: Duration = Duration::from_millis(100);
+
+/// Built-in USD prices per million tokens: (family prefix, input, cached
+/// input, output). Longer prefixes are listed first so they win.
+const BUILTIN_PRICING: &[(&str, f64, f64, f64)] = &[
+    ("gpt-5-codex", 1.25, 0.125, 10.0),
+    ("gpt-5-mini", 0.25, 0.025, 2.0),
+    ("gpt-5-nano", 0.05, 0.005, 0.4),
+    ("gpt-5", 1.25, 0.125, 10.0),
+    ("gpt-4.1-mini", 0.4, 0.1, 1.6),
+    ("gpt-4.1-nano", 0.1, 0.025, 0.4),
+    ("gpt-4.1", 2.0, 0.5, 8.0),
+    ("gpt-4o-mini", 0.15, 0.075, 0.6),
+    ("gpt-4o", 2.5, 1.25, 10.0),
+    ("o4-mini", 1.1, 0.275, 4.4),
+    ("o3", 2.0, 0.5, 8.0),
+    ("codex-mini-latest", 1.5, 0.375, 6.0),
+];
+
+/// Usage recorded for one calendar day across all sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DailyUsage {
+    tokens: u64,
+    cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Scope {
+    Session,
+    Daily,
+}
+
+impl Scope {
+    fn label(self) -> &'static str {
+        match self {
+            Scope::Session => "Session",
+            Scope::Daily => "Daily",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Measure {
+    Tokens,
+    Cost,
+}
+
+/// Running totals for one session.
+#[derive(Debug)]
+pub(crate) struct BudgetTracker {
+    budget: Budget,
+    usage_dir: Option<PathBuf>,
+    session_tokens: u64,
+    session_cost_usd: f64,
+    priced: bool,
+    warned: HashSet<(Scope, Measure)>,
+}
+
+impl BudgetTracker {
+    /// `codex_home` is `None` for sessions that should not touch the daily
+    /// ledger. The ledger is also left alone while no daily budget is set.
+    pub(crate) fn new(budget: Budget, codex_home: Option<&Path>) -> Self {
+        let usage_dir = codex_home
+            .filter(|_| !budget.daily.is_empty())
+            .map(|home| home.join(USAGE_SUBDIR));
+        Self {
+            budget,
+            usage_dir,
+            session_tokens: 0,
+            session_cost_usd: 0.0,
+            priced: false,
+            warned: HashSet::new(),
+        }
+    }
+
+    /// Add the usage of one model response and return warnings for soft
+    /// limits crossed for the first time. Hard limits are checked separately
+    /// through [`BudgetTracker::exceeded`].
+    pub(crate) async fn record(
+        &mut self,
+        usage: &TokenUsage,
+        model_family: &ModelFamily,
+    ) -> Vec<String> {
+        let tokens = u64::try_from(usage.total_tokens).unwrap_or(0);
+        let cost = pricing_for(&self.budget, model_family).map(|pricing| cost_of(usage, &pricing));
+        self.session_tokens += tokens;
+        if let Some(cost) = cost {
+            self.session_cost_usd += cost;
+            self.priced = true;
+        }
+
+        let daily = match self.usage_dir.as_deref() {
+            Some(dir) => {
+                let path = daily_path(dir);
+                match add_to_daily(path.clone(), tokens, cost.unwrap_or(0.0)).await {
+                    Ok(daily) => Some(daily),
+                    Err(err) => {
+                        warn!("failed to record daily usage in {}: {err}", path.display());
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let mut warnings = Vec::new();
+        let session = (self.session_tokens, self.session_cost_usd);
+        self.check_soft_limits(Scope::Session, session, &mut warnings);
+        if let Some(daily) = daily {
+            self.check_soft_limits(Scope::Daily, (daily.tokens, daily.cost_usd), &mut warnings);
+        }
+        warnings
+    }
+
+    /// The hard limit that is currently reached, if any. The daily ledger is
+    /// re-read because other sessions contribute to it.
+    pub(crate) async fn exceeded(&self) -> Option<String> {
+        let session = (self.session_tokens, self.session_cost_usd);
+        if let Some(message) = hard_limit_message(Scope::Session, &self.budget.session, session) {
+            return Some(message);
+        }
+        let dir = self.usage_dir.as_deref()?;
+        let daily = read_daily(&daily_path(dir)).await;
+        hard_limit_message(
+            Scope::Daily,
+            &self.budget.daily,
+            (daily.tokens, daily.cost_usd),
+        )
+    }
+
+    /// Estimated spend, once a priced model has been used.
+    pub(crate) async fn cost(&self) -> Option<UsageCost> {
+        if !self.priced {
+            return None;
+        }
+        let daily_usd = match self.usage_dir.as_deref() {
+            Some(dir) => read_daily(&daily_path(dir)).await.cost_usd,
+            None => self.session_cost_usd,
+        };
+        Some(UsageCost {
+            session_usd: self.session_cost_usd,
+            daily_usd,
+        })
+    }
+
+    fn check_soft_limits(
+        &mut self,
+        scope: Scope,
+        (tokens, cost): (u64, f64),
+        warnings: &mut Vec<String>,
+    ) {
+        let limits = match scope {
+            Scope::Session => self.budget.session,
+            Scope::Daily => self.budget.daily,
+        };
+        // Past a hard limit the abort message says everything.
+        if hard_limit_message(scope, &limits, (tokens, cost)).is_some() {
+            return;
+        }
+        if let Some(soft) = limits.soft_tokens
+            && tokens >= soft
+            && self.warned.insert((scope, Measure::Tokens))
+        {
+            warnings.push(format!(
+                "{} token budget: {tokens} of {soft} tokens used{}.",
+                scope.label(),
+                hard_suffix_tokens(limits.hard_tokens)
+            ));
+        }
+        if let Some(soft) = limits.soft_cost_usd
+            && cost >= soft
+            && self.warned.insert((scope, Measure::Cost))
+        {
+            warnings.push(format!(
+                "{} cost budget: ${cost:.2} of ${soft:.2} spent{}.",
+                scope.label(),
+                hard_suffix_cost(limits.hard_cost_usd)
+            ));
+        }
+    }
+}
+
+fn hard_limit_message(
+    scope: Scope,
+    limits: &BudgetLimits,
+    (tokens, cost): (u64, f64),
+) -> Option<String> {
+    if let Some(hard) = limits.hard_tokens
+        && tokens >= hard
+    {
+        return Some(format!(
+            "{} token budget exhausted: {tokens} of {hard} tokens used.",
+            scope.label()
+        ));
+    }
+    if let Some(hard) = limits.hard_cost_usd
+        && cost >= hard
+    {
+        return Some(format!(
+            "{} cost budget exhausted: ${cost:.2} of ${hard:.2} spent.",
+            scope.label()
+        ));
+    }
+    None
+}
+
+fn hard_suffix_tokens(hard: Option<u64>) -> String {
+    hard.map(|hard| format!("; turns stop at {hard}"))
+        .unwrap_or_default()
+}
+
+fn hard_suffix_cost(hard: Option<f64>) -> String {
+    hard.map(|hard| format!("; turns stop at ${hard:.2}"))
+        .unwrap_or_default()
+}
+
+/// Pricing for `model_family`: the configured table first (by family, then by
+/// slug), then the built-in table (by slug prefix, then by family prefix).
+fn pricing_for(budget: &Budget, model_family: &ModelFamily) -> Option<ModelPricing> {
+    budget
+        .pricing
+        .get(&model_family.family)
+        .or_else(|| budget.pricing.get(&model_family.slug))
+        .copied()
+        .or_else(|| {
+            BUILTIN_PRICING
+                .iter()
+                .find(|(prefix, ..)| model_family.slug.starts_with(prefix))
+                .or_else(|| {
+                    BUILTIN_PRICING
+                        .iter()
+                        .find(|(prefix, ..)| model_family.family.starts_with(prefix))
+                })
+                .map(|&(_, input, cached, output)| ModelPricing {
+                    input_per_million: input,
+                    cached_input_per_million: Some(cached),
+                    output_per_million: output,
+                })
+        })
+}
+
+fn cost_of(usage: &TokenUsage, pricing: &ModelPricing) -> f64 {
+    let cached = usage.cached_input_tokens.max(0) as f64;
+    let uncached = (usage.input_tokens - usage.cached_input_tokens).max(0) as f64;
+    let output = usage.output_tokens.max(0) as f64;
+    let cached_price = pricing
+        .cached_input_per_million
+        .unwrap_or(pricing.input_per_million);
+    (uncached * pricing.input_per_million
+        + cached * cached_price
+        + output * pricing.output_per_million)
+        / 1_000_000.0
+}
+
+fn daily_path(usage_dir: &Path) -> PathBuf {
+    usage_dir.join(format!("{}.json", Local::now().format("%Y-%m-%d")))
+}
+
+/// Ledgers are replaced by rename, so readers never see a partial write and
+/// need no lock. They are not fsynced: losing the last update in a crash only
+/// makes a spend estimate slightly low.
+async fn read_daily(path: &Path) -> DailyUsage {
+    tokio::fs::read_to_string(path)
+        .await
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Add to the ledger at `path` and return the new totals. Concurrent
+/// sessions serialize on an advisory lock on `<ledger>.lock`.
+async fn add_to_daily(path: PathBuf, tokens: u64, cost_usd: f64) -> io::Result<DailyUsage> {
+    tokio::task::spawn_blocking(move || -> io::Result<DailyUsage> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.with_extension("lock"))?;
+        lock_with_retries(&lock)?;
+
+        let mut daily: DailyUsage = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        daily.tokens += tokens;
+        daily.cost_usd += cost_usd;
+
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(&daily)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(daily)
+    })
+    .await?
+}
+
+fn lock_with_retries(file: &std::fs::File) -> io::Result<()> {
+    for _ in 0..LOCK_RETRIES {
+        match file.try_lock() {
+            Ok(()) => return Ok(()),
+            Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(LOCK_RETRY_SLEEP),
+            Err(std::fs::TryLockError::Error(err)) => return Err(err),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::WouldBlock,
+        "could not lock the daily usage ledger",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_family::find_family_for_model;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    fn family(slug: &str) -> ModelFamily {
+        find_family_for_model(slug).expect("known model family")
+    }
+
+    #[test]
+    fn prices_usage_from_builtin_and_configured_tables() {
+        let tracker_budget = Budget::default();
+        let pricing = pricing_for(&tracker_budget, &family("gpt-5-codex")).expect("builtin price");
+        let cost = cost_of(&usage(1_000_000, 200_000, 100_000), &pricing);
+        assert!((cost - (0.8 * 1.25 + 0.2 * 0.125 + 0.1 * 10.0)).abs() < 1e-9);
+
+        let custom = Budget {
+            pricing: HashMap::from([(
+                "gpt-5-codex".to_string(),
+                ModelPricing {
+                    input_per_million: 2.0,
+                    cached_input_per_million: None,
+                    output_per_million: 4.0,
+                },
+            )]),
+            ..Budget::default()
+        };
+        let pricing = pricing_for(&custom, &family("gpt-5-codex")).expect("configured price");
+        let cost = cost_of(&usage(500_000, 500_000, 250_000), &pricing);
+        assert!((cost - 2.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn warns_once_per_soft_limit_and_reports_hard_limit() {
+        let budget = Budget {
+            session: BudgetLimits {
+                soft_tokens: Some(100),
+                hard_tokens: Some(300),
+                ..BudgetLimits::default()
+            },
+            ..Budget::default()
+        };
+        let mut tracker = BudgetTracker::new(budget, None);
+        let gpt5 = family("gpt-5");
+
+        assert_eq!(
+            tracker.record(&usage(50, 0, 0), &gpt5).await,
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            tracker.record(&usage(60, 0, 0), &gpt5).await,
+            vec!["Session token budget: 110 of 100 tokens used; turns stop at 300.".to_string()]
+        );
+        assert_eq!(
+            tracker.record(&usage(10, 0, 0), &gpt5).await,
+            Vec::<String>::new()
+        );
+        assert_eq!(tracker.exceeded().await, None);
+
+        tracker.record(&usage(200, 0, 0), &gpt5).await;
+        assert_eq!(
+            tracker.exceeded().await.as_deref(),
+            Some("Session token budget exhausted: 320 of 300 tokens used.")
+        );
+    }
+
+    #[tokio::test]
+    async fn daily_ledger_is_shared_between_sessions() {
+        let home = TempDir::new().expect("tempdir");
+        let budget = Budget {
+            daily: BudgetLimits {
+                hard_cost_usd: Some(1.0),
+                ..BudgetLimits::default()
+            },
+            ..Budget::default()
+        };
+        let gpt5 = family("gpt-5");
+
+        let mut first = BudgetTracker::new(budget.clone(), Some(home.path()));
+        first.record(&usage(0, 0, 60_000), &gpt5).await;
+        assert_eq!(first.exceeded().await, None);
+
+        let mut second = BudgetTracker::new(budget, Some(home.path()));
+        second.record(&usage(0, 0, 60_000), &gpt5).await;
+        assert_eq!(
+            second.exceeded().await.as_deref(),
+            Some("Daily cost budget exhausted: $1.20 of $1.00 spent.")
+        );
+        assert!(first.exceeded().await.is_some());
+
+        let cost = second.cost().await.expect("priced");
+        assert!((cost.session_usd - 0.6).abs() < 1e-9);
+        assert!((cost.daily_usd - 1.2).abs() < 1e-9);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_ledger_updates_are_not_lost() {
+        let home = TempDir::new().expect("tempdir");
+        let path = daily_path(&home.path().join(USAGE_SUBDIR));
+
+        let updates: Vec<_> = (0..8)
+            .map(|_| tokio::spawn(add_to_daily(path.clone(), 10, 0.5)))
+            .collect();
+        for update in updates {
+            update.await.expect("join").expect("update");
+        }
+
+        assert_eq!(
+            read_daily(&path).await,
+            DailyUsage {
+                tokens: 80,
+                cost_usd: 4.0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn ledger_is_untouched_without_a_daily_budget() {
+        let home = TempDir::new().expect("tempdir");
+        let mut tracker = BudgetTracker::new(Budget::default(), Some(home.path()));
+        tracker.record(&usage(10, 0, 10), &family("gpt-5")).await;
+
+        assert!(!home.path().join(USAGE_SUBDIR).exists());
+        let cost = tracker.cost().await.expect("priced");
+        assert_eq!(cost.daily_usd, cost.session_usd);
+    }
+
+    #[tokio::test]
+    async fn unpriced_models_only_count_tokens() {
+        let mut tracker = BudgetTracker::new(Budget::default(), None);
+        tracker
+            .record(&usage(10, 0, 10), &family("gpt-oss-20b"))
+            .await;
+        assert_eq!(tracker.cost().await, None);
+    }
+}
@@ -0,0 +1,473 @@
+//! Record/replay of model traffic for offline regression tests.
+//!
+//! With `CODEX_RECORD_DIR` set, every model request writes its (redacted)
+//! JSON body and the raw SSE bytes of the reply into that directory. With
+//! `CODEX_REPLAY_DIR` set, requests never reach the network: the reply is
+//! streamed from the recording whose key matches the request. Replay wins
+//! when both are set.
+//!
+//! Relative directories are resolved against the session's working
+//! directory. Cassettes are keyed by a hash of the normalized request body,
+//! so recordings stay valid across runs even though conversation ids, the
+//! working directory and `CODEX_HOME` change. Secrets are redacted from both
+//! the recorded request and the recorded reply.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use bytes::Bytes;
+use futures::Stream;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use regex_lite::Regex;
+use serde_json::Map;
+use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::error::CodexErr;
+use crate::error::Result;
+use crate::flags::CODEX_RECORD_DIR;
+use crate::flags::CODEX_REPLAY_DIR;
+use crate::model_provider_info::WireApi;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Request fields that differ between otherwise identical requests.
+const VOLATILE_FIELDS: &[&str] = &["prompt_cache_key"];
+
+/// Object keys whose values are always redacted from recorded requests.
+const SECRET_KEY_SUFFIXES: &[&str] = &[
+    "api_key",
+    "apikey",
+    "authorization",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "secret",
+    "password",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// The recording that belongs to a single model request.
+#[derive(Debug, Clone)]
+pub(crate) struct Cassette {
+    mode: CassetteMode,
+    key: String,
+    sse_path: PathBuf,
+}
+
+impl Cassette {
+    /// Returns the cassette for `payload` when recording or replaying is
+    /// enabled. In record mode the redacted request body is written out
+    /// immediately.
+    pub(crate) async fn for_request(
+        config: &Config,
+        wire_api: WireApi,
+        payload: &Value,
+    ) -> Result<Option<Self>> {
+        let (mode, dir) = if let Some(dir) = *CODEX_REPLAY_DIR {
+            (CassetteMode::Replay, dir)
+        } else if let Some(dir) = *CODEX_RECORD_DIR {
+            (CassetteMode::Record, dir)
+        } else {
+            return Ok(None);
+        };
+        let paths = [
+            (config.cwd.as_path(), "<cwd>"),
+            (config.codex_home.as_path(), "<codex_home>"),
+        ];
+        Self::open(mode, &config.cwd.join(dir), wire_api, payload, &paths)
+            .await
+            .map(Some)
+    }
+
+    /// `paths` are replaced by their placeholder before hashing the request.
+    async fn open(
+        mode: CassetteMode,
+        dir: &Path,
+        wire_api: WireApi,
+        payload: &Value,
+        paths: &[(&Path, &str)],
+    ) -> Result<Self> {
+        let mut redacted = payload.clone();
+        redact(&mut redacted);
+        let key = format!(
+            "{}-{}",
+            wire_api_name(wire_api),
+            request_hash(&redacted, paths)
+        );
+        let sse_path = dir.join(format!("{key}.sse"));
+
+        if mode == CassetteMode::Record {
+            tokio::fs::create_dir_all(dir).await?;
+            let body = serde_json::to_string_pretty(&redacted)?;
+            tokio::fs::write(dir.join(format!("{key}.request.json")), body).await?;
+        }
+
+        Ok(Self {
+            mode,
+            key,
+            sse_path,
+        })
+    }
+
+    pub(crate) fn is_replay(&self) -> bool {
+        self.mode == CassetteMode::Replay
+    }
+
+    /// The recorded SSE bytes for this request.
+    pub(crate) async fn replay_stream(&self) -> Result<BoxStream<'static, Result<Bytes>>> {
+        let data = tokio::fs::read(&self.sse_path).await.map_err(|err| {
+            CodexErr::Fatal(format!(
+                "no cassette recorded for request {} ({}): {err}",
+                self.key,
+                self.sse_path.display()
+            ))
+        })?;
+        Ok(futures::stream::iter([Ok(Bytes::from(data))]).boxed())
+    }
+}
+
+/// Passes `stream` through, copying its bytes into the cassette when
+/// recording. The redacted recording is written once `stream` ends or is
+/// dropped.
+pub(crate) fn record_stream<S>(
+    cassette: Option<&Cassette>,
+    stream: S,
+) -> BoxStream<'static, Result<Bytes>>
+where
+    S: Stream<Item = Result<Bytes>> + Send + 'static,
+{
+    let Some(cassette) = cassette.filter(|cassette| cassette.mode == CassetteMode::Record) else {
+        return stream.boxed();
+    };
+    let path = cassette.sse_path.clone();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Bytes>();
+    tokio::spawn(async move {
+        let mut body = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            body.extend_from_slice(&chunk);
+        }
+        let body = redact_sse(&String::from_utf8_lossy(&body));
+        // Write through a temporary file so a replay never sees half a reply.
+        let tmp = path.with_extension("sse.tmp");
+        let written = match tokio::fs::write(&tmp, body).await {
+            Ok(()) => tokio::fs::rename(&tmp, &path).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = written {
+            warn!("failed to record cassette {}: {err}", path.display());
+        }
+    });
+    stream
+        .inspect(move |chunk| {
+            if let Ok(bytes) = chunk {
+                let _ = tx.send(bytes.clone());
+            }
+        })
+        .boxed()
+}
+
+fn wire_api_name(wire_api: WireApi) -> &'static str {
+    match wire_api {
+        WireApi::Responses => "responses",
+        WireApi::Chat => "chat",
+        WireApi::Messages => "messages",
+    }
+}
+
+/// Stable hash of a request body with volatile values normalized away and
+/// each of `paths` replaced by its placeholder.
+fn request_hash(payload: &Value, paths: &[(&Path, &str)]) -> String {
+    let mut paths: Vec<(String, &str)> = paths
+        .iter()
+        .map(|(path, placeholder)| (path.to_string_lossy().into_owned(), *placeholder))
+        .filter(|(path, _)| !path.is_empty())
+        .collect();
+    // Replace nested paths before the directories that contain them.
+    paths.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+    let normalized = normalize(payload, &paths);
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.to_string().as_bytes());
+    let digest = hasher.finalize();
+    let hex = format!("{digest:x}");
+    hex.get(..16).unwrap_or(&hex).to_string()
+}
+
+/// Sorts object keys, drops volatile fields and replaces values that change
+/// from run to run (UUIDs, the working directory) with placeholders.
+fn normalize(value: &Value, paths: &[(String, &str)]) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map
+                .iter()
+                .filter(|(key, _)| !VOLATILE_FIELDS.contains(&key.as_str()))
+                .collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let mut normalized = Map::new();
+            for (key, value) in entries {
+                normalized.insert(key.clone(), normalize(value, paths));
+            }
+            Value::Object(normalized)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| normalize(v, paths)).collect()),
+        Value::String(text) => {
+            let text = paths
+                .iter()
+                .fold(text.clone(), |text, (path, placeholder)| {
+                    text.replace(path.as_str(), placeholder)
+                });
+            Value::String(uuid_regex().replace_all(&text, "<uuid>").into_owned())
+        }
+        other => other.clone(),
+    }
+}
+
+/// Replaces secret-looking values in place.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if SECRET_KEY_SUFFIXES
+                    .iter()
+                    .any(|suffix| key.ends_with(suffix))
+                {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        Value::String(text) => {
+            if secret_regex().is_match(text) {
+                *text = secret_regex().replace_all(text, REDACTED).into_owned();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Applies [`redact`] to the JSON `data:` payloads of an SSE body and masks
+/// tokens on every other line. Lines without secrets are kept byte for byte.
+fn redact_sse(body: &str) -> String {
+    body.split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            if let Some(data) = content.strip_prefix("data:")
+                && let Ok(original) = serde_json::from_str::<Value>(data)
+            {
+                let mut value = original.clone();
+                redact(&mut value);
+                return if value == original {
+                    line.to_string()
+                } else {
+                    format!("data: {value}{}", &line[content.len()..])
+                };
+            }
+            secret_regex().replace_all(line, REDACTED).into_owned()
+        })
+        .collect()
+}
+
+fn uuid_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    #[expect(clippy::unwrap_used)]
+    RE.get_or_init(|| {
+        Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+            .unwrap()
+    })
+}
+
+fn secret_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    #[expect(clippy::unwrap_used)]
+    RE.get_or_init(|| {
+        Regex::new(r"(sk-[A-Za-z0-9_-]{16,}|(?i:bearer)\s+[A-Za-z0-9._-]{16,})").unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn hash_ignores_volatile_values() {
+        let first = json!({
+            "model": "gpt-5-codex",
+            "prompt_cache_key": "5f0c8a51-0f3b-4a8e-9c5e-0d8f1f7c2a11",
+            "input": [{"type": "message", "content": "cwd is /work/a, session 5f0c8a51-0f3b-4a8e-9c5e-0d8f1f7c2a11"}],
+        });
+        let second = json!({
+            "input": [{"type": "message", "content": "cwd is /work/b, session 0b4bd7a2-1d44-4f0e-8a0b-7f1ac3e3d9f0"}],
+            "prompt_cache_key": "0b4bd7a2-1d44-4f0e-8a0b-7f1ac3e3d9f0",
+            "model": "gpt-5-codex",
+        });
+
+        assert_eq!(
+            request_hash(&first, &[(Path::new("/work/a"), "<cwd>")]),
+            request_hash(&second, &[(Path::new("/work/b"), "<cwd>")])
+        );
+        assert_ne!(
+            request_hash(&first, &[]),
+            request_hash(&json!({"model": "gpt-5"}), &[])
+        );
+    }
+
+    #[test]
+    fn hash_replaces_nested_paths_first() {
+        let request = |home: &str| json!({"input": format!("read {home}/AGENTS.md in /work")});
+        let paths = |home| {
+            [
+                (Path::new("/work"), "<cwd>"),
+                (Path::new(home), "<codex_home>"),
+            ]
+        };
+
+        assert_eq!(
+            request_hash(&request("/work/.codex-a"), &paths("/work/.codex-a")),
+            request_hash(&request("/work/.codex-b"), &paths("/work/.codex-b"))
+        );
+    }
+
+    #[test]
+    fn redacts_secret_keys_and_tokens() {
+        let mut payload = json!({
+            "api_key": "abc",
+            "max_tokens": 100,
+            "metadata": {"Authorization": "xyz"},
+            "messages": [{"content": "use sk-abcdefghijklmnopqrstu and Bearer abcdefghijklmnopqrstuvwx"}],
+        });
+        redact(&mut payload);
+
+        assert_eq!(
+            payload,
+            json!({
+                "api_key": REDACTED,
+                "max_tokens": 100,
+                "metadata": {"Authorization": REDACTED},
+                "messages": [{"content": "use [REDACTED] and [REDACTED]"}],
+            })
+        );
+    }
+
+    #[test]
+    fn redacts_secrets_in_sse_bodies() {
+        let body = concat!(
+            "event: response.created\r\n",
+            "data: {\"type\":\"response.created\",\"usage\":{\"input_tokens\":3}}\r\n\r\n",
+            "data: {\"metadata\":{\"api_key\":\"abc\"},\"type\":\"delta\"}\n\n",
+            "data: not json, Bearer abcdefghijklmnopqrstuvwx\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        assert_eq!(
+            redact_sse(body),
+            concat!(
+                "event: response.created\r\n",
+                "data: {\"type\":\"response.created\",\"usage\":{\"input_tokens\":3}}\r\n\r\n",
+                "data: {\"metadata\":{\"api_key\":\"[REDACTED]\"},\"type\":\"delta\"}\n\n",
+                "data: not json, [REDACTED]\n\n",
+                "data: [DONE]\n\n",
+            )
+        );
+    }
+
+    /// Waits for the recorder task to write the reply of `cassette`.
+    async fn recorded_reply(cassette: &Cassette) -> Vec<u8> {
+        for _ in 0..200 {
+            if let Ok(data) = tokio::fs::read(&cassette.sse_path).await {
+                return data;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!(
+            "reply was never recorded to {}",
+            cassette.sse_path.display()
+        );
+    }
+
+    #[tokio::test]
+    async fn records_then_replays_stream() {
+        let dir = TempDir::new().expect("tempdir");
+        let payload = json!({"model": "gpt-5-codex", "input": []});
+
+        let recorder = Cassette::open(
+            CassetteMode::Record,
+            dir.path(),
+            WireApi::Chat,
+            &payload,
+            &[],
+        )
+        .await
+        .expect("open record cassette");
+        let live = futures::stream::iter([
+            Ok(Bytes::from_static(b"data: {\"a\":1}\n\n")),
+            Ok(Bytes::from_static(
+                b"data: {\"token\":\"sk-abcdefghijklmnopqrstu\"}\n\n",
+            )),
+            Ok(Bytes::from_static(b"data: [DONE]\n\n")),
+        ]);
+        let passed_through: Vec<Bytes> = record_stream(Some(&recorder), live)
+            .map(|chunk| chunk.expect("chunk"))
+            .collect()
+            .await;
+        assert_eq!(passed_through.len(), 3);
+        assert!(
+            dir.path()
+                .join(format!("{}.request.json", recorder.key))
+                .exists()
+        );
+        recorded_reply(&recorder).await;
+
+        let player = Cassette::open(
+            CassetteMode::Replay,
+            dir.path(),
+            WireApi::Chat,
+            &payload,
+            &[],
+        )
+        .await
+        .expect("open replay cassette");
+        assert!(player.is_replay());
+        let replayed: Vec<Bytes> = player
+            .replay_stream()
+            .await
+            .expect("recording exists")
+            .map(|chunk| chunk.expect("chunk"))
+            .collect()
+            .await;
+        assert_eq!(
+            replayed.concat(),
+            b"data: {\"a\":1}\n\ndata: {\"token\":\"[REDACTED]\"}\n\ndata: [DONE]\n\n".to_vec()
+        );
+
+        let missing = Cassette::open(
+            CassetteMode::Replay,
+            dir.path(),
+            WireApi::Responses,
+            &payload,
+            &[],
+        )
+        .await
+        .expect("open replay cassette");
+        assert!(matches!(
+            missing.replay_stream().await,
+            Err(CodexErr::Fatal(_))
+        ));
+    }
+}
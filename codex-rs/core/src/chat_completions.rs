@@ -2,9 +2,12 @@ use std::time::Duration;
 
 use crate::CodexAuth;
 use crate::ModelProviderInfo;
+use crate::cassette::Cassette;
+use crate::cassette::record_stream;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
+use crate::config::Config;
 use crate::default_client::CodexHttpClient;
 use crate::error::CodexErr;
 use crate::error::ConnectionFailedError;
@@ -12,7 +15,7 @@ use crate::error::ResponseStreamFailed;
 use crate::error::Result;
 use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
-use crate::model_family::ModelFamily;
+use crate::model_provider_info::WireApi;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::util::backoff;
 use bytes::Bytes;
@@ -41,7 +44,7 @@ use tracing::trace;
 /// Implementation for the classic Chat Completions API.
 pub(crate) async fn stream_chat_completions(
     prompt: &Prompt,
-    model_family: &ModelFamily,
+    config: &Config,
     client: &CodexHttpClient,
     provider: &ModelProviderInfo,
     otel_event_manager: &OtelEventManager,
@@ -57,7 +60,7 @@ pub(crate) async fn stream_chat_completions(
     // Build messages array
     let mut messages = Vec::<serde_json::Value>::new();
 
-    let full_instructions = prompt.get_full_instructions(model_family);
+    let full_instructions = prompt.get_full_instructions(&config.model_family);
     messages.push(json!({"role": "system", "content": full_instructions}));
 
     let input = prompt.get_formatted_input();
@@ -332,7 +335,7 @@ pub(crate) async fn stream_chat_completions(
 
     let tools_json = create_tools_json_for_chat_completions_api(&prompt.tools)?;
     let payload = json!({
-        "model": model_family.slug,
+        "model": config.model_family.slug,
         "messages": messages,
         "stream": true,
         "tools": tools_json,
@@ -344,6 +347,18 @@ pub(crate) async fn stream_chat_completions(
         serde_json::to_string_pretty(&payload).unwrap_or_default()
     );
 
+    let cassette = Cassette::for_request(config, WireApi::Chat, &payload).await?;
+    if let Some(cassette) = cassette.as_ref().filter(|cassette| cassette.is_replay()) {
+        let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+        tokio::spawn(process_chat_sse(
+            cassette.replay_stream().await?,
+            tx_event,
+            provider.stream_idle_timeout(),
+            otel_event_manager.clone(),
+        ));
+        return Ok(ResponseStream { rx_event });
+    }
+
     let mut attempt = 0;
     let max_retries = provider.request_max_retries();
     loop {
@@ -390,7 +405,7 @@ pub(crate) async fn stream_chat_completions(
                     })
                 });
                 tokio::spawn(process_chat_sse(
-                    stream,
+                    record_stream(cassette.as_ref(), stream),
                     tx_event,
                     provider.stream_idle_timeout(),
                     otel_event_manager.clone(),
@@ -888,9 +903,10 @@ where
                         token_usage,
                     })));
                 }
-                Poll::Ready(Some(Ok(ResponseEvent::Created))) => {
-                    // These events are exclusive to the Responses API and
-                    // will never appear in a Chat Completions stream.
+                Poll::Ready(Some(Ok(ResponseEvent::Created | ResponseEvent::CacheHit))) => {
+                    // `Created` is exclusive to the Responses API and cached
+                    // replays bypass this adapter, so neither appears in a
+                    // Chat Completions stream.
                     continue;
                 }
                 Poll::Ready(Some(Ok(ResponseEvent::OutputTextDelta(delta)))) => {
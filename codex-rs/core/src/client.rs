@@ -35,6 +35,8 @@ use tracing::warn;
 use crate::AuthManager;
 use crate::auth::CodexAuth;
 use crate::auth::RefreshTokenError;
+use crate::cassette::Cassette;
+use crate::cassette::record_stream;
 use crate::chat_completions::AggregateStreamExt;
 use crate::chat_completions::stream_chat_completions;
 use crate::client_common::Prompt;
@@ -53,14 +55,25 @@ use crate::error::Result;
 use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
 use crate::error::UsageLimitReachedError;
+use crate::failover::FailoverTarget;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
+use crate::messages_api::MessagesRequestOptions;
+use crate::messages_api::build_messages_request;
+use crate::messages_api::freeform_tool_names;
+use crate::messages_api::stream_messages;
+use crate::messages_api::stream_messages_from_fixture;
 use crate::model_family::ModelFamily;
+use crate::model_family::derive_default_model_family;
+use crate::model_family::find_family_for_model;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::openai_model_info::get_model_info;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::RateLimitWindow;
 use crate::protocol::TokenUsage;
+use crate::response_cache::ResponseCache;
+use crate::token_counter::UsageEstimator;
+use crate::token_counter::tokenizer_for;
 use crate::token_data::PlanType;
 use crate::tools::spec::create_tools_json_for_responses_api;
 use crate::util::backoff;
@@ -133,6 +146,41 @@ impl ModelClient {
         }
     }
 
+    /// Returns a client for the same conversation that sends requests for
+    /// `target.model` to `target.provider`. Model metadata is re-derived for
+    /// the new model rather than inherited from the current one.
+    pub(crate) fn for_failover(&self, target: &FailoverTarget) -> Self {
+        let model_family = find_family_for_model(&target.model)
+            .unwrap_or_else(|| derive_default_model_family(&target.model));
+        let model_info = get_model_info(&model_family);
+
+        let mut config = (*self.config).clone();
+        config.model_provider_id = target.provider_id.clone();
+        config.model_provider = target.provider.clone();
+        config.model = target.model.clone();
+        config.model_family = model_family;
+        config.model_context_window = model_info.as_ref().map(|info| info.context_window);
+        config.model_max_output_tokens = model_info.as_ref().map(|info| info.max_output_tokens);
+        config.model_auto_compact_token_limit = model_info
+            .as_ref()
+            .and_then(|info| info.auto_compact_token_limit);
+
+        let otel_event_manager = self
+            .otel_event_manager
+            .with_model(target.model.as_str(), config.model_family.slug.as_str());
+
+        Self::new(
+            Arc::new(config),
+            self.auth_manager.clone(),
+            otel_event_manager,
+            target.provider.clone(),
+            self.effort,
+            self.summary,
+            self.conversation_id,
+            self.session_source.clone(),
+        )
+    }
+
     pub fn get_model_context_window(&self) -> Option<i64> {
         let pct = self.config.model_family.effective_context_window_percent;
         self.config
@@ -156,8 +204,23 @@ impl ModelClient {
     }
 
     pub async fn stream(&self, prompt: &Prompt) -> Result<ResponseStream> {
+        let Some(cache) = ResponseCache::for_config(&self.config) else {
+            return self.stream_uncached(prompt).await;
+        };
+        let Some(key) = ResponseCache::key(&self.config, self.effort, self.summary, prompt) else {
+            return self.stream_uncached(prompt).await;
+        };
+        if let Some(cached) = cache.lookup(&key) {
+            return Ok(cached);
+        }
+        let stream = self.stream_uncached(prompt).await?;
+        Ok(cache.record(key, stream))
+    }
+
+    async fn stream_uncached(&self, prompt: &Prompt) -> Result<ResponseStream> {
         match self.provider.wire_api {
             WireApi::Responses => self.stream_responses(prompt).await,
+            WireApi::Messages => self.stream_messages(prompt).await,
             WireApi::Chat => {
                 let auth = self.auth_manager.as_ref().and_then(|manager| {
                     manager.auth_for_provider(
@@ -166,10 +229,20 @@ impl ModelClient {
                     )
                 });
 
+                // Chat Completions streams rarely report usage; estimate it
+                // locally so auto-compaction still has numbers to act on.
+                let mut usage_estimator = tokenizer_for(&self.config).map(|tokenizer| {
+                    UsageEstimator::for_prompt(
+                        tokenizer,
+                        prompt,
+                        &prompt.get_full_instructions(&self.config.model_family),
+                    )
+                });
+
                 // Create the raw streaming connection first.
                 let response_stream = stream_chat_completions(
                     prompt,
-                    &self.config.model_family,
+                    &self.config,
                     &self.client,
                     &self.provider,
                     &self.otel_event_manager,
@@ -194,6 +267,23 @@ impl ModelClient {
                 tokio::spawn(async move {
                     use futures::StreamExt;
                     while let Some(ev) = aggregated.next().await {
+                        let ev = match (ev, usage_estimator.as_mut()) {
+                            (Ok(ResponseEvent::OutputItemDone(item)), Some(estimator)) => {
+                                estimator.observe_output(&item);
+                                Ok(ResponseEvent::OutputItemDone(item))
+                            }
+                            (
+                                Ok(ResponseEvent::Completed {
+                                    response_id,
+                                    token_usage: None,
+                                }),
+                                Some(estimator),
+                            ) => Ok(ResponseEvent::Completed {
+                                response_id,
+                                token_usage: Some(estimator.usage()),
+                            }),
+                            (ev, _) => ev,
+                        };
                         // Exit early if receiver hung up.
                         if tx.send(ev).await.is_err() {
                             break;
@@ -206,6 +296,41 @@ impl ModelClient {
         }
     }
 
+    /// Implementation for the Anthropic *Messages* API.
+    async fn stream_messages(&self, prompt: &Prompt) -> Result<ResponseStream> {
+        let freeform_tools = freeform_tool_names(&prompt.tools);
+        if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
+            // short circuit for tests
+            warn!(path, "Streaming from fixture");
+            return stream_messages_from_fixture(
+                path,
+                freeform_tools,
+                self.provider.clone(),
+                self.otel_event_manager.clone(),
+            )
+            .await;
+        }
+
+        let auth = self.auth_manager.as_ref().and_then(|manager| {
+            manager.auth_for_provider(
+                &self.config.model_provider_id,
+                self.provider.requires_openai_auth,
+            )
+        });
+        let options = MessagesRequestOptions::new(self.config.model_max_output_tokens, self.effort);
+        let payload = build_messages_request(prompt, &self.config.model_family, options)?;
+        stream_messages(
+            &self.config,
+            &payload,
+            freeform_tools,
+            &self.client,
+            &self.provider,
+            &self.otel_event_manager,
+            auth,
+        )
+        .await
+    }
+
     /// Implementation for the OpenAI *Responses* experimental API.
     async fn stream_responses(&self, prompt: &Prompt) -> Result<ResponseStream> {
         if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
@@ -314,10 +439,23 @@ impl ModelClient {
             attach_item_ids(&mut payload_json, &input_with_instructions);
         }
 
+        let cassette =
+            Cassette::for_request(&self.config, WireApi::Responses, &payload_json).await?;
+        if let Some(cassette) = cassette.as_ref().filter(|cassette| cassette.is_replay()) {
+            let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+            tokio::spawn(process_sse(
+                cassette.replay_stream().await?,
+                tx_event,
+                self.provider.stream_idle_timeout(),
+                self.otel_event_manager.clone(),
+            ));
+            return Ok(ResponseStream { rx_event });
+        }
+
         let max_attempts = self.provider.request_max_retries();
         for attempt in 0..=max_attempts {
             match self
-                .attempt_stream_responses(attempt, &payload_json, &auth_manager)
+                .attempt_stream_responses(attempt, &payload_json, &auth_manager, cassette.as_ref())
                 .await
             {
                 Ok(stream) => {
@@ -345,6 +483,7 @@ impl ModelClient {
         attempt: u64,
         payload_json: &Value,
         auth_manager: &Option<Arc<AuthManager>>,
+        cassette: Option<&Cassette>,
     ) -> std::result::Result<ResponseStream, StreamAttemptError> {
         // Always fetch the latest auth in case a prior attempt refreshed the token.
         let auth = auth_manager.as_ref().and_then(|manager| {
@@ -428,7 +567,7 @@ impl ModelClient {
                     })
                 });
                 tokio::spawn(process_sse(
-                    stream,
+                    record_stream(cassette, stream),
                     tx_event,
                     self.provider.stream_idle_timeout(),
                     self.otel_event_manager.clone(),
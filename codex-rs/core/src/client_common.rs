@@ -195,6 +195,9 @@ fn strip_total_output_header(output: &str) -> Option<&str> {
 
 #[derive(Debug)]
 pub enum ResponseEvent {
+    /// The response is replayed from the local response cache rather than
+    /// produced by the model.
+    CacheHit,
     Created,
     OutputItemDone(ResponseItem),
     Completed {
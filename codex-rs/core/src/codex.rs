@@ -64,8 +64,11 @@ use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
+use crate::failover::FailoverState;
+use crate::failover::classify_error;
 // Removed: legacy executor wiring replaced by ToolOrchestrator flows.
 // legacy normalize_exec_result no longer used after orchestrator migration
+use crate::budget::BudgetTracker;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::memory::MemoryDistiller;
@@ -78,6 +81,7 @@ use crate::memory::MemoryRuntime;
 use crate::memory::MemorySettingsManager;
 use crate::model_family::derive_default_model_family;
 use crate::model_family::find_family_for_model;
+use crate::model_provider_info::WireApi;
 use crate::openai_model_info::get_model_info;
 use crate::project_doc::get_user_instructions;
 use crate::protocol::AgentMessageDeltaEvent;
@@ -92,6 +96,7 @@ use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::ModelFailoverEvent;
 use crate::protocol::Op;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReviewDecision;
@@ -103,9 +108,11 @@ use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::WarningEvent;
 use crate::protocol::WebSearchBeginEvent;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
+use crate::rollout::tool_outputs::ToolOutputStore;
 use crate::shell;
 use crate::state::ActiveTurn;
 use crate::state::SessionServices;
@@ -114,6 +121,7 @@ use crate::tasks::GhostSnapshotTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::token_counter::estimate_input_tokens;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::parallel::ToolCallRuntime;
@@ -309,6 +317,34 @@ impl TurnContext {
             .as_deref()
             .unwrap_or(compact::SUMMARIZATION_PROMPT)
     }
+
+    /// Copy of this turn context whose model requests go through `client`,
+    /// with the tool set rebuilt for the client's model family.
+    fn with_client(&self, client: ModelClient) -> Self {
+        let config = client.config();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &client.get_model_family(),
+            features: &config.features,
+            allow_tools: config.provider_allows_tool_calls(),
+        });
+
+        Self {
+            sub_id: self.sub_id.clone(),
+            client,
+            cwd: self.cwd.clone(),
+            developer_instructions: self.developer_instructions.clone(),
+            base_instructions: self.base_instructions.clone(),
+            compact_prompt: self.compact_prompt.clone(),
+            user_instructions: self.user_instructions.clone(),
+            approval_policy: self.approval_policy,
+            sandbox_policy: self.sandbox_policy.clone(),
+            shell_environment_policy: self.shell_environment_policy.clone(),
+            tools_config,
+            final_output_json_schema: self.final_output_json_schema.clone(),
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+            tool_call_gate: Arc::clone(&self.tool_call_gate),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -652,6 +688,11 @@ impl Session {
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            tool_outputs: Some(ToolOutputStore::for_rollout(&rollout_path)),
+            budget: Mutex::new(BudgetTracker::new(
+                config.budget.clone(),
+                Some(&config.codex_home),
+            )),
         };
 
         let memory_root = resumed_root.unwrap_or_else(|| config.codex_home.join("memory"));
@@ -1030,6 +1071,38 @@ impl Session {
         }
     }
 
+    /// Resolve a pending patch approval with only some of its hunks accepted.
+    pub async fn notify_patch_hunk_approval(
+        &self,
+        sub_id: &str,
+        rejected_hunks: HashMap<PathBuf, Vec<usize>>,
+    ) {
+        {
+            let mut active = self.active_turn.lock().await;
+            if let Some(at) = active.as_mut() {
+                let mut ts = at.turn_state.lock().await;
+                ts.set_rejected_patch_hunks(sub_id.to_string(), rejected_hunks);
+            }
+        }
+        self.notify_approval(sub_id, ReviewDecision::Approved).await;
+    }
+
+    /// Hunks the user rejected when approving the patch for `sub_id`; empty
+    /// unless the approval was partial.
+    pub(crate) async fn take_rejected_patch_hunks(
+        &self,
+        sub_id: &str,
+    ) -> HashMap<PathBuf, Vec<usize>> {
+        let mut active = self.active_turn.lock().await;
+        match active.as_mut() {
+            Some(at) => {
+                let mut ts = at.turn_state.lock().await;
+                ts.take_rejected_patch_hunks(sub_id).unwrap_or_default()
+            }
+            None => HashMap::new(),
+        }
+    }
+
     /// Records input items: always append to conversation history and
     /// persist these response items to rollout.
     pub(crate) async fn record_conversation_items(
@@ -1334,9 +1407,27 @@ impl Session {
                 );
             }
         }
+        if let Some(token_usage) = token_usage {
+            let warnings = self
+                .services
+                .budget
+                .lock()
+                .await
+                .record(token_usage, &turn_context.client.get_model_family())
+                .await;
+            for message in warnings {
+                self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                    .await;
+            }
+        }
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Description of the hard budget limit that has been reached, if any.
+    async fn budget_exceeded(&self) -> Option<String> {
+        self.services.budget.lock().await.exceeded().await
+    }
+
     async fn update_rate_limits(
         &self,
         turn_context: &TurnContext,
@@ -1354,7 +1445,12 @@ impl Session {
             let state = self.state.lock().await;
             state.token_info_and_rate_limits()
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let cost = self.services.budget.lock().await.cost().await;
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            cost,
+        });
         self.send_event(turn_context, event).await;
     }
 
@@ -1538,6 +1634,25 @@ impl Session {
     pub(crate) async fn memory_runtime(&self) -> Option<MemoryRuntime> {
         self.ensure_memory_runtime().await
     }
+
+    /// Keep the full output of `call_id` for `read_tool_output`. Returns
+    /// whether it was stored.
+    pub(crate) async fn store_tool_output(&self, call_id: &str, content: &str) -> bool {
+        let Some(store) = self.services.tool_outputs.as_ref() else {
+            return false;
+        };
+        match store.save(call_id, content).await {
+            Ok(()) => true,
+            Err(err) => {
+                warn!("failed to store output of tool call {call_id}: {err}");
+                false
+            }
+        }
+    }
+
+    pub(crate) fn tool_output_store(&self) -> Option<&ToolOutputStore> {
+        self.services.tool_outputs.as_ref()
+    }
 }
 
 async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiver<Submission>) {
@@ -1587,6 +1702,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
+            Op::PatchHunkApproval { id, rejected_hunks } => {
+                handlers::patch_hunk_approval(&sess, id, rejected_hunks).await;
+            }
             Op::AddToHistory { text } => {
                 handlers::add_to_history(&sess, &config, text).await;
             }
@@ -1600,6 +1718,15 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
+            Op::ListUnifiedExecSessions => {
+                handlers::list_unified_exec_sessions(&sess, sub.id.clone()).await;
+            }
+            Op::WriteUnifiedExecStdin { session_id, input } => {
+                handlers::write_unified_exec_stdin(&sess, sub.id.clone(), session_id, input).await;
+            }
+            Op::TerminateUnifiedExecSession { session_id } => {
+                handlers::terminate_unified_exec_session(&sess, sub.id.clone(), session_id).await;
+            }
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
@@ -1658,8 +1785,11 @@ mod handlers {
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::UnifiedExecSessionsEvent;
     use codex_protocol::user_input::UserInput;
+    use std::collections::HashMap;
     use std::collections::HashSet;
+    use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::debug;
     use tracing::info;
@@ -1764,6 +1894,14 @@ mod handlers {
         }
     }
 
+    pub async fn patch_hunk_approval(
+        sess: &Arc<Session>,
+        id: String,
+        rejected_hunks: HashMap<PathBuf, Vec<usize>>,
+    ) {
+        sess.notify_patch_hunk_approval(&id, rejected_hunks).await;
+    }
+
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
         let config = Arc::clone(config);
@@ -1865,6 +2003,52 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_unified_exec_sessions(sess: &Session, sub_id: String) {
+        let sessions = sess.services.unified_exec_manager.list_sessions().await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::UnifiedExecSessions(UnifiedExecSessionsEvent { sessions }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn write_unified_exec_stdin(
+        sess: &Session,
+        sub_id: String,
+        session_id: i32,
+        input: String,
+    ) {
+        let result = sess
+            .services
+            .unified_exec_manager
+            .write_user_input(session_id, &input)
+            .await;
+        if let Err(err) = result {
+            send_unified_exec_warning(sess, sub_id.clone(), err.to_string()).await;
+        }
+        list_unified_exec_sessions(sess, sub_id).await;
+    }
+
+    pub async fn terminate_unified_exec_session(sess: &Session, sub_id: String, session_id: i32) {
+        let result = sess
+            .services
+            .unified_exec_manager
+            .terminate_session(session_id)
+            .await;
+        if let Err(err) = result {
+            send_unified_exec_warning(sess, sub_id.clone(), err.to_string()).await;
+        }
+        list_unified_exec_sessions(sess, sub_id).await;
+    }
+
+    async fn send_unified_exec_warning(sess: &Session, sub_id: String, message: String) {
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::Warning(WarningEvent { message }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn undo(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess
             .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
@@ -1950,6 +2134,12 @@ mod handlers {
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
         info!("Shutting down Codex instance");
+        // Background processes started through `exec_command` must not
+        // outlive the session.
+        sess.services
+            .unified_exec_manager
+            .terminate_all_sessions()
+            .await;
 
         // Gracefully flush and shutdown rollout recorder on session end so tests
         // that inspect the rollout file do not race with the background writer.
@@ -2126,8 +2316,23 @@ pub(crate) async fn run_task(
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
     let mut auto_compact_recently_attempted = false;
+    // Replaced when a request fails over to a fallback model so the rest of
+    // the task keeps using it.
+    let mut turn_context = turn_context;
+    let mut failover = FailoverState::default();
 
     loop {
+        if let Some(message) = sess.budget_exceeded().await {
+            sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+            // Cancelling the task's own token keeps the runner from reporting
+            // TaskComplete after the abort.
+            cancellation_token.cancel();
+            sess.on_task_aborted(turn_context.as_ref(), TurnAbortReason::BudgetExceeded)
+                .await;
+            return None;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -2148,6 +2353,21 @@ pub(crate) async fn run_task(
         sess.inject_memory_context(&turn_context, &mut turn_input)
             .await;
 
+        // Chat Completions servers (Ollama in particular) report usage only
+        // after the fact and may silently cut a prompt that overflows the
+        // window, so count the prompt locally and compact before sending it.
+        if !auto_compact_recently_attempted
+            && turn_context.client.get_provider().wire_api == WireApi::Chat
+            && let Some(limit) = turn_context.client.get_auto_compact_token_limit()
+            && let Some(prompt_tokens) =
+                estimate_input_tokens(&turn_context.client.config(), &turn_input)
+            && prompt_tokens >= limit
+        {
+            auto_compact_recently_attempted = true;
+            compact::run_inline_auto_compact_task(sess.clone(), turn_context.clone()).await;
+            continue;
+        }
+
         let turn_input_messages: Vec<String> = turn_input
             .iter()
             .filter_map(|item| match item {
@@ -2163,7 +2383,8 @@ pub(crate) async fn run_task(
             .collect();
         match run_turn(
             Arc::clone(&sess),
-            Arc::clone(&turn_context),
+            &mut turn_context,
+            &mut failover,
             Arc::clone(&turn_diff_tracker),
             turn_input,
             cancellation_token.child_token(),
@@ -2294,36 +2515,20 @@ fn format_memory_hits_message(min_confidence: f32, hits: &[MemoryHit]) -> String
 
 async fn run_turn(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    turn_context: &mut Arc<TurnContext>,
+    failover: &mut FailoverState,
     turn_diff_tracker: SharedTurnDiffTracker,
     input: Vec<ResponseItem>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<TurnRunResult> {
-    let mcp_tools = sess.services.mcp_connection_manager.list_all_tools();
-    let router = Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
-        Some(mcp_tools),
-    ));
-
-    let model_supports_parallel = turn_context
-        .client
-        .get_model_family()
-        .supports_parallel_tool_calls;
-    let parallel_tool_calls = model_supports_parallel;
-    let prompt = Prompt {
-        input,
-        tools: router.specs(),
-        parallel_tool_calls,
-        base_instructions_override: turn_context.base_instructions.clone(),
-        output_schema: turn_context.final_output_json_schema.clone(),
-    };
+    let (mut router, mut prompt) = build_turn_prompt(&sess, turn_context, input);
 
     let mut retries = 0;
     loop {
-        match try_run_turn(
+        let err = match try_run_turn(
             Arc::clone(&router),
             Arc::clone(&sess),
-            Arc::clone(&turn_context),
+            Arc::clone(turn_context),
             Arc::clone(&turn_diff_tracker),
             &prompt,
             cancellation_token.child_token(),
@@ -2331,21 +2536,51 @@ async fn run_turn(
         .await
         {
             Ok(output) => return Ok(output),
-            Err(CodexErr::TurnAborted {
+            Err(e) => e,
+        };
+
+        if let Some(trigger) = classify_error(&err)
+            && let Some(target) = failover.next_target(&turn_context.client.config(), trigger)
+        {
+            warn!(
+                "{err}; failing over to {}/{} ({trigger})",
+                target.provider_id, target.model
+            );
+            let event = EventMsg::ModelFailover(ModelFailoverEvent {
+                from_provider: turn_context.client.config().model_provider_id.clone(),
+                from_model: turn_context.client.get_model(),
+                to_provider: target.provider_id.clone(),
+                to_model: target.model.clone(),
+                reason: trigger,
+            });
+            sess.send_event(turn_context.as_ref(), event).await;
+
+            let client = turn_context.client.for_failover(&target);
+            *turn_context = Arc::new(turn_context.with_client(client));
+            // Rebuild the prompt so tools and history are serialized for the
+            // new model family and wire API.
+            let input = std::mem::take(&mut prompt.input);
+            (router, prompt) = build_turn_prompt(&sess, turn_context, input);
+            retries = 0;
+            continue;
+        }
+
+        match err {
+            CodexErr::TurnAborted {
                 dangling_artifacts: processed_items,
-            }) => {
+            } => {
                 return Err(CodexErr::TurnAborted {
                     dangling_artifacts: processed_items,
                 });
             }
-            Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
-            Err(CodexErr::EnvVar(var)) => return Err(CodexErr::EnvVar(var)),
-            Err(e @ CodexErr::Fatal(_)) => return Err(e),
-            Err(e @ CodexErr::ContextWindowExceeded) => {
+            CodexErr::Interrupted => return Err(CodexErr::Interrupted),
+            CodexErr::EnvVar(var) => return Err(CodexErr::EnvVar(var)),
+            e @ CodexErr::Fatal(_) => return Err(e),
+            e @ CodexErr::ContextWindowExceeded => {
                 sess.set_total_tokens_full(turn_context.as_ref()).await;
                 return Err(e);
             }
-            Err(CodexErr::UsageLimitReached(e)) => {
+            CodexErr::UsageLimitReached(e) => {
                 let rate_limits = e.rate_limits.clone();
                 if let Some(rate_limits) = rate_limits {
                     sess.update_rate_limits(turn_context.as_ref(), rate_limits)
@@ -2353,8 +2588,8 @@ async fn run_turn(
                 }
                 return Err(CodexErr::UsageLimitReached(e));
             }
-            Err(CodexErr::UsageNotIncluded) => return Err(CodexErr::UsageNotIncluded),
-            Err(e) => {
+            CodexErr::UsageNotIncluded => return Err(CodexErr::UsageNotIncluded),
+            e => {
                 // Use the configured provider-specific stream retry budget.
                 let max_retries = turn_context.client.get_provider().stream_max_retries();
                 if retries < max_retries {
@@ -2385,6 +2620,32 @@ async fn run_turn(
     }
 }
 
+fn build_turn_prompt(
+    sess: &Session,
+    turn_context: &TurnContext,
+    input: Vec<ResponseItem>,
+) -> (Arc<ToolRouter>, Prompt) {
+    let mcp_tools = sess.services.mcp_connection_manager.list_all_tools();
+    let router = Arc::new(ToolRouter::from_config(
+        &turn_context.tools_config,
+        Some(mcp_tools),
+    ));
+
+    let model_supports_parallel = turn_context
+        .client
+        .get_model_family()
+        .supports_parallel_tool_calls;
+    let parallel_tool_calls = model_supports_parallel;
+    let prompt = Prompt {
+        input,
+        tools: router.specs(),
+        parallel_tool_calls,
+        base_instructions_override: turn_context.base_instructions.clone(),
+        output_schema: turn_context.final_output_json_schema.clone(),
+    };
+    (router, prompt)
+}
+
 /// When the model is prompted, it returns a stream of events. Some of these
 /// events map to a `ResponseItem`. A `ResponseItem` may need to be
 /// "handled" such that it produces a `ResponseInputItem` that needs to be
@@ -2465,6 +2726,12 @@ async fn try_run_turn(
         };
 
         match event {
+            ResponseEvent::CacheHit => {
+                let event = EventMsg::BackgroundEvent(BackgroundEventEvent {
+                    message: "Replaying cached response".to_string(),
+                });
+                sess.send_event(&turn_context, event).await;
+            }
             ResponseEvent::Created => {}
             ResponseEvent::OutputItemDone(item) => {
                 match ToolRouter::build_tool_call(sess.as_ref(), item.clone()) {
@@ -2955,6 +3222,8 @@ mod tests {
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager: otel_event_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            tool_outputs: None,
+            budget: Mutex::new(BudgetTracker::new(config.budget.clone(), None)),
         };
 
         let turn_context = Session::make_turn_context(
@@ -3036,6 +3305,8 @@ mod tests {
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager: otel_event_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            tool_outputs: None,
+            budget: Mutex::new(BudgetTracker::new(config.budget.clone(), None)),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
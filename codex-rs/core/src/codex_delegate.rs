@@ -9,6 +9,7 @@ use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::Submission;
@@ -271,7 +272,15 @@ async fn handle_patch_approval(
         cancel_token,
     )
     .await;
-    let _ = codex.submit(Op::PatchApproval { id, decision }).await;
+    let rejected_hunks = parent_session
+        .take_rejected_patch_hunks(&parent_ctx.sub_id)
+        .await;
+    let op = if decision == ReviewDecision::Approved && !rejected_hunks.is_empty() {
+        Op::PatchHunkApproval { id, rejected_hunks }
+    } else {
+        Op::PatchApproval { id, decision }
+    };
+    let _ = codex.submit(op).await;
 }
 
 /// Await an approval decision, aborting on cancellation.
@@ -1,15 +1,20 @@
 use crate::WireApi;
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::Budget;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::KeymapBinding;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelFailover;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
 use crate::config::types::ReasoningSummaryFormat;
+use crate::config::types::ResponseCache;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SessionRetention;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::Tui;
@@ -111,12 +116,29 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Hugging Face `tokenizer.json` used to count tokens for models that do
+    /// not use a tiktoken encoding.
+    pub model_tokenizer: Option<PathBuf>,
+
+    /// How long Ollama keeps the `--oss` model loaded after it is pre-warmed
+    /// (e.g. `"30m"`, `"-1"` to keep it loaded). `None` uses Ollama's default.
+    pub oss_keep_alive: Option<String>,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Fallback providers/models to switch to when a turn fails.
+    pub model_failover: ModelFailover,
+
+    /// Token and cost budgets enforced while the session runs.
+    pub budget: Budget,
+
+    /// Local cache of model responses for repeated tool-free turns.
+    pub response_cache: ResponseCache,
+
     /// Approval policy for executing commands.
     pub approval_policy: AskForApproval,
 
@@ -179,6 +201,14 @@ pub struct Config {
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
 
+    /// User overrides for the TUI keymap from `[tui.keymap]`, keyed by action
+    /// name. Validation happens in the TUI, which owns the action list.
+    pub tui_keymap: BTreeMap<String, KeymapBinding>,
+
+    /// Color theme name from `tui.theme`; `None` means `auto`. The TUI
+    /// resolves names and loads theme files.
+    pub tui_theme: Option<String>,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -218,6 +248,9 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// Retention, compression and pruning of recorded session rollouts.
+    pub session_retention: SessionRetention,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -535,6 +568,18 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Fallback providers/models to switch to when a turn fails.
+    #[serde(default)]
+    pub model_failover: Option<ModelFailover>,
+
+    /// Token and cost budgets for sessions and for the day.
+    #[serde(default)]
+    pub budget: Option<Budget>,
+
+    /// Opt-in local cache of model responses.
+    #[serde(default)]
+    pub response_cache: Option<ResponseCache>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -544,6 +589,14 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Path to a Hugging Face `tokenizer.json` for the model. Relative paths
+    /// are resolved against `CODEX_HOME`.
+    pub model_tokenizer: Option<PathBuf>,
+
+    /// How long Ollama keeps the `--oss` model loaded once it has been
+    /// pre-warmed at startup, as an Ollama duration (`"30m"`, `"-1"`).
+    pub oss_keep_alive: Option<String>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -618,6 +671,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Retention, compression and pruning of recorded session rollouts.
+    #[serde(default)]
+    pub session_retention: Option<SessionRetention>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -1032,6 +1089,22 @@ impl Config {
                 }
             };
 
+        let model_failover = config_profile
+            .model_failover
+            .clone()
+            .or(cfg.model_failover)
+            .unwrap_or_default();
+        let budget = config_profile
+            .budget
+            .clone()
+            .or(cfg.budget)
+            .unwrap_or_default();
+        let response_cache = config_profile
+            .response_cache
+            .clone()
+            .or(cfg.response_cache)
+            .unwrap_or_default();
+
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
         let history = cfg.history.unwrap_or_default();
@@ -1144,8 +1217,13 @@ impl Config {
             model_context_window,
             model_max_output_tokens,
             model_auto_compact_token_limit,
+            model_tokenizer: cfg.model_tokenizer.map(|path| codex_home.join(path)),
+            oss_keep_alive: cfg.oss_keep_alive,
             model_provider_id,
             model_provider,
+            model_failover,
+            budget,
+            response_cache,
             cwd: resolved_cwd,
             approval_policy,
             sandbox_policy,
@@ -1181,6 +1259,7 @@ impl Config {
                 .collect(),
             codex_home,
             history,
+            session_retention: cfg.session_retention.unwrap_or_default(),
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
 
@@ -1220,6 +1299,12 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
+            tui_keymap: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.keymap.clone())
+                .unwrap_or_default(),
+            tui_theme: cfg.tui.as_ref().and_then(|t| t.theme.clone()),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1407,8 +1492,10 @@ mod tests {
     use crate::config::edit::apply_blocking;
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
+    use crate::config::types::ModelFallback;
     use crate::config::types::Notifications;
     use crate::features::Feature;
+    use codex_protocol::config_types::FailoverTrigger;
 
     use super::*;
     use pretty_assertions::assert_eq;
@@ -1461,6 +1548,45 @@ persistence = "none"
         assert_eq!(tui.notifications, Notifications::Enabled(false));
     }
 
+    #[test]
+    fn tui_config_parses_theme() {
+        let cfg = r#"
+[tui]
+theme = "high-contrast"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("TUI theme should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(tui.theme.as_deref(), Some("high-contrast"));
+    }
+
+    #[test]
+    fn tui_config_parses_keymap_overrides() {
+        let cfg = r#"
+[tui.keymap]
+submit = "ctrl-enter"
+newline = ["enter", "ctrl-j"]
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("TUI keymap should deserialize");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            tui.keymap,
+            BTreeMap::from([
+                (
+                    "newline".to_string(),
+                    KeymapBinding::Many(vec!["enter".to_string(), "ctrl-j".to_string()]),
+                ),
+                (
+                    "submit".to_string(),
+                    KeymapBinding::One("ctrl-enter".to_string()),
+                ),
+            ])
+        );
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1768,6 +1894,74 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_model_failover_replaces_base_chain() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[model_failover]
+rate_limit_threshold = 3
+
+[[model_failover.fallbacks]]
+provider = "openrouter"
+model = "base-fallback"
+
+[profiles.work.model_failover]
+fallbacks = [
+  { model = "work-fallback", on = ["context_window"] },
+  { provider = "ollama", on = ["server_error", "rate_limit"] },
+]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let base = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(base.model_failover.rate_limit_threshold(), 3);
+        assert_eq!(
+            base.model_failover.chain(),
+            &[ModelFallback {
+                provider: Some("openrouter".to_string()),
+                model: Some("base-fallback".to_string()),
+                on: vec![
+                    FailoverTrigger::ServerError,
+                    FailoverTrigger::RateLimit,
+                    FailoverTrigger::ContextWindow,
+                ],
+            }]
+        );
+
+        let work = Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                profile: Some("work".to_string()),
+                ..cfg
+            },
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(work.model_failover.rate_limit_threshold(), 1);
+        assert_eq!(
+            work.model_failover.chain(),
+            &[
+                ModelFallback {
+                    provider: None,
+                    model: Some("work-fallback".to_string()),
+                    on: vec![FailoverTrigger::ContextWindow],
+                },
+                ModelFallback {
+                    provider: Some("ollama".to_string()),
+                    model: None,
+                    on: vec![FailoverTrigger::ServerError, FailoverTrigger::RateLimit],
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn cli_override_takes_precedence_over_profile_sandbox_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2989,8 +3183,13 @@ model_verbosity = "high"
                 model_context_window: Some(200_000),
                 model_max_output_tokens: Some(100_000),
                 model_auto_compact_token_limit: Some(180_000),
+                model_tokenizer: None,
+                oss_keep_alive: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                model_failover: ModelFailover::default(),
+                budget: Budget::default(),
+                response_cache: ResponseCache::default(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: SandboxPolicy::new_read_only_policy(),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3007,6 +3206,7 @@ model_verbosity = "high"
                 project_doc_fallback_filenames: Vec::new(),
                 codex_home: fixture.codex_home(),
                 history: History::default(),
+                session_retention: SessionRetention::default(),
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
@@ -3033,6 +3233,8 @@ model_verbosity = "high"
                 notices: Default::default(),
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_keymap: Default::default(),
+                tui_theme: None,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3061,8 +3263,13 @@ model_verbosity = "high"
             model_context_window: Some(16_385),
             model_max_output_tokens: Some(4_096),
             model_auto_compact_token_limit: Some(14_746),
+            model_tokenizer: None,
+            oss_keep_alive: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            model_failover: ModelFailover::default(),
+            budget: Budget::default(),
+            response_cache: ResponseCache::default(),
             approval_policy: AskForApproval::UnlessTrusted,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3079,6 +3286,7 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            session_retention: SessionRetention::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3105,6 +3313,8 @@ model_verbosity = "high"
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
+            tui_theme: None,
             otel: OtelConfig::default(),
         };
 
@@ -3148,8 +3358,13 @@ model_verbosity = "high"
             model_context_window: Some(200_000),
             model_max_output_tokens: Some(100_000),
             model_auto_compact_token_limit: Some(180_000),
+            model_tokenizer: None,
+            oss_keep_alive: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_failover: ModelFailover::default(),
+            budget: Budget::default(),
+            response_cache: ResponseCache::default(),
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3166,6 +3381,7 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            session_retention: SessionRetention::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3192,6 +3408,8 @@ model_verbosity = "high"
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
+            tui_theme: None,
             otel: OtelConfig::default(),
         };
 
@@ -3221,8 +3439,13 @@ model_verbosity = "high"
             model_context_window: Some(272_000),
             model_max_output_tokens: Some(128_000),
             model_auto_compact_token_limit: Some(244_800),
+            model_tokenizer: None,
+            oss_keep_alive: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_failover: ModelFailover::default(),
+            budget: Budget::default(),
+            response_cache: ResponseCache::default(),
             approval_policy: AskForApproval::OnFailure,
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3239,6 +3462,7 @@ model_verbosity = "high"
             project_doc_fallback_filenames: Vec::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            session_retention: SessionRetention::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3265,6 +3489,8 @@ model_verbosity = "high"
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
+            tui_theme: None,
             otel: OtelConfig::default(),
         };
 
@@ -1,6 +1,9 @@
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use crate::config::types::Budget;
+use crate::config::types::ModelFailover;
+use crate::config::types::ResponseCache;
 use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningEffort;
 use codex_protocol::config_types::ReasoningSummary;
@@ -15,6 +18,14 @@ pub struct ConfigProfile {
     /// The key in the `model_providers` map identifying the
     /// [`ModelProviderInfo`] to use.
     pub model_provider: Option<String>,
+    /// Fallback chain for this profile; replaces the top-level
+    /// `model_failover` when set.
+    pub model_failover: Option<ModelFailover>,
+    /// Budgets for this profile; replace the top-level `budget` when set.
+    pub budget: Option<Budget>,
+    /// Response cache settings for this profile; replace the top-level
+    /// `response_cache` when set.
+    pub response_cache: Option<ResponseCache>,
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
@@ -3,6 +3,7 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use codex_protocol::config_types::FailoverTrigger;
 use serde::Deserializer;
 use serde_json::Value as JsonValue;
 use std::collections::BTreeMap;
@@ -323,6 +324,159 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// Retention policy for rollout files under `~/.codex/sessions`, enforced by
+/// `codex history prune` and optionally at startup.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SessionRetention {
+    /// Delete rollouts that have not been written to for this many days.
+    pub max_age_days: Option<u64>,
+
+    /// Delete the oldest rollouts until the total size of all rollouts is at
+    /// most this many bytes.
+    pub max_total_bytes: Option<u64>,
+
+    /// Compress rollouts with zstd once they have not been written to for
+    /// this many days.
+    pub compress_after_days: Option<u64>,
+
+    /// When compressing a rollout, replace tool outputs larger than this many
+    /// bytes with a short placeholder.
+    pub max_archived_tool_output_bytes: Option<usize>,
+
+    /// Conversation ids whose rollouts are never deleted or rewritten.
+    #[serde(default)]
+    pub pinned: Vec<String>,
+
+    /// Enforce this policy in the background when the interactive TUI starts.
+    #[serde(default)]
+    pub prune_on_startup: bool,
+}
+
+/// Ordered fallback chain tried when a turn fails against the configured
+/// provider/model.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ModelFailover {
+    /// Fallbacks in the order they should be tried.
+    #[serde(default)]
+    pub fallbacks: Vec<ModelFallback>,
+
+    /// Number of times a provider has to exhaust its retries on 429 responses
+    /// within a task before a `rate_limit` fallback is used. Defaults to 1.
+    pub rate_limit_threshold: Option<u32>,
+
+    /// Chain declared by the active custom provider in `settings.json`. Set by
+    /// front-ends rather than read from `config.toml`; when non-empty it is
+    /// used instead of `fallbacks`.
+    #[serde(skip)]
+    pub provider_fallbacks: Vec<ModelFallback>,
+}
+
+impl ModelFailover {
+    /// The chain that applies to the active provider.
+    pub fn chain(&self) -> &[ModelFallback] {
+        if self.provider_fallbacks.is_empty() {
+            &self.fallbacks
+        } else {
+            &self.provider_fallbacks
+        }
+    }
+
+    pub fn rate_limit_threshold(&self) -> u32 {
+        self.rate_limit_threshold.unwrap_or(1).max(1)
+    }
+}
+
+/// Token and cost budgets for a single session and for the calendar day.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Budget {
+    /// Limits for a single session.
+    #[serde(default, skip_serializing_if = "BudgetLimits::is_empty")]
+    pub session: BudgetLimits,
+
+    /// Limits across all sessions on the current (local) day.
+    #[serde(default, skip_serializing_if = "BudgetLimits::is_empty")]
+    pub daily: BudgetLimits,
+
+    /// Prices keyed by model family (e.g. `gpt-5`). Entries override the
+    /// built-in table.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pricing: HashMap<String, ModelPricing>,
+}
+
+/// Soft limits emit a warning once crossed; hard limits end the turn.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct BudgetLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_cost_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard_cost_usd: Option<f64>,
+}
+
+impl BudgetLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// USD prices per million tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    /// Price of cached input tokens. Defaults to the regular input price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_per_million: Option<f64>,
+    pub output_per_million: f64,
+}
+
+/// Opt-in local cache of model responses, for repeated runs of tool-free
+/// turns on identical input.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ResponseCache {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds a cached response is served for. Defaults to one day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+
+    /// Maximum number of cached responses. Defaults to 1000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<usize>,
+
+    /// Maximum total size of the cache on disk, in bytes. Defaults to 64 MiB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+}
+
+/// One entry in a [`ModelFailover`] chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModelFallback {
+    /// Key into the `model_providers` map. Defaults to the provider that
+    /// failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// Model to request from `provider`. Defaults to the model that failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Error classes that may switch to this entry. Defaults to all of them.
+    #[serde(default = "default_failover_triggers")]
+    pub on: Vec<FailoverTrigger>,
+}
+
+fn default_failover_triggers() -> Vec<FailoverTrigger> {
+    vec![
+        FailoverTrigger::ServerError,
+        FailoverTrigger::RateLimit,
+        FailoverTrigger::ContextWindow,
+    ]
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -401,6 +555,34 @@ pub struct Tui {
     /// Defaults to `false`.
     #[serde(default)]
     pub notifications: Notifications,
+
+    /// Overrides for the TUI keymap, keyed by action name (e.g. `submit`).
+    /// Unknown actions and malformed bindings are reported at startup.
+    #[serde(default)]
+    pub keymap: BTreeMap<String, KeymapBinding>,
+
+    /// Color theme: `auto` (default), `dark`, `light`, `high-contrast`, or
+    /// the name of a theme file in `$CODEX_HOME/themes/`.
+    #[serde(default)]
+    pub theme: Option<String>,
+}
+
+/// One or more key specs (e.g. `"ctrl-enter"`) bound to a TUI action. An
+/// empty list unbinds the action.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum KeymapBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeymapBinding {
+    pub fn specs(&self) -> Vec<&str> {
+        match self {
+            Self::One(spec) => vec![spec.as_str()],
+            Self::Many(specs) => specs.iter().map(String::as_str).collect(),
+        }
+    }
 }
 
 /// Settings for notices we display to users via the tui and app-server clients
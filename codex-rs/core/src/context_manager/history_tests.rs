@@ -412,6 +412,23 @@ fn format_exec_output_reports_omitted_lines_and_keeps_head_and_tail() {
     );
 }
 
+#[test]
+fn format_exec_output_names_stored_output() {
+    let content = "line\n".repeat(truncate::MODEL_FORMAT_MAX_LINES + 1);
+
+    let truncated = truncate::format_output_for_model_body_with_handle(&content, Some("call_7"));
+    assert!(
+        truncated.contains("call read_tool_output with call_id \"call_7\""),
+        "expected read_tool_output hint: {truncated}"
+    );
+
+    let small = "line\n".repeat(3);
+    assert_eq!(
+        truncate::format_output_for_model_body_with_handle(&small, Some("call_7")),
+        small
+    );
+}
+
 #[test]
 fn format_exec_output_prefers_line_marker_when_both_limits_exceeded() {
     let total_lines = truncate::MODEL_FORMAT_MAX_LINES + 42;
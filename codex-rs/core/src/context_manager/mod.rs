@@ -3,4 +3,8 @@ mod normalize;
 mod truncate;
 
 pub(crate) use history::ContextManager;
+pub(crate) use truncate::MODEL_FORMAT_MAX_BYTES;
+pub(crate) use truncate::MODEL_FORMAT_MAX_LINES;
+pub(crate) use truncate::exceeds_model_format_limits;
 pub(crate) use truncate::format_output_for_model_body;
+pub(crate) use truncate::format_output_for_model_body_with_handle;
@@ -30,6 +30,21 @@ pub(crate) fn globally_truncate_function_output_items(
 }
 
 pub(crate) fn format_output_for_model_body(content: &str) -> String {
+    format_output_for_model_body_with_handle(content, None)
+}
+
+/// Whether `content` is too large to reach the model untruncated.
+pub(crate) fn exceeds_model_format_limits(content: &str) -> bool {
+    content.len() > MODEL_FORMAT_MAX_BYTES || content.lines().count() > MODEL_FORMAT_MAX_LINES
+}
+
+/// Same as [`format_output_for_model_body`]. When the full output was stored
+/// under `stored_call_id`, the truncation header tells the model how to read
+/// the omitted part with `read_tool_output`.
+pub(crate) fn format_output_for_model_body_with_handle(
+    content: &str,
+    stored_call_id: Option<&str>,
+) -> String {
     // Head+tail truncation for the model: show the beginning and end with an elision.
     // Clients still receive full streams; only this formatted summary is capped.
     let total_lines = content.lines().count();
@@ -37,7 +52,12 @@ pub(crate) fn format_output_for_model_body(content: &str) -> String {
         return content.to_string();
     }
     let output = truncate_formatted_exec_output(content, total_lines);
-    format!("Total output lines: {total_lines}\n\n{output}")
+    match stored_call_id {
+        Some(call_id) => format!(
+            "Total output lines: {total_lines}\nFull output stored; call read_tool_output with call_id \"{call_id}\" to read line ranges or search it.\n\n{output}"
+        ),
+        None => format!("Total output lines: {total_lines}\n\n{output}"),
+    }
 }
 
 fn truncate_formatted_exec_output(content: &str, total_lines: usize) -> String {
@@ -9,15 +9,19 @@ use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
+use crate::protocol::Op;
 use crate::protocol::SessionConfiguredEvent;
 use crate::rollout::RolloutRecorder;
+use crate::rollout::history_index::user_turn_message;
+use codex_git::GhostCommit;
+use codex_git::restore_ghost_commit;
 use codex_protocol::ConversationId;
-use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 use std::collections::HashMap;
+use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -30,6 +34,13 @@ pub struct NewConversation {
     pub session_configured: SessionConfiguredEvent,
 }
 
+/// A conversation branched from an earlier turn of a recorded rollout.
+pub struct ForkedConversation {
+    pub new_conversation: NewConversation,
+    /// Id of the ghost snapshot the workspace was restored to, if any.
+    pub restored_snapshot: Option<String>,
+}
+
 /// [`ConversationManager`] is responsible for creating conversations and
 /// maintaining them in memory.
 pub struct ConversationManager {
@@ -189,6 +200,112 @@ impl ConversationManager {
 
         self.finalize_spawn(codex, conversation_id).await
     }
+
+    /// Start a new conversation from the state just before user turn `turn`
+    /// (1-based) of the rollout at `path`, leaving that rollout untouched.
+    /// When `restore_workspace` is set and a ghost snapshot was captured
+    /// during that turn, the session's working tree is reset to it once the
+    /// new conversation has been spawned. Restoring is refused unless
+    /// `config.cwd` is the directory the session ran in.
+    pub async fn fork_conversation_at_turn(
+        &self,
+        turn: usize,
+        config: Config,
+        path: PathBuf,
+        restore_workspace: bool,
+    ) -> CodexResult<ForkedConversation> {
+        let history = RolloutRecorder::get_rollout_history(&path).await?;
+        let items = history.get_rollout_items();
+        let user_positions = user_message_positions(&items);
+        let available = user_positions.len();
+        if turn == 0 || turn > available {
+            return Err(CodexErr::ForkTurnOutOfRange { turn, available });
+        }
+
+        let snapshot = if restore_workspace {
+            let start = user_positions[turn - 1];
+            let end = user_positions.get(turn).copied().unwrap_or(items.len());
+            ghost_snapshot_in(&items[start..end])
+        } else {
+            None
+        };
+        // The snapshot belongs to the session's working tree; never reset a
+        // different one.
+        if snapshot.is_some() {
+            let session_cwd = session_cwd(&items).unwrap_or_default();
+            if session_cwd != config.cwd {
+                return Err(CodexErr::ForkWorkspaceMismatch {
+                    session_cwd,
+                    cwd: config.cwd,
+                });
+            }
+        }
+
+        let cwd = config.cwd.clone();
+        let history = truncate_before_nth_user_message(history, turn - 1);
+        let auth_manager = self.auth_manager.clone();
+        let CodexSpawnOk {
+            codex,
+            conversation_id,
+        } = Codex::spawn(config, auth_manager, history, self.session_source.clone()).await?;
+        let new_conversation = self.finalize_spawn(codex, conversation_id).await?;
+
+        let restored_snapshot = match snapshot {
+            Some(ghost_commit) => match restore_snapshot(cwd, ghost_commit).await {
+                Ok(commit_id) => Some(commit_id),
+                Err(err) => {
+                    self.remove_conversation(&conversation_id).await;
+                    let _ = new_conversation.conversation.submit(Op::Shutdown).await;
+                    return Err(err);
+                }
+            },
+            None => None,
+        };
+
+        Ok(ForkedConversation {
+            new_conversation,
+            restored_snapshot,
+        })
+    }
+}
+
+/// Working directory recorded in the rollout's session metadata.
+fn session_cwd(items: &[RolloutItem]) -> Option<PathBuf> {
+    items.iter().find_map(|item| match item {
+        RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.cwd.clone()),
+        _ => None,
+    })
+}
+
+/// First ghost snapshot recorded among `items`.
+fn ghost_snapshot_in(items: &[RolloutItem]) -> Option<GhostCommit> {
+    items.iter().find_map(|item| match item {
+        RolloutItem::ResponseItem(ResponseItem::GhostSnapshot { ghost_commit }) => {
+            Some(ghost_commit.clone())
+        }
+        _ => None,
+    })
+}
+
+async fn restore_snapshot(cwd: PathBuf, ghost_commit: GhostCommit) -> CodexResult<String> {
+    let commit_id = ghost_commit.id().to_string();
+    tokio::task::spawn_blocking(move || restore_ghost_commit(&cwd, &ghost_commit))
+        .await?
+        .map_err(|err| {
+            CodexErr::Io(io::Error::other(format!(
+                "failed to restore snapshot {commit_id}: {err}"
+            )))
+        })?;
+    Ok(commit_id)
+}
+
+/// Indices of the items starting each user turn, in rollout order.
+fn user_message_positions(items: &[RolloutItem]) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| user_turn_message(item).map(|_| idx))
+        .collect()
 }
 
 /// Return a prefix of `items` obtained by cutting strictly before the nth user message
@@ -197,18 +314,7 @@ fn truncate_before_nth_user_message(history: InitialHistory, n: usize) -> Initia
     // Work directly on rollout items, and cut the vector at the nth user message input.
     let items: Vec<RolloutItem> = history.get_rollout_items();
 
-    // Find indices of user message inputs in rollout order.
-    let mut user_positions: Vec<usize> = Vec::new();
-    for (idx, item) in items.iter().enumerate() {
-        if let RolloutItem::ResponseItem(item @ ResponseItem::Message { .. }) = item
-            && matches!(
-                crate::event_mapping::parse_turn_item(item),
-                Some(TurnItem::UserMessage(_))
-            )
-        {
-            user_positions.push(idx);
-        }
-    }
+    let user_positions = user_message_positions(&items);
 
     // If fewer than or equal to n user messages exist, treat as empty (out of range).
     if user_positions.len() <= n {
@@ -13,6 +13,7 @@ use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -135,6 +136,16 @@ pub enum CodexErr {
     #[error("unsupported operation: {0}")]
     UnsupportedOperation(String),
 
+    #[error("cannot fork at turn {turn}: the conversation has {available} user turn(s)")]
+    ForkTurnOutOfRange { turn: usize, available: usize },
+
+    #[error(
+        "cannot restore the workspace: the conversation ran in {}, not in {}",
+        session_cwd.display(),
+        cwd.display()
+    )]
+    ForkWorkspaceMismatch { session_cwd: PathBuf, cwd: PathBuf },
+
     #[error("{0}")]
     RefreshTokenFailed(RefreshTokenFailedError),
 
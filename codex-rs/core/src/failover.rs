@@ -0,0 +1,317 @@
+//! Switching a task to a fallback provider/model when the configured one
+//! keeps failing.
+//!
+//! The chain comes from [`ModelFailover`]. Failover is sticky for the rest of
+//! the task and each entry is tried at most once; the next task starts again
+//! from the configured provider/model.
+
+use codex_protocol::config_types::FailoverTrigger;
+use reqwest::StatusCode;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::config::types::ModelFailover;
+use crate::error::CodexErr;
+use crate::model_provider_info::ModelProviderInfo;
+
+/// Error bodies from OpenAI-compatible chat endpoints that indicate the prompt
+/// did not fit in the model's context window.
+const CONTEXT_WINDOW_ERROR_MARKERS: &[&str] = &[
+    "context_length_exceeded",
+    "maximum context length",
+    "prompt is too long",
+];
+
+/// Maps an error that ended a model request to the failover trigger it
+/// corresponds to, if any.
+pub(crate) fn classify_error(err: &CodexErr) -> Option<FailoverTrigger> {
+    match err {
+        CodexErr::ContextWindowExceeded => Some(FailoverTrigger::ContextWindow),
+        CodexErr::InternalServerError | CodexErr::ConnectionFailed(_) => {
+            Some(FailoverTrigger::ServerError)
+        }
+        CodexErr::RetryLimit(e) => classify_status(e.status),
+        CodexErr::UnexpectedStatus(e) => {
+            if e.status == StatusCode::BAD_REQUEST {
+                let body = e.body.to_ascii_lowercase();
+                CONTEXT_WINDOW_ERROR_MARKERS
+                    .iter()
+                    .any(|marker| body.contains(marker))
+                    .then_some(FailoverTrigger::ContextWindow)
+            } else {
+                classify_status(e.status)
+            }
+        }
+        _ => None,
+    }
+}
+
+fn classify_status(status: StatusCode) -> Option<FailoverTrigger> {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        Some(FailoverTrigger::RateLimit)
+    } else if status.is_server_error() {
+        Some(FailoverTrigger::ServerError)
+    } else {
+        None
+    }
+}
+
+/// Provider/model a task should switch to.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FailoverTarget {
+    pub(crate) provider_id: String,
+    pub(crate) provider: ModelProviderInfo,
+    pub(crate) model: String,
+}
+
+/// Tracks how far a task has moved along its fallback chain.
+#[derive(Debug, Default)]
+pub(crate) struct FailoverState {
+    next_index: usize,
+    rate_limit_hits: u32,
+}
+
+impl FailoverState {
+    /// Returns the next target for `trigger`, or `None` when the error should
+    /// be handled by the regular retry logic instead.
+    pub(crate) fn next_target(
+        &mut self,
+        config: &Config,
+        trigger: FailoverTrigger,
+    ) -> Option<FailoverTarget> {
+        let failover: &ModelFailover = &config.model_failover;
+        let chain = failover.chain();
+        if self.next_index >= chain.len() {
+            return None;
+        }
+
+        if trigger == FailoverTrigger::RateLimit {
+            self.rate_limit_hits += 1;
+            if self.rate_limit_hits < failover.rate_limit_threshold() {
+                return None;
+            }
+        }
+
+        for (index, fallback) in chain.iter().enumerate().skip(self.next_index) {
+            if !fallback.on.contains(&trigger) {
+                continue;
+            }
+            let provider_id = fallback
+                .provider
+                .clone()
+                .unwrap_or_else(|| config.model_provider_id.clone());
+            let Some(provider) = config.model_providers.get(&provider_id).cloned() else {
+                warn!(provider = %provider_id, "skipping fallback for unknown model provider");
+                continue;
+            };
+            let model = fallback
+                .model
+                .clone()
+                .unwrap_or_else(|| config.model.clone());
+            if provider_id == config.model_provider_id && model == config.model {
+                continue;
+            }
+
+            self.next_index = index + 1;
+            self.rate_limit_hits = 0;
+            return Some(FailoverTarget {
+                provider_id,
+                provider,
+                model,
+            });
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigOverrides;
+    use crate::config::ConfigToml;
+    use crate::config::types::ModelFallback;
+    use crate::error::RetryLimitReachedError;
+    use crate::error::UnexpectedResponseError;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn config_with_chain(
+        fallbacks: Vec<ModelFallback>,
+        rate_limit_threshold: Option<u32>,
+    ) -> Config {
+        let codex_home = TempDir::new().expect("tempdir");
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect("load default config");
+        for provider_id in ["ollama", "openrouter"] {
+            config
+                .model_providers
+                .insert(provider_id.to_string(), config.model_provider.clone());
+        }
+        config.model_failover = ModelFailover {
+            fallbacks,
+            rate_limit_threshold,
+            provider_fallbacks: Vec::new(),
+        };
+        config
+    }
+
+    fn fallback(
+        provider: Option<&str>,
+        model: Option<&str>,
+        on: &[FailoverTrigger],
+    ) -> ModelFallback {
+        ModelFallback {
+            provider: provider.map(str::to_string),
+            model: model.map(str::to_string),
+            on: on.to_vec(),
+        }
+    }
+
+    fn retry_limit(status: StatusCode) -> CodexErr {
+        CodexErr::RetryLimit(RetryLimitReachedError {
+            status,
+            request_id: None,
+        })
+    }
+
+    #[test]
+    fn classifies_provider_errors() {
+        assert_eq!(
+            classify_error(&retry_limit(StatusCode::BAD_GATEWAY)),
+            Some(FailoverTrigger::ServerError)
+        );
+        assert_eq!(
+            classify_error(&retry_limit(StatusCode::TOO_MANY_REQUESTS)),
+            Some(FailoverTrigger::RateLimit)
+        );
+        assert_eq!(
+            classify_error(&CodexErr::InternalServerError),
+            Some(FailoverTrigger::ServerError)
+        );
+        assert_eq!(
+            classify_error(&CodexErr::ContextWindowExceeded),
+            Some(FailoverTrigger::ContextWindow)
+        );
+        assert_eq!(
+            classify_error(&CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                status: StatusCode::BAD_REQUEST,
+                body: r#"{"error":{"code":"context_length_exceeded"}}"#.to_string(),
+                request_id: None,
+            })),
+            Some(FailoverTrigger::ContextWindow)
+        );
+        assert_eq!(
+            classify_error(&CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                status: StatusCode::BAD_REQUEST,
+                body: "Unknown parameter".to_string(),
+                request_id: None,
+            })),
+            None
+        );
+        assert_eq!(classify_error(&CodexErr::Interrupted), None);
+    }
+
+    #[test]
+    fn walks_the_chain_in_order_and_skips_non_matching_entries() {
+        let config = config_with_chain(
+            vec![
+                fallback(None, Some("big-context"), &[FailoverTrigger::ContextWindow]),
+                fallback(
+                    Some("ollama"),
+                    Some("local"),
+                    &[FailoverTrigger::ServerError],
+                ),
+                fallback(Some("openrouter"), None, &[FailoverTrigger::ServerError]),
+            ],
+            None,
+        );
+        let mut state = FailoverState::default();
+
+        let first = state
+            .next_target(&config, FailoverTrigger::ServerError)
+            .expect("first server error fallback");
+        assert_eq!(first.provider_id, "ollama");
+        assert_eq!(first.model, "local");
+
+        let second = state
+            .next_target(&config, FailoverTrigger::ServerError)
+            .expect("second server error fallback");
+        assert_eq!(second.provider_id, "openrouter");
+        assert_eq!(second.model, config.model);
+
+        // The context window entry sits before the current position.
+        assert_eq!(
+            state.next_target(&config, FailoverTrigger::ContextWindow),
+            None
+        );
+    }
+
+    #[test]
+    fn rate_limit_waits_for_threshold() {
+        let config = config_with_chain(
+            vec![fallback(
+                Some("ollama"),
+                None,
+                &[FailoverTrigger::RateLimit],
+            )],
+            Some(2),
+        );
+        let mut state = FailoverState::default();
+
+        assert_eq!(state.next_target(&config, FailoverTrigger::RateLimit), None);
+        let target = state
+            .next_target(&config, FailoverTrigger::RateLimit)
+            .expect("fallback after threshold");
+        assert_eq!(target.provider_id, "ollama");
+    }
+
+    #[test]
+    fn skips_unknown_providers_and_the_current_model() {
+        let config = config_with_chain(
+            vec![
+                fallback(
+                    Some("does-not-exist"),
+                    None,
+                    &[FailoverTrigger::ServerError],
+                ),
+                fallback(None, None, &[FailoverTrigger::ServerError]),
+            ],
+            None,
+        );
+        let mut state = FailoverState::default();
+
+        assert_eq!(
+            state.next_target(&config, FailoverTrigger::ServerError),
+            None
+        );
+    }
+
+    #[test]
+    fn provider_chain_takes_precedence() {
+        let mut config = config_with_chain(
+            vec![fallback(
+                Some("ollama"),
+                None,
+                &[FailoverTrigger::ServerError],
+            )],
+            None,
+        );
+        config.model_failover.provider_fallbacks = vec![fallback(
+            Some("openrouter"),
+            Some("provider-fallback"),
+            &[FailoverTrigger::ServerError],
+        )];
+        let mut state = FailoverState::default();
+
+        let target = state
+            .next_target(&config, FailoverTrigger::ServerError)
+            .expect("provider fallback");
+        assert_eq!(target.provider_id, "openrouter");
+        assert_eq!(target.model, "provider-fallback");
+    }
+}
@@ -3,4 +3,11 @@ use env_flags::env_flags;
 env_flags! {
     /// Fixture path for offline tests (see client.rs).
     pub CODEX_RS_SSE_FIXTURE: Option<&str> = None;
+
+    /// Directory to record model requests and SSE replies into (see cassette.rs).
+    pub CODEX_RECORD_DIR: Option<&str> = None;
+
+    /// Directory of recorded model replies to serve instead of the network
+    /// (see cassette.rs).
+    pub CODEX_REPLAY_DIR: Option<&str> = None;
 }
@@ -8,6 +8,8 @@
 mod apply_patch;
 pub mod auth;
 pub mod bash;
+mod budget;
+mod cassette;
 mod chat_completions;
 mod client;
 mod client_common;
@@ -24,6 +26,7 @@ mod environment_context;
 pub mod error;
 pub mod exec;
 pub mod exec_env;
+mod failover;
 pub mod features;
 mod flags;
 pub mod git_info;
@@ -33,10 +36,13 @@ mod mcp_connection_manager;
 mod mcp_tool_call;
 pub mod memory;
 mod message_history;
+mod messages_api;
 mod model_provider_info;
 pub mod parse_command;
+mod response_cache;
 mod response_processing;
 pub mod sandboxing;
+mod token_counter;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
@@ -53,6 +59,7 @@ mod event_mapping;
 pub mod review_format;
 pub use codex_protocol::protocol::InitialHistory;
 pub use conversation_manager::ConversationManager;
+pub use conversation_manager::ForkedConversation;
 pub use conversation_manager::NewConversation;
 // Re-export common auth types for workspace consumers
 pub use auth::AuthManager;
@@ -73,15 +80,21 @@ mod tools;
 pub mod turn_diff_tracker;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
+pub use rollout::RetentionReport;
 pub use rollout::RolloutRecorder;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::enforce_retention;
 pub use rollout::find_conversation_path_by_id_str;
+pub use rollout::history_index::HistorySearchHit;
+pub use rollout::history_index::HistorySearchQuery;
+pub use rollout::history_index::search_history;
 pub use rollout::list::ConversationItem;
 pub use rollout::list::ConversationsPage;
 pub use rollout::list::Cursor;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
+pub use rollout::spawn_startup_retention;
 mod function_tool;
 mod state;
 mod tasks;
@@ -0,0 +1,1141 @@
+//! Client for the Anthropic *Messages* API (`/v1/messages`).
+//!
+//! Requests are built from the same [`Prompt`] the Responses and Chat
+//! Completions clients use, and the streamed content blocks are mapped back
+//! onto [`ResponseEvent`]s. Thinking blocks round-trip through
+//! `ResponseItem::Reasoning`: the thinking text is kept as reasoning content
+//! and its signature in `encrypted_content`, so the block can be replayed
+//! verbatim on later turns as the API requires.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::CodexAuth;
+use crate::ModelProviderInfo;
+use crate::cassette::Cassette;
+use crate::cassette::record_stream;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::client_common::tools::ToolSpec;
+use crate::config::Config;
+use crate::default_client::CodexHttpClient;
+use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
+use crate::error::ResponseStreamFailed;
+use crate::error::Result;
+use crate::error::RetryLimitReachedError;
+use crate::error::UnexpectedResponseError;
+use crate::model_family::ModelFamily;
+use crate::model_provider_info::WireApi;
+use crate::protocol::TokenUsage;
+use crate::tools::spec::create_tools_json_for_messages_api;
+use crate::util::backoff;
+use bytes::Bytes;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::config_types::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use eventsource_stream::Eventsource;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use reqwest::StatusCode;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tokio_util::io::ReaderStream;
+use tracing::debug;
+use tracing::trace;
+
+const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const DEFAULT_MAX_TOKENS: u32 = 8_192;
+/// Smallest thinking budget the Messages API accepts.
+const MIN_THINKING_BUDGET_TOKENS: u32 = 1_024;
+/// Reasoning item ids marking blocks that came from a Messages stream. Only
+/// these carry signatures the API will accept back.
+const THINKING_ITEM_ID: &str = "messages_thinking";
+const REDACTED_THINKING_ITEM_ID: &str = "messages_redacted_thinking";
+const CONTEXT_WINDOW_ERROR_MARKER: &str = "prompt is too long";
+
+/// Generation limits for a Messages request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MessagesRequestOptions {
+    pub(crate) max_tokens: u32,
+    pub(crate) thinking_budget_tokens: Option<u32>,
+}
+
+impl MessagesRequestOptions {
+    /// Derive the limits from the configured output cap and reasoning effort.
+    /// Extended thinking is only requested when an effort is configured, and
+    /// its budget always leaves room for the visible answer.
+    pub(crate) fn new(
+        max_output_tokens: Option<i64>,
+        effort: Option<ReasoningEffortConfig>,
+    ) -> Self {
+        let max_tokens = max_output_tokens
+            .and_then(|tokens| u32::try_from(tokens).ok())
+            .filter(|tokens| *tokens > 0)
+            .unwrap_or(DEFAULT_MAX_TOKENS);
+        let budget = match effort {
+            None | Some(ReasoningEffortConfig::Minimal) => None,
+            Some(ReasoningEffortConfig::Low) => Some(2_048),
+            Some(ReasoningEffortConfig::Medium) => Some(8_192),
+            Some(ReasoningEffortConfig::High) => Some(16_384),
+        };
+        let thinking_budget_tokens = budget
+            .map(|budget: u32| {
+                if budget >= max_tokens {
+                    max_tokens / 2
+                } else {
+                    budget
+                }
+            })
+            .filter(|budget| *budget >= MIN_THINKING_BUDGET_TOKENS);
+        Self {
+            max_tokens,
+            thinking_budget_tokens,
+        }
+    }
+}
+
+/// Names of the freeform tools in `tools`. Calls to these are surfaced as
+/// custom tool calls rather than function calls.
+pub(crate) fn freeform_tool_names(tools: &[ToolSpec]) -> HashSet<String> {
+    tools
+        .iter()
+        .filter_map(|tool| match tool {
+            ToolSpec::Freeform(tool) => Some(tool.name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Build the JSON body of a streaming Messages request for `prompt`.
+pub(crate) fn build_messages_request(
+    prompt: &Prompt,
+    model_family: &ModelFamily,
+    options: MessagesRequestOptions,
+) -> Result<Value> {
+    if prompt.output_schema.is_some() {
+        return Err(CodexErr::UnsupportedOperation(
+            "output_schema is not supported for Messages API".to_string(),
+        ));
+    }
+
+    let mut conversation = Conversation::default();
+    let full_instructions = prompt.get_full_instructions(model_family);
+    if !full_instructions.trim().is_empty() {
+        conversation.system.push(full_instructions.into_owned());
+    }
+    for item in &prompt.get_formatted_input() {
+        conversation.push_item(item);
+    }
+
+    let mut body = serde_json::Map::new();
+    body.insert("model".to_string(), json!(model_family.slug));
+    body.insert("max_tokens".to_string(), json!(options.max_tokens));
+    if !conversation.system.is_empty() {
+        body.insert(
+            "system".to_string(),
+            json!(conversation.system.join("\n\n")),
+        );
+    }
+    body.insert("messages".to_string(), json!(conversation.messages));
+    let tools_json = create_tools_json_for_messages_api(&prompt.tools)?;
+    if !tools_json.is_empty() {
+        body.insert("tools".to_string(), json!(tools_json));
+        body.insert(
+            "tool_choice".to_string(),
+            json!({
+                "type": "auto",
+                "disable_parallel_tool_use": !prompt.parallel_tool_calls,
+            }),
+        );
+    }
+    if let Some(budget_tokens) = options.thinking_budget_tokens {
+        body.insert(
+            "thinking".to_string(),
+            json!({"type": "enabled", "budget_tokens": budget_tokens}),
+        );
+    }
+    body.insert("stream".to_string(), json!(true));
+    Ok(Value::Object(body))
+}
+
+/// The `system` prompt and alternating `messages` of a request.
+#[derive(Default)]
+struct Conversation {
+    system: Vec<String>,
+    messages: Vec<Value>,
+}
+
+impl Conversation {
+    /// Append content blocks for `role`. Consecutive blocks for the same role
+    /// are merged into one message: the API requires roles to alternate and
+    /// all results for a batch of tool calls to share one user message.
+    fn push(&mut self, role: &str, blocks: Vec<Value>) {
+        if blocks.is_empty() {
+            return;
+        }
+        if let Some(last) = self.messages.last_mut()
+            && last.get("role").and_then(Value::as_str) == Some(role)
+            && let Some(content) = last.get_mut("content").and_then(Value::as_array_mut)
+        {
+            content.extend(blocks);
+            return;
+        }
+        self.messages.push(json!({"role": role, "content": blocks}));
+    }
+
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                if role == "system" || role == "developer" {
+                    let text = content
+                        .iter()
+                        .filter_map(|c| match c {
+                            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                                Some(text.as_str())
+                            }
+                            ContentItem::InputImage { .. } => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    if !text.trim().is_empty() {
+                        self.system.push(text);
+                    }
+                    return;
+                }
+                let blocks = content
+                    .iter()
+                    .filter_map(|c| match c {
+                        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                            (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+                        }
+                        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+                    })
+                    .collect();
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                self.push(role, blocks);
+            }
+            ResponseItem::Reasoning {
+                id,
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => {
+                // Reasoning from other providers carries signatures the
+                // Messages API cannot verify, so only replay our own blocks.
+                let block = match id.as_str() {
+                    THINKING_ITEM_ID => json!({
+                        "type": "thinking",
+                        "thinking": reasoning_text(content.as_deref()),
+                        "signature": signature,
+                    }),
+                    REDACTED_THINKING_ITEM_ID => {
+                        json!({"type": "redacted_thinking", "data": signature})
+                    }
+                    _ => return,
+                };
+                self.push("assistant", vec![block]);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                self.push(
+                    "assistant",
+                    vec![json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": tool_input(arguments),
+                    })],
+                );
+            }
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let call_id = call_id.clone().or_else(|| id.clone()).unwrap_or_default();
+                self.push(
+                    "assistant",
+                    vec![json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": "local_shell",
+                        "input": action,
+                    })],
+                );
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                self.push(
+                    "assistant",
+                    vec![json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": {"input": input},
+                    })],
+                );
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let content = match &output.content_items {
+                    Some(items) => json!(
+                        items
+                            .iter()
+                            .map(|item| match item {
+                                FunctionCallOutputContentItem::InputText { text } => {
+                                    json!({"type": "text", "text": text})
+                                }
+                                FunctionCallOutputContentItem::InputImage { image_url } => {
+                                    image_block(image_url)
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    ),
+                    None => json!(output.content),
+                };
+                let mut block = json!({
+                    "type": "tool_result",
+                    "tool_use_id": call_id,
+                    "content": content,
+                });
+                if output.success == Some(false)
+                    && let Some(obj) = block.as_object_mut()
+                {
+                    obj.insert("is_error".to_string(), json!(true));
+                }
+                self.push("user", vec![block]);
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push(
+                    "user",
+                    vec![json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": output,
+                    })],
+                );
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+}
+
+fn reasoning_text(content: Option<&[ReasoningItemContent]>) -> String {
+    content
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| match entry {
+            ReasoningItemContent::ReasoningText { text } | ReasoningItemContent::Text { text } => {
+                text.as_str()
+            }
+        })
+        .collect()
+}
+
+/// Tool inputs must be JSON objects; anything else is sent as an empty one.
+fn tool_input(arguments: &str) -> Value {
+    match serde_json::from_str::<Value>(arguments) {
+        Ok(input @ Value::Object(_)) => input,
+        _ => json!({}),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        })
+    } else {
+        json!({"type": "image", "source": {"type": "url", "url": image_url}})
+    }
+}
+
+/// Send `payload` to the provider's Messages endpoint and stream the reply.
+pub(crate) async fn stream_messages(
+    config: &Config,
+    payload: &Value,
+    freeform_tools: HashSet<String>,
+    client: &CodexHttpClient,
+    provider: &ModelProviderInfo,
+    otel_event_manager: &OtelEventManager,
+    auth: Option<CodexAuth>,
+) -> Result<ResponseStream> {
+    debug!(
+        "POST to {}: {}",
+        provider.get_full_url(&auth),
+        serde_json::to_string_pretty(payload).unwrap_or_default()
+    );
+
+    let cassette = Cassette::for_request(config, WireApi::Messages, payload).await?;
+    if let Some(cassette) = cassette.as_ref().filter(|cassette| cassette.is_replay()) {
+        let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+        tokio::spawn(process_messages_sse(
+            cassette.replay_stream().await?,
+            tx_event,
+            freeform_tools,
+            provider.stream_idle_timeout(),
+            otel_event_manager.clone(),
+        ));
+        return Ok(ResponseStream { rx_event });
+    }
+
+    let sets_version_header = provider
+        .http_headers
+        .iter()
+        .flat_map(HashMap::keys)
+        .chain(provider.env_http_headers.iter().flat_map(HashMap::keys))
+        .any(|name| name.eq_ignore_ascii_case(ANTHROPIC_VERSION_HEADER));
+
+    let mut attempt = 0;
+    let max_retries = provider.request_max_retries();
+    loop {
+        attempt += 1;
+
+        let mut req_builder = provider.create_request_builder(client, &auth).await?;
+        if !sets_version_header {
+            req_builder = req_builder.header(ANTHROPIC_VERSION_HEADER, ANTHROPIC_VERSION);
+        }
+
+        let res = otel_event_manager
+            .log_request(attempt, || {
+                req_builder
+                    .header(reqwest::header::ACCEPT, "text/event-stream")
+                    .json(payload)
+                    .send()
+            })
+            .await;
+
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+                let stream = resp.bytes_stream().map_err(|e| {
+                    CodexErr::ResponseStreamFailed(ResponseStreamFailed {
+                        source: e,
+                        request_id: None,
+                    })
+                });
+                tokio::spawn(process_messages_sse(
+                    record_stream(cassette.as_ref(), stream),
+                    tx_event,
+                    freeform_tools,
+                    provider.stream_idle_timeout(),
+                    otel_event_manager.clone(),
+                ));
+                return Ok(ResponseStream { rx_event });
+            }
+            Ok(res) => {
+                let status = res.status();
+                if !(status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) {
+                    let body = (res.text().await).unwrap_or_default();
+                    if status == StatusCode::BAD_REQUEST
+                        && body.contains(CONTEXT_WINDOW_ERROR_MARKER)
+                    {
+                        return Err(CodexErr::ContextWindowExceeded);
+                    }
+                    return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
+                        status,
+                        body,
+                        request_id: None,
+                    }));
+                }
+
+                if attempt > max_retries {
+                    return Err(CodexErr::RetryLimit(RetryLimitReachedError {
+                        status,
+                        request_id: None,
+                    }));
+                }
+
+                let retry_after_secs = res
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.parse::<u64>().ok());
+
+                let delay = retry_after_secs
+                    .map(|s| Duration::from_millis(s * 1_000))
+                    .unwrap_or_else(|| backoff(attempt));
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                if attempt > max_retries {
+                    return Err(CodexErr::ConnectionFailed(ConnectionFailedError {
+                        source: e,
+                    }));
+                }
+                let delay = backoff(attempt);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// used in tests to stream from a text SSE file
+pub(crate) async fn stream_messages_from_fixture(
+    path: impl AsRef<Path>,
+    freeform_tools: HashSet<String>,
+    provider: ModelProviderInfo,
+    otel_event_manager: OtelEventManager,
+) -> Result<ResponseStream> {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+    let f = std::fs::File::open(path.as_ref())?;
+    let lines = std::io::BufReader::new(f).lines();
+
+    // insert \n\n after each line for proper SSE parsing
+    let mut content = String::new();
+    for line in lines {
+        content.push_str(&line?);
+        content.push_str("\n\n");
+    }
+
+    let rdr = std::io::Cursor::new(content);
+    let stream = ReaderStream::new(rdr).map_err(CodexErr::Io);
+    tokio::spawn(process_messages_sse(
+        stream,
+        tx_event,
+        freeform_tools,
+        provider.stream_idle_timeout(),
+        otel_event_manager,
+    ));
+    Ok(ResponseStream { rx_event })
+}
+
+/// A content block that is still streaming, keyed by its index.
+enum ContentBlock {
+    Text(String),
+    Thinking {
+        thinking: String,
+        signature: String,
+    },
+    RedactedThinking(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+}
+
+impl ContentBlock {
+    fn from_start(block: &Value) -> Option<Self> {
+        let text_field = |name: &str| {
+            block
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        match block.get("type").and_then(Value::as_str)? {
+            "text" => Some(Self::Text(text_field("text"))),
+            "thinking" => Some(Self::Thinking {
+                thinking: text_field("thinking"),
+                signature: text_field("signature"),
+            }),
+            "redacted_thinking" => Some(Self::RedactedThinking(text_field("data"))),
+            "tool_use" => Some(Self::ToolUse {
+                id: text_field("id"),
+                name: text_field("name"),
+                input: String::new(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn into_item(self, freeform_tools: &HashSet<String>) -> Option<ResponseItem> {
+        match self {
+            Self::Text(text) if text.is_empty() => None,
+            Self::Text(text) => Some(ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text }],
+            }),
+            Self::Thinking {
+                thinking,
+                signature,
+            } => Some(ResponseItem::Reasoning {
+                id: THINKING_ITEM_ID.to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText { text: thinking }]),
+                encrypted_content: (!signature.is_empty()).then_some(signature),
+            }),
+            Self::RedactedThinking(data) => Some(ResponseItem::Reasoning {
+                id: REDACTED_THINKING_ITEM_ID.to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(data),
+            }),
+            Self::ToolUse { id, name, input } => {
+                let arguments = if input.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    input
+                };
+                if freeform_tools.contains(&name) {
+                    let input = serde_json::from_str::<Value>(&arguments)
+                        .ok()
+                        .and_then(|value| {
+                            value
+                                .get("input")
+                                .and_then(Value::as_str)
+                                .map(str::to_string)
+                        })
+                        .unwrap_or(arguments);
+                    Some(ResponseItem::CustomToolCall {
+                        id: None,
+                        status: None,
+                        call_id: id,
+                        name,
+                        input,
+                    })
+                } else {
+                    Some(ResponseItem::FunctionCall {
+                        id: None,
+                        name,
+                        arguments,
+                        call_id: id,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Token counts reported across `message_start` and `message_delta`.
+#[derive(Default)]
+struct MessagesUsage {
+    input_tokens: i64,
+    cache_read_input_tokens: i64,
+    cache_creation_input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl MessagesUsage {
+    fn update(&mut self, usage: &Value) {
+        let field = |name: &str| usage.get(name).and_then(Value::as_i64);
+        if let Some(tokens) = field("input_tokens") {
+            self.input_tokens = tokens;
+        }
+        if let Some(tokens) = field("cache_read_input_tokens") {
+            self.cache_read_input_tokens = tokens;
+        }
+        if let Some(tokens) = field("cache_creation_input_tokens") {
+            self.cache_creation_input_tokens = tokens;
+        }
+        if let Some(tokens) = field("output_tokens") {
+            self.output_tokens = tokens;
+        }
+    }
+
+    fn to_token_usage(&self) -> TokenUsage {
+        // The Messages API reports cached tokens separately; Codex counts
+        // them as part of the input like the Responses API does.
+        let input_tokens =
+            self.input_tokens + self.cache_read_input_tokens + self.cache_creation_input_tokens;
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens: self.cache_read_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + self.output_tokens,
+        }
+    }
+}
+
+/// SSE processor for the Messages streaming format. Each content block is
+/// forwarded as a single `OutputItemDone` once it stops, with text and
+/// thinking deltas streamed live in between.
+async fn process_messages_sse<S>(
+    stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent>>,
+    freeform_tools: HashSet<String>,
+    idle_timeout: Duration,
+    otel_event_manager: OtelEventManager,
+) where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+{
+    let mut stream = stream.eventsource();
+    let mut blocks: HashMap<u64, ContentBlock> = HashMap::new();
+    let mut response_id = String::new();
+    let mut usage: Option<MessagesUsage> = None;
+
+    loop {
+        let start = std::time::Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        let duration = start.elapsed();
+        otel_event_manager.log_sse_event(&response, duration);
+
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(e.to_string(), None)))
+                    .await;
+                return;
+            }
+            Ok(None) => {
+                let error = CodexErr::Stream("stream closed before message_stop".into(), None);
+                otel_event_manager.see_event_completed_failed(&error);
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(
+                        "idle timeout waiting for SSE".into(),
+                        None,
+                    )))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", sse.data);
+        let event: Value = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+        let index = event.get("index").and_then(Value::as_u64);
+
+        match event
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+        {
+            "message_start" => {
+                if let Some(message) = event.get("message") {
+                    if let Some(id) = message.get("id").and_then(Value::as_str) {
+                        response_id = id.to_string();
+                    }
+                    if let Some(message_usage) = message.get("usage") {
+                        usage
+                            .get_or_insert_with(MessagesUsage::default)
+                            .update(message_usage);
+                    }
+                }
+                if tx_event.send(Ok(ResponseEvent::Created)).await.is_err() {
+                    return;
+                }
+            }
+            "content_block_start" => {
+                let Some(index) = index else { continue };
+                let Some(block) = event
+                    .get("content_block")
+                    .and_then(ContentBlock::from_start)
+                else {
+                    continue;
+                };
+                blocks.insert(index, block);
+            }
+            "content_block_delta" => {
+                let (Some(index), Some(delta)) = (index, event.get("delta")) else {
+                    continue;
+                };
+                let Some(block) = blocks.get_mut(&index) else {
+                    continue;
+                };
+                let delta_text = |name: &str| delta.get(name).and_then(Value::as_str);
+                let forwarded = match (block, delta.get("type").and_then(Value::as_str)) {
+                    (ContentBlock::Text(text), Some("text_delta")) => {
+                        delta_text("text").map(|delta| {
+                            text.push_str(delta);
+                            ResponseEvent::OutputTextDelta(delta.to_string())
+                        })
+                    }
+                    (ContentBlock::Thinking { thinking, .. }, Some("thinking_delta")) => {
+                        delta_text("thinking").map(|delta| {
+                            thinking.push_str(delta);
+                            ResponseEvent::ReasoningContentDelta(delta.to_string())
+                        })
+                    }
+                    (ContentBlock::Thinking { signature, .. }, Some("signature_delta")) => {
+                        if let Some(delta) = delta_text("signature") {
+                            signature.push_str(delta);
+                        }
+                        None
+                    }
+                    (ContentBlock::ToolUse { input, .. }, Some("input_json_delta")) => {
+                        if let Some(delta) = delta_text("partial_json") {
+                            input.push_str(delta);
+                        }
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(forwarded) = forwarded
+                    && tx_event.send(Ok(forwarded)).await.is_err()
+                {
+                    return;
+                }
+            }
+            "content_block_stop" => {
+                let Some(block) = index.and_then(|index| blocks.remove(&index)) else {
+                    continue;
+                };
+                if let Some(item) = block.into_item(&freeform_tools)
+                    && tx_event
+                        .send(Ok(ResponseEvent::OutputItemDone(item)))
+                        .await
+                        .is_err()
+                {
+                    return;
+                }
+            }
+            "message_delta" => {
+                if let Some(delta_usage) = event.get("usage") {
+                    usage
+                        .get_or_insert_with(MessagesUsage::default)
+                        .update(delta_usage);
+                }
+            }
+            "message_stop" => {
+                let token_usage = usage.as_ref().map(MessagesUsage::to_token_usage);
+                if let Some(token_usage) = &token_usage {
+                    otel_event_manager.sse_event_completed(
+                        token_usage.input_tokens,
+                        token_usage.output_tokens,
+                        Some(token_usage.cached_input_tokens),
+                        None,
+                        token_usage.total_tokens,
+                    );
+                }
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id,
+                        token_usage,
+                    }))
+                    .await;
+                return;
+            }
+            "error" => {
+                let message = event
+                    .get("error")
+                    .and_then(|error| error.get("message"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("Messages API stream failed")
+                    .to_string();
+                let error = if message.contains(CONTEXT_WINDOW_ERROR_MARKER) {
+                    CodexErr::ContextWindowExceeded
+                } else {
+                    CodexErr::Stream(message, None)
+                };
+                otel_event_manager.see_event_completed_failed(&error);
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+            // `ping` and unknown event types carry nothing to forward.
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_family::find_family_for_model;
+    use codex_app_server_protocol::AuthMode;
+    use codex_protocol::ConversationId;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn otel_event_manager() -> OtelEventManager {
+        OtelEventManager::new(
+            ConversationId::new(),
+            "test",
+            "test",
+            Some("test@test.com".to_string()),
+            Some(AuthMode::ApiKey),
+            false,
+            "test".to_string(),
+        )
+    }
+
+    async fn run_sse(events: Vec<Value>, freeform_tools: &[&str]) -> Vec<Result<ResponseEvent>> {
+        let mut body = String::new();
+        for event in events {
+            let kind = event
+                .get("type")
+                .and_then(Value::as_str)
+                .expect("fixture event missing type");
+            body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent>>(16);
+        let stream = ReaderStream::new(std::io::Cursor::new(body)).map_err(CodexErr::Io);
+        tokio::spawn(process_messages_sse(
+            stream,
+            tx,
+            freeform_tools.iter().map(ToString::to_string).collect(),
+            Duration::from_secs(5),
+            otel_event_manager(),
+        ));
+
+        let mut out = Vec::new();
+        while let Some(event) = rx.recv().await {
+            out.push(event);
+        }
+        out
+    }
+
+    fn user_text(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_thinking_text_and_tool_use() {
+        let events = run_sse(
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 10, "cache_read_input_tokens": 5, "output_tokens": 1}}}),
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "plan"}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+                json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Hi"}}),
+                json!({"type": "content_block_stop", "index": 1}),
+                json!({"type": "ping"}),
+                json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+                json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+                json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "[\"ls\"]}"}}),
+                json!({"type": "content_block_stop", "index": 2}),
+                json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 20}}),
+                json!({"type": "message_stop"}),
+            ],
+            &[],
+        )
+        .await;
+        let events: Vec<ResponseEvent> = events.into_iter().map(Result::unwrap).collect();
+
+        assert_eq!(events.len(), 7, "unexpected events: {events:?}");
+        assert!(matches!(events[0], ResponseEvent::Created));
+        assert!(
+            matches!(&events[1], ResponseEvent::ReasoningContentDelta(delta) if delta == "plan")
+        );
+        match &events[2] {
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                id,
+                content,
+                encrypted_content,
+                ..
+            }) => {
+                assert_eq!(id, THINKING_ITEM_ID);
+                assert_eq!(reasoning_text(content.as_deref()), "plan");
+                assert_eq!(encrypted_content.as_deref(), Some("sig"));
+            }
+            other => panic!("expected thinking item, got {other:?}"),
+        }
+        assert!(matches!(&events[3], ResponseEvent::OutputTextDelta(delta) if delta == "Hi"));
+        assert!(matches!(
+            &events[4],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, .. }) if role == "assistant"
+        ));
+        match &events[5] {
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            }) => {
+                assert_eq!(name, "shell");
+                assert_eq!(arguments, "{\"command\":[\"ls\"]}");
+                assert_eq!(call_id, "toolu_1");
+            }
+            other => panic!("expected function call, got {other:?}"),
+        }
+        match &events[6] {
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+            } => {
+                assert_eq!(response_id, "msg_1");
+                let usage = token_usage.clone().expect("usage reported");
+                assert_eq!(usage.input_tokens, 15);
+                assert_eq!(usage.cached_input_tokens, 5);
+                assert_eq!(usage.output_tokens, 20);
+                assert_eq!(usage.total_tokens, 35);
+            }
+            other => panic!("expected completed, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn freeform_tool_use_becomes_custom_tool_call() {
+        let events = run_sse(
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_2"}}),
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "tool_use", "id": "toolu_2", "name": "apply_patch", "input": {}}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "input_json_delta", "partial_json": "{\"input\":\"*** Begin Patch\"}"}}),
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({"type": "message_stop"}),
+            ],
+            &["apply_patch"],
+        )
+        .await;
+
+        match &events[1] {
+            Ok(ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            })) => {
+                assert_eq!(call_id, "toolu_2");
+                assert_eq!(name, "apply_patch");
+                assert_eq!(input, "*** Begin Patch");
+            }
+            other => panic!("expected custom tool call, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_errors_are_surfaced() {
+        let events = run_sse(
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_3"}}),
+                json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+            ],
+            &[],
+        )
+        .await;
+        assert!(
+            matches!(events.last(), Some(Err(CodexErr::Stream(message, None))) if message == "Overloaded")
+        );
+
+        let events = run_sse(
+            vec![json!({"type": "message_start", "message": {"id": "msg_4"}})],
+            &[],
+        )
+        .await;
+        assert!(matches!(
+            events.last(),
+            Some(Err(CodexErr::Stream(_, None)))
+        ));
+    }
+
+    #[test]
+    fn builds_request_from_history() {
+        let family = find_family_for_model("gpt-5").expect("known model family");
+        let mut prompt = Prompt::default();
+        prompt.input = vec![
+            user_text("list files"),
+            ResponseItem::Reasoning {
+                id: THINKING_ITEM_ID.to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "use ls".to_string(),
+                }]),
+                encrypted_content: Some("sig".to_string()),
+            },
+            ResponseItem::Reasoning {
+                id: "rs_openai".to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("opaque".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"ls\"]}".to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "README.md".to_string(),
+                    content_items: None,
+                    success: Some(false),
+                },
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                }],
+            },
+        ];
+
+        let payload = build_messages_request(
+            &prompt,
+            &family,
+            MessagesRequestOptions::new(None, Some(ReasoningEffortConfig::Low)),
+        )
+        .unwrap();
+
+        assert_eq!(payload["max_tokens"], json!(DEFAULT_MAX_TOKENS));
+        assert_eq!(
+            payload["thinking"],
+            json!({"type": "enabled", "budget_tokens": 2_048})
+        );
+        assert!(payload["system"].as_str().is_some());
+        assert_eq!(
+            payload["messages"],
+            json!([
+                {"role": "user", "content": [{"type": "text", "text": "list files"}]},
+                {"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "use ls", "signature": "sig"},
+                    {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["ls"]}},
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "README.md", "is_error": true},
+                    {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"}},
+                ]},
+            ])
+        );
+    }
+
+    #[test]
+    fn thinking_budget_fits_within_max_tokens() {
+        assert_eq!(
+            MessagesRequestOptions::new(Some(4_096), Some(ReasoningEffortConfig::High)),
+            MessagesRequestOptions {
+                max_tokens: 4_096,
+                thinking_budget_tokens: Some(2_048),
+            }
+        );
+        assert_eq!(
+            MessagesRequestOptions::new(Some(1_500), Some(ReasoningEffortConfig::Medium))
+                .thinking_budget_tokens,
+            None
+        );
+        assert_eq!(
+            MessagesRequestOptions::new(None, None).thinking_budget_tokens,
+            None
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Opt-in local cache of model responses.
+//!
+//! CI jobs often run the same `exec` prompt over identical input. With
+//! `[response_cache] enabled = true`, each request is keyed on a hash of its
+//! instructions, input items, tools and model settings, and a fresh stored
+//! reply is replayed through the usual [`ResponseEvent`] stream instead of
+//! calling the model. Replays start with [`ResponseEvent::CacheHit`] and
+//! report no token usage, so budgets and the usage ledger are not charged
+//! again for a reply that cost nothing.
+//!
+//! Only tool-free turns are cached: a request is bypassed when the current
+//! turn already ran a tool with side effects, and a reply is only stored when
+//! it calls nothing but read-only tools. Entries live under
+//! `$CODEX_HOME/cache/responses` and are pruned by age, count and total size.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_protocol::config_types::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::Verbosity;
+use codex_protocol::models::ResponseItem;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tracing::debug;
+use tracing::warn;
+
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::client_common::tools::ToolSpec;
+use crate::config::Config;
+use crate::error::Result;
+
+const DEFAULT_TTL_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Tools that only read state, so replaying a call to them repeats no side
+/// effects.
+const IDEMPOTENT_TOOLS: &[&str] = &[
+    "read_file",
+    "list_dir",
+    "grep_files",
+    "view_image",
+    "read_tool_output",
+];
+
+/// Everything that influences the model's reply. Serialized and hashed to
+/// form the cache key.
+#[derive(Serialize)]
+struct CacheKeyMaterial<'a> {
+    base_url: Option<&'a str>,
+    model: &'a str,
+    effort: Option<ReasoningEffortConfig>,
+    summary: ReasoningSummaryConfig,
+    verbosity: Option<Verbosity>,
+    max_output_tokens: Option<i64>,
+    instructions: &'a str,
+    input: &'a [ResponseItem],
+    tools: &'a [ToolSpec],
+    parallel_tool_calls: bool,
+    output_schema: Option<&'a Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    response_id: String,
+    items: Vec<ResponseItem>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseCache {
+    dir: PathBuf,
+    ttl: Duration,
+    max_entries: usize,
+    max_bytes: u64,
+}
+
+impl ResponseCache {
+    /// The cache configured for this session, if enabled.
+    pub(crate) fn for_config(config: &Config) -> Option<Self> {
+        let settings = &config.response_cache;
+        settings.enabled.then(|| Self {
+            dir: config.codex_home.join("cache").join("responses"),
+            ttl: Duration::from_secs(settings.ttl_secs.unwrap_or(DEFAULT_TTL_SECS)),
+            max_entries: settings.max_entries.unwrap_or(DEFAULT_MAX_ENTRIES),
+            max_bytes: settings.max_bytes.unwrap_or(DEFAULT_MAX_BYTES),
+        })
+    }
+
+    /// Cache key for `prompt`, or `None` when the current turn has already
+    /// run a tool with side effects and must reach the model.
+    pub(crate) fn key(
+        config: &Config,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        prompt: &Prompt,
+    ) -> Option<String> {
+        let current_turn = prompt
+            .input
+            .iter()
+            .rposition(|item| matches!(item, ResponseItem::Message { role, .. } if role == "user"))
+            .map_or(&prompt.input[..], |start| &prompt.input[start..]);
+        if !current_turn.iter().all(is_idempotent) {
+            return None;
+        }
+
+        let instructions = prompt.get_full_instructions(&config.model_family);
+        let material = CacheKeyMaterial {
+            base_url: config.model_provider.base_url.as_deref(),
+            model: &config.model,
+            effort,
+            summary,
+            verbosity: config.model_verbosity,
+            max_output_tokens: config.model_max_output_tokens,
+            instructions: &instructions,
+            input: &prompt.input,
+            tools: &prompt.tools,
+            parallel_tool_calls: prompt.parallel_tool_calls,
+            output_schema: prompt.output_schema.as_ref(),
+        };
+        let bytes = serde_json::to_vec(&material).ok()?;
+        Some(format!("{:x}", Sha256::digest(&bytes)))
+    }
+
+    /// Replays the stored reply for `key` when one is fresh.
+    pub(crate) fn lookup(&self, key: &str) -> Option<ResponseStream> {
+        let path = self.entry_path(key);
+        let modified = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()?;
+        if is_expired(modified, self.ttl) {
+            return None;
+        }
+        let data = std::fs::read(&path).ok()?;
+        let cached: CachedResponse = match serde_json::from_slice(&data) {
+            Ok(cached) => cached,
+            Err(err) => {
+                warn!(
+                    "ignoring unreadable cached response {}: {err}",
+                    path.display()
+                );
+                return None;
+            }
+        };
+        debug!("serving response {} from cache", cached.response_id);
+
+        let mut events = vec![ResponseEvent::CacheHit, ResponseEvent::Created];
+        events.extend(cached.items.into_iter().map(ResponseEvent::OutputItemDone));
+        events.push(ResponseEvent::Completed {
+            response_id: cached.response_id,
+            token_usage: None,
+        });
+
+        let (tx, rx_event) = mpsc::channel(events.len());
+        for event in events {
+            // The channel has room for every event.
+            let _ = tx.try_send(Ok(event));
+        }
+        Some(ResponseStream { rx_event })
+    }
+
+    /// Passes `stream` through and stores its reply under `key` once it
+    /// completes, unless the reply calls a tool with side effects.
+    pub(crate) fn record(self, key: String, mut stream: ResponseStream) -> ResponseStream {
+        let (tx, rx_event) = mpsc::channel::<Result<ResponseEvent>>(16);
+        tokio::spawn(async move {
+            let mut items = Vec::new();
+            let mut cacheable = true;
+            while let Some(event) = stream.rx_event.recv().await {
+                match &event {
+                    Ok(ResponseEvent::OutputItemDone(item)) => {
+                        cacheable &= is_idempotent(item);
+                        items.push(item.clone());
+                    }
+                    Ok(ResponseEvent::Completed { response_id, .. }) if cacheable => {
+                        let cached = CachedResponse {
+                            response_id: response_id.clone(),
+                            items: std::mem::take(&mut items),
+                        };
+                        let cache = self.clone();
+                        let key = key.clone();
+                        tokio::task::spawn_blocking(move || cache.store(&key, &cached));
+                    }
+                    _ => {}
+                }
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        ResponseStream { rx_event }
+    }
+
+    fn store(&self, key: &str, cached: &CachedResponse) {
+        let path = self.entry_path(key);
+        let written = std::fs::create_dir_all(&self.dir)
+            .and_then(|()| serde_json::to_vec(cached).map_err(std::io::Error::from))
+            .and_then(|data| std::fs::write(&path, data));
+        if let Err(err) = written {
+            warn!("failed to cache response at {}: {err}", path.display());
+            return;
+        }
+        self.prune();
+    }
+
+    /// Removes expired entries, then the oldest ones until the cache fits its
+    /// entry and size limits.
+    fn prune(&self) {
+        let Ok(read_dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut entries: Vec<(PathBuf, SystemTime, u64)> = read_dir
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok()?;
+                Some((entry.path(), meta.modified().ok()?, meta.len()))
+            })
+            .collect();
+        entries.sort_by_key(|(_, modified, _)| std::cmp::Reverse(*modified));
+
+        let mut kept = 0usize;
+        let mut kept_bytes = 0u64;
+        for (path, modified, len) in entries {
+            let fits = kept < self.max_entries && kept_bytes + len <= self.max_bytes;
+            if fits && !is_expired(modified, self.ttl) {
+                kept += 1;
+                kept_bytes += len;
+            } else {
+                remove_entry(&path);
+            }
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+fn is_expired(modified: SystemTime, ttl: Duration) -> bool {
+    modified.elapsed().is_ok_and(|age| age > ttl)
+}
+
+fn remove_entry(path: &Path) {
+    if let Err(err) = std::fs::remove_file(path) {
+        debug!("failed to prune cached response {}: {err}", path.display());
+    }
+}
+
+/// Whether replaying `item` repeats no side effects: plain messages and
+/// reasoning, or calls to read-only tools and their outputs.
+fn is_idempotent(item: &ResponseItem) -> bool {
+    match item {
+        ResponseItem::FunctionCall { name, .. } | ResponseItem::CustomToolCall { name, .. } => {
+            IDEMPOTENT_TOOLS.contains(&name.as_str())
+        }
+        ResponseItem::LocalShellCall { .. } | ResponseItem::WebSearchCall { .. } => false,
+        ResponseItem::Message { .. }
+        | ResponseItem::Reasoning { .. }
+        | ResponseItem::FunctionCallOutput { .. }
+        | ResponseItem::CustomToolCallOutput { .. }
+        | ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Other => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigOverrides;
+    use crate::config::ConfigToml;
+    use crate::config::types::ResponseCache as ResponseCacheToml;
+    use crate::protocol::TokenUsage;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn function_call(name: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call_1".to_string(),
+        }
+    }
+
+    fn test_config(codex_home: &TempDir) -> Config {
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect("load config");
+        config.response_cache = ResponseCacheToml {
+            enabled: true,
+            max_entries: Some(1),
+            ..ResponseCacheToml::default()
+        };
+        config
+    }
+
+    async fn collect(mut stream: ResponseStream) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        while let Some(event) = stream.rx_event.recv().await {
+            events.push(event.expect("event"));
+        }
+        events
+    }
+
+    #[test]
+    fn bypasses_turns_with_side_effecting_tools() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let config = test_config(&codex_home);
+        let key = |input: Vec<ResponseItem>| {
+            let prompt = Prompt {
+                input,
+                ..Prompt::default()
+            };
+            ResponseCache::key(&config, None, ReasoningSummaryConfig::default(), &prompt)
+        };
+
+        let plain = key(vec![message("user", "summarize this diff")]);
+        assert!(plain.is_some());
+        assert_eq!(plain, key(vec![message("user", "summarize this diff")]));
+        assert_ne!(plain, key(vec![message("user", "summarize that diff")]));
+
+        // Earlier turns do not matter; the current one does.
+        assert!(
+            key(vec![
+                message("user", "fix it"),
+                function_call("shell"),
+                message("user", "summarize this diff"),
+                function_call("read_file"),
+            ])
+            .is_some()
+        );
+        assert_eq!(
+            key(vec![
+                message("user", "summarize this diff"),
+                function_call("shell"),
+            ]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_tool_free_replies_and_skips_others() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let config = test_config(&codex_home);
+        let cache = ResponseCache::for_config(&config).expect("cache enabled");
+
+        let live = |items: Vec<ResponseItem>| {
+            let (tx, rx_event) = mpsc::channel(8);
+            for item in items {
+                tx.try_send(Ok(ResponseEvent::OutputItemDone(item)))
+                    .expect("send");
+            }
+            tx.try_send(Ok(ResponseEvent::Completed {
+                response_id: "resp_1".to_string(),
+                token_usage: Some(TokenUsage {
+                    input_tokens: 100,
+                    output_tokens: 20,
+                    total_tokens: 120,
+                    ..Default::default()
+                }),
+            }))
+            .expect("send");
+            ResponseStream { rx_event }
+        };
+
+        let passed = collect(cache.clone().record(
+            "tool-free".to_string(),
+            live(vec![message("assistant", "looks good")]),
+        ))
+        .await;
+        assert_eq!(passed.len(), 2);
+        collect(
+            cache
+                .clone()
+                .record("shell".to_string(), live(vec![function_call("shell")])),
+        )
+        .await;
+
+        // Stores happen on a blocking task; wait for the entry to land.
+        for _ in 0..100 {
+            if cache.entry_path("tool-free").exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let replayed = collect(cache.lookup("tool-free").expect("cached")).await;
+        assert!(matches!(replayed[0], ResponseEvent::CacheHit));
+        assert!(matches!(
+            &replayed[2],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, .. }) if role == "assistant"
+        ));
+        assert!(matches!(
+            &replayed[3],
+            ResponseEvent::Completed { response_id, token_usage: None } if response_id == "resp_1"
+        ));
+        assert!(cache.lookup("shell").is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Transparent zstd compression for archived rollout files.
+//!
+//! A compressed rollout keeps its original name with a `.zst` suffix
+//! (`rollout-<ts>-<uuid>.jsonl.zst`). Readers go through
+//! [`read_rollout_text`] so they never need to care which form is on disk;
+//! a compressed rollout is decompressed back in place before it is resumed
+//! and appended to again.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Suffix appended to the `.jsonl` name of a compressed rollout.
+pub const COMPRESSED_ROLLOUT_SUFFIX: &str = ".zst";
+
+const COMPRESSION_LEVEL: i32 = 9;
+
+/// Whether `name` is a rollout file name, compressed or not.
+pub(crate) fn is_rollout_file_name(name: &str) -> bool {
+    name.starts_with("rollout-") && (name.ends_with(".jsonl") || name.ends_with(".jsonl.zst"))
+}
+
+/// Whether the rollout at `path` is stored zstd-compressed.
+pub fn is_compressed_rollout(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".jsonl.zst"))
+}
+
+/// Read the full JSONL text of a rollout, decompressing it when needed.
+pub async fn read_rollout_text(path: &Path) -> io::Result<String> {
+    if !is_compressed_rollout(path) {
+        return tokio::fs::read_to_string(path).await;
+    }
+    let bytes = tokio::fs::read(path).await?;
+    let decoded = tokio::task::spawn_blocking(move || zstd::decode_all(bytes.as_slice()))
+        .await
+        .map_err(io::Error::other)??;
+    String::from_utf8(decoded).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Write `text` as the compressed form of the rollout at `path` and remove
+/// the uncompressed original. Returns the path of the compressed file.
+pub(crate) async fn write_compressed_rollout(path: &Path, text: String) -> io::Result<PathBuf> {
+    let mut name = path.as_os_str().to_owned();
+    name.push(COMPRESSED_ROLLOUT_SUFFIX);
+    let compressed_path = PathBuf::from(name);
+
+    let encoded =
+        tokio::task::spawn_blocking(move || zstd::encode_all(text.as_bytes(), COMPRESSION_LEVEL))
+            .await
+            .map_err(io::Error::other)??;
+    replace_file(&compressed_path, &encoded).await?;
+    tokio::fs::remove_file(path).await?;
+    Ok(compressed_path)
+}
+
+/// Decompress the rollout at `path` back to plain JSONL so it can be appended
+/// to. Returns the path of the uncompressed file; uncompressed inputs are
+/// returned unchanged.
+pub async fn decompress_rollout_in_place(path: &Path) -> io::Result<PathBuf> {
+    if !is_compressed_rollout(path) {
+        return Ok(path.to_path_buf());
+    }
+    let text = read_rollout_text(path).await?;
+    let plain_path = path.with_extension("");
+    replace_file(&plain_path, text.as_bytes()).await?;
+    tokio::fs::remove_file(path).await?;
+    Ok(plain_path)
+}
+
+/// Write `contents` to `path` through a temporary sibling so readers never
+/// observe a partially written file.
+pub(crate) async fn replace_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp = PathBuf::from(tmp_name);
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn compress_and_decompress_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir
+            .path()
+            .join("rollout-2025-01-01T00-00-00-00000000-0000-0000-0000-000000000001.jsonl");
+        let text = "{\"a\":1}\n{\"b\":2}\n".to_string();
+        tokio::fs::write(&path, &text).await.unwrap();
+
+        let compressed = write_compressed_rollout(&path, text.clone()).await.unwrap();
+        assert!(is_compressed_rollout(&compressed));
+        assert!(!path.exists());
+        assert_eq!(read_rollout_text(&compressed).await.unwrap(), text);
+
+        let restored = decompress_rollout_in_place(&compressed).await.unwrap();
+        assert_eq!(restored, path);
+        assert!(!compressed.exists());
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), text);
+    }
+}
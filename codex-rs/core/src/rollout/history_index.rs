@@ -0,0 +1,704 @@
+//! Incremental full-text index over recorded rollouts.
+//!
+//! The index lives in `~/.codex/history_index.jsonl` and holds one entry per
+//! rollout file, split into user turns. Rollouts are append-only, so each
+//! entry remembers how many bytes of its file have been indexed; refreshing
+//! only parses what was appended since the previous search.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ConversationId;
+use codex_protocol::items::TurnItem;
+use codex_protocol::items::UserMessageItem;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use serde::Deserialize;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tracing::warn;
+
+use super::SESSIONS_SUBDIR;
+use super::compression::is_compressed_rollout;
+use super::compression::is_rollout_file_name;
+use super::compression::read_rollout_text;
+use crate::event_mapping::parse_turn_item;
+
+pub const HISTORY_INDEX_FILE: &str = "history_index.jsonl";
+
+/// Upper bound on the text kept per turn so the index stays small.
+const MAX_TURN_TEXT_BYTES: usize = 16 * 1024;
+/// Tool outputs are only partially indexed; the head usually names the failure.
+const MAX_TOOL_OUTPUT_BYTES: usize = 1024;
+const SNIPPET_RADIUS: usize = 60;
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Filters for [`search_history`]. Every populated field must match.
+#[derive(Debug, Clone, Default)]
+pub struct HistorySearchQuery {
+    /// Whitespace-separated terms; all must appear (case-insensitively) in the
+    /// same turn.
+    pub text: Option<String>,
+    /// Only turns whose working directory is this path or below it.
+    pub cwd: Option<PathBuf>,
+    /// Substring of the model slug used for the turn.
+    pub model: Option<String>,
+    /// Inclusive lower bound on the turn date (`YYYY-MM-DD`).
+    pub since: Option<String>,
+    /// Inclusive upper bound on the turn date (`YYYY-MM-DD`).
+    pub until: Option<String>,
+    /// Name of a tool that must have been called during the turn.
+    pub tool: Option<String>,
+    /// Maximum number of hits; defaults to 20.
+    pub limit: Option<usize>,
+}
+
+/// A single turn matching a [`HistorySearchQuery`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistorySearchHit {
+    pub conversation_id: ConversationId,
+    pub path: PathBuf,
+    /// 1-based index of the user turn within the conversation.
+    pub turn_index: usize,
+    pub timestamp: String,
+    pub cwd: Option<PathBuf>,
+    pub model: Option<String>,
+    pub tools: Vec<String>,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IndexedSession {
+    path: PathBuf,
+    conversation_id: Option<ConversationId>,
+    /// Number of bytes of the rollout file reflected in `turns`.
+    indexed_bytes: u64,
+    /// Latest cwd/model seen, inherited by turns that start later.
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    turns: Vec<IndexedTurn>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IndexedTurn {
+    index: usize,
+    timestamp: String,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    tools: BTreeSet<String>,
+    text: String,
+}
+
+/// Local index over every rollout under `~/.codex/sessions`.
+#[derive(Debug, Default)]
+pub struct HistoryIndex {
+    sessions: HashMap<PathBuf, IndexedSession>,
+}
+
+impl HistoryIndex {
+    /// Load the persisted index. A missing or unreadable index yields an empty
+    /// one, which the next [`HistoryIndex::refresh`] rebuilds from scratch.
+    pub async fn load(codex_home: &Path) -> Self {
+        let path = codex_home.join(HISTORY_INDEX_FILE);
+        let Ok(text) = tokio::fs::read_to_string(&path).await else {
+            return Self::default();
+        };
+        let mut sessions = HashMap::new();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<IndexedSession>(line) {
+                Ok(session) => {
+                    sessions.insert(session.path.clone(), session);
+                }
+                Err(err) => warn!("ignoring corrupt history index entry: {err}"),
+            }
+        }
+        Self { sessions }
+    }
+
+    pub async fn save(&self, codex_home: &Path) -> io::Result<()> {
+        let path = codex_home.join(HISTORY_INDEX_FILE);
+        let mut entries: Vec<&IndexedSession> = self.sessions.values().collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut out = String::new();
+        for entry in entries {
+            out.push_str(&serde_json::to_string(entry)?);
+            out.push('\n');
+        }
+        // Each save writes its own temp file, so concurrent saves never mix
+        // their writes; the last rename wins.
+        let dir = codex_home.to_path_buf();
+        tokio::task::spawn_blocking(move || -> io::Result<()> {
+            let tmp = NamedTempFile::new_in(&dir)?;
+            std::fs::write(tmp.path(), out)?;
+            tmp.persist(&path).map_err(|err| err.error)?;
+            Ok(())
+        })
+        .await?
+    }
+
+    /// Bring the index up to date with the rollouts on disk, parsing only the
+    /// bytes appended since the last refresh. Returns the number of rollout
+    /// files that were (re)indexed.
+    pub async fn refresh(&mut self, codex_home: &Path) -> io::Result<usize> {
+        let root = codex_home.join(SESSIONS_SUBDIR);
+        let files = collect_rollout_files(&root).await?;
+        let mut updated = 0;
+
+        let present: BTreeSet<&PathBuf> = files.iter().map(|(path, _)| path).collect();
+        self.sessions.retain(|path, _| present.contains(path));
+
+        for (path, len) in &files {
+            let entry = self
+                .sessions
+                .entry(path.clone())
+                .or_insert_with(|| IndexedSession {
+                    path: path.clone(),
+                    ..Default::default()
+                });
+            if entry.indexed_bytes == *len {
+                continue;
+            }
+            if entry.indexed_bytes > *len {
+                // The file was rewritten; start over.
+                *entry = IndexedSession {
+                    path: path.clone(),
+                    ..Default::default()
+                };
+            }
+            match index_appended_lines(entry).await {
+                Ok(()) => updated += 1,
+                Err(err) => warn!("failed to index rollout {}: {err}", path.display()),
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Return matching turns, newest first.
+    pub fn search(&self, query: &HistorySearchQuery) -> Vec<HistorySearchHit> {
+        let terms: Vec<String> = query
+            .text
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        let model = query.model.as_deref().map(str::to_lowercase);
+
+        let mut hits = Vec::new();
+        for session in self.sessions.values() {
+            let Some(conversation_id) = session.conversation_id else {
+                continue;
+            };
+            for turn in &session.turns {
+                if !turn_matches(turn, query, &terms, model.as_deref()) {
+                    continue;
+                }
+                hits.push(HistorySearchHit {
+                    conversation_id,
+                    path: session.path.clone(),
+                    turn_index: turn.index,
+                    timestamp: turn.timestamp.clone(),
+                    cwd: turn.cwd.clone(),
+                    model: turn.model.clone(),
+                    tools: turn.tools.iter().cloned().collect(),
+                    snippet: snippet(&turn.text, terms.first().map(String::as_str)),
+                });
+            }
+        }
+        hits.sort_by(|a, b| {
+            b.timestamp
+                .cmp(&a.timestamp)
+                .then_with(|| b.turn_index.cmp(&a.turn_index))
+        });
+        hits.truncate(query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+        hits
+    }
+}
+
+/// Refresh the on-disk index and run `query` against it.
+pub async fn search_history(
+    codex_home: &Path,
+    query: &HistorySearchQuery,
+) -> io::Result<Vec<HistorySearchHit>> {
+    let mut index = HistoryIndex::load(codex_home).await;
+    if index.refresh(codex_home).await? > 0 {
+        index.save(codex_home).await?;
+    }
+    Ok(index.search(query))
+}
+
+fn turn_matches(
+    turn: &IndexedTurn,
+    query: &HistorySearchQuery,
+    terms: &[String],
+    model: Option<&str>,
+) -> bool {
+    if let Some(cwd) = &query.cwd
+        && !turn
+            .cwd
+            .as_ref()
+            .is_some_and(|turn_cwd| turn_cwd.starts_with(cwd))
+    {
+        return false;
+    }
+    if let Some(model) = model
+        && !turn
+            .model
+            .as_ref()
+            .is_some_and(|turn_model| turn_model.to_lowercase().contains(model))
+    {
+        return false;
+    }
+    let date = turn.timestamp.get(..10).unwrap_or(&turn.timestamp);
+    if let Some(since) = &query.since
+        && date < since.as_str()
+    {
+        return false;
+    }
+    if let Some(until) = &query.until
+        && date > until.as_str()
+    {
+        return false;
+    }
+    if let Some(tool) = &query.tool
+        && !turn.tools.contains(tool)
+    {
+        return false;
+    }
+    if terms.is_empty() {
+        return true;
+    }
+    let haystack = turn.text.to_lowercase();
+    terms.iter().all(|term| haystack.contains(term.as_str()))
+}
+
+/// Excerpt of `text` around the first occurrence of `term`.
+fn snippet(text: &str, term: Option<&str>) -> String {
+    let start = term
+        .and_then(|term| find_case_insensitive(text, term))
+        .unwrap_or(0);
+    let mut from = start.saturating_sub(SNIPPET_RADIUS);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (start + SNIPPET_RADIUS * 2).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+    let mut excerpt = text[from..to]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if from > 0 {
+        excerpt.insert(0, '…');
+    }
+    if to < text.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// Byte offset in `text` of the first case-insensitive match of `term`, which
+/// must already be lowercase. Lowercasing can change a character's length
+/// (e.g. `İ`), so offsets into the lowered text are mapped back to `text`.
+fn find_case_insensitive(text: &str, term: &str) -> Option<usize> {
+    let mut lowered = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+    for (offset, ch) in text.char_indices() {
+        for lower in ch.to_lowercase() {
+            lowered.push(lower);
+            origins.resize(lowered.len(), offset);
+        }
+    }
+    lowered.find(term).map(|pos| origins[pos])
+}
+
+/// Recursively collect `rollout-*.jsonl[.zst]` files with their current sizes.
+async fn collect_rollout_files(root: &Path) -> io::Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    if !root.exists() {
+        return Ok(files);
+    }
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file()
+                && let Some(name) = entry.file_name().to_str()
+                && is_rollout_file_name(name)
+            {
+                files.push((path, entry.metadata().await?.len()));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Parse the complete lines appended to `session.path` since the last refresh.
+async fn index_appended_lines(session: &mut IndexedSession) -> io::Result<()> {
+    if is_compressed_rollout(&session.path) {
+        // Compressed rollouts are never appended to; index them in one go and
+        // record the compressed size so unchanged files are skipped later.
+        let text = read_rollout_text(&session.path).await?;
+        for line in text.lines() {
+            if let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(line.trim()) {
+                index_rollout_line(session, rollout_line);
+            }
+        }
+        session.indexed_bytes = tokio::fs::metadata(&session.path).await?.len();
+        return Ok(());
+    }
+
+    let mut file = tokio::fs::File::open(&session.path).await?;
+    file.seek(io::SeekFrom::Start(session.indexed_bytes))
+        .await?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).await?;
+
+    // A writer may be mid-line; leave the partial line for the next refresh.
+    let Some(last_newline) = bytes.iter().rposition(|b| *b == b'\n') else {
+        return Ok(());
+    };
+    let complete = &bytes[..=last_newline];
+    for line in String::from_utf8_lossy(complete).lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(trimmed) {
+            index_rollout_line(session, rollout_line);
+        }
+    }
+    session.indexed_bytes += complete.len() as u64;
+    Ok(())
+}
+
+/// The user message that starts a turn, if `item` is one. Turns are numbered
+/// by these items both here and when forking a conversation at a turn, so a
+/// search hit's `turn_index` can be forked directly. Session prefix items such
+/// as the environment context do not start a turn.
+pub(crate) fn user_turn_message(item: &RolloutItem) -> Option<UserMessageItem> {
+    match item {
+        RolloutItem::ResponseItem(item @ ResponseItem::Message { .. }) => {
+            match parse_turn_item(item) {
+                Some(TurnItem::UserMessage(message)) => Some(message),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn index_rollout_line(session: &mut IndexedSession, line: RolloutLine) {
+    let RolloutLine { timestamp, item } = line;
+    if let Some(message) = user_turn_message(&item) {
+        let turn = IndexedTurn {
+            index: session.turns.len() + 1,
+            timestamp,
+            cwd: session.cwd.clone(),
+            model: session.model.clone(),
+            tools: BTreeSet::new(),
+            text: String::new(),
+        };
+        session.turns.push(turn);
+        append_turn_text(session, &message.message(), usize::MAX);
+        return;
+    }
+    match item {
+        RolloutItem::SessionMeta(meta_line) => {
+            session.conversation_id.get_or_insert(meta_line.meta.id);
+            session.cwd.get_or_insert(meta_line.meta.cwd);
+        }
+        RolloutItem::TurnContext(context) => {
+            session.cwd = Some(context.cwd.clone());
+            session.model = Some(context.model.clone());
+            if let Some(turn) = session.turns.last_mut() {
+                turn.cwd = Some(context.cwd);
+                turn.model = Some(context.model);
+            }
+        }
+        RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+            append_turn_text(session, &event.message, usize::MAX);
+        }
+        RolloutItem::ResponseItem(item) => index_response_item(session, item),
+        RolloutItem::EventMsg(_) | RolloutItem::Compacted(_) => {}
+    }
+}
+
+fn index_response_item(session: &mut IndexedSession, item: ResponseItem) {
+    match item {
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => {
+            record_tool(session, &name);
+            append_turn_text(session, &arguments, MAX_TOOL_OUTPUT_BYTES);
+        }
+        ResponseItem::CustomToolCall { name, input, .. } => {
+            record_tool(session, &name);
+            append_turn_text(session, &input, MAX_TOOL_OUTPUT_BYTES);
+        }
+        ResponseItem::LocalShellCall { action, .. } => {
+            record_tool(session, "local_shell");
+            let LocalShellAction::Exec(exec) = action;
+            append_turn_text(session, &exec.command.join(" "), MAX_TOOL_OUTPUT_BYTES);
+        }
+        ResponseItem::FunctionCallOutput { output, .. } => {
+            append_turn_text(session, &output.content, MAX_TOOL_OUTPUT_BYTES);
+        }
+        ResponseItem::CustomToolCallOutput { output, .. } => {
+            append_turn_text(session, &output, MAX_TOOL_OUTPUT_BYTES);
+        }
+        // User messages start turns (see `user_turn_message`); assistant text
+        // is indexed through `AgentMessage` events instead.
+        ResponseItem::Message { .. }
+        | ResponseItem::Reasoning { .. }
+        | ResponseItem::WebSearchCall { .. }
+        | ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Other => {}
+    }
+}
+
+fn record_tool(session: &mut IndexedSession, name: &str) {
+    if let Some(turn) = session.turns.last_mut() {
+        turn.tools.insert(name.to_string());
+    }
+}
+
+fn append_turn_text(session: &mut IndexedSession, text: &str, max_bytes: usize) {
+    let Some(turn) = session.turns.last_mut() else {
+        return;
+    };
+    let budget = MAX_TURN_TEXT_BYTES
+        .saturating_sub(turn.text.len())
+        .min(max_bytes);
+    if budget == 0 || text.trim().is_empty() {
+        return;
+    }
+    let mut end = text.len().min(budget);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if !turn.text.is_empty() {
+        turn.text.push('\n');
+    }
+    turn.text.push_str(&text[..end]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const CONVERSATION_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    fn rollout_path(codex_home: &Path) -> PathBuf {
+        codex_home
+            .join(SESSIONS_SUBDIR)
+            .join("2025/01/02")
+            .join(format!(
+                "rollout-2025-01-02T12-00-00-{CONVERSATION_ID}.jsonl"
+            ))
+    }
+
+    fn write_lines(path: &Path, lines: &[serde_json::Value]) {
+        use std::io::Write;
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        for line in lines {
+            writeln!(file, "{line}").unwrap();
+        }
+    }
+
+    fn user_message_line(timestamp: &str, text: &str) -> serde_json::Value {
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{"type": "input_text", "text": text}]
+            }
+        })
+    }
+
+    fn session_lines() -> Vec<serde_json::Value> {
+        vec![
+            json!({
+                "timestamp": "2025-01-02T12:00:00Z",
+                "type": "session_meta",
+                "payload": {
+                    "id": CONVERSATION_ID,
+                    "timestamp": "2025-01-02T12:00:00Z",
+                    "cwd": "/work/repo",
+                    "originator": "codex",
+                    "cli_version": "0.0.0",
+                    "instructions": null,
+                    "source": "cli",
+                    "model_provider": "openai"
+                }
+            }),
+            // Session prefix items are not turns.
+            user_message_line(
+                "2025-01-02T12:00:00Z",
+                "<environment_context>\n  <cwd>/work/repo</cwd>\n</environment_context>",
+            ),
+            user_message_line("2025-01-02T12:00:01Z", "why does the sandbox fail?"),
+            json!({
+                "timestamp": "2025-01-02T12:00:01Z",
+                "type": "turn_context",
+                "payload": {
+                    "cwd": "/work/repo/linux-sandbox",
+                    "approval_policy": "never",
+                    "sandbox_policy": {"mode": "read-only"},
+                    "model": "gpt-5-codex",
+                    "summary": "auto"
+                }
+            }),
+            json!({
+                "timestamp": "2025-01-02T12:00:02Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call",
+                    "name": "shell",
+                    "arguments": "{\"command\":[\"cargo\",\"test\"]}",
+                    "call_id": "call-1"
+                }
+            }),
+            json!({
+                "timestamp": "2025-01-02T12:00:03Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call_output",
+                    "call_id": "call-1",
+                    "output": "error: Landlock ruleset creation failed"
+                }
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn finds_turn_by_text_and_filters() {
+        let home = TempDir::new().unwrap();
+        write_lines(&rollout_path(home.path()), &session_lines());
+
+        let hits = search_history(
+            home.path(),
+            &HistorySearchQuery {
+                text: Some("landlock ruleset".to_string()),
+                tool: Some("shell".to_string()),
+                model: Some("codex".to_string()),
+                cwd: Some(PathBuf::from("/work/repo")),
+                since: Some("2025-01-01".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert_eq!(hit.conversation_id.to_string(), CONVERSATION_ID);
+        assert_eq!(hit.turn_index, 1);
+        assert_eq!(hit.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(hit.tools, vec!["shell".to_string()]);
+        assert!(hit.snippet.contains("Landlock ruleset"));
+
+        let none = search_history(
+            home.path(),
+            &HistorySearchQuery {
+                text: Some("landlock".to_string()),
+                until: Some("2024-12-31".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_saves_leave_a_complete_index() {
+        let home = TempDir::new().unwrap();
+        write_lines(&rollout_path(home.path()), &session_lines());
+        let mut index = HistoryIndex::load(home.path()).await;
+        index.refresh(home.path()).await.unwrap();
+        let index = std::sync::Arc::new(index);
+
+        let saves: Vec<_> = (0..8)
+            .map(|_| {
+                let index = index.clone();
+                let home = home.path().to_path_buf();
+                tokio::spawn(async move { index.save(&home).await })
+            })
+            .collect();
+        for save in saves {
+            save.await.unwrap().unwrap();
+        }
+
+        let reloaded = HistoryIndex::load(home.path()).await;
+        assert_eq!(reloaded.sessions.len(), 1);
+        let leftovers: Vec<_> = std::fs::read_dir(home.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != HISTORY_INDEX_FILE && name != SESSIONS_SUBDIR)
+            .collect();
+        assert_eq!(leftovers, Vec::<std::ffi::OsString>::new());
+    }
+
+    #[test]
+    fn snippet_handles_text_that_changes_length_when_lowercased() {
+        let text = format!("{} Landlock ruleset failed", "İ".repeat(100));
+        let excerpt = snippet(&text, Some("landlock"));
+        assert!(excerpt.contains("Landlock ruleset failed"), "{excerpt}");
+        assert!(excerpt.starts_with('…'));
+    }
+
+    #[tokio::test]
+    async fn refresh_only_indexes_appended_lines() {
+        let home = TempDir::new().unwrap();
+        let path = rollout_path(home.path());
+        write_lines(&path, &session_lines());
+
+        let mut index = HistoryIndex::load(home.path()).await;
+        assert_eq!(index.refresh(home.path()).await.unwrap(), 1);
+        index.save(home.path()).await.unwrap();
+
+        let mut reloaded = HistoryIndex::load(home.path()).await;
+        assert_eq!(reloaded.refresh(home.path()).await.unwrap(), 0);
+
+        write_lines(
+            &path,
+            &[user_message_line(
+                "2025-01-03T09:00:00Z",
+                "now fix the seatbelt profile",
+            )],
+        );
+        assert_eq!(reloaded.refresh(home.path()).await.unwrap(), 1);
+
+        let hits = reloaded.search(&HistorySearchQuery {
+            text: Some("seatbelt".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].turn_index, 2);
+        assert_eq!(hits[0].cwd, Some(PathBuf::from("/work/repo/linux-sandbox")));
+    }
+}
@@ -13,6 +13,11 @@ use time::macros::format_description;
 use uuid::Uuid;
 
 use super::SESSIONS_SUBDIR;
+use super::compression::COMPRESSED_ROLLOUT_SUFFIX;
+use super::compression::is_compressed_rollout;
+use super::compression::is_rollout_file_name;
+use super::compression::read_rollout_text;
+use super::tool_outputs::TOOL_OUTPUTS_DIR_SUFFIX;
 use crate::protocol::EventMsg;
 use codex_file_search as file_search;
 use codex_protocol::protocol::RolloutItem;
@@ -145,12 +150,13 @@ pub(crate) async fn get_conversations(
 /// Returns the entire file contents as a String.
 #[allow(dead_code)]
 pub(crate) async fn get_conversation(path: &Path) -> io::Result<String> {
-    tokio::fs::read_to_string(path).await
+    read_rollout_text(path).await
 }
 
 /// Load conversation file paths from disk using directory traversal.
 ///
-/// Directory layout: `~/.codex/sessions/YYYY/MM/DD/rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl`
+/// Directory layout: `~/.codex/sessions/YYYY/MM/DD/rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl`,
+/// optionally with a `.zst` suffix for compressed rollouts.
 /// Returned newest (latest) first.
 async fn traverse_directories_for_paths(
     root: PathBuf,
@@ -185,7 +191,7 @@ async fn traverse_directories_for_paths(
                     break 'outer;
                 }
                 let mut day_files = collect_files(day_path, |name_str, path| {
-                    if !name_str.starts_with("rollout-") || !name_str.ends_with(".jsonl") {
+                    if !is_rollout_file_name(name_str) {
                         return None;
                     }
 
@@ -342,7 +348,8 @@ where
 }
 
 fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
-    // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl
+    // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl[.zst]
+    let name = name.strip_suffix(COMPRESSED_ROLLOUT_SUFFIX).unwrap_or(name);
     let core = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
 
     // Scan from the right for a '-' such that the suffix parses as a UUID.
@@ -391,6 +398,22 @@ async fn read_head_and_tail(
 ) -> io::Result<HeadTailSummary> {
     use tokio::io::AsyncBufReadExt;
 
+    if is_compressed_rollout(path) {
+        // Compressed rollouts cannot be read incrementally from either end.
+        let text = read_rollout_text(path).await?;
+        let mut summary = HeadTailSummary::default();
+        for line in text.lines() {
+            if summary.head.len() >= head_limit {
+                break;
+            }
+            apply_head_line(&mut summary, line);
+        }
+        let (tail, updated_at) = collect_last_response_values(text.as_bytes(), tail_limit);
+        summary.tail = tail;
+        summary.updated_at = updated_at;
+        return Ok(summary);
+    }
+
     let file = tokio::fs::File::open(path).await?;
     let reader = tokio::io::BufReader::new(file);
     let mut lines = reader.lines();
@@ -399,48 +422,7 @@ async fn read_head_and_tail(
     while summary.head.len() < head_limit {
         let line_opt = lines.next_line().await?;
         let Some(line) = line_opt else { break };
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
-        let Ok(rollout_line) = parsed else { continue };
-
-        match rollout_line.item {
-            RolloutItem::SessionMeta(session_meta_line) => {
-                summary.source = Some(session_meta_line.meta.source.clone());
-                summary.model_provider = session_meta_line.meta.model_provider.clone();
-                summary.created_at = summary
-                    .created_at
-                    .clone()
-                    .or_else(|| Some(rollout_line.timestamp.clone()));
-                if let Ok(val) = serde_json::to_value(session_meta_line) {
-                    summary.head.push(val);
-                    summary.saw_session_meta = true;
-                }
-            }
-            RolloutItem::ResponseItem(item) => {
-                summary.created_at = summary
-                    .created_at
-                    .clone()
-                    .or_else(|| Some(rollout_line.timestamp.clone()));
-                if let Ok(val) = serde_json::to_value(item) {
-                    summary.head.push(val);
-                }
-            }
-            RolloutItem::TurnContext(_) => {
-                // Not included in `head`; skip.
-            }
-            RolloutItem::Compacted(_) => {
-                // Not included in `head`; skip.
-            }
-            RolloutItem::EventMsg(ev) => {
-                if matches!(ev, EventMsg::UserMessage(_)) {
-                    summary.saw_user_event = true;
-                }
-            }
-        }
+        apply_head_line(&mut summary, &line);
     }
 
     if tail_limit != 0 {
@@ -451,6 +433,52 @@ async fn read_head_and_tail(
     Ok(summary)
 }
 
+/// Fold one JSONL record from the start of a rollout into `summary`.
+fn apply_head_line(summary: &mut HeadTailSummary, line: &str) {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
+    let Ok(rollout_line) = parsed else { return };
+
+    match rollout_line.item {
+        RolloutItem::SessionMeta(session_meta_line) => {
+            summary.source = Some(session_meta_line.meta.source.clone());
+            summary.model_provider = session_meta_line.meta.model_provider.clone();
+            summary.created_at = summary
+                .created_at
+                .clone()
+                .or_else(|| Some(rollout_line.timestamp.clone()));
+            if let Ok(val) = serde_json::to_value(session_meta_line) {
+                summary.head.push(val);
+                summary.saw_session_meta = true;
+            }
+        }
+        RolloutItem::ResponseItem(item) => {
+            summary.created_at = summary
+                .created_at
+                .clone()
+                .or_else(|| Some(rollout_line.timestamp.clone()));
+            if let Ok(val) = serde_json::to_value(item) {
+                summary.head.push(val);
+            }
+        }
+        RolloutItem::TurnContext(_) => {
+            // Not included in `head`; skip.
+        }
+        RolloutItem::Compacted(_) => {
+            // Not included in `head`; skip.
+        }
+        RolloutItem::EventMsg(ev) => {
+            if matches!(ev, EventMsg::UserMessage(_)) {
+                summary.saw_user_event = true;
+            }
+        }
+    }
+}
+
 /// Read up to `HEAD_RECORD_LIMIT` records from the start of the rollout file at `path`.
 /// This should be enough to produce a summary including the session meta line.
 pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Value>> {
@@ -552,23 +580,27 @@ pub async fn find_conversation_path_by_id_str(
     id_str: &str,
 ) -> io::Result<Option<PathBuf>> {
     // Validate UUID format early.
-    if Uuid::parse_str(id_str).is_err() {
+    let Ok(id) = Uuid::parse_str(id_str) else {
         return Ok(None);
-    }
+    };
 
     let mut root = codex_home.to_path_buf();
     root.push(SESSIONS_SUBDIR);
     if !root.exists() {
         return Ok(None);
     }
+    // Fuzzy matching can rank other files whose path contains the id ahead
+    // of the rollout, so look at a few candidates.
     // This is safe because we know the values are valid.
     #[allow(clippy::unwrap_used)]
-    let limit = NonZero::new(1).unwrap();
+    let limit = NonZero::new(8).unwrap();
     // This is safe because we know the values are valid.
     #[allow(clippy::unwrap_used)]
     let threads = NonZero::new(2).unwrap();
     let cancel = Arc::new(AtomicBool::new(false));
-    let exclude: Vec<String> = Vec::new();
+    // Spilled tool outputs live in `<rollout>.tool-outputs/`, whose name
+    // contains the id as well.
+    let exclude = vec![format!("*{TOOL_OUTPUTS_DIR_SUFFIX}")];
     let compute_indices = false;
 
     let results = file_search::run(
@@ -586,6 +618,11 @@ pub async fn find_conversation_path_by_id_str(
     Ok(results
         .matches
         .into_iter()
-        .next()
-        .map(|m| root.join(m.path)))
+        .map(|m| root.join(m.path))
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_timestamp_uuid_from_filename)
+                .is_some_and(|(_, uuid)| uuid == id)
+        }))
 }
@@ -7,14 +7,21 @@ pub const ARCHIVED_SESSIONS_SUBDIR: &str = "archived_sessions";
 pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
+pub mod compression;
+pub mod history_index;
 pub mod list;
 pub(crate) mod policy;
 pub mod recorder;
+pub mod retention;
+pub(crate) mod tool_outputs;
 
 pub use codex_protocol::protocol::SessionMeta;
 pub use list::find_conversation_path_by_id_str;
 pub use recorder::RolloutRecorder;
 pub use recorder::RolloutRecorderParams;
+pub use retention::RetentionReport;
+pub use retention::enforce_retention;
+pub use retention::spawn_startup_retention;
 
 #[cfg(test)]
 pub mod tests;
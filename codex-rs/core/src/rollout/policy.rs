@@ -15,6 +15,37 @@ pub(crate) fn is_persisted_response_item(item: &RolloutItem) -> bool {
     }
 }
 
+/// Replace tool outputs larger than `max_bytes` in an archived rollout `item`
+/// with a short placeholder. Returns true when the item was changed.
+pub(crate) fn drop_large_tool_output(item: &mut RolloutItem, max_bytes: usize) -> bool {
+    match item {
+        RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput { output, .. }) => {
+            let size = output.content.len()
+                + output.content_items.as_ref().map_or(0, |items| {
+                    serde_json::to_string(items).map_or(0, |s| s.len())
+                });
+            if size <= max_bytes {
+                return false;
+            }
+            output.content = dropped_output_placeholder(size);
+            output.content_items = None;
+            true
+        }
+        RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput { output, .. }) => {
+            if output.len() <= max_bytes {
+                return false;
+            }
+            *output = dropped_output_placeholder(output.len());
+            true
+        }
+        _ => false,
+    }
+}
+
+fn dropped_output_placeholder(size: usize) -> String {
+    format!("[tool output of {size} bytes dropped when the session was archived]")
+}
+
 /// Whether a `ResponseItem` should be persisted in rollout files.
 #[inline]
 pub(crate) fn should_persist_response_item(item: &ResponseItem) -> bool {
@@ -44,6 +75,7 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
         | EventMsg::UndoCompleted(_)
+        | EventMsg::ModelFailover(_)
         | EventMsg::TurnAborted(_) => true,
         EventMsg::Error(_)
         | EventMsg::Warning(_)
@@ -76,6 +108,7 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::UndoStarted(_)
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::UnifiedExecSessions(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::ShutdownComplete
         | EventMsg::ViewImageToolCall(_)
@@ -85,3 +118,38 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ItemCompleted(_) => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn drops_only_oversized_tool_outputs() {
+        let mut small = RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+            call_id: "c1".to_string(),
+            output: "ok".to_string(),
+        });
+        assert!(!drop_large_tool_output(&mut small, 16));
+
+        let mut large = RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+            call_id: "c2".to_string(),
+            output: FunctionCallOutputPayload {
+                content: "x".repeat(64),
+                content_items: None,
+                success: Some(true),
+            },
+        });
+        assert!(drop_large_tool_output(&mut large, 16));
+        let RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput { output, .. }) = large
+        else {
+            panic!("expected function call output");
+        };
+        assert_eq!(
+            output.content,
+            "[tool output of 64 bytes dropped when the session was archived]"
+        );
+        assert_eq!(output.success, Some(true));
+    }
+}
@@ -19,6 +19,8 @@ use tracing::info;
 use tracing::warn;
 
 use super::SESSIONS_SUBDIR;
+use super::compression::decompress_rollout_in_place;
+use super::compression::read_rollout_text;
 use super::list::ConversationsPage;
 use super::list::Cursor;
 use super::list::get_conversations;
@@ -151,14 +153,18 @@ impl RolloutRecorder {
                     }),
                 )
             }
-            RolloutRecorderParams::Resume { path } => (
-                tokio::fs::OpenOptions::new()
-                    .append(true)
-                    .open(&path)
-                    .await?,
-                path,
-                None,
-            ),
+            RolloutRecorderParams::Resume { path } => {
+                // Archived rollouts may be compressed; appending needs plain JSONL.
+                let path = decompress_rollout_in_place(&path).await?;
+                (
+                    tokio::fs::OpenOptions::new()
+                        .append(true)
+                        .open(&path)
+                        .await?,
+                    path,
+                    None,
+                )
+            }
         };
 
         // Clone the cwd for the spawned task to collect git info asynchronously
@@ -209,7 +215,7 @@ impl RolloutRecorder {
 
     pub async fn get_rollout_history(path: &Path) -> std::io::Result<InitialHistory> {
         info!("Resuming rollout from {path:?}");
-        let text = tokio::fs::read_to_string(path).await?;
+        let text = read_rollout_text(path).await?;
         if text.trim().is_empty() {
             return Err(IoError::other("empty session file"));
         }
@@ -0,0 +1,359 @@
+//! Retention policy enforcement for recorded rollouts.
+//!
+//! Applies a [`SessionRetention`] policy to every rollout under
+//! `~/.codex/sessions` and `~/.codex/archived_sessions`: old rollouts are
+//! deleted, idle ones are zstd-compressed (optionally dropping large tool
+//! outputs first), and the oldest are removed until the total size fits the
+//! budget. Spilled tool outputs are deleted with their rollout. Pinned
+//! conversations are never touched, and neither are rollouts written to
+//! recently, since another Codex process may still be appending to them.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_protocol::protocol::RolloutLine;
+use serde::Serialize;
+use tracing::info;
+use tracing::warn;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::compression::is_compressed_rollout;
+use super::compression::is_rollout_file_name;
+use super::compression::read_rollout_text;
+use super::compression::write_compressed_rollout;
+use super::policy::drop_large_tool_output;
+use super::tool_outputs::remove_tool_outputs;
+use crate::config::Config;
+use crate::config::types::SessionRetention;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Rollouts modified this recently may belong to a live session and are left
+/// alone.
+const ACTIVE_ROLLOUT_GRACE: Duration = Duration::from_secs(60 * 60);
+
+/// Outcome of applying a retention policy.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct RetentionReport {
+    /// Rollouts that were (or, in a dry run, would be) deleted.
+    pub deleted: Vec<PathBuf>,
+    /// Rollouts that were (or would be) compressed, by original path.
+    pub compressed: Vec<PathBuf>,
+    /// Total size of all rollouts before the policy was applied.
+    pub bytes_before: u64,
+    /// Total size of all rollouts afterwards. In a dry run compression
+    /// savings are not estimated.
+    pub bytes_after: u64,
+}
+
+#[derive(Debug)]
+struct RolloutFile {
+    path: PathBuf,
+    len: u64,
+    modified: SystemTime,
+    pinned: bool,
+}
+
+/// Apply `policy` to the rollouts under `codex_home`. With `dry_run`, nothing
+/// is modified and the report lists what would happen.
+pub async fn enforce_retention(
+    codex_home: &Path,
+    policy: &SessionRetention,
+    dry_run: bool,
+) -> io::Result<RetentionReport> {
+    enforce_retention_at(codex_home, policy, dry_run, SystemTime::now()).await
+}
+
+/// Enforce the configured retention policy in the background when
+/// `session_retention.prune_on_startup` is set.
+pub fn spawn_startup_retention(config: &Config) {
+    if !config.session_retention.prune_on_startup {
+        return;
+    }
+    let codex_home = config.codex_home.clone();
+    let policy = config.session_retention.clone();
+    tokio::spawn(async move {
+        match enforce_retention(&codex_home, &policy, false).await {
+            Ok(report) => info!(
+                deleted = report.deleted.len(),
+                compressed = report.compressed.len(),
+                bytes_before = report.bytes_before,
+                bytes_after = report.bytes_after,
+                "applied session retention policy"
+            ),
+            Err(err) => warn!("failed to apply session retention policy: {err}"),
+        }
+    });
+}
+
+async fn enforce_retention_at(
+    codex_home: &Path,
+    policy: &SessionRetention,
+    dry_run: bool,
+    now: SystemTime,
+) -> io::Result<RetentionReport> {
+    let pinned: Vec<String> = policy
+        .pinned
+        .iter()
+        .map(|id| id.trim().to_lowercase())
+        .filter(|id| !id.is_empty())
+        .collect();
+    let mut files = Vec::new();
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        collect_rollout_files(&codex_home.join(subdir), &pinned, &mut files).await?;
+    }
+    // Oldest first, so size-based pruning removes the least recently used.
+    files.sort_by_key(|file| file.modified);
+
+    let mut report = RetentionReport {
+        bytes_before: files.iter().map(|file| file.len).sum(),
+        ..Default::default()
+    };
+    let age = |file: &RolloutFile| now.duration_since(file.modified).unwrap_or_default();
+    let protected = |file: &RolloutFile| file.pinned || age(file) < ACTIVE_ROLLOUT_GRACE;
+
+    if let Some(days) = policy.max_age_days {
+        let max_age = Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY));
+        let mut kept = Vec::with_capacity(files.len());
+        for file in files {
+            if !protected(&file) && age(&file) > max_age {
+                delete(&file.path, dry_run, &mut report).await?;
+            } else {
+                kept.push(file);
+            }
+        }
+        files = kept;
+    }
+
+    if let Some(days) = policy.compress_after_days {
+        let idle = Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY));
+        for file in &mut files {
+            if protected(file) || is_compressed_rollout(&file.path) || age(file) <= idle {
+                continue;
+            }
+            report.compressed.push(file.path.clone());
+            if dry_run {
+                continue;
+            }
+            let mut text = read_rollout_text(&file.path).await?;
+            if let Some(max_bytes) = policy.max_archived_tool_output_bytes {
+                text = drop_large_tool_outputs(&text, max_bytes);
+            }
+            file.path = write_compressed_rollout(&file.path, text).await?;
+            file.len = tokio::fs::metadata(&file.path).await?.len();
+        }
+    }
+
+    let mut total: u64 = files.iter().map(|file| file.len).sum();
+    if let Some(max_total) = policy.max_total_bytes {
+        for file in &files {
+            if total <= max_total {
+                break;
+            }
+            if protected(file) {
+                continue;
+            }
+            delete(&file.path, dry_run, &mut report).await?;
+            total = total.saturating_sub(file.len);
+        }
+    }
+    report.bytes_after = total;
+
+    Ok(report)
+}
+
+async fn delete(path: &Path, dry_run: bool, report: &mut RetentionReport) -> io::Result<()> {
+    if !dry_run {
+        tokio::fs::remove_file(path).await?;
+        remove_tool_outputs(path).await?;
+    }
+    report.deleted.push(path.to_path_buf());
+    Ok(())
+}
+
+/// Rewrite every rollout line whose tool output exceeds `max_bytes`; all
+/// other lines are kept verbatim.
+fn drop_large_tool_outputs(text: &str, max_bytes: usize) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.lines() {
+        let rewritten =
+            serde_json::from_str::<RolloutLine>(line)
+                .ok()
+                .and_then(|mut rollout_line| {
+                    drop_large_tool_output(&mut rollout_line.item, max_bytes)
+                        .then(|| serde_json::to_string(&rollout_line).ok())
+                        .flatten()
+                });
+        out.push_str(rewritten.as_deref().unwrap_or(line));
+        out.push('\n');
+    }
+    out
+}
+
+async fn collect_rollout_files(
+    root: &Path,
+    pinned: &[String],
+    files: &mut Vec<RolloutFile>,
+) -> io::Result<()> {
+    if !root.exists() {
+        return Ok(());
+    }
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_lowercase) else {
+                continue;
+            };
+            if !file_type.is_file() || !is_rollout_file_name(&name) {
+                continue;
+            }
+            let metadata = entry.metadata().await?;
+            files.push(RolloutFile {
+                path: entry.path(),
+                len: metadata.len(),
+                modified: metadata.modified()?,
+                pinned: pinned.iter().any(|id| name.contains(id.as_str())),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const OLD_ID: &str = "00000000-0000-0000-0000-000000000001";
+    const PINNED_ID: &str = "00000000-0000-0000-0000-000000000002";
+    const NEW_ID: &str = "00000000-0000-0000-0000-000000000003";
+
+    fn write_rollout(codex_home: &Path, day: &str, id: &str, output: &str) -> PathBuf {
+        let dir = codex_home.join(SESSIONS_SUBDIR).join("2025/01").join(day);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("rollout-2025-01-{day}T00-00-00-{id}.jsonl"));
+        let line = json!({
+            "timestamp": "2025-01-01T00:00:00.000Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": output,
+            }
+        });
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+        path
+    }
+
+    fn days_later(days: u64) -> SystemTime {
+        SystemTime::now() + Duration::from_secs(days * SECONDS_PER_DAY)
+    }
+
+    #[tokio::test]
+    async fn prunes_by_age_and_keeps_pinned() {
+        let home = TempDir::new().unwrap();
+        let old = write_rollout(home.path(), "01", OLD_ID, "a");
+        let pinned = write_rollout(home.path(), "02", PINNED_ID, "b");
+        let policy = SessionRetention {
+            max_age_days: Some(30),
+            pinned: vec![PINNED_ID.to_string()],
+            ..Default::default()
+        };
+
+        let dry = enforce_retention_at(home.path(), &policy, true, days_later(31))
+            .await
+            .unwrap();
+        assert_eq!(dry.deleted, vec![old.clone()]);
+        assert!(old.exists());
+
+        let report = enforce_retention_at(home.path(), &policy, false, days_later(31))
+            .await
+            .unwrap();
+        assert_eq!(report.deleted, vec![old.clone()]);
+        assert!(!old.exists());
+        assert!(pinned.exists());
+    }
+
+    #[tokio::test]
+    async fn compresses_idle_rollouts_and_drops_large_outputs() {
+        let home = TempDir::new().unwrap();
+        let path = write_rollout(home.path(), "01", OLD_ID, &"x".repeat(4096));
+        let policy = SessionRetention {
+            compress_after_days: Some(7),
+            max_archived_tool_output_bytes: Some(1024),
+            ..Default::default()
+        };
+
+        let report = enforce_retention_at(home.path(), &policy, false, days_later(8))
+            .await
+            .unwrap();
+        assert_eq!(report.compressed, vec![path.clone()]);
+        assert!(!path.exists());
+        assert!(report.bytes_after < report.bytes_before);
+
+        let compressed = PathBuf::from(format!("{}.zst", path.display()));
+        let text = read_rollout_text(&compressed).await.unwrap();
+        let line: RolloutLine = serde_json::from_str(text.trim()).unwrap();
+        let value = serde_json::to_value(&line).unwrap();
+        assert_eq!(
+            value["payload"]["output"],
+            json!("[tool output of 4096 bytes dropped when the session was archived]")
+        );
+    }
+
+    #[tokio::test]
+    async fn leaves_recently_written_rollouts_alone() {
+        let home = TempDir::new().unwrap();
+        let live = write_rollout(home.path(), "01", OLD_ID, &"a".repeat(512));
+        let policy = SessionRetention {
+            compress_after_days: Some(0),
+            max_total_bytes: Some(0),
+            ..Default::default()
+        };
+
+        let report = enforce_retention(home.path(), &policy, false)
+            .await
+            .unwrap();
+        assert_eq!(report.deleted, Vec::<PathBuf>::new());
+        assert_eq!(report.compressed, Vec::<PathBuf>::new());
+        assert!(live.exists());
+    }
+
+    #[tokio::test]
+    async fn prunes_oldest_until_under_size_budget() {
+        let home = TempDir::new().unwrap();
+        let oldest = write_rollout(home.path(), "01", OLD_ID, &"a".repeat(512));
+        std::fs::File::options()
+            .write(true)
+            .open(&oldest)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(SECONDS_PER_DAY))
+            .unwrap();
+        let newest = write_rollout(home.path(), "03", NEW_ID, &"b".repeat(512));
+        let newest_len = std::fs::metadata(&newest).unwrap().len();
+        let policy = SessionRetention {
+            max_total_bytes: Some(newest_len),
+            ..Default::default()
+        };
+
+        let report = enforce_retention(home.path(), &policy, false)
+            .await
+            .unwrap();
+        assert_eq!(report.deleted, vec![oldest.clone()]);
+        assert_eq!(report.bytes_after, newest_len);
+        assert!(!oldest.exists());
+        assert!(newest.exists());
+    }
+}
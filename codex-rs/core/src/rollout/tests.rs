@@ -1129,3 +1129,40 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_compressed_rollouts_are_listed_and_readable() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+
+    let uuid = Uuid::from_u128(42);
+    write_session_file(
+        home,
+        "2025-02-01T09-00-00",
+        uuid,
+        1,
+        Some(SessionSource::Cli),
+    )?;
+    let plain = home
+        .join("sessions/2025/02/01")
+        .join(format!("rollout-2025-02-01T09-00-00-{uuid}.jsonl"));
+    let text = fs::read_to_string(&plain)?;
+    let compressed =
+        crate::rollout::compression::write_compressed_rollout(&plain, text.clone()).await?;
+
+    let page = get_conversations(
+        home,
+        10,
+        None,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        TEST_PROVIDER,
+    )
+    .await?;
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].path, compressed);
+    assert_eq!(page.items[0].head[0]["id"], serde_json::json!(uuid));
+
+    assert_eq!(get_conversation(&compressed).await?, text);
+    Ok(())
+}
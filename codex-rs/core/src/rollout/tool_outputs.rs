@@ -0,0 +1,110 @@
+//! Full copies of tool outputs that were truncated before reaching the model.
+//!
+//! Outputs are stored next to the rollout they belong to, one file per
+//! `call_id`, in a `<rollout>.tool-outputs` directory. Retention deletes the
+//! directory together with its rollout.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::compression::COMPRESSED_ROLLOUT_SUFFIX;
+
+pub(crate) const TOOL_OUTPUTS_DIR_SUFFIX: &str = ".tool-outputs";
+
+/// Directory holding the spilled tool outputs of the rollout at
+/// `rollout_path`. Compressed and plain rollouts share the same directory.
+pub(crate) fn tool_outputs_dir(rollout_path: &Path) -> PathBuf {
+    let name = rollout_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = name
+        .strip_suffix(COMPRESSED_ROLLOUT_SUFFIX)
+        .unwrap_or(&name);
+    let stem = name.strip_suffix(".jsonl").unwrap_or(name);
+    rollout_path.with_file_name(format!("{stem}{TOOL_OUTPUTS_DIR_SUFFIX}"))
+}
+
+/// Remove the spilled tool outputs of the rollout at `rollout_path`, if any.
+pub(crate) async fn remove_tool_outputs(rollout_path: &Path) -> io::Result<()> {
+    match tokio::fs::remove_dir_all(tool_outputs_dir(rollout_path)).await {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Per-session store of full tool outputs, keyed by `call_id`.
+#[derive(Debug, Clone)]
+pub(crate) struct ToolOutputStore {
+    dir: PathBuf,
+}
+
+impl ToolOutputStore {
+    pub(crate) fn for_rollout(rollout_path: &Path) -> Self {
+        Self {
+            dir: tool_outputs_dir(rollout_path),
+        }
+    }
+
+    pub(crate) async fn save(&self, call_id: &str, content: &str) -> io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        tokio::fs::write(self.path_for(call_id), content).await
+    }
+
+    pub(crate) async fn load(&self, call_id: &str) -> io::Result<String> {
+        tokio::fs::read_to_string(self.path_for(call_id)).await
+    }
+
+    fn path_for(&self, call_id: &str) -> PathBuf {
+        // Call ids come from the model; never let them escape the directory.
+        let file_name: String = call_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{file_name}.txt"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn plain_and_compressed_rollouts_share_a_directory() {
+        let plain = Path::new("/s/2025/01/01/rollout-2025-01-01T00-00-00-abc.jsonl");
+        let compressed = Path::new("/s/2025/01/01/rollout-2025-01-01T00-00-00-abc.jsonl.zst");
+        let expected = PathBuf::from("/s/2025/01/01/rollout-2025-01-01T00-00-00-abc.tool-outputs");
+
+        assert_eq!(tool_outputs_dir(plain), expected);
+        assert_eq!(tool_outputs_dir(compressed), expected);
+    }
+
+    #[tokio::test]
+    async fn saves_loads_and_removes_outputs() {
+        let home = TempDir::new().expect("tempdir");
+        let rollout = home.path().join("rollout-x.jsonl");
+        let store = ToolOutputStore::for_rollout(&rollout);
+
+        store.save("call_1", "full output").await.expect("save");
+        assert_eq!(store.load("call_1").await.expect("load"), "full output");
+
+        // Path separators in a call id stay inside the store.
+        store.save("../escape", "x").await.expect("save");
+        assert!(tool_outputs_dir(&rollout).join("___escape.txt").exists());
+
+        remove_tool_outputs(&rollout).await.expect("remove");
+        assert!(!tool_outputs_dir(&rollout).exists());
+        remove_tool_outputs(&rollout)
+            .await
+            .expect("removing twice is a no-op");
+    }
+}
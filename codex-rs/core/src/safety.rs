@@ -185,7 +185,7 @@ fn is_write_patch_constrained_to_writable_paths(
         }
     }
 
-    true
+    action.mode_changes().keys().all(is_path_writable)
 }
 
 #[cfg(test)]
@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use crate::AuthManager;
 use crate::RolloutRecorder;
+use crate::budget::BudgetTracker;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::rollout::tool_outputs::ToolOutputStore;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecSessionManager;
 use crate::user_notification::UserNotifier;
@@ -19,4 +21,7 @@ pub(crate) struct SessionServices {
     pub(crate) auth_manager: Arc<AuthManager>,
     pub(crate) otel_event_manager: OtelEventManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
+    /// Full copies of truncated tool outputs; `None` when nothing is recorded.
+    pub(crate) tool_outputs: Option<ToolOutputStore>,
+    pub(crate) budget: Mutex<BudgetTracker>,
 }
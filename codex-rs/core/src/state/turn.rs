@@ -2,6 +2,7 @@
 
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::Notify;
@@ -68,6 +69,8 @@ impl ActiveTurn {
 #[derive(Default)]
 pub(crate) struct TurnState {
     pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
+    /// Hunks rejected by a partial patch approval, keyed like `pending_approvals`.
+    rejected_patch_hunks: HashMap<String, HashMap<PathBuf, Vec<usize>>>,
     pending_input: Vec<ResponseInputItem>,
     memory_preview_hits: Option<Vec<MemoryHit>>,
     memory_context_inserted: bool,
@@ -89,8 +92,24 @@ impl TurnState {
         self.pending_approvals.remove(key)
     }
 
+    pub(crate) fn set_rejected_patch_hunks(
+        &mut self,
+        key: String,
+        rejected_hunks: HashMap<PathBuf, Vec<usize>>,
+    ) {
+        self.rejected_patch_hunks.insert(key, rejected_hunks);
+    }
+
+    pub(crate) fn take_rejected_patch_hunks(
+        &mut self,
+        key: &str,
+    ) -> Option<HashMap<PathBuf, Vec<usize>>> {
+        self.rejected_patch_hunks.remove(key)
+    }
+
     pub(crate) fn clear_pending(&mut self) {
         self.pending_approvals.clear();
+        self.rejected_patch_hunks.clear();
         self.pending_input.clear();
         self.memory_preview_hits = None;
         self.memory_context_inserted = false;
@@ -77,7 +77,8 @@ pub(crate) trait SessionTask: Send + Sync + 'static {
     /// `ctx`, returning an optional final agent message when finished. The
     /// provided `cancellation_token` is cancelled when the session requests an
     /// abort; implementers should watch for it and terminate quickly once it
-    /// fires. Returning [`Some`] yields a final message that
+    /// fires. A task that cancels it itself is not reported as complete.
+    /// Returning [`Some`] yields a final message that
     /// [`Session::on_task_finished`] will emit to the client.
     async fn run(
         self: Arc<Self>,
@@ -125,7 +126,7 @@ impl Session {
                         Arc::clone(&session_ctx),
                         ctx,
                         input,
-                        task_cancellation_token.child_token(),
+                        task_cancellation_token.clone(),
                     )
                     .await;
                 session_ctx.clone_session().flush_rollout().await;
@@ -172,6 +173,25 @@ impl Session {
         self.send_event(turn_context.as_ref(), event).await;
     }
 
+    /// Ends a task's turn with `TurnAborted` from within the task itself, for
+    /// stops the task decides on (such as a hard budget). The task must cancel
+    /// its own cancellation token first so no `TaskComplete` follows.
+    pub async fn on_task_aborted(
+        self: &Arc<Self>,
+        turn_context: &TurnContext,
+        reason: TurnAbortReason,
+    ) {
+        let mut active = self.active_turn.lock().await;
+        if let Some(at) = active.as_mut()
+            && at.remove_task(&turn_context.sub_id)
+        {
+            *active = None;
+        }
+        drop(active);
+        let event = EventMsg::TurnAborted(TurnAbortedEvent { reason });
+        self.send_event(turn_context, event).await;
+    }
+
     async fn register_new_active_task(&self, task: RunningTask) {
         let mut active = self.active_turn.lock().await;
         let mut turn = ActiveTurn::default();
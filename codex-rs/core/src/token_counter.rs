@@ -0,0 +1,191 @@
+//! Local token counting for providers that do not report usage.
+//!
+//! Chat Completions streams usually end without a `usage` object, which
+//! leaves auto-compaction without numbers to act on. For those responses the
+//! usage is estimated with the model's tokenizer: the Hugging Face
+//! `tokenizer.json` from `model_tokenizer` when configured, otherwise the
+//! tiktoken encoding for the model.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_utils_tokenizer::Tokenizer;
+use tracing::warn;
+
+use crate::client_common::Prompt;
+use crate::config::Config;
+use crate::protocol::TokenUsage;
+
+/// Tokenizers are expensive to build, so they are shared by every session in
+/// the process. `None` records a tokenizer that failed to load.
+static TOKENIZERS: OnceLock<Mutex<HashMap<String, Option<Arc<Tokenizer>>>>> = OnceLock::new();
+
+/// Tokenizer for the configured model, if one can be loaded.
+pub(crate) fn tokenizer_for(config: &Config) -> Option<Arc<Tokenizer>> {
+    let key = match &config.model_tokenizer {
+        Some(path) => format!("file:{}", path.display()),
+        None => format!("model:{}", config.model_family.slug),
+    };
+    let cache = TOKENIZERS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(cache) = cache.lock()
+        && let Some(tokenizer) = cache.get(&key)
+    {
+        return tokenizer.clone();
+    }
+
+    let loaded = match &config.model_tokenizer {
+        Some(path) => Tokenizer::from_file(path),
+        None => Tokenizer::for_model(&config.model_family.slug),
+    };
+    let tokenizer = match loaded {
+        Ok(tokenizer) => Some(Arc::new(tokenizer)),
+        Err(err) => {
+            warn!("token counting disabled for {key}: {err}");
+            None
+        }
+    };
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(key, tokenizer.clone());
+    }
+    tokenizer
+}
+
+/// Tokens the conversation `items` take up in a prompt, counted with the
+/// model's tokenizer. Instructions and tool definitions are not included.
+pub(crate) fn estimate_input_tokens(config: &Config, items: &[ResponseItem]) -> Option<i64> {
+    let tokenizer = tokenizer_for(config)?;
+    Some(items.iter().map(|item| count_item(&tokenizer, item)).sum())
+}
+
+/// Estimates the usage of one streamed response.
+pub(crate) struct UsageEstimator {
+    tokenizer: Arc<Tokenizer>,
+    input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl UsageEstimator {
+    /// Count the tokens sent with `prompt`: instructions, tool definitions
+    /// and the conversation input.
+    pub(crate) fn for_prompt(
+        tokenizer: Arc<Tokenizer>,
+        prompt: &Prompt,
+        instructions: &str,
+    ) -> Self {
+        let tools = serde_json::to_string(&prompt.tools).unwrap_or_default();
+        let input_tokens = tokenizer.count(instructions)
+            + tokenizer.count(&tools)
+            + prompt
+                .input
+                .iter()
+                .map(|item| count_item(&tokenizer, item))
+                .sum::<i64>();
+        Self {
+            tokenizer,
+            input_tokens,
+            output_tokens: 0,
+        }
+    }
+
+    /// Add an item produced by the model.
+    pub(crate) fn observe_output(&mut self, item: &ResponseItem) {
+        self.output_tokens += count_item(&self.tokenizer, item);
+    }
+
+    pub(crate) fn usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            cached_input_tokens: 0,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: self.input_tokens + self.output_tokens,
+        }
+    }
+}
+
+fn count_item(tokenizer: &Tokenizer, item: &ResponseItem) -> i64 {
+    match item {
+        ResponseItem::Message { content, .. } => content
+            .iter()
+            .map(|content| match content {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    tokenizer.count(text)
+                }
+                ContentItem::InputImage { .. } => 0,
+            })
+            .sum(),
+        ResponseItem::Reasoning { content, .. } => content
+            .iter()
+            .flatten()
+            .map(|content| match content {
+                ReasoningItemContent::ReasoningText { text }
+                | ReasoningItemContent::Text { text } => tokenizer.count(text),
+            })
+            .sum(),
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => tokenizer.count(name) + tokenizer.count(arguments),
+        ResponseItem::FunctionCallOutput { output, .. } => match &output.content_items {
+            Some(items) => items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => tokenizer.count(text),
+                    FunctionCallOutputContentItem::InputImage { .. } => 0,
+                })
+                .sum(),
+            None => tokenizer.count(&output.content),
+        },
+        other => serde_json::to_string(other)
+            .map(|json| tokenizer.count(&json))
+            .unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_utils_tokenizer::EncodingKind;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn estimates_input_and_output_tokens() {
+        let tokenizer = Arc::new(Tokenizer::new(EncodingKind::Cl100kBase).expect("tokenizer"));
+        let prompt = Prompt {
+            input: vec![message("user", "hello world")],
+            ..Prompt::default()
+        };
+
+        let mut estimator = UsageEstimator::for_prompt(Arc::clone(&tokenizer), &prompt, "hello");
+        estimator.observe_output(&ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call_1".to_string(),
+        });
+
+        let tools = tokenizer.count("[]");
+        let usage = estimator.usage();
+        assert_eq!(usage.input_tokens, 1 + tools + 2);
+        assert_eq!(
+            usage.output_tokens,
+            tokenizer.count("shell") + tokenizer.count("{}")
+        );
+        assert_eq!(usage.total_tokens, usage.input_tokens + usage.output_tokens);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::context_manager::exceeds_model_format_limits;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
@@ -19,6 +20,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use super::exec_output_body;
 use super::format_exec_output_str;
 
 #[derive(Clone, Copy)]
@@ -276,7 +278,9 @@ impl ToolEmitter {
     ) -> Result<String, FunctionCallError> {
         let (event, result) = match out {
             Ok(output) => {
-                let content = super::format_exec_output_for_model(&output);
+                let stored = store_full_output(ctx, &output).await;
+                let content =
+                    super::format_exec_output_for_model_with_handle(&output, stored.as_deref());
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -288,7 +292,9 @@ impl ToolEmitter {
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
-                let response = super::format_exec_output_for_model(&output);
+                let stored = store_full_output(ctx, &output).await;
+                let response =
+                    super::format_exec_output_for_model_with_handle(&output, stored.as_deref());
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
@@ -317,6 +323,19 @@ impl ToolEmitter {
     }
 }
 
+/// Saves the full output of a call whose output will be truncated for the
+/// model. Returns the call id it was stored under.
+async fn store_full_output(ctx: ToolEventCtx<'_>, output: &ExecToolCallOutput) -> Option<String> {
+    let body = exec_output_body(output);
+    if !exceeds_model_format_limits(&body) {
+        return None;
+    }
+    ctx.session
+        .store_tool_output(ctx.call_id, &body)
+        .await
+        .then(|| ctx.call_id.to_string())
+}
+
 async fn emit_exec_end(
     ctx: ToolEventCtx<'_>,
     stdout: String,
@@ -1,5 +1,11 @@
 use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputPayload;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
 
+use crate::codex::Session;
+use crate::context_manager::exceeds_model_format_limits;
+use crate::context_manager::format_output_for_model_body_with_handle;
 use crate::function_tool::FunctionCallError;
 use crate::mcp_tool_call::handle_mcp_tool_call;
 use crate::tools::context::ToolInvocation;
@@ -53,6 +59,12 @@ impl ToolHandler for McpHandler {
 
         match response {
             codex_protocol::models::ResponseInputItem::McpToolCallOutput { result, .. } => {
+                if let Ok(call_tool_result) = &result
+                    && let Some(output) =
+                        store_full_output(session.as_ref(), &call_id, call_tool_result).await
+                {
+                    return Ok(output);
+                }
                 Ok(ToolOutput::Mcp { result })
             }
             codex_protocol::models::ResponseInputItem::FunctionCallOutput { output, .. } => {
@@ -73,3 +85,79 @@ impl ToolHandler for McpHandler {
         }
     }
 }
+
+/// Saves the full text of a result that would be truncated for the model and
+/// returns the truncated output pointing at it. Results with images are left
+/// alone so their content items still reach the model.
+async fn store_full_output(
+    session: &Session,
+    call_id: &str,
+    result: &CallToolResult,
+) -> Option<ToolOutput> {
+    let payload = FunctionCallOutputPayload::from(result);
+    if payload.content_items.is_some() || !exceeds_model_format_limits(&payload.content) {
+        return None;
+    }
+    let text = result_text(result);
+    if !session.store_tool_output(call_id, &text).await {
+        return None;
+    }
+    Some(ToolOutput::Function {
+        content: format_output_for_model_body_with_handle(&text, Some(call_id)),
+        content_items: None,
+        success: payload.success,
+    })
+}
+
+/// Line-oriented text of `result`, so `read_tool_output` can page through it.
+fn result_text(result: &CallToolResult) -> String {
+    if let Some(structured_content) = &result.structured_content
+        && !structured_content.is_null()
+    {
+        return serde_json::to_string_pretty(structured_content).unwrap_or_default();
+    }
+    result
+        .content
+        .iter()
+        .map(|block| match block {
+            ContentBlock::TextContent(text) => text.text.clone(),
+            other => serde_json::to_string_pretty(other).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::TextContent;
+    use pretty_assertions::assert_eq;
+
+    fn text_block(text: &str) -> ContentBlock {
+        ContentBlock::TextContent(TextContent {
+            annotations: None,
+            text: text.to_string(),
+            r#type: "text".to_string(),
+        })
+    }
+
+    #[test]
+    fn result_text_keeps_lines_readable() {
+        let result = CallToolResult {
+            content: vec![text_block("first\nsecond"), text_block("third")],
+            is_error: None,
+            structured_content: None,
+        };
+        assert_eq!(result_text(&result), "first\nsecond\nthird");
+
+        let structured = CallToolResult {
+            content: Vec::new(),
+            is_error: None,
+            structured_content: Some(serde_json::json!({"rows": [1, 2]})),
+        };
+        assert_eq!(
+            result_text(&structured),
+            "{\n  \"rows\": [\n    1,\n    2\n  ]\n}"
+        );
+    }
+}
@@ -7,6 +7,7 @@ mod memory_fetch;
 mod memory_suggest;
 mod plan;
 mod read_file;
+mod read_tool_output;
 mod search_code;
 mod shell;
 mod test_sync;
@@ -24,6 +25,7 @@ pub use memory_fetch::MemoryFetchHandler;
 pub use memory_suggest::MemorySuggestHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
+pub use read_tool_output::ReadToolOutputHandler;
 pub use search_code::SearchCodeHandler;
 pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
@@ -0,0 +1,214 @@
+use async_trait::async_trait;
+use codex_utils_string::take_bytes_at_char_boundary;
+use regex_lite::Regex;
+use serde::Deserialize;
+
+use crate::context_manager::MODEL_FORMAT_MAX_BYTES;
+use crate::context_manager::MODEL_FORMAT_MAX_LINES;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct ReadToolOutputHandler;
+
+const MAX_LINE_LENGTH: usize = 500;
+/// Pages must reach the model untruncated, so they stay within the model
+/// formatting limits, leaving room for the summary line.
+const PAGE_MAX_LINES: usize = MODEL_FORMAT_MAX_LINES - 1;
+const PAGE_MAX_BYTES: usize = MODEL_FORMAT_MAX_BYTES - 256;
+
+/// JSON arguments accepted by the `read_tool_output` tool handler.
+#[derive(Deserialize)]
+struct ReadToolOutputArgs {
+    /// Call id named in the truncation notice of the original output.
+    call_id: String,
+    /// 1-indexed line number to start reading from; defaults to 1.
+    #[serde(default = "defaults::offset")]
+    offset: usize,
+    /// Maximum number of lines to return; defaults to 200.
+    #[serde(default = "defaults::limit")]
+    limit: usize,
+    /// Optional regular expression; when set only matching lines from
+    /// `offset` onwards are returned.
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+mod defaults {
+    pub fn offset() -> usize {
+        1
+    }
+
+    pub fn limit() -> usize {
+        200
+    }
+}
+
+#[async_trait]
+impl ToolHandler for ReadToolOutputHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_tool_output handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: ReadToolOutputArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        if args.offset == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "offset must be a 1-indexed line number".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+        let pattern = args
+            .pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|err| FunctionCallError::RespondToModel(format!("invalid pattern: {err}")))?;
+
+        let store = session.tool_output_store().ok_or_else(|| {
+            FunctionCallError::RespondToModel(
+                "tool outputs are not stored in this session".to_string(),
+            )
+        })?;
+        let content = store.load(&args.call_id).await.map_err(|_| {
+            FunctionCallError::RespondToModel(format!(
+                "no stored output for call_id {}",
+                args.call_id
+            ))
+        })?;
+
+        Ok(ToolOutput::Function {
+            content: select_lines(&content, args.offset, args.limit, pattern.as_ref()),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// Up to `limit` lines of `content` starting at `offset`, keeping only lines
+/// that match `pattern` when one is given. The page is cut short when it
+/// would not fit the model formatting limits.
+fn select_lines(content: &str, offset: usize, limit: usize, pattern: Option<&Regex>) -> String {
+    let total_lines = content.lines().count();
+    let limit = limit.min(PAGE_MAX_LINES);
+    let mut selected = Vec::new();
+    let mut page_bytes = 0;
+    let mut next_offset = None;
+    for (index, line) in content.lines().enumerate().skip(offset - 1) {
+        if pattern.is_some_and(|pattern| !pattern.is_match(line)) {
+            continue;
+        }
+        let numbered = format!(
+            "L{}: {}",
+            index + 1,
+            take_bytes_at_char_boundary(line, MAX_LINE_LENGTH)
+        );
+        if selected.len() == limit || page_bytes + numbered.len() + 1 > PAGE_MAX_BYTES {
+            next_offset = Some(index + 1);
+            break;
+        }
+        page_bytes += numbered.len() + 1;
+        selected.push(numbered);
+    }
+
+    let summary = match (next_offset, selected.is_empty()) {
+        (Some(next), _) => {
+            format!(
+                "More lines follow; continue with offset {next} ({total_lines} lines in total)."
+            )
+        }
+        (None, true) if pattern.is_some() => {
+            format!("No lines match the pattern ({total_lines} lines in total).")
+        }
+        (None, true) => format!("offset {offset} is past the end ({total_lines} lines in total)."),
+        (None, false) => format!("End of output ({total_lines} lines in total)."),
+    };
+    selected.push(summary);
+    selected.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn numbered(count: usize) -> String {
+        (1..=count)
+            .map(|n| format!("line {n}\n"))
+            .collect::<String>()
+    }
+
+    #[test]
+    fn reads_a_line_range() {
+        assert_eq!(
+            select_lines(&numbered(10), 4, 2, None),
+            "L4: line 4\nL5: line 5\nMore lines follow; continue with offset 6 (10 lines in total)."
+        );
+        assert_eq!(
+            select_lines(&numbered(3), 3, 5, None),
+            "L3: line 3\nEnd of output (3 lines in total)."
+        );
+        assert_eq!(
+            select_lines(&numbered(3), 9, 5, None),
+            "offset 9 is past the end (3 lines in total)."
+        );
+    }
+
+    #[test]
+    fn pages_fit_the_model_formatting_limits() {
+        let wide = "x".repeat(MAX_LINE_LENGTH);
+        let content = format!("{wide}\n").repeat(100);
+        let page = select_lines(&content, 1, 200, None);
+        assert!(page.len() <= MODEL_FORMAT_MAX_BYTES, "{} bytes", page.len());
+        let shown = page.lines().count() - 1;
+        assert!(page.ends_with(&format!(
+            "continue with offset {} (100 lines in total).",
+            shown + 1
+        )));
+
+        let page = select_lines(&numbered(1000), 1, 1000, None);
+        assert_eq!(page.lines().count(), MODEL_FORMAT_MAX_LINES);
+        assert!(page.ends_with("continue with offset 256 (1000 lines in total)."));
+    }
+
+    #[test]
+    fn greps_from_offset() {
+        let pattern = Regex::new(r"line 1\d?$").expect("valid regex");
+        assert_eq!(
+            select_lines(&numbered(20), 2, 5, Some(&pattern)),
+            "L10: line 10\nL11: line 11\nL12: line 12\nL13: line 13\nL14: line 14\n\
+             More lines follow; continue with offset 15 (20 lines in total)."
+        );
+
+        let missing = Regex::new("error").expect("valid regex");
+        assert_eq!(
+            select_lines(&numbered(2), 1, 5, Some(&missing)),
+            "No lines match the pattern (2 lines in total)."
+        );
+    }
+}
@@ -180,7 +180,7 @@ impl ShellHandler {
                             &call_id,
                             Some(&tracker),
                         );
-                        let content = emitter.finish(event_ctx, out).await?;
+                        let content = apply.annotate_output(emitter.finish(event_ctx, out).await?);
                         return Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::context_manager::exceeds_model_format_limits;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
@@ -163,11 +164,20 @@ impl ToolHandler for UnifiedExecHandler {
                 .await;
         }
 
-        let content = serialize_response(&response).map_err(|err| {
+        let serialize_err = |err: serde_json::Error| {
             FunctionCallError::RespondToModel(format!(
                 "failed to serialize unified exec output: {err:?}"
             ))
-        })?;
+        };
+        let mut content = serialize_response(&response, None).map_err(serialize_err)?;
+        // Keep the full output when the model only sees part of it, either
+        // because of `max_output_tokens` or the model formatting limits.
+        if response.full_output.is_some() || exceeds_model_format_limits(&content) {
+            let full_output = response.full_output.as_deref().unwrap_or(&response.output);
+            if session.store_tool_output(&call_id, full_output).await {
+                content = serialize_response(&response, Some(&call_id)).map_err(serialize_err)?;
+            }
+        }
 
         Ok(ToolOutput::Function {
             content,
@@ -181,6 +191,10 @@ impl ToolHandler for UnifiedExecHandler {
 struct SerializedUnifiedExecResponse<'a> {
     chunk_id: &'a str,
     wall_time_seconds: f64,
+    /// Set when the full output was stored; placed before `output` so it
+    /// survives head/tail truncation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    read_tool_output_call_id: Option<&'a str>,
     output: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     session_id: Option<i32>,
@@ -190,10 +204,14 @@ struct SerializedUnifiedExecResponse<'a> {
     original_token_count: Option<usize>,
 }
 
-fn serialize_response(response: &UnifiedExecResponse) -> Result<String, serde_json::Error> {
+fn serialize_response(
+    response: &UnifiedExecResponse,
+    stored_call_id: Option<&str>,
+) -> Result<String, serde_json::Error> {
     let payload = SerializedUnifiedExecResponse {
         chunk_id: &response.chunk_id,
         wall_time_seconds: duration_to_seconds(response.wall_time),
+        read_tool_output_call_id: stored_call_id,
         output: &response.output,
         session_id: response.session_id,
         exit_code: response.exit_code,
@@ -10,6 +10,7 @@ pub mod sandboxing;
 pub mod spec;
 
 use crate::context_manager::format_output_for_model_body;
+use crate::context_manager::format_output_for_model_body_with_handle;
 use crate::exec::ExecToolCallOutput;
 pub use router::ToolRouter;
 use serde::Serialize;
@@ -23,6 +24,15 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
 pub fn format_exec_output_for_model(exec_output: &ExecToolCallOutput) -> String {
+    format_exec_output_for_model_with_handle(exec_output, None)
+}
+
+/// Same as [`format_exec_output_for_model`], pointing the model at the stored
+/// full output when one was saved under `stored_call_id`.
+pub(crate) fn format_exec_output_for_model_with_handle(
+    exec_output: &ExecToolCallOutput,
+    stored_call_id: Option<&str>,
+) -> String {
     let ExecToolCallOutput {
         exit_code,
         duration,
@@ -44,7 +54,8 @@ pub fn format_exec_output_for_model(exec_output: &ExecToolCallOutput) -> String
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output);
+    let formatted_output =
+        format_output_for_model_body_with_handle(&exec_output_body(exec_output), stored_call_id);
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -59,21 +70,24 @@ pub fn format_exec_output_for_model(exec_output: &ExecToolCallOutput) -> String
 }
 
 pub fn format_exec_output_str(exec_output: &ExecToolCallOutput) -> String {
+    // Truncate for model consumption before serialization.
+    format_output_for_model_body(&exec_output_body(exec_output))
+}
+
+/// The untruncated text the model would see for `exec_output`.
+pub(crate) fn exec_output_body(exec_output: &ExecToolCallOutput) -> String {
     let ExecToolCallOutput {
         aggregated_output, ..
     } = exec_output;
 
     let content = aggregated_output.text.as_str();
 
-    let body = if exec_output.timed_out {
+    if exec_output.timed_out {
         format!(
             "command timed out after {} milliseconds\n{content}",
             exec_output.duration.as_millis()
         )
     } else {
         content.to_string()
-    };
-
-    // Truncate for model consumption before serialization.
-    format_output_for_model_body(&body)
+    }
 }
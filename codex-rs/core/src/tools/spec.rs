@@ -174,7 +174,7 @@ fn create_exec_command_tool() -> ToolSpec {
         "max_output_tokens".to_string(),
         JsonSchema::Number {
             description: Some(
-                "Maximum number of tokens to return. Excess output will be truncated.".to_string(),
+                "Maximum number of tokens to return. Excess output will be truncated; when `read_tool_output_call_id` is set, read the rest with read_tool_output.".to_string(),
             ),
         },
     );
@@ -219,7 +219,7 @@ fn create_write_stdin_tool() -> ToolSpec {
         "max_output_tokens".to_string(),
         JsonSchema::Number {
             description: Some(
-                "Maximum number of tokens to return. Excess output will be truncated.".to_string(),
+                "Maximum number of tokens to return. Excess output will be truncated; when `read_tool_output_call_id` is set, read the rest with read_tool_output.".to_string(),
             ),
         },
     );
@@ -519,6 +519,54 @@ fn create_read_file_tool() -> ToolSpec {
     })
 }
 
+fn create_read_tool_output_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "call_id".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Call id named in the truncation notice of an earlier tool output.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "offset".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "The line number to start reading from. Must be 1 or greater.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "The maximum number of lines to return (defaults to 200). Long pages are cut short and name the offset to continue from.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "pattern".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional regular expression; only matching lines are returned.".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_tool_output".to_string(),
+        description: "Reads the full output of an earlier tool call that was truncated, by line range or by searching it with a pattern."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["call_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_memory_fetch_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -820,6 +868,47 @@ pub(crate) fn create_tools_json_for_chat_completions_api(
     Ok(tools_json)
 }
 
+/// Converts tools to the Anthropic Messages API format. Freeform tools have no
+/// Messages equivalent, so they are exposed as a function taking a single
+/// `input` string; the Messages client maps calls back to custom tool calls.
+pub(crate) fn create_tools_json_for_messages_api(
+    tools: &[ToolSpec],
+) -> crate::error::Result<Vec<serde_json::Value>> {
+    let tools_json = tools
+        .iter()
+        .filter_map(|tool| match tool {
+            ToolSpec::Function(tool) => {
+                Some(serde_json::to_value(&tool.parameters).map(|input_schema| {
+                    json!({
+                        "name": tool.name,
+                        "description": tool.description,
+                        "input_schema": input_schema,
+                    })
+                }))
+            }
+            ToolSpec::Freeform(tool) => Some(Ok(json!({
+                "name": tool.name,
+                "description": tool.description,
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "input": {
+                            "type": "string",
+                            "description": format!(
+                                "Raw tool input matching this {} grammar:\n{}",
+                                tool.format.syntax, tool.format.definition
+                            ),
+                        },
+                    },
+                    "required": ["input"],
+                },
+            }))),
+            ToolSpec::LocalShell {} | ToolSpec::WebSearch {} => None,
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tools_json)
+}
+
 pub(crate) fn mcp_tool_to_openai_tool(
     fully_qualified_name: String,
     tool: mcp_types::Tool,
@@ -983,6 +1072,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::MemorySuggestHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadToolOutputHandler;
     use crate::tools::handlers::SearchCodeHandler;
     use crate::tools::handlers::ShellHandler;
     use crate::tools::handlers::TestSyncHandler;
@@ -1039,6 +1129,9 @@ pub(crate) fn build_specs(
     builder.push_spec(PLAN_TOOL.clone());
     builder.register_handler("update_plan", plan_handler);
 
+    builder.push_spec_with_parallel_support(create_read_tool_output_tool(), true);
+    builder.register_handler("read_tool_output", Arc::new(ReadToolOutputHandler));
+
     builder.push_spec_with_parallel_support(create_memory_suggest_tool(), true);
     builder.push_spec_with_parallel_support(create_search_code_tool(), true);
     builder.push_spec_with_parallel_support(create_memory_fetch_tool(), true);
@@ -1259,6 +1352,7 @@ mod tests {
             create_list_mcp_resource_templates_tool(),
             create_read_mcp_resource_tool(),
             PLAN_TOOL.clone(),
+            create_read_tool_output_tool(),
             create_memory_fetch_tool(),
             create_apply_patch_freeform_tool(),
             ToolSpec::WebSearch {},
@@ -1306,6 +1400,7 @@ mod tests {
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
                 "update_plan",
+                "read_tool_output",
                 "memory_fetch",
                 "web_search",
                 "view_image",
@@ -24,9 +24,11 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Weak;
 use std::sync::atomic::AtomicI32;
 use std::time::Duration;
 
+use codex_utils_tokenizer::Tokenizer;
 use rand::Rng;
 use rand::rng;
 use tokio::sync::Mutex;
@@ -45,7 +47,12 @@ pub(crate) const DEFAULT_YIELD_TIME_MS: u64 = 10_000;
 pub(crate) const MIN_YIELD_TIME_MS: u64 = 250;
 pub(crate) const MAX_YIELD_TIME_MS: u64 = 30_000;
 pub(crate) const DEFAULT_MAX_OUTPUT_TOKENS: usize = 10_000;
+/// Tokens given up at each cut so that the kept halves decode cleanly.
+const MAX_BOUNDARY_TOKENS: usize = 4;
 pub(crate) const UNIFIED_EXEC_OUTPUT_MAX_BYTES: usize = 1024 * 1024; // 1 MiB
+/// Recent output kept for clients showing live sessions, independent of what
+/// the model has already drained.
+pub(crate) const UNIFIED_EXEC_TAIL_MAX_BYTES: usize = 8 * 1024;
 
 pub(crate) struct UnifiedExecContext {
     pub session: Arc<Session>,
@@ -89,6 +96,8 @@ pub(crate) struct UnifiedExecResponse {
     pub session_id: Option<i32>,
     pub exit_code: Option<i32>,
     pub original_token_count: Option<usize>,
+    /// Untruncated output, kept when `output` was cut to `max_output_tokens`.
+    pub full_output: Option<String>,
 }
 
 #[derive(Default)]
@@ -99,7 +108,9 @@ pub(crate) struct UnifiedExecSessionManager {
 
 struct SessionEntry {
     session: session::UnifiedExecSession,
-    session_ref: Arc<Session>,
+    /// Weak so that stored sessions do not keep the `Session` that owns this
+    /// manager alive; dropping the `Session` drops (and kills) them.
+    session_ref: Weak<Session>,
     turn_ref: Arc<TurnContext>,
     call_id: String,
     command: String,
@@ -125,10 +136,20 @@ pub(crate) fn generate_chunk_id() -> String {
         .collect()
 }
 
+/// Keep the head and tail of `output` within `max_tokens`. Tokens are counted
+/// with the model's tokenizer when one is available, otherwise one token per
+/// character.
 pub(crate) fn truncate_output_to_tokens(
     output: &str,
     max_tokens: usize,
+    tokenizer: Option<&Tokenizer>,
 ) -> (String, Option<usize>) {
+    if let Some(tokenizer) = tokenizer
+        && let Some(result) = truncate_with_tokenizer(tokenizer, output, max_tokens)
+    {
+        return result;
+    }
+
     if max_tokens == 0 {
         let total_tokens = output.chars().count();
         let message = format!("…{total_tokens} tokens truncated…");
@@ -156,6 +177,45 @@ pub(crate) fn truncate_output_to_tokens(
     (truncated_output, Some(total_tokens))
 }
 
+/// Token-accurate variant of [`truncate_output_to_tokens`]. Returns `None`
+/// when the kept head or tail cannot be decoded back into text, so the caller
+/// falls back to counting characters.
+fn truncate_with_tokenizer(
+    tokenizer: &Tokenizer,
+    output: &str,
+    max_tokens: usize,
+) -> Option<(String, Option<usize>)> {
+    let tokens = tokenizer.encode(output, false);
+    let total_tokens = tokens.len();
+    if total_tokens <= max_tokens {
+        return Some((output.to_string(), None));
+    }
+
+    let half = max_tokens / 2;
+    // A cut can land inside a multi-byte character that spans several byte
+    // tokens; give up a few tokens on each side until the halves decode.
+    let head = (0..=MAX_BOUNDARY_TOKENS.min(half)).find_map(|drop| {
+        let end = half - drop;
+        tokenizer
+            .decode(&tokens[..end])
+            .ok()
+            .map(|text| (text, end))
+    })?;
+    let tail = (0..=MAX_BOUNDARY_TOKENS.min(half)).find_map(|drop| {
+        let start = total_tokens - half + drop;
+        tokenizer
+            .decode(&tokens[start..])
+            .ok()
+            .map(|text| (text, total_tokens - start))
+    })?;
+
+    let truncated = total_tokens - head.1 - tail.1;
+    Some((
+        format!("{}…{truncated} tokens truncated…{}", head.0, tail.0),
+        Some(total_tokens),
+    ))
+}
+
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
@@ -223,6 +283,25 @@ mod tests {
             .await
     }
 
+    #[test]
+    fn truncation_counts_model_tokens() {
+        let tokenizer = Tokenizer::try_default().expect("load tokenizer");
+        let text = "word ".repeat(1_000);
+        let total = tokenizer.encode(&text, false).len();
+
+        let (output, original_token_count) =
+            truncate_output_to_tokens(&text, 100, Some(&tokenizer));
+
+        assert_eq!(original_token_count, Some(total));
+        assert!(output.contains(&format!("…{} tokens truncated…", total - 100)));
+        assert!(output.starts_with("word word"));
+        assert!(tokenizer.count(&output) < 120);
+        assert_eq!(
+            truncate_output_to_tokens("short", 100, Some(&tokenizer)),
+            ("short".to_string(), None)
+        );
+    }
+
     #[test]
     fn push_chunk_trims_only_excess_bytes() {
         let mut buffer = OutputBufferState::default();
@@ -241,6 +320,49 @@ mod tests {
         assert_eq!(snapshot.get(1).unwrap(), &vec![b'b']);
     }
 
+    #[test]
+    fn tail_survives_drain_and_keeps_latest_bytes() {
+        let mut buffer = OutputBufferState::default();
+        buffer.push_chunk(vec![b'a'; UNIFIED_EXEC_TAIL_MAX_BYTES]);
+        buffer.push_chunk(b"done".to_vec());
+        buffer.drain();
+
+        let tail = buffer.tail();
+        assert_eq!(tail.len(), UNIFIED_EXEC_TAIL_MAX_BYTES);
+        assert!(tail.ends_with(b"adone"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn live_sessions_are_listed_and_can_be_terminated() -> anyhow::Result<()> {
+        skip_if_sandbox!(Ok(()));
+
+        let (session, turn) = test_session_and_turn();
+        let manager = &session.services.unified_exec_manager;
+
+        let open_shell = exec_command(&session, &turn, "bash -i", Some(2_500)).await?;
+        let session_id = open_shell.session_id.expect("expected session id");
+
+        manager
+            .write_user_input(session_id, "echo codex-panel\n")
+            .await?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let sessions = manager.list_sessions().await;
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, session_id);
+        assert_eq!(sessions[0].command, "bash -i");
+        assert!(sessions[0].output_tail.contains("codex-panel"));
+
+        manager.terminate_session(session_id).await?;
+        assert!(manager.list_sessions().await.is_empty());
+        assert!(matches!(
+            manager.terminate_session(session_id).await,
+            Err(UnifiedExecError::UnknownSessionId { .. })
+        ));
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn unified_exec_persists_across_requests() -> anyhow::Result<()> {
         skip_if_sandbox!(Ok(()));
@@ -19,16 +19,23 @@ use codex_utils_pty::ExecCommandSession;
 use codex_utils_pty::SpawnedPty;
 
 use super::UNIFIED_EXEC_OUTPUT_MAX_BYTES;
+use super::UNIFIED_EXEC_TAIL_MAX_BYTES;
 use super::UnifiedExecError;
 
 #[derive(Debug, Default)]
 pub(crate) struct OutputBufferState {
     chunks: VecDeque<Vec<u8>>,
     pub(crate) total_bytes: usize,
+    /// Last output bytes, kept across `drain` calls.
+    tail: VecDeque<u8>,
 }
 
 impl OutputBufferState {
     pub(super) fn push_chunk(&mut self, chunk: Vec<u8>) {
+        self.tail.extend(&chunk);
+        let overflow = self.tail.len().saturating_sub(UNIFIED_EXEC_TAIL_MAX_BYTES);
+        self.tail.drain(..overflow);
+
         self.total_bytes = self.total_bytes.saturating_add(chunk.len());
         self.chunks.push_back(chunk);
 
@@ -62,6 +69,10 @@ impl OutputBufferState {
     pub(super) fn snapshot(&self) -> Vec<Vec<u8>> {
         self.chunks.iter().cloned().collect()
     }
+
+    pub(super) fn tail(&self) -> Vec<u8> {
+        self.tail.iter().copied().collect()
+    }
 }
 
 pub(crate) type OutputBuffer = Arc<Mutex<OutputBufferState>>;
@@ -130,6 +141,12 @@ impl UnifiedExecSession {
         self.session.exit_code()
     }
 
+    /// Most recent output, whether or not it was already returned to the model.
+    pub(super) async fn output_tail(&self) -> String {
+        let guard = self.output_buffer.lock().await;
+        String::from_utf8_lossy(&guard.tail()).into_owned()
+    }
+
     async fn snapshot_output(&self) -> Vec<Vec<u8>> {
         let guard = self.output_buffer.lock().await;
         guard.snapshot()
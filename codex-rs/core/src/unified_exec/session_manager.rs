@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use codex_utils_tokenizer::Tokenizer;
 use tokio::sync::Notify;
 use tokio::sync::mpsc;
 use tokio::time::Duration;
@@ -8,7 +9,9 @@ use tokio::time::Instant;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::StreamOutput;
 use crate::exec_env::create_env;
+use crate::protocol::UnifiedExecSessionInfo;
 use crate::sandboxing::ExecEnv;
+use crate::token_counter::tokenizer_for;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventStage;
@@ -32,6 +35,15 @@ use super::session::OutputBuffer;
 use super::session::UnifiedExecSession;
 use super::truncate_output_to_tokens;
 
+/// Stdin writer, output buffer and notifier of a live session, plus the
+/// tokenizer used to truncate its output.
+type SessionHandles = (
+    mpsc::Sender<Vec<u8>>,
+    OutputBuffer,
+    Arc<Notify>,
+    Option<Arc<Tokenizer>>,
+);
+
 impl UnifiedExecSessionManager {
     pub(crate) async fn exec_command(
         &self,
@@ -59,7 +71,10 @@ impl UnifiedExecSessionManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let (output, original_token_count) = truncate_output_to_tokens(&text, max_tokens);
+        let tokenizer = tokenizer_for(&context.turn.client.config());
+        let (output, original_token_count) =
+            truncate_output_to_tokens(&text, max_tokens, tokenizer.as_deref());
+        let full_output = original_token_count.is_some().then_some(text);
         let chunk_id = generate_chunk_id();
         let exit_code = session.exit_code();
         let session_id = if session.has_exited() {
@@ -79,6 +94,7 @@ impl UnifiedExecSessionManager {
             session_id,
             exit_code,
             original_token_count,
+            full_output,
         };
 
         // If the command completed during this call, emit an ExecCommandEnd via the emitter.
@@ -103,7 +119,7 @@ impl UnifiedExecSessionManager {
     ) -> Result<UnifiedExecResponse, UnifiedExecError> {
         let session_id = request.session_id;
 
-        let (writer_tx, output_buffer, output_notify) =
+        let (writer_tx, output_buffer, output_notify, tokenizer) =
             self.prepare_session_handles(session_id).await?;
 
         if !request.input.is_empty() {
@@ -120,7 +136,9 @@ impl UnifiedExecSessionManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let (output, original_token_count) = truncate_output_to_tokens(&text, max_tokens);
+        let (output, original_token_count) =
+            truncate_output_to_tokens(&text, max_tokens, tokenizer.as_deref());
+        let full_output = original_token_count.is_some().then_some(text);
         let chunk_id = generate_chunk_id();
 
         let status = self.refresh_session_state(session_id).await;
@@ -145,6 +163,7 @@ impl UnifiedExecSessionManager {
             session_id,
             exit_code,
             original_token_count,
+            full_output,
         };
 
         if let (Some(exit), Some(entry)) = (response.exit_code, completion_entry) {
@@ -156,6 +175,49 @@ impl UnifiedExecSessionManager {
         Ok(response)
     }
 
+    /// Live sessions, ordered by id, for clients that show them to the user.
+    pub(crate) async fn list_sessions(&self) -> Vec<UnifiedExecSessionInfo> {
+        let sessions = self.sessions.lock().await;
+        let mut infos = Vec::with_capacity(sessions.len());
+        for (session_id, entry) in sessions.iter() {
+            if entry.session.has_exited() {
+                continue;
+            }
+            infos.push(UnifiedExecSessionInfo {
+                session_id: *session_id,
+                command: entry.command.clone(),
+                cwd: entry.cwd.clone(),
+                uptime_secs: entry.started_at.elapsed().as_secs(),
+                output_tail: entry.session.output_tail().await,
+            });
+        }
+        infos.sort_by_key(|info| info.session_id);
+        infos
+    }
+
+    /// Write user input to a session without collecting its output; the
+    /// model still sees that output on its next `write_stdin`.
+    pub(crate) async fn write_user_input(
+        &self,
+        session_id: i32,
+        input: &str,
+    ) -> Result<(), UnifiedExecError> {
+        let (writer_tx, _, _) = self.prepare_session_handles(session_id).await?;
+        Self::send_input(&writer_tx, input.as_bytes()).await
+    }
+
+    /// Kill a session. Dropping it kills the process.
+    pub(crate) async fn terminate_session(&self, session_id: i32) -> Result<(), UnifiedExecError> {
+        match self.sessions.lock().await.remove(&session_id) {
+            Some(_) => Ok(()),
+            None => Err(UnifiedExecError::UnknownSessionId { session_id }),
+        }
+    }
+
+    pub(crate) async fn terminate_all_sessions(&self) {
+        self.sessions.lock().await.clear();
+    }
+
     async fn refresh_session_state(&self, session_id: i32) -> SessionStatus {
         let mut sessions = self.sessions.lock().await;
         let Some(entry) = sessions.get(&session_id) else {
@@ -183,17 +245,21 @@ impl UnifiedExecSessionManager {
     async fn prepare_session_handles(
         &self,
         session_id: i32,
-    ) -> Result<(mpsc::Sender<Vec<u8>>, OutputBuffer, Arc<Notify>), UnifiedExecError> {
+    ) -> Result<SessionHandles, UnifiedExecError> {
         let sessions = self.sessions.lock().await;
-        let (output_buffer, output_notify, writer_tx) =
-            if let Some(entry) = sessions.get(&session_id) {
-                let (buffer, notify) = entry.session.output_handles();
-                (buffer, notify, entry.session.writer_sender())
-            } else {
-                return Err(UnifiedExecError::UnknownSessionId { session_id });
-            };
-
-        Ok((writer_tx, output_buffer, output_notify))
+        let Some(entry) = sessions.get(&session_id) else {
+            return Err(UnifiedExecError::UnknownSessionId { session_id });
+        };
+        let (output_buffer, output_notify) = entry.session.output_handles();
+        // Count tokens with the tokenizer of the model that started the session.
+        let tokenizer = tokenizer_for(&entry.turn_ref.client.config());
+
+        Ok((
+            entry.session.writer_sender(),
+            output_buffer,
+            output_notify,
+            tokenizer,
+        ))
     }
 
     async fn send_input(
@@ -218,7 +284,7 @@ impl UnifiedExecSessionManager {
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         let entry = SessionEntry {
             session,
-            session_ref: Arc::clone(&context.session),
+            session_ref: Arc::downgrade(&context.session),
             turn_ref: Arc::clone(&context.turn),
             call_id: context.call_id.clone(),
             command: command.to_string(),
@@ -235,6 +301,9 @@ impl UnifiedExecSessionManager {
         exit_code: i32,
         duration: Duration,
     ) {
+        let Some(session) = entry.session_ref.upgrade() else {
+            return;
+        };
         let output = ExecToolCallOutput {
             exit_code,
             stdout: StreamOutput::new(aggregated_output.clone()),
@@ -244,7 +313,7 @@ impl UnifiedExecSessionManager {
             timed_out: false,
         };
         let event_ctx = ToolEventCtx::new(
-            entry.session_ref.as_ref(),
+            session.as_ref(),
             entry.turn_ref.as_ref(),
             &entry.call_id,
             None,
@@ -0,0 +1,242 @@
+use std::sync::Arc;
+
+use codex_app_server_protocol::AuthMode;
+use codex_core::ContentItem;
+use codex_core::ModelClient;
+use codex_core::ModelProviderInfo;
+use codex_core::Prompt;
+use codex_core::ResponseEvent;
+use codex_core::ResponseItem;
+use codex_core::WireApi;
+use codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+use codex_protocol::models::ReasoningItemContent;
+use core_test_support::load_default_config_for_test;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tempfile::TempDir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const FIXTURE: &str = include_str!("fixtures/messages_tool_use.sse");
+
+fn network_disabled() -> bool {
+    std::env::var(CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok()
+}
+
+/// Streams one turn from a mocked Messages endpoint and returns the events
+/// together with the request body the client sent.
+async fn run_stream(sse_body: &str, prompt: Prompt) -> (Vec<ResponseEvent>, Value) {
+    let server = MockServer::start().await;
+
+    let template =
+        ResponseTemplate::new(200).set_body_raw(sse_body.to_string(), "text/event-stream");
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-api-key", "test-key"))
+        .and(header("anthropic-version", "2023-06-01"))
+        .respond_with(template)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = ModelProviderInfo {
+        name: "mock".into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        env_key_instructions: None,
+        experimental_bearer_token: Some("test-key".to_string()),
+        wire_api: WireApi::Messages,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(5_000),
+        requires_openai_auth: false,
+    };
+
+    let codex_home = TempDir::new().expect("create TempDir");
+    let mut config = load_default_config_for_test(&codex_home);
+    config.model_provider_id = provider.name.clone();
+    config.model_provider = provider.clone();
+    let effort = config.model_reasoning_effort;
+    let summary = config.model_reasoning_summary;
+    let config = Arc::new(config);
+
+    let conversation_id = ConversationId::new();
+    let otel_event_manager = OtelEventManager::new(
+        conversation_id,
+        config.model.as_str(),
+        config.model_family.slug.as_str(),
+        None,
+        Some(AuthMode::ApiKey),
+        false,
+        "test".to_string(),
+    );
+
+    let client = ModelClient::new(
+        Arc::clone(&config),
+        None,
+        otel_event_manager,
+        provider,
+        effort,
+        summary,
+        conversation_id,
+        codex_protocol::protocol::SessionSource::Exec,
+    );
+
+    let mut stream = client.stream(&prompt).await.expect("stream messages");
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event.expect("stream event"));
+    }
+
+    let requests = server.received_requests().await.expect("recorded requests");
+    let body = requests
+        .first()
+        .map(|request| serde_json::from_slice(&request.body).expect("json body"))
+        .expect("one request");
+    (events, body)
+}
+
+fn user_prompt(text: &str) -> Prompt {
+    let mut prompt = Prompt::default();
+    prompt.input = vec![ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: text.to_string(),
+        }],
+    }];
+    prompt
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn streams_fixture_turn_with_thinking_and_tool_use() {
+    if network_disabled() {
+        println!(
+            "Skipping test because it cannot execute when network is disabled in a Codex sandbox."
+        );
+        return;
+    }
+
+    let (events, body) = run_stream(FIXTURE, user_prompt("what is here?")).await;
+
+    assert_eq!(body["stream"], Value::Bool(true));
+    assert_eq!(
+        body["messages"],
+        serde_json::json!([
+            {"role": "user", "content": [{"type": "text", "text": "what is here?"}]},
+        ])
+    );
+    assert!(body["system"].as_str().is_some_and(|s| !s.is_empty()));
+
+    assert_eq!(events.len(), 7, "unexpected events: {events:?}");
+    assert!(matches!(events[0], ResponseEvent::Created));
+    assert!(
+        matches!(&events[1], ResponseEvent::ReasoningContentDelta(text) if text == "Need to list files.")
+    );
+    match &events[2] {
+        ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+            content: Some(content),
+            encrypted_content,
+            ..
+        }) => {
+            assert_eq!(
+                content,
+                &vec![ReasoningItemContent::ReasoningText {
+                    text: "Need to list files.".to_string()
+                }]
+            );
+            assert_eq!(encrypted_content.as_deref(), Some("c2lnbmF0dXJl"));
+        }
+        other => panic!("expected reasoning item, got {other:?}"),
+    }
+    assert!(matches!(&events[3], ResponseEvent::OutputTextDelta(text) if text == "Listing files."));
+    assert!(matches!(
+        &events[4],
+        ResponseEvent::OutputItemDone(ResponseItem::Message { role, .. }) if role == "assistant"
+    ));
+    match &events[5] {
+        ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        }) => {
+            assert_eq!(name, "shell");
+            assert_eq!(arguments, r#"{"command":["ls"]}"#);
+            assert_eq!(call_id, "toolu_fixture");
+        }
+        other => panic!("expected function call, got {other:?}"),
+    }
+    match &events[6] {
+        ResponseEvent::Completed {
+            response_id,
+            token_usage,
+        } => {
+            assert_eq!(response_id, "msg_fixture");
+            let usage = token_usage.clone().expect("usage reported");
+            assert_eq!(usage.input_tokens, 12);
+            assert_eq!(usage.output_tokens, 30);
+        }
+        other => panic!("expected completed, got {other:?}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replays_thinking_and_tool_results_on_next_turn() {
+    if network_disabled() {
+        println!(
+            "Skipping test because it cannot execute when network is disabled in a Codex sandbox."
+        );
+        return;
+    }
+
+    let (first_turn, _) = run_stream(FIXTURE, user_prompt("what is here?")).await;
+    let mut prompt = user_prompt("what is here?");
+    prompt
+        .input
+        .extend(first_turn.into_iter().filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item),
+            _ => None,
+        }));
+    prompt.input.push(ResponseItem::FunctionCallOutput {
+        call_id: "toolu_fixture".to_string(),
+        output: codex_protocol::models::FunctionCallOutputPayload {
+            content: "README.md".to_string(),
+            content_items: None,
+            success: Some(true),
+        },
+    });
+
+    let (_, body) = run_stream(FIXTURE, prompt).await;
+    assert_eq!(
+        body["messages"][1],
+        serde_json::json!({
+            "role": "assistant",
+            "content": [
+                {"type": "thinking", "thinking": "Need to list files.", "signature": "c2lnbmF0dXJl"},
+                {"type": "text", "text": "Listing files."},
+                {"type": "tool_use", "id": "toolu_fixture", "name": "shell", "input": {"command": ["ls"]}},
+            ],
+        })
+    );
+    assert_eq!(
+        body["messages"][2],
+        serde_json::json!({
+            "role": "user",
+            "content": [
+                {"type": "tool_result", "tool_use_id": "toolu_fixture", "content": "README.md"},
+            ],
+        })
+    );
+}
@@ -1,5 +1,6 @@
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
+use codex_core::HistorySearchQuery;
 use codex_core::ModelProviderInfo;
 use codex_core::NewConversation;
 use codex_core::built_in_model_providers;
@@ -8,11 +9,13 @@ use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::RolloutItem;
 use codex_core::protocol::RolloutLine;
+use codex_core::search_history;
 use codex_protocol::items::TurnItem;
 use codex_protocol::user_input::UserInput;
 use core_test_support::load_default_config_for_test;
 use core_test_support::skip_if_no_network;
 use core_test_support::wait_for_event;
+use std::path::Path;
 use tempfile::TempDir;
 use wiremock::Mock;
 use wiremock::MockServer;
@@ -166,3 +169,169 @@ async fn fork_conversation_twice_drops_to_first_message() {
         serde_json::to_value(&expected_after_second).unwrap()
     );
 }
+
+/// Texts of the user turns recorded in the rollout at `path`.
+fn user_texts(path: &Path) -> Vec<String> {
+    let text = std::fs::read_to_string(path).expect("read rollout file");
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let rl: RolloutLine = serde_json::from_str(line).expect("rollout line");
+            match rl.item {
+                RolloutItem::ResponseItem(item) => match parse_turn_item(&item) {
+                    Some(TurnItem::UserMessage(message)) => Some(message.message()),
+                    _ => None,
+                },
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fork_conversation_at_turn_keeps_earlier_turns_and_original_rollout() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let sse = sse_completed("resp");
+    let response = ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_raw(sse, "text/event-stream");
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(response)
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let model_provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        ..built_in_model_providers()["openai"].clone()
+    };
+
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home);
+    config.model_provider = model_provider;
+    let config_for_fork = config.clone();
+
+    let conversation_manager = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"));
+    let NewConversation {
+        conversation: codex,
+        ..
+    } = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation");
+
+    for text in ["first", "second", "third"] {
+        codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.to_string(),
+                }],
+            })
+            .await
+            .unwrap();
+        let _ = wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+    }
+
+    let base_path = codex.rollout_path();
+    let base_before = std::fs::read_to_string(&base_path).expect("read base rollout");
+
+    // Forking at turn 3 keeps the first two turns only.
+    let forked = conversation_manager
+        .fork_conversation_at_turn(3, config_for_fork.clone(), base_path.clone(), false)
+        .await
+        .expect("fork at turn 3");
+    assert_eq!(forked.restored_snapshot, None);
+    let fork_path = forked.new_conversation.conversation.rollout_path();
+    assert_ne!(fork_path, base_path);
+    assert_eq!(user_texts(&fork_path), vec!["first", "second"]);
+
+    // The source rollout is left untouched.
+    let base_after = std::fs::read_to_string(&base_path).expect("read base rollout");
+    assert_eq!(base_before, base_after);
+
+    // Turns are 1-based and must exist in the source rollout.
+    for turn in [0, 4] {
+        let err = conversation_manager
+            .fork_conversation_at_turn(turn, config_for_fork.clone(), base_path.clone(), false)
+            .await
+            .err()
+            .expect("out of range turn should fail");
+        assert!(matches!(
+            err,
+            codex_core::error::CodexErr::ForkTurnOutOfRange { available: 3, .. }
+        ));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn history_search_hit_forks_at_the_matching_turn() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let sse = sse_completed("resp");
+    let response = ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_raw(sse, "text/event-stream");
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(response)
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let model_provider = ModelProviderInfo {
+        base_url: Some(format!("{}/v1", server.uri())),
+        ..built_in_model_providers()["openai"].clone()
+    };
+
+    let home = TempDir::new().unwrap();
+    let mut config = load_default_config_for_test(&home);
+    config.model_provider = model_provider;
+    let config_for_fork = config.clone();
+
+    let conversation_manager = ConversationManager::with_auth(CodexAuth::from_api_key("dummy"));
+    let NewConversation {
+        conversation: codex,
+        ..
+    } = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation");
+
+    for text in ["first", "second needle", "third"] {
+        codex
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: text.to_string(),
+                }],
+            })
+            .await
+            .unwrap();
+        let _ = wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+    }
+
+    // The rollout starts with session prefix items (instructions, environment
+    // context), which must not shift the turn numbering.
+    let hits = search_history(
+        home.path(),
+        &HistorySearchQuery {
+            text: Some("needle".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("search history");
+    assert_eq!(hits.len(), 1);
+    let hit = &hits[0];
+    assert_eq!(hit.turn_index, 2);
+
+    let forked = conversation_manager
+        .fork_conversation_at_turn(hit.turn_index, config_for_fork, hit.path.clone(), false)
+        .await
+        .expect("fork at the hit");
+    let fork_path = forked.new_conversation.conversation.rollout_path();
+    assert_eq!(user_texts(&fork_path), vec!["first"]);
+}
@@ -20,6 +20,7 @@ mod items;
 mod json_result;
 mod list_dir;
 mod live_cli;
+mod model_failover;
 mod model_overrides;
 mod model_tools;
 mod otel;
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::config_types::ModelFailover;
+use codex_core::config_types::ModelFallback;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::config_types::FailoverTrigger;
+use codex_protocol::user_input::UserInput;
+use core_test_support::load_sse_fixture_with_id;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_with_timeout;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_string_contains;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn provider(name: &str, base_url: String) -> ModelProviderInfo {
+    ModelProviderInfo {
+        name: name.into(),
+        base_url: Some(base_url),
+        env_key: Some("PATH".into()),
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2_000),
+        requires_openai_auth: false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn server_error_fails_over_to_next_provider() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/primary/v1/responses"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let completed = load_sse_fixture_with_id("tests/fixtures/completed_template.json", "resp_ok");
+    Mock::given(method("POST"))
+        .and(path("/fallback/v1/responses"))
+        .and(body_string_contains("\"model\":\"fallback-model\""))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(completed, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let primary = provider("primary", format!("{}/primary/v1", server.uri()));
+    let fallback = provider("fallback", format!("{}/fallback/v1", server.uri()));
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = primary;
+            config
+                .model_providers
+                .insert("fallback".to_string(), fallback);
+            config.model_failover = ModelFailover {
+                fallbacks: vec![ModelFallback {
+                    provider: Some("fallback".to_string()),
+                    model: Some("fallback-model".to_string()),
+                    on: vec![FailoverTrigger::ServerError],
+                }],
+                ..ModelFailover::default()
+            };
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    let failover = wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::ModelFailover(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+    let EventMsg::ModelFailover(failover) = failover else {
+        unreachable!("predicate only matches failover events");
+    };
+    assert_eq!(failover.to_provider, "fallback");
+    assert_eq!(failover.to_model, "fallback-model");
+    assert_eq!(failover.reason, FailoverTrigger::ServerError);
+
+    let next = wait_for_event_with_timeout(
+        &codex,
+        |ev| matches!(ev, EventMsg::TaskComplete(_) | EventMsg::Error(_)),
+        Duration::from_secs(5),
+    )
+    .await;
+    assert!(
+        matches!(next, EventMsg::TaskComplete(_)),
+        "expected the fallback to answer, got {next:?}"
+    );
+}
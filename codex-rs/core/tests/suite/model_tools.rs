@@ -98,7 +98,8 @@ async fn model_selects_expected_tools() {
             "list_mcp_resources".to_string(),
             "list_mcp_resource_templates".to_string(),
             "read_mcp_resource".to_string(),
-            "update_plan".to_string()
+            "update_plan".to_string(),
+            "read_tool_output".to_string()
         ],
         "codex-mini-latest should expose the local shell tool",
     );
@@ -111,7 +112,8 @@ async fn model_selects_expected_tools() {
             "list_mcp_resources".to_string(),
             "list_mcp_resource_templates".to_string(),
             "read_mcp_resource".to_string(),
-            "update_plan".to_string()
+            "update_plan".to_string(),
+            "read_tool_output".to_string()
         ],
         "o3 should expose the generic shell tool",
     );
@@ -125,6 +127,7 @@ async fn model_selects_expected_tools() {
             "list_mcp_resource_templates".to_string(),
             "read_mcp_resource".to_string(),
             "update_plan".to_string(),
+            "read_tool_output".to_string(),
             "apply_patch".to_string()
         ],
         "gpt-5-codex should expose the apply_patch tool",
@@ -230,6 +230,7 @@ async fn prompt_tools_are_consistent_across_requests() {
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
                 "update_plan",
+                "read_tool_output",
                 "view_image",
             ],
         ),
@@ -241,6 +242,7 @@ async fn prompt_tools_are_consistent_across_requests() {
                 "list_mcp_resource_templates",
                 "read_mcp_resource",
                 "update_plan",
+                "read_tool_output",
                 "apply_patch",
                 "view_image",
             ],
@@ -80,3 +80,22 @@ async fn find_ignores_granular_gitignore_rules() {
 
     assert_eq!(found, Some(expected));
 }
+
+#[tokio::test]
+async fn find_skips_spilled_tool_outputs_of_the_rollout() {
+    let home = TempDir::new().unwrap();
+    let id = Uuid::new_v4();
+    let expected = write_minimal_rollout_with_id(home.path(), id);
+    let tool_outputs =
+        expected.with_file_name(format!("rollout-2024-01-01T00-00-00-{id}.tool-outputs"));
+    std::fs::create_dir_all(&tool_outputs).unwrap();
+    for call in 0..20 {
+        std::fs::write(tool_outputs.join(format!("call_{call}.txt")), "output").unwrap();
+    }
+
+    let found = find_conversation_path_by_id_str(home.path(), &id.to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(found, Some(expected));
+}
@@ -56,6 +56,16 @@ pub struct Cli {
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// Repair turns to send when the final message does not match
+    /// `--output-schema`. Exec exits with status 3 if it still does not.
+    #[arg(
+        long = "output-schema-retries",
+        value_name = "COUNT",
+        default_value_t = 2,
+        requires = "output_schema"
+    )]
+    pub output_schema_retries: u32,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
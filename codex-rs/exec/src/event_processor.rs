@@ -4,6 +4,8 @@ use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::SessionConfiguredEvent;
 
+use crate::exec_events::OutputSchemaInvalidEvent;
+
 pub(crate) enum CodexStatus {
     Running,
     InitiateShutdown,
@@ -22,6 +24,9 @@ pub(crate) trait EventProcessor {
     /// Handle a single event emitted by the agent.
     fn process_event(&mut self, event: Event) -> CodexStatus;
 
+    /// Report that the final message failed `--output-schema` validation.
+    fn process_output_schema_invalid(&mut self, event: OutputSchemaInvalidEvent);
+
     fn print_final_output(&mut self) {}
 }
 
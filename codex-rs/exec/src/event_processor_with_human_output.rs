@@ -19,6 +19,7 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelFailoverEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -41,6 +42,7 @@ use std::time::Instant;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::exec_events::OutputSchemaInvalidEvent;
 use codex_common::create_config_summary_entries;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -198,6 +200,19 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::StreamError(StreamErrorEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::ModelFailover(ModelFailoverEvent {
+                from_provider,
+                from_model,
+                to_provider,
+                to_model,
+                reason,
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {from_model} ({from_provider}) -> {to_model} ({to_provider}) after {reason}",
+                    "model failover:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::TaskStarted(_) => {
                 // Ignore.
             }
@@ -514,6 +529,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ListCustomPromptsResponse(_) => {
                 // Currently ignored in exec output.
             }
+            EventMsg::UnifiedExecSessions(_) => {
+                // Currently ignored in exec output.
+            }
             EventMsg::MemoryPreview(_) => {
                 // CLI agent does not support interactive memory preview.
             }
@@ -535,6 +553,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 TurnAbortReason::ReviewEnded => {
                     ts_msg!(self, "task aborted: review ended");
                 }
+                TurnAbortReason::BudgetExceeded => {
+                    ts_msg!(self, "{}", "task aborted: budget exceeded".style(self.red));
+                    return CodexStatus::InitiateShutdown;
+                }
             },
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::WebSearchBegin(_)
@@ -559,6 +581,33 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         CodexStatus::Running
     }
 
+    fn process_output_schema_invalid(&mut self, event: OutputSchemaInvalidEvent) {
+        let outcome = if event.will_retry {
+            "asking the model to repair it"
+        } else {
+            "giving up"
+        };
+        ts_msg!(
+            self,
+            "{} final message does not match the schema (attempt {}/{}); {outcome}",
+            "output schema:".style(self.red).style(self.bold),
+            event.attempt,
+            event.max_attempts
+        );
+        for violation in &event.violations {
+            ts_msg!(self, "  {}: {}", violation.path, violation.message);
+        }
+        if !event.will_retry {
+            // Keep stdout for the final message; scripts read this line from stderr.
+            let error = serde_json::json!({
+                "error": "output_schema_mismatch",
+                "attempts": event.attempt,
+                "violations": event.violations,
+            });
+            eprintln!("{error}");
+        }
+    }
+
     fn print_final_output(&mut self) {
         if let Some(usage_info) = &self.last_total_token_usage {
             eprintln!(
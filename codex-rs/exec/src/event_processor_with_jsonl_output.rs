@@ -17,6 +17,7 @@ use crate::exec_events::McpToolCallItem;
 use crate::exec_events::McpToolCallItemError;
 use crate::exec_events::McpToolCallItemResult;
 use crate::exec_events::McpToolCallStatus;
+use crate::exec_events::OutputSchemaInvalidEvent;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::RateLimitUsage;
@@ -49,6 +50,8 @@ use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
+use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::UsageCost;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -66,6 +69,7 @@ pub struct EventProcessorWithJsonOutput {
     running_todo_list: Option<RunningTodoList>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsage>,
     last_rate_limits: Option<RateLimitSnapshot>,
+    last_cost: Option<UsageCost>,
     running_mcp_tool_calls: HashMap<String, RunningMcpToolCall>,
     last_critical_error: Option<ThreadErrorEvent>,
 }
@@ -100,6 +104,7 @@ impl EventProcessorWithJsonOutput {
             running_todo_list: None,
             last_total_token_usage: None,
             last_rate_limits: None,
+            last_cost: None,
             running_mcp_tool_calls: HashMap::new(),
             last_critical_error: None,
         }
@@ -123,6 +128,9 @@ impl EventProcessorWithJsonOutput {
                     self.last_total_token_usage = Some(info.total_token_usage.clone());
                 }
                 self.last_rate_limits = ev.rate_limits.clone();
+                if ev.cost.is_some() {
+                    self.last_cost = ev.cost;
+                }
                 Vec::new()
             }
             EventMsg::TaskStarted(ev) => self.handle_task_started(ev),
@@ -138,6 +146,13 @@ impl EventProcessorWithJsonOutput {
                 message: ev.message.clone(),
             })],
             EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
+            EventMsg::TurnAborted(ev) if ev.reason == TurnAbortReason::BudgetExceeded => {
+                vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+                    error: ThreadErrorEvent {
+                        message: "turn aborted: token or cost budget exceeded".to_string(),
+                    },
+                })]
+            }
             _ => Vec::new(),
         }
     }
@@ -435,6 +450,7 @@ impl EventProcessorWithJsonOutput {
             usage.output_tokens = u.output_tokens;
         }
         usage.rate_limits = self.last_rate_limits.as_ref().map(RateLimitUsage::from);
+        usage.cost_usd = self.last_cost.map(|cost| cost.session_usd);
 
         let mut items = Vec::new();
 
@@ -482,13 +498,25 @@ impl EventProcessor for EventProcessorWithJsonOutput {
 
         let Event { msg, .. } = event;
 
-        if let EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) = msg {
-            if let Some(output_file) = self.last_message_path.as_deref() {
-                handle_last_message(last_agent_message.as_deref(), output_file);
+        match msg {
+            EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
+                if let Some(output_file) = self.last_message_path.as_deref() {
+                    handle_last_message(last_agent_message.as_deref(), output_file);
+                }
+                CodexStatus::InitiateShutdown
             }
-            CodexStatus::InitiateShutdown
-        } else {
-            CodexStatus::Running
+            EventMsg::TurnAborted(ev) if ev.reason == TurnAbortReason::BudgetExceeded => {
+                CodexStatus::InitiateShutdown
+            }
+            _ => CodexStatus::Running,
+        }
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn process_output_schema_invalid(&mut self, event: OutputSchemaInvalidEvent) {
+        match serde_json::to_string(&ThreadEvent::OutputSchemaInvalid(event)) {
+            Ok(line) => println!("{line}"),
+            Err(e) => error!("Failed to serialize event: {e:?}"),
         }
     }
 }
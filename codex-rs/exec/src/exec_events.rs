@@ -35,6 +35,9 @@ pub enum ThreadEvent {
     /// Represents an unrecoverable error emitted directly by the event stream.
     #[serde(rename = "error")]
     Error(ThreadErrorEvent),
+    /// The final message did not match `--output-schema`.
+    #[serde(rename = "output_schema.invalid")]
+    OutputSchemaInvalid(OutputSchemaInvalidEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -66,6 +69,9 @@ pub struct Usage {
     pub cached_input_tokens: i64,
     /// The number of output tokens used during the turn.
     pub output_tokens: i64,
+    /// Estimated cost of the session so far, in USD, when the model is priced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
     /// Latest rate limit snapshot observed for the session.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rate_limits: Option<RateLimitUsage>,
@@ -129,6 +135,26 @@ pub struct ThreadErrorEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct OutputSchemaInvalidEvent {
+    /// 1-based attempt whose final message failed validation.
+    pub attempt: u32,
+    /// Attempts allowed in total: the original turn plus repair turns.
+    pub max_attempts: u32,
+    /// Whether a repair turn is being sent. When false, exec exits with
+    /// status 3.
+    pub will_retry: bool,
+    pub violations: Vec<OutputSchemaViolation>,
+}
+
+/// One way in which a final message fails the output schema.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct OutputSchemaViolation {
+    /// Location of the offending value, e.g. `$.items[2].name`.
+    pub path: String,
+    pub message: String,
+}
+
 /// Canonical representation of a thread item and its domain-specific payload.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadItem {
@@ -9,6 +9,7 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod output_schema;
 
 use anyhow::Context;
 pub use cli::Cli;
@@ -42,6 +43,9 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnAbortedEvent;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
@@ -65,10 +69,14 @@ use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
 const AGENT_PROMPT_PREVIEW_CHARS: usize = 160;
+/// Exit status when the final message never matches `--output-schema`.
+const OUTPUT_SCHEMA_EXIT_CODE: i32 = 3;
 
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::event_processor::handle_last_message;
+use crate::exec_events::OutputSchemaInvalidEvent;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_conversation_path_by_id_str;
 
@@ -109,6 +117,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         no_wait,
         prompt,
         output_schema: output_schema_path,
+        output_schema_retries,
         config_overrides,
     } = cli;
 
@@ -341,7 +350,14 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     }
     refresh_model_metadata(&mut config);
     codex_agentic_core::provider::sanitize_reasoning_overrides(&mut config);
+    if let Some(budget) = agent_context
+        .as_ref()
+        .and_then(|ctx| ctx.profile.budget.clone())
+    {
+        config.budget = budget;
+    }
     codex_agentic_core::provider::sanitize_tool_overrides(&mut config);
+    codex_agentic_core::provider::apply_provider_fallbacks(&mut config);
     apply_overlay_to_config(&mut config, &overlay_prompt);
 
     if let Err(err) = enforce_login_restrictions(&config).await {
@@ -373,12 +389,21 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         let _ = tracing_subscriber::registry().with(fmt_layer).try_init();
     }
 
+    // With `--output-schema` the last message is written below, once it
+    // validates or the repair attempts run out, rather than after each turn.
+    let processor_last_message_file = if output_schema.is_some() {
+        None
+    } else {
+        last_message_file.clone()
+    };
     let mut event_processor: Box<dyn EventProcessor> = match json_mode {
-        true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
+        true => Box::new(EventProcessorWithJsonOutput::new(
+            processor_last_message_file,
+        )),
         _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
             stdout_with_ansi,
             &config,
-            last_message_file.clone(),
+            processor_last_message_file,
         )),
     };
 
@@ -387,6 +412,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             .await
             .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
     }
+    codex_agentic_core::provider::discover_model_limits(&mut config).await;
 
     let default_cwd = config.cwd.to_path_buf();
     let default_approval_policy = config.approval_policy;
@@ -562,29 +588,68 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         ctx.run = Some(record);
     }
 
-    let initial_prompt_task_id = conversation
-        .submit(Op::UserTurn {
-            items,
-            cwd: default_cwd,
-            approval_policy: default_approval_policy,
-            sandbox_policy: default_sandbox_policy,
-            model: default_model,
-            effort: default_effort,
-            summary: default_summary,
-            final_output_json_schema: output_schema,
-        })
-        .await?;
+    let user_turn = |items: Vec<UserInput>| Op::UserTurn {
+        items,
+        cwd: default_cwd.clone(),
+        approval_policy: default_approval_policy,
+        sandbox_policy: default_sandbox_policy.clone(),
+        model: default_model.clone(),
+        effort: default_effort,
+        summary: default_summary,
+        final_output_json_schema: output_schema.clone(),
+    };
+    let initial_prompt_task_id = conversation.submit(user_turn(items)).await?;
     info!("Sent prompt with event ID: {initial_prompt_task_id}");
 
     // Run the loop until the task is complete.
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let max_output_attempts = output_schema_retries.saturating_add(1);
+    let mut output_attempt: u32 = 1;
+    let mut output_schema_failed = false;
     while let Some(event) = rx.recv().await {
-        if matches!(event.msg, EventMsg::Error(_)) {
+        if matches!(
+            &event.msg,
+            EventMsg::Error(_)
+                | EventMsg::TurnAborted(TurnAbortedEvent {
+                    reason: TurnAbortReason::BudgetExceeded
+                })
+        ) {
             error_seen = true;
         }
+        let (final_message, output_check) = match (&event.msg, output_schema.as_ref()) {
+            (EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }), Some(schema)) => (
+                Some(last_agent_message.clone()),
+                output_schema::validate_output(schema, last_agent_message.as_deref()).err(),
+            ),
+            _ => (None, None),
+        };
         let shutdown: CodexStatus = event_processor.process_event(event);
+        let will_retry = output_check.is_some() && output_attempt < max_output_attempts;
+        if let Some(final_message) = final_message
+            && !will_retry
+            && let Some(output_file) = last_message_file.as_deref()
+        {
+            handle_last_message(final_message.as_deref(), output_file);
+        }
+        if let Some(violations) = output_check {
+            event_processor.process_output_schema_invalid(OutputSchemaInvalidEvent {
+                attempt: output_attempt,
+                max_attempts: max_output_attempts,
+                will_retry,
+                violations: violations.clone(),
+            });
+            if will_retry {
+                output_attempt += 1;
+                let repair = UserInput::Text {
+                    text: output_schema::repair_prompt(&violations),
+                };
+                conversation.submit(user_turn(vec![repair])).await?;
+                continue;
+            }
+            output_schema_failed = true;
+        }
         match shutdown {
             CodexStatus::Running => continue,
             CodexStatus::InitiateShutdown => {
@@ -600,9 +665,16 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     if let Some(ctx) = agent_context.as_mut()
         && let Some(record) = ctx.run.take()
     {
-        let exit_code = if error_seen { Some(1) } else { Some(0) };
+        let exit_code = if error_seen {
+            Some(1)
+        } else if output_schema_failed {
+            Some(OUTPUT_SCHEMA_EXIT_CODE)
+        } else {
+            Some(0)
+        };
+        let failed = error_seen || output_schema_failed;
         if let Some(log_path) = ctx.log_path.as_ref() {
-            let status_line = if error_seen {
+            let status_line = if failed {
                 exit_code
                     .map(|code| format!("status: failed (exit {code})"))
                     .unwrap_or_else(|| "status: failed".to_string())
@@ -613,7 +685,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
         if let Err(err) =
             ctx.store
-                .complete_run(&ctx.profile.slug, &record.run_id, exit_code, error_seen)
+                .complete_run(&ctx.profile.slug, &record.run_id, exit_code, failed)
         {
             tracing::warn!(?err, "failed to finalize agent run {}", record.run_id);
         }
@@ -622,6 +694,9 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     if error_seen {
         std::process::exit(1);
     }
+    if output_schema_failed {
+        std::process::exit(OUTPUT_SCHEMA_EXIT_CODE);
+    }
 
     Ok(())
 }
@@ -640,6 +715,7 @@ fn refresh_model_metadata(config: &mut Config) {
         config.model_max_output_tokens = None;
         config.model_auto_compact_token_limit = None;
     }
+    codex_agentic_core::provider::apply_model_limits(config);
 }
 
 fn agent_prompt_preview(prompt: &str) -> String {
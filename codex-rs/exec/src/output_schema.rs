@@ -0,0 +1,449 @@
+//! Checks the final agent message against the `--output-schema` JSON Schema.
+//!
+//! Only the keywords that structured-output schemas use are enforced: `type`,
+//! `enum`, `const`, `properties`, `required`, `additionalProperties`, `items`,
+//! `anyOf`/`oneOf`/`allOf`, local `$ref`s, and the length and range bounds.
+//! Unknown keywords are ignored rather than rejected.
+
+use serde_json::Value;
+
+use crate::exec_events::OutputSchemaViolation;
+
+/// Violations beyond this many are dropped so repair prompts stay short.
+const MAX_VIOLATIONS: usize = 20;
+
+/// Parse `message` as JSON and validate it against `schema`.
+pub(crate) fn validate_output(
+    schema: &Value,
+    message: Option<&str>,
+) -> Result<(), Vec<OutputSchemaViolation>> {
+    let message = message.map(str::trim).unwrap_or_default();
+    if message.is_empty() {
+        return Err(vec![violation(
+            "$",
+            "the final message is empty; expected a JSON value",
+        )]);
+    }
+    let value: Value = serde_json::from_str(message).map_err(|err| {
+        vec![violation(
+            "$",
+            format!("the final message is not valid JSON: {err}"),
+        )]
+    })?;
+
+    let mut violations = Vec::new();
+    Validator { root: schema }.check(schema, &value, "$", &[], &mut violations);
+    violations.truncate(MAX_VIOLATIONS);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Follow-up prompt asking the model to fix its previous final message.
+pub(crate) fn repair_prompt(violations: &[OutputSchemaViolation]) -> String {
+    let mut prompt =
+        String::from("Your last message does not conform to the required JSON output schema:\n");
+    for violation in violations {
+        prompt.push_str(&format!("- {}: {}\n", violation.path, violation.message));
+    }
+    prompt.push_str(
+        "\nReply again with only the corrected JSON value. It must satisfy the schema; do not add prose or code fences.",
+    );
+    prompt
+}
+
+fn violation(path: &str, message: impl Into<String>) -> OutputSchemaViolation {
+    OutputSchemaViolation {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    /// `refs` holds the references followed since the last step into a child
+    /// value; meeting one of them again means the schema loops without ever
+    /// constraining the value.
+    fn check(
+        &self,
+        schema: &'a Value,
+        value: &Value,
+        path: &str,
+        refs: &[&'a str],
+        out: &mut Vec<OutputSchemaViolation>,
+    ) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                out.push(violation(path, "no value is allowed here"));
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if refs.contains(&reference) {
+                out.push(violation(
+                    path,
+                    format!("schema reference `{reference}` refers back to itself"),
+                ));
+                return;
+            }
+            match self.resolve(reference) {
+                Some(target) => {
+                    let mut refs = refs.to_vec();
+                    refs.push(reference);
+                    self.check(target, value, path, &refs, out);
+                }
+                None => out.push(violation(
+                    path,
+                    format!("schema reference `{reference}` cannot be resolved"),
+                )),
+            }
+        }
+
+        if let Some(expected) = schema.get("type")
+            && !type_matches(expected, value)
+        {
+            out.push(violation(
+                path,
+                format!(
+                    "expected {}, got {}",
+                    describe_type(expected),
+                    type_name(value)
+                ),
+            ));
+            return;
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+            && !allowed.contains(value)
+        {
+            out.push(violation(
+                path,
+                format!("must be one of {}", Value::Array(allowed.clone())),
+            ));
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != value
+        {
+            out.push(violation(path, format!("must equal {expected}")));
+        }
+
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            for subschema in all_of {
+                self.check(subschema, value, path, refs, out);
+            }
+        }
+        if let Some(any_of) = schema.get("anyOf").and_then(Value::as_array)
+            && self.matching(any_of, value, path, refs) == 0
+        {
+            out.push(violation(
+                path,
+                "does not match any of the allowed shapes (anyOf)",
+            ));
+        }
+        if let Some(one_of) = schema.get("oneOf").and_then(Value::as_array) {
+            let matches = self.matching(one_of, value, path, refs);
+            if matches != 1 {
+                out.push(violation(
+                    path,
+                    format!("must match exactly one allowed shape (oneOf), matched {matches}"),
+                ));
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            out.push(violation(
+                                path,
+                                format!("missing required property `{name}`"),
+                            ));
+                        }
+                    }
+                }
+                for (name, child) in object {
+                    let child_path = format!("{path}.{name}");
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(subschema) => self.check(subschema, child, &child_path, &[], out),
+                        None => match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => {
+                                out.push(violation(path, format!("unexpected property `{name}`")))
+                            }
+                            Some(additional) => {
+                                self.check(additional, child, &child_path, &[], out)
+                            }
+                            None => {}
+                        },
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+                    && (items.len() as u64) < min
+                {
+                    out.push(violation(path, format!("must have at least {min} items")));
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                    && (items.len() as u64) > max
+                {
+                    out.push(violation(path, format!("must have at most {max} items")));
+                }
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.check(item_schema, item, &format!("{path}[{index}]"), &[], out);
+                    }
+                }
+            }
+            Value::String(text) => {
+                let length = text.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+                    && length < min
+                {
+                    out.push(violation(
+                        path,
+                        format!("must be at least {min} characters long"),
+                    ));
+                }
+                if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+                    && length > max
+                {
+                    out.push(violation(
+                        path,
+                        format!("must be at most {max} characters long"),
+                    ));
+                }
+            }
+            Value::Number(number) => {
+                let Some(number) = number.as_f64() else {
+                    return;
+                };
+                let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+                if let Some(minimum) = bound("minimum")
+                    && number < minimum
+                {
+                    out.push(violation(path, format!("must be >= {minimum}")));
+                }
+                if let Some(maximum) = bound("maximum")
+                    && number > maximum
+                {
+                    out.push(violation(path, format!("must be <= {maximum}")));
+                }
+                if let Some(minimum) = bound("exclusiveMinimum")
+                    && number <= minimum
+                {
+                    out.push(violation(path, format!("must be > {minimum}")));
+                }
+                if let Some(maximum) = bound("exclusiveMaximum")
+                    && number >= maximum
+                {
+                    out.push(violation(path, format!("must be < {maximum}")));
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+    }
+
+    /// Number of `schemas` that `value` satisfies.
+    fn matching(&self, schemas: &'a [Value], value: &Value, path: &str, refs: &[&'a str]) -> usize {
+        schemas
+            .iter()
+            .filter(|subschema| {
+                let mut scratch = Vec::new();
+                self.check(subschema, value, path, refs, &mut scratch);
+                scratch.is_empty()
+            })
+            .count()
+    }
+
+    /// Resolve a local reference such as `#/$defs/item`.
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        if pointer.is_empty() {
+            Some(self.root)
+        } else {
+            self.root.pointer(pointer)
+        }
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| is_type(name, value)),
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("a valid type").to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "answer": { "type": "string", "minLength": 1 },
+                "tags": { "type": "array", "items": { "$ref": "#/$defs/tag" } },
+                "score": { "type": ["integer", "null"], "minimum": 0 }
+            },
+            "required": ["answer", "tags", "score"],
+            "additionalProperties": false,
+            "$defs": {
+                "tag": { "type": "string", "enum": ["a", "b"] }
+            }
+        })
+    }
+
+    /// Object keys iterate in insertion order when serde_json's
+    /// `preserve_order` is enabled elsewhere in the workspace.
+    fn sorted(mut violations: Vec<OutputSchemaViolation>) -> Vec<OutputSchemaViolation> {
+        violations.sort_by(|a, b| (&a.path, &a.message).cmp(&(&b.path, &b.message)));
+        violations
+    }
+
+    #[test]
+    fn accepts_conforming_output() {
+        assert_eq!(
+            validate_output(
+                &schema(),
+                Some(r#" {"answer": "yes", "tags": ["a"], "score": null} "#)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reports_each_violation_with_its_path() {
+        let violations = validate_output(
+            &schema(),
+            Some(r#"{"answer": "", "tags": ["a", "c"], "score": -1, "extra": true}"#),
+        )
+        .unwrap_err();
+        assert_eq!(
+            sorted(violations),
+            vec![
+                violation("$", "unexpected property `extra`"),
+                violation("$.answer", "must be at least 1 characters long"),
+                violation("$.score", "must be >= 0"),
+                violation("$.tags[1]", r#"must be one of ["a","b"]"#),
+            ]
+        );
+
+        let violations = validate_output(&schema(), Some(r#"{"answer": 1}"#)).unwrap_err();
+        assert_eq!(
+            sorted(violations),
+            vec![
+                violation("$", "missing required property `score`"),
+                violation("$", "missing required property `tags`"),
+                violation("$.answer", "expected string, got number"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_prose_and_empty_messages() {
+        let violations = validate_output(&schema(), Some("```json\n{}\n```")).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(
+            violations[0]
+                .message
+                .starts_with("the final message is not valid JSON")
+        );
+        assert_eq!(
+            validate_output(&schema(), None),
+            Err(vec![violation(
+                "$",
+                "the final message is empty; expected a JSON value"
+            )])
+        );
+
+        let prompt = repair_prompt(&[violation("$.answer", "expected string, got number")]);
+        assert!(prompt.contains("- $.answer: expected string, got number\n"));
+    }
+
+    #[test]
+    fn reports_reference_cycles_instead_of_recursing_forever() {
+        let self_ref = json!({ "$ref": "#" });
+        assert_eq!(
+            validate_output(&self_ref, Some("1")),
+            Err(vec![violation(
+                "$",
+                "schema reference `#` refers back to itself"
+            )])
+        );
+
+        let loop_via_defs = json!({
+            "$ref": "#/$defs/a",
+            "$defs": {
+                "a": { "$ref": "#/$defs/b" },
+                "b": { "anyOf": [{ "$ref": "#/$defs/a" }] }
+            }
+        });
+        assert!(validate_output(&loop_via_defs, Some("{}")).is_err());
+
+        // Recursion through child values is a tree, not a loop.
+        let tree = json!({
+            "type": "object",
+            "properties": {
+                "children": { "type": "array", "items": { "$ref": "#" } }
+            }
+        });
+        assert_eq!(
+            validate_output(&tree, Some(r#"{"children": [{"children": []}]}"#)),
+            Ok(())
+        );
+    }
+}
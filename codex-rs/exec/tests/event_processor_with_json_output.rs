@@ -893,6 +893,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            cost: None,
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
@@ -912,6 +913,7 @@ fn task_complete_produces_turn_completed_with_usage() {
                 input_tokens: 1200,
                 cached_input_tokens: 200,
                 output_tokens: 345,
+                cost_usd: None,
                 rate_limits: None,
             },
         })]
@@ -940,6 +942,7 @@ fn task_complete_includes_rate_limits() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: None,
             rate_limits: Some(snapshot.clone()),
+            cost: None,
         }),
     );
     assert!(ep.collect_thread_events(&token_event).is_empty());
@@ -958,6 +961,7 @@ fn task_complete_includes_rate_limits() {
                 input_tokens: 0,
                 cached_input_tokens: 0,
                 output_tokens: 0,
+                cost_usd: None,
                 rate_limits: Some(RateLimitUsage::from(&snapshot)),
             },
         })]
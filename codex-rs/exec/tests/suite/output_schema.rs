@@ -2,8 +2,10 @@
 #![allow(clippy::expect_used, clippy::unwrap_used)]
 
 use core_test_support::responses;
+use core_test_support::test_codex_exec::TestCodexExecBuilder;
 use core_test_support::test_codex_exec::test_codex_exec;
 use serde_json::Value;
+use std::path::PathBuf;
 use wiremock::matchers::any;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -25,7 +27,7 @@ async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
     let server = responses::start_mock_server().await;
     let body = responses::sse(vec![
         responses::ev_response_created("resp1"),
-        responses::ev_assistant_message("m1", "fixture hello"),
+        responses::ev_assistant_message("m1", r#"{"answer":"fixture hello"}"#),
         responses::ev_completed("resp1"),
     ]);
     let response_mock = responses::mount_sse_once_match(&server, any(), body).await;
@@ -61,3 +63,108 @@ async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn answer_schema(test: &TestCodexExecBuilder) -> PathBuf {
+    let schema_path = test.cwd_path().join("schema.json");
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": { "answer": { "type": "string" } },
+        "required": ["answer"],
+        "additionalProperties": false
+    });
+    std::fs::write(&schema_path, schema.to_string()).unwrap();
+    schema_path
+}
+
+fn assistant_turn(id: &str, text: &str) -> String {
+    responses::sse(vec![
+        responses::ev_response_created(id),
+        responses::ev_assistant_message(id, text),
+        responses::ev_completed(id),
+    ])
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_repairs_output_that_does_not_match_schema() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let schema_path = answer_schema(&test);
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            assistant_turn("resp1", "Sure! The answer is 42."),
+            assistant_turn("resp2", r#"{"answer":"42"}"#),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--output-schema")
+        .arg(&schema_path)
+        .arg("-m")
+        .arg("gpt-5")
+        .arg("what is the answer")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#"{"answer":"42"}"#));
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let repair_request = requests[1].body_json().to_string();
+    assert!(repair_request.contains("does not conform to the required JSON output schema"));
+    assert!(repair_request.contains("not valid JSON"));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_exits_with_status_3_when_output_never_matches_schema() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let schema_path = answer_schema(&test);
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            assistant_turn("resp1", r#"{"answer":1}"#),
+            assistant_turn("resp2", r#"{"result":"1"}"#),
+        ],
+    )
+    .await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--output-schema")
+        .arg(&schema_path)
+        .arg("--output-schema-retries")
+        .arg("1")
+        .arg("--json")
+        .arg("-m")
+        .arg("gpt-5")
+        .arg("what is the answer")
+        .assert()
+        .code(3)
+        .get_output()
+        .clone();
+
+    assert_eq!(response_mock.requests().len(), 2);
+    let events: Vec<Value> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let invalid: Vec<&Value> = events
+        .iter()
+        .filter(|event| event["type"] == "output_schema.invalid")
+        .collect();
+    assert_eq!(invalid.len(), 2);
+    assert_eq!(invalid[0]["will_retry"], true);
+    assert_eq!(
+        invalid[0]["violations"][0],
+        serde_json::json!({ "path": "$.answer", "message": "expected string, got number" })
+    );
+    assert_eq!(invalid[1]["will_retry"], false);
+    assert_eq!(invalid[1]["attempt"], 2);
+    Ok(())
+}
@@ -5,9 +5,15 @@ use serde_json::Value as JsonValue;
 use std::collections::VecDeque;
 use std::io;
 
+use crate::models::LocalModel;
+use crate::models::RunningModel;
+use crate::models::local_models_from_value;
+use crate::models::running_models_from_value;
 use crate::parser::pull_events_from_value;
 use crate::pull::PullEvent;
 use crate::pull::PullProgressReporter;
+use crate::show::ModelDetails;
+use crate::show::model_details_from_value;
 use crate::url::base_url_to_host_root;
 use crate::url::is_openai_compatible_base_url;
 use codex_core::BUILT_IN_OSS_MODEL_PROVIDER_ID;
@@ -129,6 +135,111 @@ impl OllamaClient {
         Ok(names)
     }
 
+    /// List the models stored locally with their disk usage and
+    /// quantization details (`/api/tags`).
+    pub async fn fetch_local_models(&self) -> io::Result<Vec<LocalModel>> {
+        let url = format!("{}/api/tags", self.host_root.trim_end_matches('/'));
+        let val = self.get_json(url, "list models").await?;
+        Ok(local_models_from_value(&val))
+    }
+
+    /// List the models currently loaded into memory (`/api/ps`).
+    pub async fn fetch_running_models(&self) -> io::Result<Vec<RunningModel>> {
+        let url = format!("{}/api/ps", self.host_root.trim_end_matches('/'));
+        let val = self.get_json(url, "list running models").await?;
+        Ok(running_models_from_value(&val))
+    }
+
+    async fn get_json(&self, url: String, action: &str) -> io::Result<JsonValue> {
+        let resp = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if !resp.status().is_success() {
+            return Err(io::Error::other(format!(
+                "failed to {action}: HTTP {}",
+                resp.status()
+            )));
+        }
+        resp.json::<JsonValue>().await.map_err(io::Error::other)
+    }
+
+    /// Remove `model` and any blobs no other model uses (`/api/delete`).
+    pub async fn delete_model(&self, model: &str) -> io::Result<()> {
+        let url = format!("{}/api/delete", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .delete(url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        match resp.status() {
+            status if status.is_success() => Ok(()),
+            reqwest::StatusCode::NOT_FOUND => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("model {model} not found"),
+            )),
+            status => Err(io::Error::other(format!(
+                "failed to delete model {model}: HTTP {status}"
+            ))),
+        }
+    }
+
+    /// Load `model` into memory without generating anything, so the first
+    /// turn does not pay for the load. `keep_alive` is an Ollama duration
+    /// (`"30m"`, or `"-1"` to keep the model loaded); `None` keeps the
+    /// server default.
+    pub async fn preload_model(&self, model: &str, keep_alive: Option<&str>) -> io::Result<()> {
+        let url = format!("{}/api/generate", self.host_root.trim_end_matches('/'));
+        let mut body = serde_json::json!({ "model": model });
+        if let Some(keep_alive) = keep_alive {
+            // Ollama reads bare numbers as seconds but rejects unitless
+            // strings, so send `-1` or `300` as JSON numbers.
+            body["keep_alive"] = match keep_alive.trim().parse::<i64>() {
+                Ok(seconds) => JsonValue::from(seconds),
+                Err(_) => JsonValue::from(keep_alive.trim()),
+            };
+        }
+        let resp = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if !resp.status().is_success() {
+            return Err(io::Error::other(format!(
+                "failed to load model {model}: HTTP {}",
+                resp.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fetch metadata for `model` from `/api/show`, including its context
+    /// length and Modelfile parameters.
+    pub async fn fetch_model_details(&self, model: &str) -> io::Result<ModelDetails> {
+        let url = format!("{}/api/show", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .post(url)
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        if !resp.status().is_success() {
+            return Err(io::Error::other(format!(
+                "failed to show model {model}: HTTP {}",
+                resp.status()
+            )));
+        }
+        let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+        Ok(model_details_from_value(&val))
+    }
+
     /// Start a model pull and emit streaming events. The returned stream ends when
     /// a Success event is observed or the server closes the connection.
     pub async fn pull_model_stream(
@@ -192,7 +303,7 @@ impl OllamaClient {
     pub async fn pull_with_reporter(
         &self,
         model: &str,
-        reporter: &mut dyn PullProgressReporter,
+        reporter: &mut (dyn PullProgressReporter + Send),
     ) -> io::Result<()> {
         reporter.on_event(&PullEvent::Status(format!("Pulling model {model}...")))?;
         let mut stream = self.pull_model_stream(model).await?;
@@ -273,6 +384,130 @@ mod tests {
         assert!(models.contains(&"mistral".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_fetch_model_details_happy_path() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_fetch_model_details_happy_path",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "llama3.2:3b" }),
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "parameters": "num_ctx 8192",
+                        "model_info": {
+                            "general.architecture": "llama",
+                            "llama.context_length": 131072
+                        }
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = OllamaClient::from_host_root(server.uri());
+        let details = client
+            .fetch_model_details("llama3.2:3b")
+            .await
+            .expect("fetch model details");
+        assert_eq!(details.context_length, Some(131_072));
+        assert_eq!(details.context_window(), Some(8_192));
+    }
+
+    #[tokio::test]
+    async fn test_manage_local_models() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_manage_local_models",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/api/tags"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "models": [{
+                            "name": "llama3.2:3b",
+                            "size": 2_019_393_189u64,
+                            "details": { "quantization_level": "Q4_K_M" }
+                        }]
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/api/ps"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+                serde_json::json!({ "models": [{ "name": "llama3.2:3b", "size": 3 }] }).to_string(),
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("DELETE"))
+            .and(wiremock::matchers::path("/api/delete"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "llama3.2:3b" }),
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("DELETE"))
+            .and(wiremock::matchers::path("/api/delete"))
+            .respond_with(wiremock::ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/generate"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "llama3.2:3b", "keep_alive": -1 }),
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = OllamaClient::from_host_root(server.uri());
+        let models = client.fetch_local_models().await.expect("list models");
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].size, 2_019_393_189);
+        assert_eq!(models[0].spec.quantization_level.as_deref(), Some("Q4_K_M"));
+
+        let running = client.fetch_running_models().await.expect("list running");
+        assert_eq!(running[0].name, "llama3.2:3b");
+
+        client
+            .preload_model("llama3.2:3b", Some("-1"))
+            .await
+            .expect("preload model");
+        client
+            .delete_model("llama3.2:3b")
+            .await
+            .expect("delete model");
+        let err = client
+            .delete_model("missing")
+            .await
+            .expect_err("missing model");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
     #[tokio::test]
     async fn test_probe_server_happy_path_openai_compat_and_native() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
@@ -1,14 +1,20 @@
 mod client;
+mod models;
 mod parser;
 mod pull;
+mod show;
 mod url;
 
 pub use client::OllamaClient;
 use codex_core::config::Config;
+pub use models::LocalModel;
+pub use models::ModelSpec;
+pub use models::RunningModel;
 pub use pull::CliProgressReporter;
 pub use pull::PullEvent;
 pub use pull::PullProgressReporter;
 pub use pull::TuiProgressReporter;
+pub use show::ModelDetails;
 
 /// Default OSS model to use when `--oss` is passed without an explicit `-m`.
 pub const DEFAULT_OSS_MODEL: &str = "gpt-oss:20b";
@@ -17,6 +23,12 @@ pub const DEFAULT_OSS_MODEL: &str = "gpt-oss:20b";
 ///
 /// - Ensures a local Ollama server is reachable.
 /// - Checks if the model exists locally and pulls it if missing.
+/// - Starts loading the model in the background, honouring
+///   `oss_keep_alive`, so the first turn does not wait for it.
+///
+/// `oss_keep_alive` only applies to that preload: turns go through Ollama's
+/// OpenAI-compatible endpoint, which takes no `keep_alive` and resets the
+/// unload timer to the server default (`OLLAMA_KEEP_ALIVE`) on every request.
 pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
     // Only download when the requested model is the default OSS model (or when -m is not provided).
     let model = config.model.as_ref();
@@ -40,5 +52,16 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
         }
     }
 
+    let model = model.to_string();
+    let keep_alive = config.oss_keep_alive.clone();
+    tokio::spawn(async move {
+        if let Err(err) = ollama_client
+            .preload_model(&model, keep_alive.as_deref())
+            .await
+        {
+            tracing::warn!("Failed to pre-warm {model}: {err}");
+        }
+    });
+
     Ok(())
 }
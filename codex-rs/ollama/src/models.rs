@@ -0,0 +1,161 @@
+use serde_json::Value as JsonValue;
+
+/// The `details` object Ollama reports for a model: its family, size class
+/// and quantization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelSpec {
+    pub family: Option<String>,
+    /// Parameter count as reported by Ollama, e.g. `20.9B`.
+    pub parameter_size: Option<String>,
+    /// Quantization of the weights, e.g. `Q4_K_M` or `MXFP4`.
+    pub quantization_level: Option<String>,
+    /// On-disk format, usually `gguf`.
+    pub format: Option<String>,
+}
+
+/// A model stored by the local Ollama instance (`/api/tags`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalModel {
+    pub name: String,
+    /// Disk usage of the model's blobs, in bytes.
+    pub size: u64,
+    pub modified_at: Option<String>,
+    pub digest: Option<String>,
+    pub spec: ModelSpec,
+}
+
+/// A model currently loaded into memory (`/api/ps`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunningModel {
+    pub name: String,
+    /// Memory used by the loaded model, in bytes.
+    pub size: u64,
+    /// Portion of `size` held in GPU memory.
+    pub size_vram: u64,
+    /// When Ollama will unload the model unless it is used again.
+    pub expires_at: Option<String>,
+}
+
+pub(crate) fn model_spec_from_value(value: &JsonValue) -> ModelSpec {
+    let details = value.get("details");
+    let field = |key: &str| {
+        details
+            .and_then(|details| details.get(key))
+            .and_then(JsonValue::as_str)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    ModelSpec {
+        family: field("family"),
+        parameter_size: field("parameter_size"),
+        quantization_level: field("quantization_level"),
+        format: field("format"),
+    }
+}
+
+pub(crate) fn local_models_from_value(value: &JsonValue) -> Vec<LocalModel> {
+    models_array(value)
+        .filter_map(|model| {
+            Some(LocalModel {
+                name: model_name(model)?,
+                size: model.get("size").and_then(JsonValue::as_u64).unwrap_or(0),
+                modified_at: string_field(model, "modified_at"),
+                digest: string_field(model, "digest"),
+                spec: model_spec_from_value(model),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn running_models_from_value(value: &JsonValue) -> Vec<RunningModel> {
+    models_array(value)
+        .filter_map(|model| {
+            Some(RunningModel {
+                name: model_name(model)?,
+                size: model.get("size").and_then(JsonValue::as_u64).unwrap_or(0),
+                size_vram: model
+                    .get("size_vram")
+                    .and_then(JsonValue::as_u64)
+                    .unwrap_or(0),
+                expires_at: string_field(model, "expires_at"),
+            })
+        })
+        .collect()
+}
+
+fn models_array(value: &JsonValue) -> impl Iterator<Item = &JsonValue> {
+    value
+        .get("models")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn model_name(model: &JsonValue) -> Option<String> {
+    string_field(model, "name").or_else(|| string_field(model, "model"))
+}
+
+fn string_field(value: &JsonValue, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(JsonValue::as_str)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_tags_and_running_models() {
+        let tags = serde_json::json!({
+            "models": [
+                {
+                    "name": "gpt-oss:20b",
+                    "size": 13_780_173_839u64,
+                    "digest": "aa4295ac10c3",
+                    "modified_at": "2025-08-05T10:00:00Z",
+                    "details": {
+                        "format": "gguf",
+                        "family": "gptoss",
+                        "parameter_size": "20.9B",
+                        "quantization_level": "MXFP4"
+                    }
+                },
+                { "size": 1 }
+            ]
+        });
+        assert_eq!(
+            local_models_from_value(&tags),
+            vec![LocalModel {
+                name: "gpt-oss:20b".to_string(),
+                size: 13_780_173_839,
+                modified_at: Some("2025-08-05T10:00:00Z".to_string()),
+                digest: Some("aa4295ac10c3".to_string()),
+                spec: ModelSpec {
+                    family: Some("gptoss".to_string()),
+                    parameter_size: Some("20.9B".to_string()),
+                    quantization_level: Some("MXFP4".to_string()),
+                    format: Some("gguf".to_string()),
+                },
+            }]
+        );
+
+        let ps = serde_json::json!({
+            "models": [
+                { "model": "gpt-oss:20b", "size": 14_000, "size_vram": 12_000, "expires_at": "2025-08-05T10:30:00Z" }
+            ]
+        });
+        assert_eq!(
+            running_models_from_value(&ps),
+            vec![RunningModel {
+                name: "gpt-oss:20b".to_string(),
+                size: 14_000,
+                size_vram: 12_000,
+                expires_at: Some("2025-08-05T10:30:00Z".to_string()),
+            }]
+        );
+        assert_eq!(running_models_from_value(&serde_json::json!({})), vec![]);
+    }
+}
@@ -135,13 +135,111 @@ impl PullProgressReporter for CliProgressReporter {
     }
 }
 
-/// For now the TUI reporter delegates to the CLI reporter. This keeps UI and
-/// CLI behavior aligned until a dedicated TUI integration is implemented.
-#[derive(Default)]
-pub struct TuiProgressReporter(CliProgressReporter);
+/// Reporter for the TUI: condenses pull events into one-line status
+/// messages and hands them to a callback, which usually forwards them to the
+/// app event loop. Byte progress is reported once per whole percent so a
+/// large pull does not flood the UI.
+pub struct TuiProgressReporter {
+    on_status: Box<dyn FnMut(String) + Send>,
+    totals_by_digest: HashMap<String, (u64, u64)>,
+    last_percent: Option<u64>,
+}
+
+impl TuiProgressReporter {
+    pub fn new(on_status: impl FnMut(String) + Send + 'static) -> Self {
+        Self {
+            on_status: Box::new(on_status),
+            totals_by_digest: HashMap::new(),
+            last_percent: None,
+        }
+    }
+}
 
 impl PullProgressReporter for TuiProgressReporter {
     fn on_event(&mut self, event: &PullEvent) -> io::Result<()> {
-        self.0.on_event(event)
+        match event {
+            PullEvent::Status(status) => {
+                if !status.eq_ignore_ascii_case("pulling manifest") {
+                    (self.on_status)(status.clone());
+                }
+            }
+            PullEvent::ChunkProgress {
+                digest,
+                total,
+                completed,
+            } => {
+                let entry = self.totals_by_digest.entry(digest.clone()).or_default();
+                if let Some(total) = *total {
+                    entry.0 = total;
+                }
+                if let Some(completed) = *completed {
+                    entry.1 = completed;
+                }
+                let (sum_total, sum_completed) = self
+                    .totals_by_digest
+                    .values()
+                    .fold((0u64, 0u64), |acc, (t, c)| (acc.0 + *t, acc.1 + *c));
+                if sum_total == 0 {
+                    return Ok(());
+                }
+                let percent = sum_completed.saturating_mul(100) / sum_total;
+                if self.last_percent == Some(percent) {
+                    return Ok(());
+                }
+                self.last_percent = Some(percent);
+                let done_gb = (sum_completed as f64) / (1024.0 * 1024.0 * 1024.0);
+                let total_gb = (sum_total as f64) / (1024.0 * 1024.0 * 1024.0);
+                (self.on_status)(format!(
+                    "Downloading {done_gb:.2}/{total_gb:.2} GB ({percent}%)"
+                ));
+            }
+            PullEvent::Success => (self.on_status)("Download complete".to_string()),
+            // The caller reports the error returned by `pull_with_reporter`.
+            PullEvent::Error(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn tui_reporter_throttles_progress_to_whole_percents() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&lines);
+        let mut reporter = TuiProgressReporter::new(move |line| {
+            if let Ok(mut lines) = sink.lock() {
+                lines.push(line);
+            }
+        });
+
+        let gib = 1024 * 1024 * 1024;
+        for completed in [0, gib / 1000, gib / 2, gib / 2 + 1] {
+            reporter
+                .on_event(&PullEvent::ChunkProgress {
+                    digest: "sha256:a".to_string(),
+                    total: Some(gib),
+                    completed: Some(completed),
+                })
+                .unwrap();
+        }
+        reporter
+            .on_event(&PullEvent::Status("pulling manifest".to_string()))
+            .unwrap();
+        reporter.on_event(&PullEvent::Success).unwrap();
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "Downloading 0.00/1.00 GB (0%)".to_string(),
+                "Downloading 0.50/1.00 GB (50%)".to_string(),
+                "Download complete".to_string(),
+            ]
+        );
     }
 }
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value as JsonValue;
+
+use crate::models::ModelSpec;
+use crate::models::model_spec_from_value;
+
+/// Model metadata reported by Ollama's `/api/show` endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDetails {
+    /// Context length the model was trained with (`<arch>.context_length`).
+    pub context_length: Option<i64>,
+    /// Parameters from the Modelfile, e.g. `num_ctx` or `temperature`.
+    /// Repeated parameters such as `stop` keep their last value.
+    pub parameters: BTreeMap<String, String>,
+    /// Family, parameter count and quantization.
+    pub spec: ModelSpec,
+}
+
+impl ModelDetails {
+    /// The context window the Modelfile pins with `num_ctx`. Without it the
+    /// window depends on the server (`OLLAMA_CONTEXT_LENGTH`, its default),
+    /// which `/api/show` does not report, so this returns `None` rather than
+    /// guessing.
+    pub fn context_window(&self) -> Option<i64> {
+        self.parameters
+            .get("num_ctx")
+            .and_then(|value| value.parse().ok())
+            .filter(|value: &i64| *value > 0)
+    }
+
+    /// Maximum tokens generated per response (`num_predict`), when the
+    /// Modelfile bounds it.
+    pub fn max_output_tokens(&self) -> Option<i64> {
+        self.parameters
+            .get("num_predict")
+            .and_then(|value| value.parse().ok())
+            .filter(|value: &i64| *value > 0)
+    }
+}
+
+pub(crate) fn model_details_from_value(value: &JsonValue) -> ModelDetails {
+    let model_info = value.get("model_info");
+    let architecture = model_info
+        .and_then(|info| info.get("general.architecture"))
+        .and_then(JsonValue::as_str);
+    let context_length = architecture
+        .and_then(|arch| model_info?.get(format!("{arch}.context_length")))
+        .and_then(JsonValue::as_i64);
+
+    let parameters = value
+        .get("parameters")
+        .and_then(JsonValue::as_str)
+        .map(|parameters| {
+            parameters
+                .lines()
+                .filter_map(|line| {
+                    let (key, value) = line.trim().split_once(char::is_whitespace)?;
+                    Some((key.to_string(), value.trim().trim_matches('"').to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ModelDetails {
+        context_length,
+        parameters,
+        spec: model_spec_from_value(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_context_length_and_parameters() {
+        let value = serde_json::json!({
+            "parameters": "num_ctx                        16384\nstop                           \"<|end|>\"\nnum_predict 2048",
+            "details": { "family": "llama", "quantization_level": "Q4_K_M" },
+            "model_info": {
+                "general.architecture": "llama",
+                "llama.context_length": 131072
+            }
+        });
+
+        let details = model_details_from_value(&value);
+        assert_eq!(details.context_length, Some(131_072));
+        assert_eq!(
+            details.parameters.get("stop").map(String::as_str),
+            Some("<|end|>")
+        );
+        assert_eq!(details.context_window(), Some(16_384));
+        assert_eq!(details.max_output_tokens(), Some(2_048));
+        assert_eq!(details.spec.quantization_level.as_deref(), Some("Q4_K_M"));
+    }
+
+    #[test]
+    fn leaves_the_window_to_the_server_without_num_ctx() {
+        let value = serde_json::json!({
+            "model_info": {
+                "general.architecture": "gptoss",
+                "gptoss.context_length": 131072
+            }
+        });
+
+        let details = model_details_from_value(&value);
+        assert_eq!(details.context_length, Some(131_072));
+        assert_eq!(details.context_window(), None);
+        assert_eq!(details.max_output_tokens(), None);
+        assert_eq!(
+            model_details_from_value(&serde_json::json!({})),
+            ModelDetails::default()
+        );
+    }
+}
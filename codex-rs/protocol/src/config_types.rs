@@ -85,3 +85,19 @@ pub enum ForcedLoginMethod {
     Chatgpt,
     Api,
 }
+
+/// Error classes that can move a turn onto the next entry of a model
+/// fallback chain.
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Display, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FailoverTrigger {
+    /// 5xx responses or connection failures that outlasted the provider's retries.
+    ServerError,
+    /// 429 responses that outlasted the provider's retries.
+    RateLimit,
+    /// The prompt no longer fits in the model's context window.
+    ContextWindow,
+}
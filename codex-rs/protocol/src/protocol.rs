@@ -11,6 +11,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use crate::ConversationId;
+use crate::config_types::FailoverTrigger;
 use crate::config_types::ReasoningEffort as ReasoningEffortConfig;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use crate::custom_prompts::CustomPrompt;
@@ -159,6 +160,15 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Approve only part of a code patch. Hunks are numbered per file as in
+    /// [`FileChange::hunks`]; files without rejected hunks are applied in full.
+    PatchHunkApproval {
+        /// The id of the submission we are approving
+        id: String,
+        /// Indices of the rejected hunks, keyed by the path of the change.
+        rejected_hunks: HashMap<PathBuf, Vec<usize>>,
+    },
+
     /// Append an entry to the persistent cross-session message history.
     ///
     /// Note the entry is not guaranteed to be logged if the user has
@@ -178,6 +188,16 @@ pub enum Op {
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Request the live unified exec sessions (long-running `exec_command`
+    /// processes). Reply is delivered via `EventMsg::UnifiedExecSessions`.
+    ListUnifiedExecSessions,
+
+    /// Write `input` to the stdin of a live unified exec session.
+    WriteUnifiedExecStdin { session_id: i32, input: String },
+
+    /// Kill a live unified exec session.
+    TerminateUnifiedExecSession { session_id: i32 },
+
     /// Request the agent to summarize the current conversation context.
     /// The agent will use its existing context (either conversation history or previous response id)
     /// to generate a summary which will be returned as an AgentMessage event.
@@ -533,6 +553,10 @@ pub enum EventMsg {
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// Notification that the turn switched to a fallback model after the
+    /// previous one failed; later output in the turn comes from `to_model`.
+    ModelFailover(ModelFailoverEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -551,6 +575,9 @@ pub enum EventMsg {
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// Live unified exec sessions, in reply to `Op::ListUnifiedExecSessions`.
+    UnifiedExecSessions(UnifiedExecSessionsEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -808,6 +835,18 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Estimated spend, present once usage of a priced model was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<UsageCost>,
+}
+
+/// Estimated spend derived from token usage and the model pricing table.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UsageCost {
+    /// Estimated cost of this session so far, in USD.
+    pub session_usd: f64,
+    /// Estimated cost of all sessions today, in USD.
+    pub daily_usd: f64,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
@@ -1340,6 +1379,15 @@ pub struct StreamErrorEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ModelFailoverEvent {
+    pub from_provider: String,
+    pub from_model: String,
+    pub to_provider: String,
+    pub to_model: String,
+    pub reason: FailoverTrigger,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -1421,6 +1469,24 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ListUnifiedExecSessions`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UnifiedExecSessionsEvent {
+    pub sessions: Vec<UnifiedExecSessionInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UnifiedExecSessionInfo {
+    /// Id the model uses to address the session with `write_stdin`.
+    pub session_id: i32,
+    pub command: String,
+    pub cwd: PathBuf,
+    /// Seconds since the session was started.
+    pub uptime_secs: u64,
+    /// The most recent output, including output the model has already read.
+    pub output_tail: String,
+}
+
 #[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct SessionConfiguredEvent {
     /// Name left as session_id instead of conversation_id for backwards compatibility.
@@ -1486,6 +1552,38 @@ pub enum FileChange {
     },
 }
 
+impl FileChange {
+    /// The pieces of this change that can be approved independently: each
+    /// `@@` hunk of an update, or the whole change for adds, deletes, and
+    /// updates without content hunks (pure renames and mode changes).
+    pub fn hunks(&self) -> Vec<&str> {
+        match self {
+            FileChange::Add { content } | FileChange::Delete { content } => vec![content],
+            FileChange::Update { unified_diff, .. } => {
+                let mut starts: Vec<usize> = Vec::new();
+                let mut offset = 0;
+                for line in unified_diff.split_inclusive('\n') {
+                    if line.starts_with("@@") {
+                        starts.push(offset);
+                    }
+                    offset += line.len();
+                }
+                if starts.is_empty() {
+                    return vec![unified_diff];
+                }
+                starts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, start)| {
+                        let end = starts.get(i + 1).copied().unwrap_or(unified_diff.len());
+                        &unified_diff[*start..end]
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct Chunk {
     /// 1-based line index of the first line in the original file
@@ -1505,6 +1603,8 @@ pub enum TurnAbortReason {
     Interrupted,
     Replaced,
     ReviewEnded,
+    /// A hard token or cost budget was reached.
+    BudgetExceeded,
 }
 
 #[cfg(test)]
@@ -1598,4 +1698,27 @@ mod tests {
         assert_eq!(deserialized, event);
         Ok(())
     }
+
+    #[test]
+    fn file_change_hunks_split_updates_on_hunk_headers() {
+        let update = FileChange::Update {
+            unified_diff: "@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -9,1 +9,2 @@\n z\n+y\n".to_string(),
+            move_path: None,
+        };
+        assert_eq!(
+            update.hunks(),
+            vec!["@@ -1,2 +1,2 @@\n a\n-b\n+B\n", "@@ -9,1 +9,2 @@\n z\n+y\n"]
+        );
+
+        let mode_only = FileChange::Update {
+            unified_diff: "new mode 100755\n".to_string(),
+            move_path: None,
+        };
+        assert_eq!(mode_only.hunks(), vec!["new mode 100755\n"]);
+
+        let add = FileChange::Add {
+            content: "hello\n".to_string(),
+        };
+        assert_eq!(add.hunks(), vec!["hello\n"]);
+    }
 }
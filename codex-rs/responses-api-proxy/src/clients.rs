@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+const TOKEN_PREFIX: &str = "rap-";
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// A client allowed to use the proxy, as stored in the `--clients` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ClientConfig {
+    pub name: String,
+    /// Hex-encoded SHA-256 of the client's proxy token. The token itself is
+    /// only shown once, when it is issued.
+    pub token_sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_day: Option<u64>,
+    /// Admins can read the usage of every client from `/usage`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub admin: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ClientsFile {
+    #[serde(default)]
+    clients: Vec<ClientConfig>,
+}
+
+/// Limits for a client created with `--issue-token`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NewClient {
+    pub name: String,
+    pub requests_per_minute: Option<u32>,
+    pub tokens_per_day: Option<u64>,
+    pub admin: bool,
+}
+
+/// Why a request was turned away before reaching upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Rejection {
+    Unauthorized,
+    RateLimited { retry_after: Duration },
+    TokenQuotaExhausted { tokens_per_day: u64 },
+}
+
+/// Identifies callers by their proxy token and enforces per-client request
+/// rates. Without a clients file every caller is the anonymous client, which
+/// keeps the single-user behaviour of the proxy.
+pub(crate) struct ClientRegistry {
+    clients: Vec<ClientConfig>,
+    anonymous: Option<ClientConfig>,
+    recent_requests: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl ClientRegistry {
+    /// Registry that admits every caller without a token.
+    pub(crate) fn open() -> Self {
+        Self {
+            clients: Vec::new(),
+            anonymous: Some(ClientConfig {
+                name: "default".to_string(),
+                token_sha256: String::new(),
+                requests_per_minute: None,
+                tokens_per_day: None,
+                admin: true,
+            }),
+            recent_requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let clients = read_clients_file(path)?.clients;
+        if clients.is_empty() {
+            return Err(anyhow!(
+                "{} does not list any clients; add one with --issue-token",
+                path.display()
+            ));
+        }
+        Ok(Self {
+            clients,
+            anonymous: None,
+            recent_requests: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Resolve the client from the value of an incoming `Authorization`
+    /// header.
+    pub(crate) fn authenticate(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<&ClientConfig, Rejection> {
+        if let Some(anonymous) = self.anonymous.as_ref() {
+            return Ok(anonymous);
+        }
+        let token = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or(Rejection::Unauthorized)?;
+        let digest = token_digest(token);
+        self.clients
+            .iter()
+            .find(|client| client.token_sha256 == digest)
+            .ok_or(Rejection::Unauthorized)
+    }
+
+    /// Admit one request for `client` at `now`, counting it against the
+    /// client's per-minute rate. `tokens_today` is the client's usage so far
+    /// today, as recorded by the usage ledger.
+    pub(crate) fn admit(
+        &self,
+        client: &ClientConfig,
+        tokens_today: u64,
+        now: Instant,
+    ) -> Result<(), Rejection> {
+        if let Some(tokens_per_day) = client.tokens_per_day
+            && tokens_today >= tokens_per_day
+        {
+            return Err(Rejection::TokenQuotaExhausted { tokens_per_day });
+        }
+
+        let Some(limit) = client.requests_per_minute else {
+            return Ok(());
+        };
+        let mut recent_requests = self
+            .recent_requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let window = recent_requests.entry(client.name.clone()).or_default();
+        while window
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= RATE_WINDOW)
+        {
+            window.pop_front();
+        }
+        if window.len() >= limit as usize {
+            let retry_after = window
+                .front()
+                .map(|oldest| RATE_WINDOW.saturating_sub(now.duration_since(*oldest)))
+                .unwrap_or(RATE_WINDOW);
+            return Err(Rejection::RateLimited { retry_after });
+        }
+        window.push_back(now);
+        Ok(())
+    }
+
+    pub(crate) fn clients(&self) -> impl Iterator<Item = &ClientConfig> {
+        self.anonymous.iter().chain(self.clients.iter())
+    }
+}
+
+/// Add a client to the clients file at `path` (creating it if needed) and
+/// return its newly generated token.
+pub(crate) fn issue_token(path: &Path, new_client: NewClient) -> Result<String> {
+    let mut file = if path.exists() {
+        read_clients_file(path)?
+    } else {
+        ClientsFile::default()
+    };
+    if file
+        .clients
+        .iter()
+        .any(|client| client.name == new_client.name)
+    {
+        return Err(anyhow!(
+            "client {} already exists in {}",
+            new_client.name,
+            path.display()
+        ));
+    }
+
+    let token = format!("{TOKEN_PREFIX}{}", hex(&rand::random::<[u8; 24]>()));
+    file.clients.push(ClientConfig {
+        name: new_client.name,
+        token_sha256: token_digest(&token),
+        requests_per_minute: new_client.requests_per_minute,
+        tokens_per_day: new_client.tokens_per_day,
+        admin: new_client.admin,
+    });
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let mut data = serde_json::to_string_pretty(&file)?;
+    data.push('\n');
+    fs::write(path, data).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(token)
+}
+
+fn read_clients_file(path: &Path) -> Result<ClientsFile> {
+    let data =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&data).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn token_digest(token: &str) -> String {
+    hex(&Sha256::digest(token.as_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn registry_with(client: NewClient) -> (tempfile::TempDir, ClientRegistry, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clients.json");
+        let token = issue_token(&path, client).unwrap();
+        let registry = ClientRegistry::load(&path).unwrap();
+        (dir, registry, token)
+    }
+
+    #[test]
+    fn issued_tokens_authenticate_their_client() {
+        let (dir, registry, token) = registry_with(NewClient {
+            name: "alice".to_string(),
+            ..NewClient::default()
+        });
+
+        let header = format!("Bearer {token}");
+        let client = registry.authenticate(Some(&header)).unwrap();
+        assert_eq!(client.name, "alice");
+        assert_eq!(
+            registry.authenticate(Some("Bearer rap-nope")),
+            Err(Rejection::Unauthorized)
+        );
+        assert_eq!(registry.authenticate(None), Err(Rejection::Unauthorized));
+
+        let stored = fs::read_to_string(dir.path().join("clients.json")).unwrap();
+        assert!(!stored.contains(&token));
+        assert!(
+            issue_token(
+                &dir.path().join("clients.json"),
+                NewClient {
+                    name: "alice".to_string(),
+                    ..NewClient::default()
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn enforces_request_rate_and_daily_tokens() {
+        let (_dir, registry, token) = registry_with(NewClient {
+            name: "bob".to_string(),
+            requests_per_minute: Some(2),
+            tokens_per_day: Some(1_000),
+            admin: false,
+        });
+        let header = format!("Bearer {token}");
+        let client = registry.authenticate(Some(&header)).unwrap().clone();
+        let start = Instant::now();
+
+        assert_eq!(registry.admit(&client, 0, start), Ok(()));
+        assert_eq!(
+            registry.admit(&client, 0, start + Duration::from_secs(10)),
+            Ok(())
+        );
+        assert_eq!(
+            registry.admit(&client, 0, start + Duration::from_secs(20)),
+            Err(Rejection::RateLimited {
+                retry_after: Duration::from_secs(40)
+            })
+        );
+        assert_eq!(
+            registry.admit(&client, 0, start + Duration::from_secs(60)),
+            Ok(())
+        );
+        assert_eq!(
+            registry.admit(&client, 1_000, start + Duration::from_secs(120)),
+            Err(Rejection::TokenQuotaExhausted {
+                tokens_per_day: 1_000
+            })
+        );
+    }
+}
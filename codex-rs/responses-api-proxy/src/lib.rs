@@ -7,6 +7,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -14,11 +15,13 @@ use anyhow::anyhow;
 use clap::Parser;
 use reqwest::blocking::Client;
 use reqwest::header::AUTHORIZATION;
+use reqwest::header::CONTENT_TYPE;
 use reqwest::header::HOST;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use serde::Serialize;
+use serde_json::json;
 use tiny_http::Header;
 use tiny_http::Method;
 use tiny_http::Request;
@@ -26,8 +29,17 @@ use tiny_http::Response;
 use tiny_http::Server;
 use tiny_http::StatusCode;
 
+mod clients;
 mod read_api_key;
+mod usage;
+use clients::ClientConfig;
+use clients::ClientRegistry;
+use clients::NewClient;
+use clients::Rejection;
+use clients::issue_token;
 use read_api_key::read_auth_header_from_stdin;
+use usage::UsageLedger;
+use usage::UsageTap;
 
 /// CLI arguments for the proxy.
 #[derive(Debug, Clone, Parser)]
@@ -44,6 +56,32 @@ pub struct Args {
     /// Enable HTTP shutdown endpoint at GET /shutdown
     #[arg(long)]
     pub http_shutdown: bool,
+
+    /// JSON file of clients allowed to use the proxy. When set, every request
+    /// must carry one of their proxy tokens as `Authorization: Bearer <token>`.
+    #[arg(long, value_name = "FILE")]
+    pub clients: Option<PathBuf>,
+
+    /// Append the usage of every completed response to this JSONL file.
+    #[arg(long, value_name = "FILE")]
+    pub usage_log: Option<PathBuf>,
+
+    /// Add a client named NAME to the `--clients` file, print its new proxy
+    /// token, and exit.
+    #[arg(long, value_name = "NAME", requires = "clients")]
+    pub issue_token: Option<String>,
+
+    /// Requests per minute allowed for the client created by `--issue-token`.
+    #[arg(long, value_name = "N", requires = "issue_token")]
+    pub requests_per_minute: Option<u32>,
+
+    /// Tokens per UTC day allowed for the client created by `--issue-token`.
+    #[arg(long, value_name = "N", requires = "issue_token")]
+    pub tokens_per_day: Option<u64>,
+
+    /// Let the client created by `--issue-token` read every client's usage.
+    #[arg(long, requires = "issue_token")]
+    pub admin: bool,
 }
 
 #[derive(Serialize)]
@@ -54,7 +92,26 @@ struct ServerInfo {
 
 /// Entry point for the library main, for parity with other crates.
 pub fn run_main(args: Args) -> Result<()> {
+    if let (Some(name), Some(path)) = (args.issue_token.clone(), args.clients.as_deref()) {
+        let token = issue_token(
+            path,
+            NewClient {
+                name,
+                requests_per_minute: args.requests_per_minute,
+                tokens_per_day: args.tokens_per_day,
+                admin: args.admin,
+            },
+        )?;
+        println!("{token}");
+        return Ok(());
+    }
+
     let auth_header = read_auth_header_from_stdin()?;
+    let registry = Arc::new(match args.clients.as_deref() {
+        Some(path) => ClientRegistry::load(path)?,
+        None => ClientRegistry::open(),
+    });
+    let ledger = Arc::new(UsageLedger::open(args.usage_log.as_deref())?);
 
     let (listener, bound_addr) = bind_listener(args.port)?;
     if let Some(path) = args.server_info.as_ref() {
@@ -75,13 +132,30 @@ pub fn run_main(args: Args) -> Result<()> {
     let http_shutdown = args.http_shutdown;
     for request in server.incoming_requests() {
         let client = client.clone();
+        let registry = registry.clone();
+        let ledger = ledger.clone();
         std::thread::spawn(move || {
             if http_shutdown && request.method() == &Method::Get && request.url() == "/shutdown" {
                 let _ = request.respond(Response::new_empty(StatusCode(200)));
                 std::process::exit(0);
             }
 
-            if let Err(e) = forward_request(&client, auth_header, request) {
+            let caller = match registry.authenticate(incoming_authorization(&request).as_deref()) {
+                Ok(caller) => caller,
+                Err(rejection) => {
+                    respond_rejection(request, &rejection);
+                    return;
+                }
+            };
+
+            if request.method() == &Method::Get && request.url() == "/usage" {
+                respond_usage(request, caller, &registry, &ledger);
+                return;
+            }
+
+            if let Err(e) =
+                forward_request(&client, auth_header, request, caller, &registry, ledger)
+            {
                 eprintln!("forwarding error: {e}");
             }
         });
@@ -115,7 +189,82 @@ fn write_server_info(path: &Path, port: u16) -> Result<()> {
     Ok(())
 }
 
-fn forward_request(client: &Client, auth_header: &'static str, mut req: Request) -> Result<()> {
+fn incoming_authorization(req: &Request) -> Option<String> {
+    req.headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .map(|header| header.value.as_str().to_string())
+}
+
+fn json_response(status: u16, body: &serde_json::Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    let mut response = Response::from_data(body.to_string()).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+        response.add_header(header);
+    }
+    response
+}
+
+fn respond_rejection(req: Request, rejection: &Rejection) {
+    let response = match rejection {
+        Rejection::Unauthorized => json_response(
+            401,
+            &json!({"error": {"message": "missing or unknown proxy token", "type": "unauthorized"}}),
+        ),
+        Rejection::RateLimited { retry_after } => {
+            let mut response = json_response(
+                429,
+                &json!({"error": {"message": "client request rate exceeded", "type": "rate_limit_exceeded"}}),
+            );
+            let seconds = retry_after.as_secs().max(1).to_string();
+            if let Ok(header) = Header::from_bytes("Retry-After", seconds) {
+                response.add_header(header);
+            }
+            response
+        }
+        Rejection::TokenQuotaExhausted { tokens_per_day } => json_response(
+            429,
+            &json!({"error": {
+                "message": format!("client daily quota of {tokens_per_day} tokens exhausted"),
+                "type": "insufficient_quota",
+            }}),
+        ),
+    };
+    let _ = req.respond(response);
+}
+
+/// `GET /usage`: all-time and today's usage, for every client when the caller
+/// is an admin and for the caller otherwise.
+fn respond_usage(
+    req: Request,
+    caller: &ClientConfig,
+    registry: &ClientRegistry,
+    ledger: &UsageLedger,
+) {
+    let clients: Vec<serde_json::Value> = registry
+        .clients()
+        .filter(|client| caller.admin || client.name == caller.name)
+        .map(|client| {
+            let (total, today) = ledger.totals_for(&client.name);
+            json!({
+                "client": client.name,
+                "requests_per_minute": client.requests_per_minute,
+                "tokens_per_day": client.tokens_per_day,
+                "total": total,
+                "today": today,
+            })
+        })
+        .collect();
+    let _ = req.respond(json_response(200, &json!({ "clients": clients })));
+}
+
+fn forward_request(
+    client: &Client,
+    auth_header: &'static str,
+    mut req: Request,
+    caller: &ClientConfig,
+    registry: &ClientRegistry,
+    ledger: Arc<UsageLedger>,
+) -> Result<()> {
     // Only allow POST /v1/responses exactly, no query string.
     let method = req.method().clone();
     let url_path = req.url().to_string();
@@ -127,6 +276,13 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
         return Ok(());
     }
 
+    if let Err(rejection) =
+        registry.admit(caller, ledger.tokens_today(&caller.name), Instant::now())
+    {
+        respond_rejection(req, &rejection);
+        return Ok(());
+    }
+
     // Read request body
     let mut body = Vec::new();
     let mut reader = req.as_reader();
@@ -187,6 +343,11 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
         }
     }
 
+    let json_body = upstream_resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
     let content_length = upstream_resp.content_length().and_then(|len| {
         if len <= usize::MAX as u64 {
             Some(len as usize)
@@ -198,7 +359,7 @@ fn forward_request(client: &Client, auth_header: &'static str, mut req: Request)
     let response = Response::new(
         StatusCode(status.as_u16()),
         response_headers,
-        upstream_resp,
+        UsageTap::new(upstream_resp, ledger, caller.name.clone(), json_body),
         content_length,
         None,
     );
@@ -0,0 +1,422 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+/// Non-streaming responses are buffered up to this size to read their usage.
+const MAX_JSON_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+/// Token usage reported by a completed Responses API response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Usage {
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl Usage {
+    fn from_response_usage(usage: &JsonValue) -> Option<Self> {
+        if !usage.is_object() {
+            return None;
+        }
+        let count = |value: Option<&JsonValue>| value.and_then(JsonValue::as_u64).unwrap_or(0);
+        let input_tokens = count(usage.get("input_tokens"));
+        let output_tokens = count(usage.get("output_tokens"));
+        Some(Self {
+            input_tokens,
+            cached_input_tokens: count(
+                usage
+                    .get("input_tokens_details")
+                    .and_then(|details| details.get("cached_tokens")),
+            ),
+            output_tokens,
+            reasoning_output_tokens: count(
+                usage
+                    .get("output_tokens_details")
+                    .and_then(|details| details.get("reasoning_tokens")),
+            ),
+            total_tokens: usage
+                .get("total_tokens")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or(input_tokens + output_tokens),
+        })
+    }
+
+    fn add(&mut self, other: &Usage) {
+        self.input_tokens += other.input_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reasoning_output_tokens += other.reasoning_output_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+}
+
+/// One line of the usage ledger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct UsageRecord {
+    pub timestamp: DateTime<Utc>,
+    pub client: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_id: Option<String>,
+    #[serde(flatten)]
+    pub usage: Usage,
+}
+
+/// Usage aggregated over a set of responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct UsageTotals {
+    pub responses: u64,
+    #[serde(flatten)]
+    pub usage: Usage,
+}
+
+impl UsageTotals {
+    fn add(&mut self, usage: &Usage) {
+        self.responses += 1;
+        self.usage.add(usage);
+    }
+}
+
+#[derive(Debug)]
+struct Totals {
+    all_time: BTreeMap<String, UsageTotals>,
+    day: NaiveDate,
+    today: BTreeMap<String, UsageTotals>,
+}
+
+impl Totals {
+    fn add(&mut self, record: &UsageRecord) {
+        self.all_time
+            .entry(record.client.clone())
+            .or_default()
+            .add(&record.usage);
+        let day = record.timestamp.date_naive();
+        if day > self.day {
+            self.day = day;
+            self.today.clear();
+        }
+        if day == self.day {
+            self.today
+                .entry(record.client.clone())
+                .or_default()
+                .add(&record.usage);
+        }
+    }
+
+    fn roll_over(&mut self, now: DateTime<Utc>) {
+        let day = now.date_naive();
+        if day > self.day {
+            self.day = day;
+            self.today.clear();
+        }
+    }
+}
+
+/// Usage per client, optionally persisted as JSONL so totals and daily quotas
+/// survive restarts.
+pub(crate) struct UsageLedger {
+    file: Option<Mutex<File>>,
+    totals: Mutex<Totals>,
+}
+
+impl UsageLedger {
+    /// Open the ledger at `path`, replaying its existing records, or keep
+    /// usage in memory only when no path is given.
+    pub(crate) fn open(path: Option<&Path>) -> Result<Self> {
+        let mut totals = Totals {
+            all_time: BTreeMap::new(),
+            day: Utc::now().date_naive(),
+            today: BTreeMap::new(),
+        };
+        let Some(path) = path else {
+            return Ok(Self {
+                file: None,
+                totals: Mutex::new(totals),
+            });
+        };
+
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        if path.exists() {
+            let reader = BufReader::new(
+                File::open(path).with_context(|| format!("failed to read {}", path.display()))?,
+            );
+            for line in reader.lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<UsageRecord>(&line) {
+                    Ok(record) => totals.add(&record),
+                    Err(err) => eprintln!("skipping malformed usage record: {err}"),
+                }
+            }
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        Ok(Self {
+            file: Some(Mutex::new(file)),
+            totals: Mutex::new(totals),
+        })
+    }
+
+    pub(crate) fn record(&self, record: &UsageRecord) {
+        if let Some(file) = self.file.as_ref() {
+            let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+            let written = serde_json::to_string(record)
+                .map_err(std::io::Error::from)
+                .and_then(|line| writeln!(file, "{line}"));
+            if let Err(err) = written {
+                eprintln!("failed to write usage record: {err}");
+            }
+        }
+        self.totals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .add(record);
+    }
+
+    /// Total tokens used by `client` since midnight UTC.
+    pub(crate) fn tokens_today(&self, client: &str) -> u64 {
+        self.totals_for(client).1.usage.total_tokens
+    }
+
+    /// All-time and today's totals for `client`.
+    pub(crate) fn totals_for(&self, client: &str) -> (UsageTotals, UsageTotals) {
+        let mut totals = self.totals.lock().unwrap_or_else(PoisonError::into_inner);
+        totals.roll_over(Utc::now());
+        (
+            totals.all_time.get(client).copied().unwrap_or_default(),
+            totals.today.get(client).copied().unwrap_or_default(),
+        )
+    }
+}
+
+/// Passes an upstream response body through unchanged while looking for the
+/// final response object, whose `usage` is recorded in the ledger once the
+/// body has been read or dropped.
+pub(crate) struct UsageTap<R> {
+    inner: R,
+    ledger: Arc<UsageLedger>,
+    client: String,
+    /// Non-streaming responses are parsed whole; streams line by line.
+    json_body: bool,
+    pending: Vec<u8>,
+    completed: Option<JsonValue>,
+    recorded: bool,
+}
+
+impl<R> UsageTap<R> {
+    pub(crate) fn new(inner: R, ledger: Arc<UsageLedger>, client: String, json_body: bool) -> Self {
+        Self {
+            inner,
+            ledger,
+            client,
+            json_body,
+            pending: Vec::new(),
+            completed: None,
+            recorded: false,
+        }
+    }
+
+    fn scan(&mut self, chunk: &[u8]) {
+        if self.json_body {
+            if self.pending.len() + chunk.len() <= MAX_JSON_BODY_BYTES {
+                self.pending.extend_from_slice(chunk);
+            }
+            return;
+        }
+
+        for byte in chunk {
+            if *byte == b'\n' {
+                let line = std::mem::take(&mut self.pending);
+                self.scan_sse_line(&line);
+            } else {
+                self.pending.push(*byte);
+            }
+        }
+    }
+
+    fn scan_sse_line(&mut self, line: &[u8]) {
+        let Some(data) = line.strip_prefix(b"data:") else {
+            return;
+        };
+        let Ok(event) = serde_json::from_slice::<JsonValue>(data.trim_ascii()) else {
+            return;
+        };
+        let is_final = matches!(
+            event.get("type").and_then(JsonValue::as_str),
+            Some("response.completed" | "response.incomplete" | "response.failed")
+        );
+        if is_final && let Some(response) = event.get("response") {
+            self.completed = Some(response.clone());
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+
+        let response = if self.json_body {
+            serde_json::from_slice::<JsonValue>(&self.pending).ok()
+        } else {
+            self.completed.take()
+        };
+        let Some(response) = response else {
+            return;
+        };
+        let Some(usage) = response.get("usage").and_then(Usage::from_response_usage) else {
+            return;
+        };
+        let text = |key: &str| {
+            response
+                .get(key)
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+        };
+        self.ledger.record(&UsageRecord {
+            timestamp: Utc::now(),
+            client: self.client.clone(),
+            model: text("model"),
+            response_id: text("id"),
+            usage,
+        });
+    }
+}
+
+impl<R: Read> Read for UsageTap<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read == 0 {
+            self.finish();
+        } else {
+            self.scan(&buf[..read]);
+        }
+        Ok(read)
+    }
+}
+
+impl<R> Drop for UsageTap<R> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    const STREAM: &str = concat!(
+        "event: response.created\n",
+        "data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_1\"}}\n\n",
+        "event: response.completed\n",
+        "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_1\",\"model\":\"gpt-5\",",
+        "\"usage\":{\"input_tokens\":120,\"input_tokens_details\":{\"cached_tokens\":100},",
+        "\"output_tokens\":30,\"output_tokens_details\":{\"reasoning_tokens\":10},\"total_tokens\":150}}}\n\n",
+    );
+
+    fn read_through(tap: UsageTap<Cursor<Vec<u8>>>) -> Vec<u8> {
+        let mut tap = tap;
+        let mut out = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let read = tap.read(&mut buf).unwrap();
+            if read == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..read]);
+        }
+        out
+    }
+
+    #[test]
+    fn records_usage_from_completed_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.jsonl");
+        let ledger = Arc::new(UsageLedger::open(Some(&path)).unwrap());
+
+        let body = read_through(UsageTap::new(
+            Cursor::new(STREAM.as_bytes().to_vec()),
+            Arc::clone(&ledger),
+            "alice".to_string(),
+            false,
+        ));
+        assert_eq!(body, STREAM.as_bytes());
+
+        let expected = Usage {
+            input_tokens: 120,
+            cached_input_tokens: 100,
+            output_tokens: 30,
+            reasoning_output_tokens: 10,
+            total_tokens: 150,
+        };
+        let (all_time, today) = ledger.totals_for("alice");
+        assert_eq!(
+            all_time,
+            UsageTotals {
+                responses: 1,
+                usage: expected
+            }
+        );
+        assert_eq!(today, all_time);
+        assert_eq!(ledger.tokens_today("bob"), 0);
+
+        let line = fs::read_to_string(&path).unwrap();
+        let record: UsageRecord = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(record.client, "alice");
+        assert_eq!(record.model.as_deref(), Some("gpt-5"));
+        assert_eq!(record.response_id.as_deref(), Some("resp_1"));
+
+        let reopened = UsageLedger::open(Some(&path)).unwrap();
+        assert_eq!(reopened.tokens_today("alice"), 150);
+    }
+
+    #[test]
+    fn records_usage_from_json_body_on_drop() {
+        let ledger = Arc::new(UsageLedger::open(None).unwrap());
+        let body = br#"{"id":"resp_2","usage":{"input_tokens":5,"output_tokens":7}}"#;
+        let mut tap = UsageTap::new(
+            Cursor::new(body.to_vec()),
+            Arc::clone(&ledger),
+            "default".to_string(),
+            true,
+        );
+        let mut buf = [0u8; 1024];
+        let read = tap.read(&mut buf).unwrap();
+        assert_eq!(read, body.len());
+        drop(tap);
+
+        assert_eq!(ledger.tokens_today("default"), 12);
+    }
+}
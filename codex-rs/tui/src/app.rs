@@ -6,6 +6,10 @@ use crate::app_event::AppEvent;
 use crate::app_event::ByokDraftField;
 use crate::app_event::CustomProviderForm;
 use crate::app_event_sender::AppEventSender;
+use crate::app_tabs::ParkedTab;
+use crate::app_tabs::TabBadge;
+use crate::app_tabs::TabId;
+use crate::app_tabs::Tabs;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
@@ -14,9 +18,13 @@ use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::custom_prompt_view::PromptCancelled;
 use crate::bottom_pane::custom_prompt_view::PromptSubmitted;
 use crate::chatwidget::ChatWidget;
+use crate::chatwidget::ExternalEditTarget;
 use crate::chatwidget::refresh_model_metadata;
+use crate::diff_browser::DiffBrowserOverlay;
+use crate::diff_browser::DiffSource;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::external_editor::edit_text;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::MemorySuggestionEntry;
@@ -27,6 +35,8 @@ use crate::index_delta::spawn_delta_monitor;
 use crate::index_status::IndexStatusSnapshot;
 use crate::index_status::format_age;
 use crate::index_worker::IndexWorker;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::memory_manager::run_memory_manager;
 use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
@@ -43,6 +53,7 @@ use codex_agentic_core::AgentRunStatus;
 use codex_agentic_core::AgentStore;
 use codex_agentic_core::CustomProvider;
 use codex_agentic_core::DEFAULT_SEARCH_CONFIDENCE_MIN;
+use codex_agentic_core::ProviderModels;
 use codex_agentic_core::fetch_custom_provider_models;
 use codex_agentic_core::index::builder::BuildOptions;
 use codex_agentic_core::index::events::IndexEvent as CoreIndexEvent;
@@ -52,6 +63,8 @@ use codex_agentic_core::persist_default_model_selection;
 use codex_agentic_core::provider::DEFAULT_OLLAMA_ENDPOINT;
 use codex_agentic_core::provider::DEFAULT_OPENAI_PROVIDER_ID;
 use codex_agentic_core::provider::OSS_PROVIDER_ID;
+use codex_agentic_core::provider::apply_model_limits;
+use codex_agentic_core::provider::apply_provider_fallbacks;
 use codex_agentic_core::provider::custom_providers;
 use codex_agentic_core::provider::sanitize_reasoning_overrides;
 use codex_agentic_core::provider::sanitize_tool_overrides;
@@ -66,15 +79,20 @@ use codex_core::WireApi;
 use codex_core::config::Config;
 use codex_core::config::OPENAI_DEFAULT_MODEL;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::types::Budget;
 use codex_core::config_types::ProviderKind;
+use codex_core::git_info::get_git_repo_root;
 use codex_core::memory::MemoryPreviewModeExt;
 use codex_core::memory::MemoryRetriever;
 use codex_core::memory::MemoryRuntime;
 use codex_core::model_family::find_family_for_model;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
+use codex_ollama::OllamaClient;
+use codex_ollama::TuiProgressReporter;
 use codex_protocol::ConversationId;
 use color_eyre::eyre::Result;
 use color_eyre::eyre::WrapErr;
@@ -353,6 +371,8 @@ struct AgentDraft {
     enabled_tools: String,
     approval_mode: Option<String>,
     sandbox_mode: Option<String>,
+    /// Not editable in the TUI; carried over so saving keeps it.
+    budget: Option<Budget>,
 }
 
 impl AgentDraft {
@@ -366,6 +386,7 @@ impl AgentDraft {
             enabled_tools: String::new(),
             approval_mode: None,
             sandbox_mode: None,
+            budget: None,
         }
     }
 
@@ -387,6 +408,7 @@ impl AgentDraft {
             },
             approval_mode: profile.approval_mode.clone(),
             sandbox_mode: profile.sandbox_mode.clone(),
+            budget: profile.budget.clone(),
         }
     }
 
@@ -476,6 +498,7 @@ impl AgentDraft {
         }
         profile.approval_mode = self.approval_mode.filter(|value| !value.trim().is_empty());
         profile.sandbox_mode = self.sandbox_mode.filter(|value| !value.trim().is_empty());
+        profile.budget = self.budget;
         Ok(profile)
     }
 }
@@ -643,6 +666,10 @@ fn normalize_custom_provider_base_url(
             wire_api = WireApi::Responses;
             base.truncate(base.len() - "/responses".len());
             base = base.trim_end_matches('/').to_string();
+        } else if lower.ends_with("/messages") {
+            wire_api = WireApi::Messages;
+            base.truncate(base.len() - "/messages".len());
+            base = base.trim_end_matches('/').to_string();
         }
 
         Some(base)
@@ -686,9 +713,15 @@ pub(crate) struct App {
     last_index_attempt: Option<DateTime<Utc>>,
     index_completion_toast_until: Option<Instant>,
     index_completion_message: Option<String>,
+    /// Model currently being pulled from the model picker, if any.
+    ollama_pull: Option<String>,
 
     pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
 
+    /// Conversations open in other tabs. `chat_widget` and
+    /// `transcript_cells` above belong to the active tab.
+    pub(crate) tabs: Tabs,
+
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
     pub(crate) deferred_history_lines: Vec<Line<'static>>,
@@ -728,6 +761,7 @@ impl App {
         initial_images: Vec<PathBuf>,
         resume_selection: ResumeSelection,
         feedback: codex_feedback::CodexFeedback,
+        theme_problems: Vec<String>,
     ) -> Result<AppExitInfo> {
         use tokio_stream::StreamExt;
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
@@ -739,13 +773,20 @@ impl App {
         ));
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
+        let keymap_problems = crate::keymap::install(&config.tui_keymap);
+        let tabs = Tabs::new();
+        let tab_event_tx = app_event_tx.for_tab(tabs.active_id());
 
+        let jump_to_turn = match &resume_selection {
+            ResumeSelection::ResumeAtTurn { turn, .. } => Some(*turn),
+            _ => None,
+        };
         let mut chat_widget = match resume_selection {
             ResumeSelection::StartFresh | ResumeSelection::Exit => {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: tab_event_tx.clone(),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -754,7 +795,7 @@ impl App {
                 };
                 ChatWidget::new(init, conversation_manager.clone())
             }
-            ResumeSelection::Resume(path) => {
+            ResumeSelection::Resume(path) | ResumeSelection::ResumeAtTurn { path, .. } => {
                 let resumed = conversation_manager
                     .resume_conversation_from_rollout(
                         config.clone(),
@@ -768,7 +809,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: tab_event_tx.clone(),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -781,6 +822,49 @@ impl App {
                     resumed.session_configured,
                 )
             }
+            ResumeSelection::Fork {
+                path,
+                turn,
+                restore_snapshot,
+            } => {
+                let forked = conversation_manager
+                    .fork_conversation_at_turn(turn, config.clone(), path.clone(), restore_snapshot)
+                    .await
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to fork session from {} at turn {turn}",
+                            path.display()
+                        )
+                    })?;
+                let init = crate::chatwidget::ChatWidgetInit {
+                    config: config.clone(),
+                    frame_requester: tui.frame_requester(),
+                    app_event_tx: tab_event_tx.clone(),
+                    initial_prompt: initial_prompt.clone(),
+                    initial_images: initial_images.clone(),
+                    enhanced_keys_supported,
+                    auth_manager: auth_manager.clone(),
+                    feedback: feedback.clone(),
+                };
+                let mut widget = ChatWidget::new_from_existing(
+                    init,
+                    forked.new_conversation.conversation,
+                    forked.new_conversation.session_configured,
+                );
+                let message = format!("Forked session at turn {turn}.");
+                let hint = match forked.restored_snapshot {
+                    Some(commit_id) => {
+                        let short_id: String = commit_id.chars().take(7).collect();
+                        Some(format!("Workspace restored to snapshot {short_id}."))
+                    }
+                    None if restore_snapshot => {
+                        Some("No snapshot was recorded for that turn.".to_string())
+                    }
+                    None => None,
+                };
+                widget.add_info_message(message, hint);
+                widget
+            }
         };
 
         let cwd = config.cwd.clone();
@@ -853,13 +937,18 @@ impl App {
             last_index_attempt,
             index_completion_toast_until: None,
             index_completion_message: None,
+            ollama_pull: None,
             enhanced_keys_supported,
             transcript_cells: Vec::new(),
+            tabs,
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
-            backtrack: BacktrackState::default(),
+            backtrack: BacktrackState {
+                pending_jump: jump_to_turn,
+                ..BacktrackState::default()
+            },
             feedback,
             pending_update_action: None,
             skip_world_writable_scan_once: false,
@@ -906,6 +995,9 @@ impl App {
 
         app.refresh_index_status_line();
         app.refresh_agent_status_line();
+        for problem in theme_problems.into_iter().chain(keymap_problems) {
+            app.chat_widget.add_warning_message(problem);
+        }
 
         spawn_status_refresh(app.app_event_tx.clone());
         spawn_toast_tick(app.app_event_tx.clone());
@@ -1034,6 +1126,177 @@ impl App {
         self.config.cwd.join(".codex/index/manifest.json").exists()
     }
 
+    /// Sender for widgets that belong to the tab on screen.
+    pub(crate) fn active_tab_event_tx(&self) -> AppEventSender {
+        self.app_event_tx.for_tab(self.tabs.active_id())
+    }
+
+    fn refresh_tab_status_line(&mut self) {
+        self.chat_widget
+            .set_tab_status_line(self.tabs.status_line());
+    }
+
+    /// Start a new conversation in a tab after the active one and switch to it.
+    fn open_new_tab(&mut self, tui: &mut tui::Tui) {
+        let previous_id = self.tabs.active_id();
+        let id = self.tabs.open();
+        let init = crate::chatwidget::ChatWidgetInit {
+            config: self.config.clone(),
+            frame_requester: tui.frame_requester(),
+            app_event_tx: self.app_event_tx.for_tab(id),
+            initial_prompt: None,
+            initial_images: Vec::new(),
+            enhanced_keys_supported: self.enhanced_keys_supported,
+            auth_manager: self.auth_manager.clone(),
+            feedback: self.feedback.clone(),
+        };
+        let mut chat_widget = ChatWidget::new(init, self.server.clone());
+        chat_widget.sync_custom_providers(custom_providers(&self.settings));
+        let previous = self.swap_active_tab(ParkedTab {
+            chat_widget,
+            transcript_cells: Vec::new(),
+            deferred_events: Vec::new(),
+        });
+        self.tabs.park(previous_id, previous);
+        self.on_tab_switched(tui);
+    }
+
+    /// Switch `offset` tabs forward (backward when negative), wrapping around.
+    fn switch_tab(&mut self, tui: &mut tui::Tui, offset: isize) {
+        let previous_id = self.tabs.active_id();
+        let Some(next) = self.tabs.activate(offset) else {
+            return;
+        };
+        let previous = self.swap_active_tab(next);
+        self.tabs.park(previous_id, previous);
+        self.on_tab_switched(tui);
+    }
+
+    /// Close the active tab, shutting down its conversation, and switch to a
+    /// neighbouring tab. The last remaining tab cannot be closed.
+    async fn close_active_tab(&mut self, tui: &mut tui::Tui) {
+        let closed_id = self.tabs.active_id();
+        let Some(next) = self.tabs.close() else {
+            return;
+        };
+        let closed = self.swap_active_tab(next);
+        closed.chat_widget.submit_op(Op::Shutdown);
+        // Drop the manager's handle too so the conversation can be freed.
+        if let Some(conversation_id) = closed.chat_widget.conversation_id() {
+            self.server.remove_conversation(&conversation_id).await;
+        }
+        // The closed tab may have been the last one streaming.
+        self.stop_commit_animation(closed_id);
+        self.on_tab_switched(tui);
+    }
+
+    /// Put `next` on screen and return the state it replaced. Events the tab
+    /// deferred while in the background are queued again so they now reach
+    /// the app as coming from the active tab.
+    fn swap_active_tab(&mut self, next: ParkedTab) -> ParkedTab {
+        self.reset_backtrack_state();
+        let tab = self.tabs.active_id();
+        for event in next.deferred_events {
+            // Skip `AppEventSender::send`; these were logged when first sent.
+            let _ = self.app_event_tx.app_event_tx.send(AppEvent::Tab {
+                tab,
+                event: Box::new(event),
+            });
+        }
+        ParkedTab {
+            chat_widget: std::mem::replace(&mut self.chat_widget, next.chat_widget),
+            transcript_cells: std::mem::replace(&mut self.transcript_cells, next.transcript_cells),
+            deferred_events: Vec::new(),
+        }
+    }
+
+    fn on_tab_switched(&mut self, tui: &mut tui::Tui) {
+        // Scrollback only ever holds the active tab's transcript; re-inserting
+        // it on top of the previous tab's would grow it on every switch.
+        if let Err(err) = tui.clear_history() {
+            tracing::warn!("failed to clear terminal history on tab switch: {err}");
+        }
+        tui.insert_history_lines(vec![
+            Line::from(format!(
+                "── tab {} of {} ──",
+                self.tabs.active_position(),
+                self.tabs.len()
+            ))
+            .dim(),
+        ]);
+        self.render_transcript_once(tui);
+        self.has_emitted_history_lines = true;
+        self.chat_widget
+            .sync_custom_providers(custom_providers(&self.settings));
+        self.refresh_index_status_line();
+        self.refresh_agent_status_line();
+        self.chat_widget.set_model_pull_status_line(
+            self.ollama_pull
+                .as_ref()
+                .map(|model| format!("Pulling {model}…")),
+        );
+        self.refresh_tab_status_line();
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// Route an event from a tab that is not on screen to its parked state.
+    fn handle_background_tab_event(&mut self, tab: TabId, event: AppEvent) {
+        match event {
+            AppEvent::StartCommitAnimation => self.start_commit_animation(tab),
+            AppEvent::StopCommitAnimation => self.stop_commit_animation(tab),
+            AppEvent::CodexEvent(event) => {
+                let badge = TabBadge::for_event(&event.msg);
+                let turn_complete = matches!(&event.msg, EventMsg::TaskComplete(_));
+                let Some(parked) = self.tabs.parked_mut(tab) else {
+                    return;
+                };
+                parked.chat_widget.handle_codex_event(event);
+                if let Some(badge) = badge {
+                    self.tabs.set_badge(tab, badge);
+                    self.refresh_tab_status_line();
+                }
+                if turn_complete {
+                    self.maybe_refresh_index_post_turn();
+                }
+            }
+            event => {
+                let Some(parked) = self.tabs.parked_mut(tab) else {
+                    return;
+                };
+                match event {
+                    AppEvent::InsertHistoryCell(cell) => parked.transcript_cells.push(cell.into()),
+                    AppEvent::CodexOp(op) => parked.chat_widget.submit_op(op),
+                    other => parked.deferred_events.push(other),
+                }
+            }
+        }
+    }
+
+    /// Commit ticks run while any tab is streaming.
+    fn start_commit_animation(&mut self, tab: TabId) {
+        self.tabs.set_animating(tab, true);
+        if self
+            .commit_anim_running
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            let tx = self.app_event_tx.clone();
+            let running = self.commit_anim_running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(50));
+                    tx.send(AppEvent::CommitTick);
+                }
+            });
+        }
+    }
+
+    fn stop_commit_animation(&mut self, tab: TabId) {
+        if !self.tabs.set_animating(tab, false) {
+            self.commit_anim_running.store(false, Ordering::Release);
+        }
+    }
+
     #[cfg(target_os = "windows")]
     fn spawn_world_writable_scan(
         cwd: PathBuf,
@@ -1080,6 +1343,7 @@ impl App {
             refresh_model_metadata(&mut self.config);
             sanitize_reasoning_overrides(&mut self.config);
             sanitize_tool_overrides(&mut self.config);
+            apply_provider_fallbacks(&mut self.config);
             self.chat_widget
                 .set_model_provider(&self.config.model_provider_id, &self.config.model_provider);
             true
@@ -1293,12 +1557,22 @@ impl App {
     }
 
     async fn handle_event(&mut self, tui: &mut tui::Tui, event: AppEvent) -> Result<bool> {
+        let event = match event {
+            AppEvent::Tab { tab, event } if tab != self.tabs.active_id() => {
+                self.handle_background_tab_event(tab, *event);
+                return Ok(true);
+            }
+            AppEvent::Tab { event, .. } => *event,
+            event => event,
+        };
         match event {
+            // Unwrapped above; senders never stamp an event twice.
+            AppEvent::Tab { .. } => {}
             AppEvent::NewSession => {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: self.config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: self.app_event_tx.clone(),
+                    app_event_tx: self.active_tab_event_tx(),
                     initial_prompt: None,
                     initial_images: Vec::new(),
                     enhanced_keys_supported: self.enhanced_keys_supported,
@@ -1315,6 +1589,7 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
+                self.maybe_jump_to_resumed_turn(tui);
                 let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
@@ -1335,26 +1610,16 @@ impl App {
                 }
             }
             AppEvent::StartCommitAnimation => {
-                if self
-                    .commit_anim_running
-                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-                    .is_ok()
-                {
-                    let tx = self.app_event_tx.clone();
-                    let running = self.commit_anim_running.clone();
-                    thread::spawn(move || {
-                        while running.load(Ordering::Relaxed) {
-                            thread::sleep(Duration::from_millis(50));
-                            tx.send(AppEvent::CommitTick);
-                        }
-                    });
-                }
+                self.start_commit_animation(self.tabs.active_id());
             }
             AppEvent::StopCommitAnimation => {
-                self.commit_anim_running.store(false, Ordering::Release);
+                self.stop_commit_animation(self.tabs.active_id());
             }
             AppEvent::CommitTick => {
                 self.chat_widget.on_commit_tick();
+                for parked in self.tabs.parked_states_mut() {
+                    parked.chat_widget.on_commit_tick();
+                }
             }
             AppEvent::CodexEvent(event) => {
                 let turn_complete = matches!(&event.msg, EventMsg::TaskComplete(_));
@@ -1370,20 +1635,36 @@ impl App {
                 return Ok(false);
             }
             AppEvent::CodexOp(op) => self.chat_widget.submit_op(op),
+            AppEvent::PollExecSessions => self.chat_widget.poll_exec_sessions(),
+            AppEvent::ToggleExecSessionPin { session_id } => {
+                self.chat_widget.toggle_exec_session_pin(session_id);
+            }
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
                 // Enter alternate screen using TUI helper and build pager lines
                 let _ = tui.enter_alt_screen();
-                let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
-                    vec!["No changes detected.".italic().into()]
+                let mut diffs = vec![(DiffSource::WorkingTree, text.clone())];
+                if let Some(turn_diff) = self.chat_widget.last_turn_diff() {
+                    diffs.push((DiffSource::LastTurn, turn_diff.to_string()));
+                }
+                let root =
+                    get_git_repo_root(&self.config.cwd).unwrap_or_else(|| self.config.cwd.clone());
+                let browser = DiffBrowserOverlay::new(diffs, root);
+                if browser.is_empty() {
+                    // Nothing to browse: show the message (or "no changes").
+                    let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
+                        vec!["No changes detected.".italic().into()]
+                    } else {
+                        text.lines().map(ansi_escape_line).collect()
+                    };
+                    self.overlay = Some(Overlay::new_static_with_lines(
+                        pager_lines,
+                        "D I F F".to_string(),
+                    ));
                 } else {
-                    text.lines().map(ansi_escape_line).collect()
-                };
-                self.overlay = Some(Overlay::new_static_with_lines(
-                    pager_lines,
-                    "D I F F".to_string(),
-                ));
+                    self.overlay = Some(Overlay::DiffBrowser(browser));
+                }
                 tui.frame_requester().schedule_frame();
             }
             AppEvent::StartFileSearch(query) => {
@@ -1536,6 +1817,7 @@ impl App {
                 refresh_model_metadata(&mut self.config);
                 sanitize_reasoning_overrides(&mut self.config);
                 sanitize_tool_overrides(&mut self.config);
+                apply_provider_fallbacks(&mut self.config);
             }
             AppEvent::OpenFeedbackNote {
                 category,
@@ -1781,6 +2063,25 @@ impl App {
             } => {
                 self.on_custom_provider_models_fetched(provider_id, result);
             }
+            AppEvent::OpenOllamaPullPrompt { provider_id } => {
+                self.show_ollama_pull_prompt(provider_id);
+            }
+            AppEvent::PullOllamaModel { provider_id, model } => {
+                self.start_ollama_pull(provider_id, model);
+            }
+            AppEvent::OllamaPullProgress { model, status } => {
+                if self.ollama_pull.as_deref() == Some(model.as_str()) {
+                    self.chat_widget
+                        .set_model_pull_status_line(Some(format!("Pulling {model} • {status}")));
+                }
+            }
+            AppEvent::OllamaPullFinished {
+                provider_id,
+                model,
+                result,
+            } => {
+                self.on_ollama_pull_finished(provider_id, model, result);
+            }
             AppEvent::UpdateAskForApprovalPolicy(policy) => {
                 self.chat_widget.set_approval_policy(policy);
             }
@@ -1959,6 +2260,98 @@ impl App {
             .push_bottom_view(move |pane| pane.show_view(Box::new(view)));
     }
 
+    fn show_ollama_pull_prompt(&mut self, provider_id: String) {
+        let tx = self.app_event_tx.clone();
+        let on_submit: PromptSubmitted = Box::new(move |value: String| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                return;
+            }
+            tx.send(AppEvent::PullOllamaModel {
+                provider_id: provider_id.clone(),
+                model: trimmed.to_string(),
+            });
+        });
+        let view = CustomPromptView::new(
+            "Pull a model".to_string(),
+            "Model name, e.g. gpt-oss:20b or qwen3:8b".to_string(),
+            Some(
+                "Downloads from the Ollama library; progress shows below the composer.".to_string(),
+            ),
+            on_submit,
+        );
+        self.chat_widget
+            .push_bottom_view(move |pane| pane.show_view(Box::new(view)));
+    }
+
+    fn start_ollama_pull(&mut self, provider_id: String, model: String) {
+        if let Some(pulling) = &self.ollama_pull {
+            self.chat_widget.add_error_message(format!(
+                "Already pulling `{pulling}`; wait for it to finish."
+            ));
+            return;
+        }
+        let Some(provider) = self.config.model_providers.get(&provider_id).cloned() else {
+            self.chat_widget
+                .add_error_message(format!("Provider `{provider_id}` not found"));
+            return;
+        };
+        if provider.base_url.is_none() {
+            self.chat_widget
+                .add_error_message(format!("Provider `{provider_id}` has no base URL"));
+            return;
+        }
+
+        self.ollama_pull = Some(model.clone());
+        self.chat_widget
+            .set_model_pull_status_line(Some(format!("Pulling {model}…")));
+
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let progress_tx = tx.clone();
+            let progress_model = model.clone();
+            let mut reporter = TuiProgressReporter::new(move |status| {
+                progress_tx.send(AppEvent::OllamaPullProgress {
+                    model: progress_model.clone(),
+                    status,
+                });
+            });
+            let result = match OllamaClient::try_from_provider_info(&provider).await {
+                Ok(client) => client.pull_with_reporter(&model, &mut reporter).await,
+                Err(err) => Err(err),
+            }
+            .map_err(|err| err.to_string());
+            tx.send(AppEvent::OllamaPullFinished {
+                provider_id,
+                model,
+                result,
+            });
+        });
+    }
+
+    fn on_ollama_pull_finished(
+        &mut self,
+        provider_id: String,
+        model: String,
+        result: std::result::Result<(), String>,
+    ) {
+        self.ollama_pull = None;
+        self.chat_widget.set_model_pull_status_line(None);
+        match result {
+            Ok(()) => {
+                self.chat_widget
+                    .add_info_message(format!("Pulled `{model}`; select it with /model."), None);
+                if self.settings.custom_provider(&provider_id).is_some() {
+                    self.refresh_byok_provider_models(&provider_id);
+                }
+            }
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to pull `{model}`: {err}"));
+            }
+        }
+    }
+
     fn show_memory_suggest_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let on_submit: PromptSubmitted = Box::new(move |value: String| {
@@ -4584,6 +4977,7 @@ impl App {
             self.config.model_provider = info.clone();
             self.chat_widget.set_model_provider(&provider_id, &info);
         }
+        apply_provider_fallbacks(&mut self.config);
 
         if let Some(original) = original_id.as_ref()
             && original != &provider_id
@@ -4655,6 +5049,7 @@ impl App {
                 refresh_model_metadata(&mut self.config);
                 self.chat_widget.set_model(&self.config.model);
             }
+            apply_provider_fallbacks(&mut self.config);
         }
 
         self.chat_widget
@@ -4667,13 +5062,14 @@ impl App {
     fn on_custom_provider_models_fetched(
         &mut self,
         provider_id: String,
-        result: std::result::Result<Vec<String>, String>,
+        result: std::result::Result<ProviderModels, String>,
     ) {
         match result {
-            Ok(models) => {
+            Ok(listing) => {
                 let mut updated = self.settings.clone();
                 if let Some(provider) = updated.custom_providers_mut().get_mut(&provider_id) {
-                    provider.cached_models = Some(models);
+                    provider.cached_models = Some(listing.models);
+                    provider.model_context_windows = listing.context_windows;
                     provider.last_model_refresh = Some(Utc::now().to_rfc3339());
                     if let Ok(persisted) = codex_agentic_core::persist_settings(updated.clone()) {
                         self.settings = persisted;
@@ -4681,6 +5077,9 @@ impl App {
                             &mut self.config,
                             &self.settings,
                         );
+                        if self.config.model_provider_id == provider_id {
+                            apply_model_limits(&mut self.config);
+                        }
                         self.chat_widget
                             .sync_model_providers(&self.config.model_providers);
                         self.chat_widget
@@ -4707,14 +5106,40 @@ impl App {
         }
     }
 
+    /// Suspends the TUI while the user edits `seed` in `$VISUAL`/`$EDITOR`, then
+    /// stores the result back into `target`.
+    fn edit_in_external_editor(
+        &mut self,
+        tui: &mut tui::Tui,
+        target: ExternalEditTarget,
+        seed: &str,
+    ) {
+        match edit_text(tui, seed) {
+            Ok(text) => self.chat_widget.apply_external_edit(target, text),
+            Err(err) => self
+                .chat_widget
+                .add_error_message(format!("External editor: {err}")),
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
         match key_event {
             KeyEvent {
-                code: KeyCode::Char('t'),
+                kind: KeyEventKind::Press,
+                ..
+            } if keymap().matches(KeyAction::OpenEditor, key_event) => {
+                match self.chat_widget.external_edit_seed() {
+                    Some((target, seed)) => self.edit_in_external_editor(tui, target, &seed),
+                    None => self.chat_widget.handle_key_event(key_event),
+                }
+            }
+            KeyEvent {
                 modifiers,
                 kind: KeyEventKind::Press,
                 ..
-            } if modifiers.contains(KeyModifiers::CONTROL) => {
+            } if keymap().matches(KeyAction::Transcript, key_event) => {
+                // Holding shift skips the agent runs overlay.
                 if !self.active_agent_runs.is_empty() && !modifiers.contains(KeyModifiers::SHIFT) {
                     self.show_agent_runs_overlay();
                 } else {
@@ -4724,6 +5149,30 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
             }
+            KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            } if keymap().matches(KeyAction::NewTab, key_event) => {
+                self.open_new_tab(tui);
+            }
+            KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            } if keymap().matches(KeyAction::NextTab, key_event) => {
+                self.switch_tab(tui, 1);
+            }
+            KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            } if keymap().matches(KeyAction::PreviousTab, key_event) => {
+                self.switch_tab(tui, -1);
+            }
+            KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            } if keymap().matches(KeyAction::CloseTab, key_event) => {
+                self.close_active_tab(tui).await;
+            }
             KeyEvent {
                 code: KeyCode::Char(ch @ '1'..='9'),
                 modifiers,
@@ -5134,6 +5583,9 @@ mod tests {
     use codex_core::AuthManager;
     use codex_core::CodexAuth;
     use codex_core::ConversationManager;
+    use codex_core::protocol::Event;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::Op;
     use codex_core::protocol::SessionConfiguredEvent;
     use codex_protocol::ConversationId;
     use ratatui::prelude::Line;
@@ -5179,7 +5631,9 @@ mod tests {
             last_index_attempt: None,
             index_completion_toast_until: None,
             index_completion_message: None,
+            ollama_pull: None,
             transcript_cells: Vec::new(),
+            tabs: Tabs::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -5218,6 +5672,22 @@ mod tests {
         assert_eq!(slugify_provider_id("  Mixed__Value  "), "mixed-value");
     }
 
+    #[test]
+    fn base_url_suffix_selects_wire_api() {
+        let (base_url, wire_api) = normalize_custom_provider_base_url(Some(
+            "https://api.anthropic.com/v1/messages".into(),
+        ))
+        .unwrap();
+        assert_eq!(base_url.as_deref(), Some("https://api.anthropic.com/v1"));
+        assert_eq!(wire_api, WireApi::Messages);
+
+        let (_, wire_api) = normalize_custom_provider_base_url(Some(
+            "http://localhost:8080/v1/chat/completions".into(),
+        ))
+        .unwrap();
+        assert_eq!(wire_api, WireApi::Chat);
+    }
+
     #[test]
     fn provider_id_validation() {
         assert!(is_valid_provider_id("custom-provider"));
@@ -5386,4 +5856,57 @@ mod tests {
             "context note missing summary: {notes:?}"
         );
     }
+
+    #[test]
+    fn background_tab_events_reach_the_parked_conversation() {
+        let mut app = make_test_app();
+        let (background, _tx, _rx, mut op_rx) = make_chatwidget_manual_with_sender();
+        let first = app.tabs.active_id();
+        app.tabs.open();
+        app.tabs.park(
+            first,
+            ParkedTab {
+                chat_widget: background,
+                transcript_cells: Vec::new(),
+                deferred_events: Vec::new(),
+            },
+        );
+
+        app.handle_background_tab_event(
+            first,
+            AppEvent::InsertHistoryCell(Box::new(AgentMessageCell::new(
+                vec![Line::from("background reply")],
+                true,
+            ))),
+        );
+        app.handle_background_tab_event(first, AppEvent::CodexOp(Op::Interrupt));
+        app.handle_background_tab_event(first, AppEvent::OpenApprovalsPopup);
+        app.handle_background_tab_event(
+            first,
+            AppEvent::CodexEvent(Event {
+                id: "1".to_string(),
+                msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                    call_id: "call-1".to_string(),
+                    command: vec!["echo".to_string(), "hi".to_string()],
+                    cwd: PathBuf::from("/tmp"),
+                    reason: None,
+                    risk: None,
+                    parsed_cmd: vec![],
+                }),
+            }),
+        );
+
+        assert!(app.transcript_cells.is_empty());
+        assert!(matches!(op_rx.try_recv(), Ok(Op::Interrupt)));
+        assert_eq!(
+            app.tabs.status_line().as_deref(),
+            Some("Tabs: 1 (needs approval)  [2]  (ctrl + n next · ctrl + o new)")
+        );
+        let parked = app.tabs.parked_mut(first).expect("first tab is parked");
+        assert_eq!(parked.transcript_cells.len(), 1);
+        assert!(matches!(
+            parked.deferred_events.as_slice(),
+            [AppEvent::OpenApprovalsPopup]
+        ));
+    }
 }
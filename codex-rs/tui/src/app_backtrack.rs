@@ -28,6 +28,9 @@ pub(crate) struct BacktrackState {
     pub(crate) overlay_preview_active: bool,
     /// Pending fork request: (base_id, nth_user_message, prefill).
     pub(crate) pending: Option<(ConversationId, usize, String)>,
+    /// User turn (1-based) to preview once a resumed session has replayed
+    /// that far. Set by `codex resume --turn`.
+    pub(crate) pending_jump: Option<usize>,
 }
 
 impl App {
@@ -44,6 +47,13 @@ impl App {
             self.overlay_forward_event(tui, event)?;
             return Ok(true);
         }
+        // Esc/Enter edit the search query while it is being typed.
+        if let Some(Overlay::Transcript(t)) = &self.overlay
+            && t.is_editing_search()
+        {
+            self.overlay_forward_event(tui, event)?;
+            return Ok(true);
+        }
         if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
@@ -181,6 +191,39 @@ impl App {
         tui.frame_requester().schedule_frame();
     }
 
+    /// Begin (or move) the backtrack preview to the user message that started
+    /// the turn containing transcript cell `cell_idx`, e.g. a search match.
+    fn begin_overlay_backtrack_at_cell(&mut self, tui: &mut tui::Tui, cell_idx: usize) {
+        let Some(nth_user_message) = nth_user_for_cell(&self.transcript_cells, cell_idx) else {
+            return;
+        };
+        self.backtrack.primed = true;
+        self.backtrack.base_id = self.chat_widget.conversation_id();
+        self.backtrack.overlay_preview_active = true;
+        self.apply_backtrack_selection(nth_user_message);
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// Open the transcript at the turn requested by `codex resume --turn`
+    /// as soon as the replayed history contains it.
+    pub(crate) fn maybe_jump_to_resumed_turn(&mut self, tui: &mut tui::Tui) {
+        let Some(turn) = self.backtrack.pending_jump else {
+            return;
+        };
+        if user_count(&self.transcript_cells) < turn {
+            return;
+        }
+        self.backtrack.pending_jump = None;
+        if self.overlay.is_none() {
+            self.open_transcript_overlay(tui);
+        }
+        self.backtrack.primed = true;
+        self.backtrack.base_id = self.chat_widget.conversation_id();
+        self.backtrack.overlay_preview_active = true;
+        self.apply_backtrack_selection(turn.saturating_sub(1));
+        tui.frame_requester().schedule_frame();
+    }
+
     /// Step selection to the next older user message and update overlay.
     fn step_backtrack_and_highlight(&mut self, tui: &mut tui::Tui) {
         let count = user_count(&self.transcript_cells);
@@ -226,6 +269,10 @@ impl App {
             if overlay.is_done() {
                 self.close_transcript_overlay(tui);
                 tui.frame_requester().schedule_frame();
+            } else if let Overlay::Transcript(t) = overlay
+                && let Some(cell_idx) = t.take_backtrack_request()
+            {
+                self.begin_overlay_backtrack_at_cell(tui, cell_idx);
             }
         }
         Ok(())
@@ -344,7 +391,7 @@ impl App {
         let init = crate::chatwidget::ChatWidgetInit {
             config: cfg,
             frame_requester: tui.frame_requester(),
-            app_event_tx: self.app_event_tx.clone(),
+            app_event_tx: self.active_tab_event_tx(),
             initial_prompt: None,
             initial_images: Vec::new(),
             enhanced_keys_supported: self.enhanced_keys_supported,
@@ -394,6 +441,17 @@ fn nth_user_position(
         .find_map(|(i, idx)| (i == nth).then_some(idx))
 }
 
+/// Index (among user messages) of the user message at or before `cell_idx`.
+fn nth_user_for_cell(
+    cells: &[Arc<dyn crate::history_cell::HistoryCell>],
+    cell_idx: usize,
+) -> Option<usize> {
+    user_positions_iter(cells)
+        .take_while(|idx| *idx <= cell_idx)
+        .count()
+        .checked_sub(1)
+}
+
 fn user_positions_iter(
     cells: &[Arc<dyn crate::history_cell::HistoryCell>],
 ) -> impl Iterator<Item = usize> + '_ {
@@ -421,6 +479,27 @@ mod tests {
     use ratatui::prelude::Line;
     use std::sync::Arc;
 
+    #[test]
+    fn nth_user_for_cell_maps_cells_to_their_turn() {
+        let cells: Vec<Arc<dyn HistoryCell>> = vec![
+            Arc::new(AgentMessageCell::new(vec![Line::from("intro")], true))
+                as Arc<dyn HistoryCell>,
+            Arc::new(UserHistoryCell {
+                message: "first".to_string(),
+            }) as Arc<dyn HistoryCell>,
+            Arc::new(AgentMessageCell::new(vec![Line::from("reply")], true))
+                as Arc<dyn HistoryCell>,
+            Arc::new(UserHistoryCell {
+                message: "second".to_string(),
+            }) as Arc<dyn HistoryCell>,
+        ];
+
+        assert_eq!(nth_user_for_cell(&cells, 0), None);
+        assert_eq!(nth_user_for_cell(&cells, 1), Some(0));
+        assert_eq!(nth_user_for_cell(&cells, 2), Some(0));
+        assert_eq!(nth_user_for_cell(&cells, 3), Some(1));
+    }
+
     #[test]
     fn trim_transcript_for_first_user_drops_user_and_newer_cells() {
         let mut cells: Vec<Arc<dyn HistoryCell>> = vec![
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use codex_agentic_core::AgentRunStatus;
+use codex_agentic_core::ProviderModels;
 use codex_agentic_core::index::events::IndexEvent;
 use codex_agentic_core::index::query::QueryHit;
 use codex_common::approval_presets::ApprovalPreset;
@@ -14,6 +15,7 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol_config_types::ReasoningEffort;
 use codex_file_search::FileMatch;
 
+use crate::app_tabs::TabId;
 use crate::bottom_pane::ApprovalRequest;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::MemorySuggestionEntry;
@@ -45,6 +47,14 @@ pub(crate) enum AgentEditReturn {
 pub(crate) enum AppEvent {
     CodexEvent(Event),
 
+    /// An event sent by the widgets of one tab. The app handles it like the
+    /// inner event when the tab is active and routes it to the tab's parked
+    /// state otherwise.
+    Tab {
+        tab: TabId,
+        event: Box<AppEvent>,
+    },
+
     /// Start a new session.
     NewSession,
 
@@ -71,6 +81,14 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Time to refresh the list of background exec sessions.
+    PollExecSessions,
+
+    /// Pin or unpin a background exec session's output in the footer.
+    ToggleExecSessionPin {
+        session_id: i32,
+    },
+
     /// Index worker emitted an event during build.
     IndexStatus(IndexEvent),
 
@@ -341,7 +359,31 @@ pub(crate) enum AppEvent {
     /// Result of asynchronously fetching models for a custom provider.
     CustomProviderModelsFetched {
         provider_id: String,
-        result: std::result::Result<Vec<String>, String>,
+        result: std::result::Result<ProviderModels, String>,
+    },
+
+    /// Ask for the name of a model to pull into an Ollama provider.
+    OpenOllamaPullPrompt {
+        provider_id: String,
+    },
+
+    /// Pull `model` into the Ollama server behind `provider_id`.
+    PullOllamaModel {
+        provider_id: String,
+        model: String,
+    },
+
+    /// Status line reported while a model pull is in flight.
+    OllamaPullProgress {
+        model: String,
+        status: String,
+    },
+
+    /// Result of a model pull started from the model picker.
+    OllamaPullFinished {
+        provider_id: String,
+        model: String,
+        result: std::result::Result<(), String>,
     },
 
     /// Begin editing a draft field for a custom provider.
@@ -0,0 +1,331 @@
+//! Switchable conversations inside one TUI process.
+//!
+//! The active tab's `ChatWidget` and transcript live directly on `App`, so the
+//! rest of the app keeps talking to "the" chat widget; every other tab is
+//! parked in [`Tabs`] until the user switches to it. Each tab's widget sends
+//! its events through an `AppEventSender` stamped with the tab's [`TabId`],
+//! which is how the app routes events from background conversations to their
+//! parked state instead of the screen. Conversations (and with them their
+//! rollout files and memory recorders) stay independent per tab because each
+//! tab owns its own `CodexConversation`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use codex_core::protocol::EventMsg;
+
+use crate::app_event::AppEvent;
+use crate::chatwidget::ChatWidget;
+use crate::history_cell::HistoryCell;
+use crate::key_hint;
+use crate::keymap::KeyAction;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct TabId(u64);
+
+/// Why a background tab wants attention.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TabBadge {
+    NeedsApproval,
+    Finished,
+}
+
+impl TabBadge {
+    /// The badge a background tab earns from `msg`, if any.
+    pub(crate) fn for_event(msg: &EventMsg) -> Option<TabBadge> {
+        match msg {
+            EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_) => {
+                Some(TabBadge::NeedsApproval)
+            }
+            EventMsg::TaskComplete(_) => Some(TabBadge::Finished),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TabBadge::NeedsApproval => "needs approval",
+            TabBadge::Finished => "done",
+        }
+    }
+}
+
+/// Everything that belongs to a tab while it is not on screen.
+pub(crate) struct ParkedTab {
+    pub(crate) chat_widget: ChatWidget,
+    pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// App-level events the tab sent while in the background (popups, model
+    /// changes, ...). They are replayed once the tab is active again.
+    pub(crate) deferred_events: Vec<AppEvent>,
+}
+
+struct TabEntry {
+    id: TabId,
+    badge: Option<TabBadge>,
+    /// True while the tab's stream is waiting on commit ticks.
+    animating: bool,
+}
+
+/// Tab order, badges and the parked state of every inactive tab.
+pub(crate) struct Tabs<T = ParkedTab> {
+    entries: Vec<TabEntry>,
+    active: usize,
+    next_id: u64,
+    parked: HashMap<TabId, T>,
+}
+
+impl<T> Tabs<T> {
+    /// A single tab, which is active.
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: vec![TabEntry {
+                id: TabId(0),
+                badge: None,
+                animating: false,
+            }],
+            active: 0,
+            next_id: 1,
+            parked: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn active_id(&self) -> TabId {
+        self.entries[self.active].id
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 1-based position of the active tab, as shown to the user.
+    pub(crate) fn active_position(&self) -> usize {
+        self.active + 1
+    }
+
+    /// Adds a tab right after the active one and makes it active. The caller
+    /// must then [`park`](Self::park) the state of the previously active tab.
+    pub(crate) fn open(&mut self) -> TabId {
+        let id = TabId(self.next_id);
+        self.next_id += 1;
+        self.active += 1;
+        self.entries.insert(
+            self.active,
+            TabEntry {
+                id,
+                badge: None,
+                animating: false,
+            },
+        );
+        id
+    }
+
+    /// Moves `offset` tabs forward (backward when negative), wrapping around,
+    /// and returns the state of the tab that is now active. The caller must
+    /// then [`park`](Self::park) the state of the previously active tab.
+    /// Returns `None`, changing nothing, when there is no other tab.
+    pub(crate) fn activate(&mut self, offset: isize) -> Option<T> {
+        let len = self.entries.len() as isize;
+        let target = (self.active as isize + offset).rem_euclid(len) as usize;
+        if target == self.active {
+            return None;
+        }
+        let state = self.parked.remove(&self.entries[target].id)?;
+        self.active = target;
+        self.entries[target].badge = None;
+        Some(state)
+    }
+
+    /// Removes the active tab and activates its right neighbour (the left one
+    /// when it was last), returning the state of the tab that is now active.
+    /// The closed tab was on screen, so it has no parked state; the caller
+    /// owns it and must shut its conversation down. Returns `None`, changing
+    /// nothing, when it is the only tab.
+    pub(crate) fn close(&mut self) -> Option<T> {
+        if self.entries.len() < 2 {
+            return None;
+        }
+        let target = if self.active + 1 < self.entries.len() {
+            self.active + 1
+        } else {
+            self.active - 1
+        };
+        let state = self.parked.remove(&self.entries[target].id)?;
+        self.entries.remove(self.active);
+        // The right neighbour shifts into the closed tab's slot.
+        self.active = target.min(self.active);
+        self.entries[self.active].badge = None;
+        Some(state)
+    }
+
+    pub(crate) fn park(&mut self, id: TabId, state: T) {
+        self.parked.insert(id, state);
+    }
+
+    pub(crate) fn parked_mut(&mut self, id: TabId) -> Option<&mut T> {
+        self.parked.get_mut(&id)
+    }
+
+    pub(crate) fn parked_states_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.parked.values_mut()
+    }
+
+    /// Flags a background tab. A pending approval is not downgraded to
+    /// "done" until the user has looked at the tab.
+    pub(crate) fn set_badge(&mut self, id: TabId, badge: TabBadge) {
+        if id == self.active_id() {
+            return;
+        }
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id)
+            && entry.badge != Some(TabBadge::NeedsApproval)
+        {
+            entry.badge = Some(badge);
+        }
+    }
+
+    /// Records whether `id` is streaming output that needs commit ticks and
+    /// returns whether any tab still does.
+    pub(crate) fn set_animating(&mut self, id: TabId, animating: bool) -> bool {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.animating = animating;
+        }
+        self.entries.iter().any(|entry| entry.animating)
+    }
+
+    /// Footer line listing the tabs, or `None` while there is only one.
+    pub(crate) fn status_line(&self) -> Option<String> {
+        if self.entries.len() < 2 {
+            return None;
+        }
+        let tabs: Vec<String> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let mut label = if idx == self.active {
+                    format!("[{}]", idx + 1)
+                } else {
+                    (idx + 1).to_string()
+                };
+                if let Some(badge) = entry.badge {
+                    label.push_str(&format!(" ({})", badge.label()));
+                }
+                label
+            })
+            .collect();
+        Some(format!(
+            "Tabs: {}  ({} next · {} new)",
+            tabs.join("  "),
+            key_hint::action_span(KeyAction::NextTab).content,
+            key_hint::action_span(KeyAction::NewTab).content,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::TaskCompleteEvent;
+    use pretty_assertions::assert_eq;
+
+    fn three_tabs() -> Tabs<&'static str> {
+        let mut tabs = Tabs::new();
+        let first = tabs.active_id();
+        tabs.open();
+        tabs.park(first, "first");
+        let second = tabs.active_id();
+        tabs.open();
+        tabs.park(second, "second");
+        tabs
+    }
+
+    #[test]
+    fn single_tab_has_no_status_line_and_cannot_switch() {
+        let mut tabs: Tabs<&str> = Tabs::new();
+        assert_eq!(tabs.status_line(), None);
+        assert_eq!(tabs.activate(1), None);
+        assert_eq!(tabs.active_position(), 1);
+    }
+
+    #[test]
+    fn activate_wraps_around_and_returns_parked_state() {
+        let mut tabs = three_tabs();
+        let third = tabs.active_id();
+        assert_eq!(tabs.active_position(), 3);
+
+        assert_eq!(tabs.activate(1), Some("first"));
+        tabs.park(third, "third");
+        assert_eq!(tabs.active_position(), 1);
+
+        let first = tabs.active_id();
+        assert_eq!(tabs.activate(-1), Some("third"));
+        tabs.park(first, "first");
+        assert_eq!(tabs.active_position(), 3);
+        assert_eq!(tabs.parked_states_mut().count(), 2);
+    }
+
+    #[test]
+    fn close_activates_a_neighbour_and_forgets_the_tab() {
+        let mut tabs = three_tabs();
+        let first = tabs.entries[0].id;
+        let second = tabs.entries[1].id;
+        tabs.set_badge(second, TabBadge::Finished);
+
+        assert_eq!(tabs.close(), Some("second"));
+        assert_eq!(tabs.len(), 2);
+        assert_eq!(tabs.active_id(), second);
+        assert_eq!(tabs.parked_states_mut().count(), 1);
+
+        assert_eq!(tabs.activate(1), Some("first"));
+        tabs.park(second, "second");
+        assert_eq!(tabs.close(), Some("second"));
+        assert_eq!(tabs.active_id(), second);
+        assert_eq!(tabs.parked_mut(first), None);
+        assert_eq!(tabs.status_line(), None);
+        assert_eq!(tabs.close(), None);
+    }
+
+    #[test]
+    fn badges_show_until_the_tab_is_activated() {
+        let mut tabs = three_tabs();
+        let third = tabs.active_id();
+        let first = tabs.entries[0].id;
+        let second = tabs.entries[1].id;
+
+        tabs.set_badge(first, TabBadge::NeedsApproval);
+        tabs.set_badge(first, TabBadge::Finished);
+        tabs.set_badge(second, TabBadge::Finished);
+        tabs.set_badge(third, TabBadge::Finished);
+        assert_eq!(
+            tabs.status_line().as_deref(),
+            Some("Tabs: 1 (needs approval)  2 (done)  [3]  (ctrl + n next · ctrl + o new)")
+        );
+
+        assert_eq!(tabs.activate(-1), Some("second"));
+        tabs.park(third, "third");
+        assert_eq!(
+            tabs.status_line().as_deref(),
+            Some("Tabs: 1 (needs approval)  [2]  3  (ctrl + n next · ctrl + o new)")
+        );
+    }
+
+    #[test]
+    fn animation_runs_while_any_tab_streams() {
+        let mut tabs = three_tabs();
+        let third = tabs.active_id();
+        let first = tabs.entries[0].id;
+
+        assert!(tabs.set_animating(first, true));
+        assert!(tabs.set_animating(third, true));
+        assert!(tabs.set_animating(first, false));
+        assert!(!tabs.set_animating(third, false));
+    }
+
+    #[test]
+    fn task_completion_and_approvals_earn_badges() {
+        let complete = EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        });
+        assert_eq!(TabBadge::for_event(&complete), Some(TabBadge::Finished));
+        assert_eq!(TabBadge::for_event(&EventMsg::ShutdownComplete), None);
+    }
+}
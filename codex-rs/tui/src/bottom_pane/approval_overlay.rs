@@ -8,14 +8,19 @@ use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
+use crate::bottom_pane::patch_hunk_review::HunkReviewOutcome;
+use crate::bottom_pane::patch_hunk_review::PatchHunkReview;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
@@ -25,7 +30,6 @@ use codex_core::protocol::SandboxRiskLevel;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
-use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -59,6 +63,8 @@ pub(crate) struct ApprovalOverlay {
     app_event_tx: AppEventSender,
     list: ListSelectionView,
     options: Vec<ApprovalOption>,
+    /// Set while the user is accepting or rejecting individual patch hunks.
+    hunk_review: Option<PatchHunkReview>,
     current_complete: bool,
     done: bool,
 }
@@ -72,6 +78,7 @@ impl ApprovalOverlay {
             app_event_tx: app_event_tx.clone(),
             list: ListSelectionView::new(Default::default(), app_event_tx),
             options: Vec::new(),
+            hunk_review: None,
             current_complete: false,
             done: false,
         };
@@ -87,6 +94,7 @@ impl ApprovalOverlay {
         self.current_request = Some(request.clone());
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
+        self.hunk_review = None;
         self.current_complete = false;
         let (options, params) = Self::build_options(variant, header);
         self.options = options;
@@ -102,8 +110,8 @@ impl ApprovalOverlay {
                 exec_options(),
                 "Would you like to run the following command?".to_string(),
             ),
-            ApprovalVariant::ApplyPatch { .. } => (
-                patch_options(),
+            ApprovalVariant::ApplyPatch { changes, .. } => (
+                patch_options(PatchHunkReview::hunk_count(changes)),
                 "Would you like to make the following edits?".to_string(),
             ),
         };
@@ -148,13 +156,18 @@ impl ApprovalOverlay {
             return;
         };
         if let Some(variant) = self.current_variant.as_ref() {
-            match (&variant, option.decision) {
-                (ApprovalVariant::Exec { id, command }, decision) => {
+            match (&variant, option.action) {
+                (ApprovalVariant::Exec { id, command }, ApprovalAction::Decide(decision)) => {
                     self.handle_exec_decision(id, command, decision);
                 }
-                (ApprovalVariant::ApplyPatch { id, .. }, decision) => {
+                (ApprovalVariant::ApplyPatch { id, .. }, ApprovalAction::Decide(decision)) => {
                     self.handle_patch_decision(id, decision);
                 }
+                (ApprovalVariant::ApplyPatch { cwd, changes, .. }, ApprovalAction::ReviewHunks) => {
+                    self.hunk_review = Some(PatchHunkReview::new(changes, cwd.clone()));
+                    return;
+                }
+                (ApprovalVariant::Exec { .. }, ApprovalAction::ReviewHunks) => return,
             }
         }
 
@@ -178,6 +191,46 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn handle_hunk_review_key(&mut self, key_event: KeyEvent) {
+        let Some(review) = self.hunk_review.as_mut() else {
+            return;
+        };
+        match review.handle_key_event(key_event) {
+            HunkReviewOutcome::Pending => {}
+            HunkReviewOutcome::Back => self.hunk_review = None,
+            HunkReviewOutcome::Submit(rejected_hunks) => {
+                self.hunk_review = None;
+                if let Some(ApprovalVariant::ApplyPatch { id, changes, .. }) =
+                    self.current_variant.as_ref()
+                {
+                    self.handle_partial_patch_decision(id, changes, rejected_hunks);
+                }
+                self.current_complete = true;
+                self.advance_queue();
+            }
+        }
+    }
+
+    fn handle_partial_patch_decision(
+        &self,
+        id: &str,
+        changes: &HashMap<PathBuf, FileChange>,
+        rejected_hunks: HashMap<PathBuf, Vec<usize>>,
+    ) {
+        let rejected_count: usize = rejected_hunks.values().map(Vec::len).sum();
+        if rejected_count == 0 {
+            self.handle_patch_decision(id, ReviewDecision::Approved);
+        } else if rejected_count >= PatchHunkReview::hunk_count(changes) {
+            self.handle_patch_decision(id, ReviewDecision::Denied);
+        } else {
+            self.app_event_tx
+                .send(AppEvent::CodexOp(Op::PatchHunkApproval {
+                    id: id.to_string(),
+                    rejected_hunks,
+                }));
+        }
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -188,12 +241,9 @@ impl ApprovalOverlay {
 
     fn try_handle_shortcut(&mut self, key_event: &KeyEvent) -> bool {
         match key_event {
-            KeyEvent {
-                kind: KeyEventKind::Press,
-                code: KeyCode::Char('a'),
-                modifiers,
-                ..
-            } if modifiers.contains(KeyModifiers::CONTROL) => {
+            e if e.kind == KeyEventKind::Press
+                && keymap().matches(KeyAction::ExpandApproval, *e) =>
+            {
                 if let Some(request) = self.current_request.as_ref() {
                     self.app_event_tx
                         .send(AppEvent::FullScreenApprovalRequest(request.clone()));
@@ -220,6 +270,10 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.hunk_review.is_some() {
+            self.handle_hunk_review_key(key_event);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -265,15 +319,24 @@ impl BottomPaneView for ApprovalOverlay {
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.hunk_review {
+            Some(review) => review.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.hunk_review {
+            Some(review) => review.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match &self.hunk_review {
+            Some(_) => None,
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -329,9 +392,9 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
-                header.push(DiffSummary::new(changes, cwd).into());
+                header.push(DiffSummary::new(changes.clone(), cwd.clone()).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch { id, cwd, changes },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -341,9 +404,9 @@ impl From<ApprovalRequest> for ApprovalRequestState {
 
 fn render_risk_lines(risk: &SandboxCommandAssessment) -> Vec<Line<'static>> {
     let level_span = match risk.risk_level {
-        SandboxRiskLevel::Low => "LOW".green().bold(),
-        SandboxRiskLevel::Medium => "MEDIUM".cyan().bold(),
-        SandboxRiskLevel::High => "HIGH".red().bold(),
+        SandboxRiskLevel::Low => "LOW".success().bold(),
+        SandboxRiskLevel::Medium => "MEDIUM".accent().bold(),
+        SandboxRiskLevel::High => "HIGH".error().bold(),
     };
 
     let mut lines = Vec::new();
@@ -387,14 +450,28 @@ fn risk_category_label(category: SandboxRiskCategory) -> &'static str {
 
 #[derive(Clone)]
 enum ApprovalVariant {
-    Exec { id: String, command: Vec<String> },
-    ApplyPatch { id: String },
+    Exec {
+        id: String,
+        command: Vec<String>,
+    },
+    ApplyPatch {
+        id: String,
+        cwd: PathBuf,
+        changes: HashMap<PathBuf, FileChange>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum ApprovalAction {
+    Decide(ReviewDecision),
+    /// Open the per-hunk review for a patch.
+    ReviewHunks,
 }
 
 #[derive(Clone)]
 struct ApprovalOption {
     label: String,
-    decision: ReviewDecision,
+    action: ApprovalAction,
     display_shortcut: Option<KeyBinding>,
     additional_shortcuts: Vec<KeyBinding>,
 }
@@ -411,46 +488,54 @@ fn exec_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
             label: "Yes, proceed".to_string(),
-            decision: ReviewDecision::Approved,
+            action: ApprovalAction::Decide(ReviewDecision::Approved),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+            additional_shortcuts: key_hint::actions(KeyAction::Approve).to_vec(),
         },
         ApprovalOption {
             label: "Yes, and don't ask again for this command".to_string(),
-            decision: ReviewDecision::ApprovedForSession,
+            action: ApprovalAction::Decide(ReviewDecision::ApprovedForSession),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+            additional_shortcuts: key_hint::actions(KeyAction::ApproveForSession).to_vec(),
         },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
-            decision: ReviewDecision::Abort,
+            action: ApprovalAction::Decide(ReviewDecision::Abort),
             display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+            additional_shortcuts: key_hint::actions(KeyAction::Deny).to_vec(),
         },
     ]
 }
 
-fn patch_options() -> Vec<ApprovalOption> {
-    vec![
-        ApprovalOption {
-            label: "Yes, proceed".to_string(),
-            decision: ReviewDecision::Approved,
+fn patch_options(hunk_count: usize) -> Vec<ApprovalOption> {
+    let mut options = vec![ApprovalOption {
+        label: "Yes, proceed".to_string(),
+        action: ApprovalAction::Decide(ReviewDecision::Approved),
+        display_shortcut: None,
+        additional_shortcuts: key_hint::actions(KeyAction::Approve).to_vec(),
+    }];
+    if hunk_count > 1 {
+        options.push(ApprovalOption {
+            label: format!("Review the {hunk_count} hunks one by one"),
+            action: ApprovalAction::ReviewHunks,
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
-        },
-        ApprovalOption {
-            label: "No, and tell Codex what to do differently".to_string(),
-            decision: ReviewDecision::Abort,
-            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-        },
-    ]
+            additional_shortcuts: key_hint::actions(KeyAction::ReviewHunks).to_vec(),
+        });
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell Codex what to do differently".to_string(),
+        action: ApprovalAction::Decide(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: key_hint::actions(KeyAction::Deny).to_vec(),
+    });
+    options
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app_event::AppEvent;
+    use crossterm::event::KeyModifiers;
     use tokio::sync::mpsc::unbounded_channel;
 
     fn make_exec_request() -> ApprovalRequest {
@@ -571,4 +656,118 @@ mod tests {
         }
         assert_eq!(decision, Some(ReviewDecision::ApprovedForSession));
     }
+
+    fn make_patch_request() -> ApprovalRequest {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("/repo/src/lib.rs"),
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -9,2 +9,2 @@\n y\n-z\n+Z\n"
+                        .to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("/repo/README.md"),
+                FileChange::Add {
+                    content: "hello\n".to_string(),
+                },
+            ),
+        ]);
+        ApprovalRequest::ApplyPatch {
+            id: "patch".to_string(),
+            reason: None,
+            cwd: PathBuf::from("/repo"),
+            changes,
+        }
+    }
+
+    fn press(view: &mut ApprovalOverlay, code: KeyCode) {
+        view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn render_lines(view: &ApprovalOverlay, width: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, view.desired_height(width));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..area.height)
+            .map(|row| {
+                (0..area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hunk_review_submits_rejected_hunks() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = ApprovalOverlay::new(make_patch_request(), AppEventSender::new(tx));
+
+        press(&mut view, KeyCode::Char('h'));
+        assert!(!view.is_complete());
+        let rendered = render_lines(&view, 80);
+        assert!(
+            rendered.iter().any(|line| line.contains("README.md")),
+            "expected the first file in the review, got {rendered:?}"
+        );
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("Hunk 1 of 3 · 3 accepted")),
+            "expected the review status line, got {rendered:?}"
+        );
+
+        // README.md, then src/lib.rs hunk 1 and hunk 2.
+        press(&mut view, KeyCode::Char('y'));
+        press(&mut view, KeyCode::Char('y'));
+        press(&mut view, KeyCode::Char('n'));
+        let rendered = render_lines(&view, 80);
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("hunk 2 of 2") && line.contains("✗ reject")),
+            "expected the second hunk to be rejected, got {rendered:?}"
+        );
+        press(&mut view, KeyCode::Enter);
+        assert!(view.is_complete());
+
+        let mut op = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(submitted) = ev {
+                op = Some(submitted);
+            }
+        }
+        assert_eq!(
+            op,
+            Some(Op::PatchHunkApproval {
+                id: "patch".to_string(),
+                rejected_hunks: HashMap::from([(PathBuf::from("/repo/src/lib.rs"), vec![1])]),
+            })
+        );
+    }
+
+    #[test]
+    fn hunk_review_escape_returns_to_options_and_full_accept_approves() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = ApprovalOverlay::new(make_patch_request(), AppEventSender::new(tx));
+
+        press(&mut view, KeyCode::Char('h'));
+        press(&mut view, KeyCode::Char('n'));
+        press(&mut view, KeyCode::Esc);
+        assert!(view.hunk_review.is_none());
+        assert!(!view.is_complete());
+
+        press(&mut view, KeyCode::Char('h'));
+        press(&mut view, KeyCode::Enter);
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::Approved));
+    }
 }
@@ -1,5 +1,8 @@
+use std::collections::HashSet;
+
 use crate::bottom_pane::ApprovalRequest;
 use crate::render::renderable::Renderable;
+use codex_core::protocol::UnifiedExecSessionInfo;
 use crossterm::event::KeyEvent;
 
 use super::CancellationEvent;
@@ -34,4 +37,14 @@ pub(crate) trait BottomPaneView: Renderable {
     ) -> Option<ApprovalRequest> {
         Some(request)
     }
+
+    /// Refresh the list of background exec sessions; return `true` if this
+    /// view shows them.
+    fn update_exec_sessions(
+        &mut self,
+        _sessions: &[UnifiedExecSessionInfo],
+        _pinned: &HashSet<i32>,
+    ) -> bool {
+        false
+    }
 }
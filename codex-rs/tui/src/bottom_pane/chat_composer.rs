@@ -35,6 +35,8 @@ use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
 use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::render::renderable::Renderable;
@@ -118,6 +120,9 @@ pub(crate) struct ChatComposer {
     index_status_line: Option<String>,
     rate_limit_summaries: Vec<String>,
     agent_status_line: Option<String>,
+    tab_status_line: Option<String>,
+    model_pull_status_line: Option<String>,
+    pinned_exec_output: Vec<String>,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -164,6 +169,9 @@ impl ChatComposer {
             index_status_line: None,
             rate_limit_summaries: Vec::new(),
             agent_status_line: None,
+            tab_status_line: None,
+            model_pull_status_line: None,
+            pinned_exec_output: Vec::new(),
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -294,6 +302,18 @@ impl ChatComposer {
         self.sync_file_search_popup();
     }
 
+    /// Replace the composer content with text edited in an external editor.
+    /// Image and paste placeholders that survived the edit keep their
+    /// attachments (as plain text rather than atomic elements); removed ones
+    /// are dropped.
+    pub(crate) fn set_text_from_external_editor(&mut self, text: String) {
+        self.textarea.set_text(&text);
+        self.textarea.set_cursor(text.len());
+        self.prune_attachments_to_text();
+        self.sync_command_popup();
+        self.sync_file_search_popup();
+    }
+
     pub(crate) fn clear_for_ctrl_c(&mut self) -> Option<String> {
         if self.is_empty() {
             return None;
@@ -867,33 +887,31 @@ impl ChatComposer {
         } else {
             self.footer_mode = reset_mode_after_activity(self.footer_mode);
         }
+        let keymap = keymap();
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('d'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.is_empty() => {
+            _ if key_event.kind == KeyEventKind::Press
+                && keymap.matches(KeyAction::Quit, key_event)
+                && self.is_empty() =>
+            {
                 self.app_event_tx.send(AppEvent::ExitRequest);
                 (InputResult::None, true)
             }
             // -------------------------------------------------------------
-            // History navigation (Up / Down) – only when the composer is not
-            // empty or when the cursor is at the correct position, to avoid
+            // History navigation – only when the composer is not empty or
+            // when the cursor is at the correct position, to avoid
             // interfering with normal cursor movement.
             // -------------------------------------------------------------
-            KeyEvent {
-                code: KeyCode::Up | KeyCode::Down,
-                ..
-            } => {
+            _ if keymap.matches(KeyAction::HistoryPrevious, key_event)
+                || keymap.matches(KeyAction::HistoryNext, key_event) =>
+            {
                 if self
                     .history
                     .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
                 {
-                    let replace_text = match key_event.code {
-                        KeyCode::Up => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Down => self.history.navigate_down(&self.app_event_tx),
-                        _ => unreachable!(),
+                    let replace_text = if keymap.matches(KeyAction::HistoryPrevious, key_event) {
+                        self.history.navigate_up(&self.app_event_tx)
+                    } else {
+                        self.history.navigate_down(&self.app_event_tx)
                     };
                     if let Some(text) = replace_text {
                         self.set_text_content(text);
@@ -902,11 +920,7 @@ impl ChatComposer {
                 }
                 self.handle_input_basic(key_event)
             }
-            KeyEvent {
-                code: KeyCode::Enter,
-                modifiers: KeyModifiers::NONE,
-                ..
-            } => {
+            _ if keymap.matches(KeyAction::Submit, key_event) => {
                 // If the first line is a bare built-in slash command (no args),
                 // dispatch it even when the slash popup isn't visible. This preserves
                 // the workflow: type a prefix ("/di"), press Tab to complete to
@@ -1043,6 +1057,11 @@ impl ChatComposer {
                 // Do not clear attached_images here; ChatWidget drains them via take_recent_submission_images().
                 (InputResult::Submitted(text), true)
             }
+            // The textarea inserts a newline for any Enter, so translate
+            // custom newline bindings into one.
+            _ if keymap.matches(KeyAction::Newline, key_event) => {
+                self.handle_input_basic(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT))
+            }
             input => self.handle_input_basic(input),
         }
     }
@@ -1160,7 +1179,6 @@ impl ChatComposer {
 
         // Normal input handling
         self.textarea.input(input);
-        let text_after = self.textarea.text();
 
         // Update paste-burst heuristic for plain Char (no Ctrl/Alt) events.
         let crossterm::event::KeyEvent {
@@ -1183,7 +1201,15 @@ impl ChatComposer {
             }
         }
 
-        // Check if any placeholders were removed and remove their corresponding pending pastes
+        self.prune_attachments_to_text();
+
+        (InputResult::None, true)
+    }
+
+    /// Drops pending pastes and attached images whose placeholders are no
+    /// longer present in the text.
+    fn prune_attachments_to_text(&mut self) {
+        let text_after = self.textarea.text();
         self.pending_pastes
             .retain(|(placeholder, _)| text_after.contains(placeholder));
 
@@ -1209,8 +1235,6 @@ impl ChatComposer {
             }
             self.attached_images = kept;
         }
-
-        (InputResult::None, true)
     }
 
     /// Attempts to remove an image or paste placeholder if the cursor is at the end of one.
@@ -1360,14 +1384,7 @@ impl ChatComposer {
             return false;
         }
 
-        let toggles = matches!(
-            key_event,
-            KeyEvent {
-                code: KeyCode::Char('?'),
-                modifiers: KeyModifiers::NONE,
-                ..
-            } if self.is_empty()
-        );
+        let toggles = self.is_empty() && keymap().matches(KeyAction::ShowShortcuts, *key_event);
 
         if !toggles {
             return false;
@@ -1388,6 +1405,9 @@ impl ChatComposer {
             context_window_percent: self.context_window_percent,
             index_status: self.index_status_line.clone(),
             agents_status: self.agent_status_line.clone(),
+            tabs_status: self.tab_status_line.clone(),
+            model_pull_status: self.model_pull_status_line.clone(),
+            pinned_exec_output: self.pinned_exec_output.clone(),
             rate_limit_summaries: self.rate_limit_summaries.clone(),
         }
     }
@@ -1543,6 +1563,22 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_tab_status_line(&mut self, status: Option<String>) {
+        if self.tab_status_line != status {
+            self.tab_status_line = status;
+        }
+    }
+
+    pub(crate) fn set_model_pull_status_line(&mut self, status: Option<String>) {
+        if self.model_pull_status_line != status {
+            self.model_pull_status_line = status;
+        }
+    }
+
+    pub(crate) fn set_pinned_exec_output(&mut self, lines: Vec<String>) {
+        self.pinned_exec_output = lines;
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -2761,6 +2797,36 @@ mod tests {
         assert_eq!(vec![path], imgs);
     }
 
+    #[test]
+    fn external_editor_text_keeps_surviving_placeholders() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let kept = PathBuf::from("/tmp/kept.png");
+        composer.attach_image(kept.clone(), 32, 16, "PNG");
+        composer.attach_image(PathBuf::from("/tmp/dropped.png"), 8, 8, "PNG");
+
+        composer.set_text_from_external_editor(
+            "[kept.png 32x16] look at @src/main.rs\nand explain".to_string(),
+        );
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::Submitted(text) => {
+                assert_eq!(text, "[kept.png 32x16] look at @src/main.rs\nand explain")
+            }
+            _ => panic!("expected Submitted"),
+        }
+        assert_eq!(vec![kept], composer.take_recent_submission_images());
+    }
+
     #[test]
     fn attach_image_without_text_submits_empty_text_and_images() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
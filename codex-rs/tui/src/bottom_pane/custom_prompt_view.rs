@@ -14,6 +14,7 @@ use std::cell::RefCell;
 
 use crate::key_hint;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 
 use super::popup_consts::standard_popup_hint_line;
 
@@ -188,7 +189,7 @@ impl Renderable for CustomPromptView {
                 width: area.width,
                 height: 1,
             };
-            let spans: Vec<Span<'static>> = vec![gutter(), context_label.clone().cyan()];
+            let spans: Vec<Span<'static>> = vec![gutter(), context_label.clone().accent()];
             Paragraph::new(Line::from(spans)).render(context_area, buf);
             input_y = input_y.saturating_add(1);
         }
@@ -304,7 +305,7 @@ impl CustomPromptView {
 }
 
 fn gutter() -> Span<'static> {
-    "▌ ".cyan()
+    "▌ ".accent()
 }
 
 fn multiline_hint_line() -> Line<'static> {
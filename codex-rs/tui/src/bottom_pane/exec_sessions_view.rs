@@ -0,0 +1,442 @@
+//! Panel listing the background processes the model started through
+//! `exec_command`, so the user can watch, type into, kill or pin them.
+
+use std::collections::HashSet;
+
+use codex_ansi_escape::ansi_escape_line;
+use codex_core::protocol::Op;
+use codex_core::protocol::UnifiedExecSessionInfo;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::InsetRenderable;
+use crate::render::renderable::Renderable;
+use crate::status_indicator_widget::fmt_elapsed_compact;
+use crate::theme::ThemeStylize;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+
+/// Output lines shown for the selected session.
+const TAIL_LINES: usize = 8;
+
+pub(crate) struct ExecSessionsView {
+    /// `None` until the first listing arrives.
+    sessions: Option<Vec<UnifiedExecSessionInfo>>,
+    pinned: HashSet<i32>,
+    selected: usize,
+    /// Text being typed into the selected session.
+    input: Option<String>,
+    complete: bool,
+    app_event_tx: AppEventSender,
+}
+
+impl ExecSessionsView {
+    pub(crate) fn new(app_event_tx: AppEventSender) -> Self {
+        Self {
+            sessions: None,
+            pinned: HashSet::new(),
+            selected: 0,
+            input: None,
+            complete: false,
+            app_event_tx,
+        }
+    }
+
+    fn selected_session(&self) -> Option<&UnifiedExecSessionInfo> {
+        self.sessions.as_ref()?.get(self.selected)
+    }
+
+    fn session_count(&self) -> usize {
+        self.sessions.as_ref().map_or(0, Vec::len)
+    }
+
+    fn handle_input_key(&mut self, key_event: KeyEvent) {
+        let Some(input) = self.input.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Enter => {
+                let mut text = self.input.take().unwrap_or_default();
+                text.push('\n');
+                if let Some(session) = self.selected_session() {
+                    self.app_event_tx
+                        .send(AppEvent::CodexOp(Op::WriteUnifiedExecStdin {
+                            session_id: session.session_id,
+                            input: text,
+                        }));
+                }
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn as_renderable(&self) -> ColumnRenderable<'_> {
+        let mut title = vec!["Background terminals".bold()];
+        if self.session_count() > 0 {
+            title.push(format!(" ({})", self.session_count()).dim());
+        }
+        let mut rows: Vec<Box<dyn Renderable>> =
+            vec![Box::new(Line::from(title)), Box::new(Line::from(""))];
+
+        match &self.sessions {
+            None => rows.push(Box::new(Line::from("Loading…".dim()))),
+            Some(sessions) if sessions.is_empty() => rows.push(Box::new(Line::from(
+                "No background terminals are running.".dim(),
+            ))),
+            Some(sessions) => {
+                for (idx, session) in sessions.iter().enumerate() {
+                    rows.push(Box::new(self.session_line(idx, session)));
+                }
+            }
+        }
+
+        if let Some(session) = self.selected_session() {
+            let tail = tail_lines(&session.output_tail, TAIL_LINES);
+            if !tail.is_empty() {
+                rows.push(Box::new(Line::from("")));
+                let lines: Vec<Line<'static>> = tail
+                    .iter()
+                    .map(|line| ansi_escape_line(line).dim())
+                    .collect();
+                let body: Box<dyn Renderable> = Box::new(Paragraph::new(lines));
+                rows.push(Box::new(InsetRenderable::new(
+                    body,
+                    Insets::tlbr(0, 2, 0, 0),
+                )));
+            }
+        }
+
+        rows.push(Box::new(Line::from("")));
+        if let Some(input) = &self.input {
+            rows.push(Box::new(Line::from(vec![
+                "› ".accent(),
+                input.clone().into(),
+                " ".reversed(),
+            ])));
+            rows.push(Box::new(Line::from(vec![
+                key_hint::plain(KeyCode::Enter).into(),
+                " send line · ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " cancel".into(),
+            ])));
+        } else {
+            rows.push(Box::new(Line::from(vec![
+                key_hint::action_span(KeyAction::ScrollUp),
+                "/".into(),
+                key_hint::action_span(KeyAction::ScrollDown),
+                " select · ".into(),
+                key_hint::action_span(KeyAction::TypeIntoSession),
+                " type · ".into(),
+                key_hint::action_span(KeyAction::KillSession),
+                " kill · ".into(),
+                key_hint::action_span(KeyAction::PinSession),
+                " pin · ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " close".into(),
+            ])));
+        }
+
+        ColumnRenderable::with(rows)
+    }
+
+    fn session_line(&self, idx: usize, session: &UnifiedExecSessionInfo) -> Line<'static> {
+        let selected = idx == self.selected;
+        let mut spans: Vec<Span<'static>> = vec![
+            if selected {
+                "› ".accent()
+            } else {
+                "  ".into()
+            },
+            format!("#{} ", session.session_id).dim(),
+            if selected {
+                session.command.clone().accent()
+            } else {
+                session.command.clone().into()
+            },
+            format!("  {}", fmt_elapsed_compact(session.uptime_secs)).dim(),
+        ];
+        if self.pinned.contains(&session.session_id) {
+            spans.push("  pinned".accent());
+        }
+        Line::from(spans)
+    }
+}
+
+impl BottomPaneView for ExecSessionsView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        if self.input.is_some() {
+            self.handle_input_key(key_event);
+            return;
+        }
+        let keymap = keymap();
+        if key_event.code == KeyCode::Esc || keymap.matches(KeyAction::ExecSessions, key_event) {
+            self.complete = true;
+        } else if keymap.matches(KeyAction::ScrollUp, key_event) {
+            self.selected = self.selected.saturating_sub(1);
+        } else if keymap.matches(KeyAction::ScrollDown, key_event) {
+            if self.selected + 1 < self.session_count() {
+                self.selected += 1;
+            }
+        } else if keymap.matches(KeyAction::TypeIntoSession, key_event) {
+            if self.selected_session().is_some() {
+                self.input = Some(String::new());
+            }
+        } else if keymap.matches(KeyAction::KillSession, key_event) {
+            if let Some(session) = self.selected_session() {
+                self.app_event_tx
+                    .send(AppEvent::CodexOp(Op::TerminateUnifiedExecSession {
+                        session_id: session.session_id,
+                    }));
+            }
+        } else if keymap.matches(KeyAction::PinSession, key_event)
+            && let Some(session) = self.selected_session()
+        {
+            self.app_event_tx.send(AppEvent::ToggleExecSessionPin {
+                session_id: session.session_id,
+            });
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        match self.input.as_mut() {
+            Some(input) => {
+                input.push_str(&pasted);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update_exec_sessions(
+        &mut self,
+        sessions: &[UnifiedExecSessionInfo],
+        pinned: &HashSet<i32>,
+    ) -> bool {
+        // Keep the same session selected when others come and go.
+        let selected_id = self.selected_session().map(|session| session.session_id);
+        let position =
+            selected_id.and_then(|id| sessions.iter().position(|session| session.session_id == id));
+        // Input typed for a session that has gone must not reach another one.
+        if position.is_none() {
+            self.input = None;
+        }
+        self.selected = position
+            .unwrap_or(self.selected)
+            .min(sessions.len().saturating_sub(1));
+        self.sessions = Some(sessions.to_vec());
+        self.pinned = pinned.clone();
+        true
+    }
+}
+
+impl Renderable for ExecSessionsView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.as_renderable().render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.as_renderable().desired_height(width)
+    }
+}
+
+/// Last `max` lines of terminal output, keeping only what a carriage return
+/// would have left visible on each line.
+fn tail_lines(output: &str, max: usize) -> Vec<String> {
+    let lines: Vec<String> = output
+        .trim_end()
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line).to_string())
+        .collect();
+    lines[lines.len().saturating_sub(max)..].to_vec()
+}
+
+/// Footer lines for pinned sessions: each one's command and latest output.
+pub(crate) fn pinned_output_lines(
+    sessions: &[UnifiedExecSessionInfo],
+    pinned: &HashSet<i32>,
+) -> Vec<String> {
+    sessions
+        .iter()
+        .filter(|session| pinned.contains(&session.session_id))
+        .map(|session| {
+            let last = tail_lines(&session.output_tail, 1)
+                .pop()
+                .map(|line| {
+                    ansi_escape_line(&line)
+                        .spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .unwrap_or_default();
+            format!(
+                "#{} {} › {}",
+                session.session_id,
+                session.command,
+                last.trim()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+    use tokio::sync::mpsc::UnboundedReceiver;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn session(session_id: i32, command: &str, output_tail: &str) -> UnifiedExecSessionInfo {
+        UnifiedExecSessionInfo {
+            session_id,
+            command: command.to_string(),
+            cwd: PathBuf::from("/repo"),
+            uptime_secs: 65,
+            output_tail: output_tail.to_string(),
+        }
+    }
+
+    fn view_with_sessions() -> (ExecSessionsView, UnboundedReceiver<AppEvent>) {
+        let (tx_raw, rx) = unbounded_channel::<AppEvent>();
+        let mut view = ExecSessionsView::new(AppEventSender::new(tx_raw));
+        view.update_exec_sessions(
+            &[
+                session(1, "npm run dev", "ready on :3000\n"),
+                session(2, "cargo watch", "\x1b[32mFinished\x1b[0m\n"),
+            ],
+            &HashSet::new(),
+        );
+        (view, rx)
+    }
+
+    fn press(view: &mut ExecSessionsView, code: KeyCode) {
+        view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn render_lines(view: &ExecSessionsView) -> Vec<String> {
+        let width = 60;
+        let area = Rect::new(0, 0, width, view.desired_height(width));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..area.height)
+            .map(|row| {
+                (0..area.width)
+                    .map(|col| buf[(col, row)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lists_sessions_with_uptime_and_selected_output() {
+        let (view, _rx) = view_with_sessions();
+        let lines = render_lines(&view);
+        assert_eq!(lines[0], "Background terminals (2)");
+        assert_eq!(lines[2], "› #1 npm run dev  1m 05s");
+        assert_eq!(lines[3], "  #2 cargo watch  1m 05s");
+        assert_eq!(lines[5], "  ready on :3000");
+    }
+
+    #[test]
+    fn typed_line_is_written_to_the_selected_session() {
+        let (mut view, mut rx) = view_with_sessions();
+        press(&mut view, KeyCode::Down);
+        press(&mut view, KeyCode::Char('i'));
+        for c in "rs".chars() {
+            press(&mut view, KeyCode::Char(c));
+        }
+        press(&mut view, KeyCode::Enter);
+
+        match rx.try_recv() {
+            Ok(AppEvent::CodexOp(Op::WriteUnifiedExecStdin { session_id, input })) => {
+                assert_eq!((session_id, input.as_str()), (2, "rs\n"));
+            }
+            other => panic!("expected a stdin write, got {other:?}"),
+        }
+        assert!(!view.is_complete());
+    }
+
+    #[test]
+    fn selection_follows_the_session_when_the_list_changes() {
+        let (mut view, mut rx) = view_with_sessions();
+        press(&mut view, KeyCode::Down);
+        view.update_exec_sessions(&[session(2, "cargo watch", "")], &HashSet::new());
+        press(&mut view, KeyCode::Char('x'));
+
+        match rx.try_recv() {
+            Ok(AppEvent::CodexOp(Op::TerminateUnifiedExecSession { session_id })) => {
+                assert_eq!(session_id, 2);
+            }
+            other => panic!("expected a terminate op, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pending_input_is_dropped_when_its_session_goes_away() {
+        let (mut view, _rx) = view_with_sessions();
+        press(&mut view, KeyCode::Down);
+        press(&mut view, KeyCode::Char('i'));
+        press(&mut view, KeyCode::Char('r'));
+
+        view.update_exec_sessions(&[session(1, "npm run dev", "")], &HashSet::new());
+        assert_eq!(view.input, None);
+
+        press(&mut view, KeyCode::Char('i'));
+        view.update_exec_sessions(&[], &HashSet::new());
+        assert_eq!(view.input, None);
+    }
+
+    #[test]
+    fn pinned_lines_show_the_last_visible_output() {
+        let sessions = [
+            session(1, "npm run dev", "compiling\r\x1b[32mready\x1b[0m\n\n"),
+            session(2, "cargo watch", "ignored"),
+        ];
+        assert_eq!(
+            pinned_output_lines(&sessions, &HashSet::from([1])),
+            vec!["#1 npm run dev › ready".to_string()]
+        );
+    }
+}
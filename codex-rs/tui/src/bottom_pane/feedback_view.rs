@@ -19,6 +19,7 @@ use crate::app_event::FeedbackCategory;
 use crate::app_event_sender::AppEventSender;
 use crate::history_cell;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 
 use super::CancellationEvent;
 use super::bottom_pane_view::BottomPaneView;
@@ -100,7 +101,7 @@ impl FeedbackNoteView {
                             "{prefix} Please open an issue using the following URL:"
                         )),
                         "".into(),
-                        Line::from(vec!["  ".into(), issue_url.cyan().underlined()]),
+                        Line::from(vec!["  ".into(), issue_url.accent().underlined()]),
                         "".into(),
                         Line::from(vec![
                             "  Or mention your thread ID ".into(),
@@ -287,7 +288,7 @@ impl FeedbackNoteView {
 }
 
 fn gutter() -> Span<'static> {
-    "▌ ".cyan()
+    "▌ ".accent()
 }
 
 fn feedback_title_and_placeholder(category: FeedbackCategory) -> (String, String) {
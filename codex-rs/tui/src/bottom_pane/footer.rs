@@ -1,6 +1,8 @@
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
 use crate::render::line_utils::prefix_lines;
+use crate::theme::ThemeStylize;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
@@ -20,6 +22,10 @@ pub(crate) struct FooterProps {
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) index_status: Option<String>,
     pub(crate) agents_status: Option<String>,
+    pub(crate) tabs_status: Option<String>,
+    pub(crate) model_pull_status: Option<String>,
+    /// Latest output of each pinned background exec session.
+    pub(crate) pinned_exec_output: Vec<String>,
     pub(crate) rate_limit_summaries: Vec<String>,
 }
 
@@ -84,7 +90,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
                 context_and_limits_line(props.context_window_percent, &props.rate_limit_summaries);
             line.extend(vec![
                 Span::from(" · ").dim(),
-                key_hint::plain(KeyCode::Char('?')).into(),
+                key_hint::action_span(KeyAction::ShowShortcuts),
                 " for shortcuts".dim(),
             ]);
             vec![line]
@@ -103,7 +109,16 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
         lines.push(Line::from(status.clone()).dim());
     }
     if let Some(status) = &props.agents_status {
-        lines.push(Line::from(status.clone()).cyan());
+        lines.push(Line::from(status.clone()).accent());
+    }
+    if let Some(status) = &props.tabs_status {
+        lines.push(Line::from(status.clone()).accent());
+    }
+    if let Some(status) = &props.model_pull_status {
+        lines.push(Line::from(status.clone()).dim());
+    }
+    for output in &props.pinned_exec_output {
+        lines.push(Line::from(output.clone()).dim());
     }
     lines
 }
@@ -126,7 +141,7 @@ fn ctrl_c_reminder_line(state: CtrlCReminderState) -> Line<'static> {
         "quit"
     };
     Line::from(vec![
-        key_hint::ctrl(KeyCode::Char('c')).into(),
+        key_hint::action_span(KeyAction::Interrupt),
         format!(" again to {action}").into(),
     ])
     .dim()
@@ -154,6 +169,7 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
     let mut paste_image = Line::from("");
     let mut edit_previous = Line::from("");
     let mut quit = Line::from("");
+    let mut open_editor = Line::from("");
     let mut show_transcript = Line::from("");
 
     for descriptor in SHORTCUTS {
@@ -165,6 +181,7 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
                 ShortcutId::PasteImage => paste_image = text,
                 ShortcutId::EditPrevious => edit_previous = text,
                 ShortcutId::Quit => quit = text,
+                ShortcutId::OpenEditor => open_editor = text,
                 ShortcutId::ShowTranscript => show_transcript = text,
             }
         }
@@ -177,7 +194,7 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
         paste_image,
         edit_previous,
         quit,
-        Line::from(""),
+        open_editor,
         show_transcript,
     ];
 
@@ -255,53 +272,42 @@ enum ShortcutId {
     PasteImage,
     EditPrevious,
     Quit,
+    OpenEditor,
     ShowTranscript,
 }
 
+/// Key shown for a shortcut: either fixed, or whatever the keymap binds to an
+/// action.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-struct ShortcutBinding {
-    key: KeyBinding,
-    condition: DisplayCondition,
+enum ShortcutKey {
+    Fixed(KeyBinding),
+    Action(KeyAction),
 }
 
-impl ShortcutBinding {
-    fn matches(&self, state: ShortcutsState) -> bool {
-        self.condition.matches(state)
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum DisplayCondition {
-    Always,
-    WhenShiftEnterHint,
-    WhenNotShiftEnterHint,
-}
-
-impl DisplayCondition {
-    fn matches(self, state: ShortcutsState) -> bool {
+impl ShortcutKey {
+    fn resolve(self, state: ShortcutsState) -> Option<KeyBinding> {
         match self {
-            DisplayCondition::Always => true,
-            DisplayCondition::WhenShiftEnterHint => state.use_shift_enter_hint,
-            DisplayCondition::WhenNotShiftEnterHint => !state.use_shift_enter_hint,
+            ShortcutKey::Fixed(key) => Some(key),
+            // Skip shift+enter on terminals that cannot report it.
+            ShortcutKey::Action(action) => key_hint::actions(action)
+                .iter()
+                .copied()
+                .find(|key| state.use_shift_enter_hint || *key != key_hint::shift(KeyCode::Enter)),
         }
     }
 }
 
 struct ShortcutDescriptor {
     id: ShortcutId,
-    bindings: &'static [ShortcutBinding],
+    key: ShortcutKey,
     prefix: &'static str,
     label: &'static str,
 }
 
 impl ShortcutDescriptor {
-    fn binding_for(&self, state: ShortcutsState) -> Option<&'static ShortcutBinding> {
-        self.bindings.iter().find(|binding| binding.matches(state))
-    }
-
     fn overlay_entry(&self, state: ShortcutsState) -> Option<Line<'static>> {
-        let binding = self.binding_for(state)?;
-        let mut line = Line::from(vec![self.prefix.into(), binding.key.into()]);
+        let key = self.key.resolve(state)?;
+        let mut line = Line::from(vec![self.prefix.into(), key.into()]);
         match self.id {
             ShortcutId::EditPrevious => {
                 if state.esc_backtrack_hint {
@@ -323,70 +329,49 @@ impl ShortcutDescriptor {
 const SHORTCUTS: &[ShortcutDescriptor] = &[
     ShortcutDescriptor {
         id: ShortcutId::Commands,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Char('/')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::plain(KeyCode::Char('/'))),
         prefix: "",
         label: " for commands",
     },
     ShortcutDescriptor {
         id: ShortcutId::InsertNewline,
-        bindings: &[
-            ShortcutBinding {
-                key: key_hint::shift(KeyCode::Enter),
-                condition: DisplayCondition::WhenShiftEnterHint,
-            },
-            ShortcutBinding {
-                key: key_hint::ctrl(KeyCode::Char('j')),
-                condition: DisplayCondition::WhenNotShiftEnterHint,
-            },
-        ],
+        key: ShortcutKey::Action(KeyAction::Newline),
         prefix: "",
         label: " for newline",
     },
     ShortcutDescriptor {
         id: ShortcutId::FilePaths,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Char('@')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::plain(KeyCode::Char('@'))),
         prefix: "",
         label: " for file paths",
     },
     ShortcutDescriptor {
         id: ShortcutId::PasteImage,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('v')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Action(KeyAction::PasteImage),
         prefix: "",
         label: " to paste images",
     },
     ShortcutDescriptor {
         id: ShortcutId::EditPrevious,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Esc),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::plain(KeyCode::Esc)),
         prefix: "",
         label: "",
     },
     ShortcutDescriptor {
         id: ShortcutId::Quit,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('c')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Action(KeyAction::Interrupt),
         prefix: "",
         label: " to exit",
     },
+    ShortcutDescriptor {
+        id: ShortcutId::OpenEditor,
+        key: ShortcutKey::Action(KeyAction::OpenEditor),
+        prefix: "",
+        label: " to edit in $EDITOR",
+    },
     ShortcutDescriptor {
         id: ShortcutId::ShowTranscript,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('t')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Action(KeyAction::Transcript),
         prefix: "",
         label: " to view transcript",
     },
@@ -423,6 +408,9 @@ mod tests {
                 context_window_percent: None,
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -437,6 +425,9 @@ mod tests {
                 context_window_percent: None,
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -451,6 +442,9 @@ mod tests {
                 context_window_percent: None,
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -465,6 +459,9 @@ mod tests {
                 context_window_percent: None,
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -479,6 +476,9 @@ mod tests {
                 context_window_percent: None,
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -493,6 +493,9 @@ mod tests {
                 context_window_percent: None,
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -507,6 +510,9 @@ mod tests {
                 context_window_percent: Some(72),
                 index_status: None,
                 agents_status: None,
+                tabs_status: None,
+                model_pull_status: None,
+                pinned_exec_output: Vec::new(),
                 rate_limit_summaries: vec![],
             },
         );
@@ -1,4 +1,5 @@
 //! Bottom pane: shows the ChatComposer or a BottomPaneView, if one is active.
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::app_event_sender::AppEventSender;
@@ -8,6 +9,7 @@ use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
+use codex_core::protocol::UnifiedExecSessionInfo;
 use codex_file_search::FileMatch;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -23,9 +25,13 @@ mod chat_composer;
 mod chat_composer_history;
 mod command_popup;
 pub mod custom_prompt_view;
+mod exec_sessions_view;
+pub(crate) use exec_sessions_view::ExecSessionsView;
+pub(crate) use exec_sessions_view::pinned_output_lines;
 mod file_search_popup;
 mod footer;
 mod list_selection_view;
+mod patch_hunk_review;
 mod prompt_args;
 pub(crate) use list_selection_view::SelectionViewParams;
 mod feedback_view;
@@ -216,6 +222,13 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Replace the composer text with text returned from an external editor,
+    /// keeping attachments whose placeholders are still present.
+    pub(crate) fn set_composer_text_from_editor(&mut self, text: String) {
+        self.composer.set_text_from_external_editor(text);
+        self.request_redraw();
+    }
+
     pub(crate) fn clear_composer_for_ctrl_c(&mut self) {
         self.composer.clear_for_ctrl_c();
         self.request_redraw();
@@ -343,6 +356,38 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_tab_status_line(&mut self, status: Option<String>) {
+        self.composer.set_tab_status_line(status);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_model_pull_status_line(&mut self, status: Option<String>) {
+        self.composer.set_model_pull_status_line(status);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_pinned_exec_output(&mut self, lines: Vec<String>) {
+        self.composer.set_pinned_exec_output(lines);
+        self.request_redraw();
+    }
+
+    /// Pass a fresh session listing to any open exec sessions panel and
+    /// return whether one is open.
+    pub(crate) fn update_exec_sessions(
+        &mut self,
+        sessions: &[UnifiedExecSessionInfo],
+        pinned: &HashSet<i32>,
+    ) -> bool {
+        let mut shown = false;
+        for view in &mut self.view_stack {
+            shown |= view.update_exec_sessions(sessions, pinned);
+        }
+        if shown {
+            self.request_redraw();
+        }
+        shown
+    }
+
     pub(crate) fn set_rate_limit_summaries(&mut self, summaries: Vec<String>) {
         self.composer.set_rate_limit_summaries(summaries);
         self.request_redraw();
@@ -360,6 +405,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Highlight the queued message at `index`, or none.
+    pub(crate) fn select_queued_user_message(&mut self, index: Option<usize>) {
+        self.queued_user_messages.selected = index;
+        self.request_redraw();
+    }
+
     /// Update custom prompts available for the slash popup.
     pub(crate) fn set_custom_prompts(&mut self, prompts: Vec<CustomPrompt>) {
         self.composer.set_custom_prompts(prompts);
@@ -374,6 +425,11 @@ impl BottomPane {
         self.is_task_running
     }
 
+    /// True when keys go to the composer rather than a modal view.
+    pub(crate) fn composer_has_focus(&self) -> bool {
+        self.view_stack.is_empty()
+    }
+
     /// Return true when the pane is in the regular composer state without any
     /// overlays or popups and not running a task. This is the safe context to
     /// use Esc-Esc for backtracking from the main view.
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::protocol::FileChange;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::diff_render::display_path_for;
+use crate::key_hint;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::InsetRenderable;
+use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
+
+/// What the approval overlay should do after a key press in the hunk review.
+#[derive(Debug, PartialEq)]
+pub(crate) enum HunkReviewOutcome {
+    Pending,
+    /// Submit the review; maps each path to the indices of its rejected hunks.
+    Submit(HashMap<PathBuf, Vec<usize>>),
+    /// Return to the whole-patch options.
+    Back,
+}
+
+struct ReviewHunk {
+    path: PathBuf,
+    /// Index within the file, as numbered by [`FileChange::hunks`].
+    index: usize,
+    /// Number of hunks in the file.
+    of: usize,
+    /// The single-hunk change rendered as a diff.
+    change: FileChange,
+    accepted: bool,
+}
+
+/// Pages through every hunk of a patch so the user can accept or reject
+/// each one before approving.
+pub(crate) struct PatchHunkReview {
+    cwd: PathBuf,
+    move_paths: HashMap<PathBuf, PathBuf>,
+    hunks: Vec<ReviewHunk>,
+    cursor: usize,
+}
+
+impl PatchHunkReview {
+    pub(crate) fn new(changes: &HashMap<PathBuf, FileChange>, cwd: PathBuf) -> Self {
+        let mut paths: Vec<&PathBuf> = changes.keys().collect();
+        paths.sort();
+
+        let mut hunks = Vec::new();
+        let mut move_paths = HashMap::new();
+        for path in paths {
+            let change = &changes[path];
+            if let FileChange::Update {
+                move_path: Some(dest),
+                ..
+            } = change
+            {
+                move_paths.insert(path.clone(), dest.clone());
+            }
+            let pieces = change.hunks();
+            let of = pieces.len();
+            for (index, piece) in pieces.into_iter().enumerate() {
+                let change = match change {
+                    FileChange::Update { .. } => FileChange::Update {
+                        unified_diff: piece.to_string(),
+                        move_path: None,
+                    },
+                    other => other.clone(),
+                };
+                hunks.push(ReviewHunk {
+                    path: path.clone(),
+                    index,
+                    of,
+                    change,
+                    accepted: true,
+                });
+            }
+        }
+
+        Self {
+            cwd,
+            move_paths,
+            hunks,
+            cursor: 0,
+        }
+    }
+
+    /// Total number of hunks across all files.
+    pub(crate) fn hunk_count(changes: &HashMap<PathBuf, FileChange>) -> usize {
+        changes.values().map(|change| change.hunks().len()).sum()
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> HunkReviewOutcome {
+        if key_event.kind == KeyEventKind::Release {
+            return HunkReviewOutcome::Pending;
+        }
+        let keymap = keymap();
+        match key_event.code {
+            _ if keymap.matches(KeyAction::Approve, key_event) => self.mark_and_advance(true),
+            _ if keymap.matches(KeyAction::Deny, key_event) => self.mark_and_advance(false),
+            KeyCode::Up | KeyCode::Left | KeyCode::Char('k') => {
+                self.cursor = self.cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Right | KeyCode::Char('j') => self.advance(),
+            KeyCode::Char(' ') => {
+                if let Some(hunk) = self.hunks.get_mut(self.cursor) {
+                    hunk.accepted = !hunk.accepted;
+                }
+            }
+            KeyCode::Enter => return HunkReviewOutcome::Submit(self.rejected_hunks()),
+            KeyCode::Esc => return HunkReviewOutcome::Back,
+            _ => {}
+        }
+        HunkReviewOutcome::Pending
+    }
+
+    fn mark_and_advance(&mut self, accepted: bool) {
+        if let Some(hunk) = self.hunks.get_mut(self.cursor) {
+            hunk.accepted = accepted;
+        }
+        self.advance();
+    }
+
+    fn advance(&mut self) {
+        if self.cursor + 1 < self.hunks.len() {
+            self.cursor += 1;
+        }
+    }
+
+    fn rejected_hunks(&self) -> HashMap<PathBuf, Vec<usize>> {
+        let mut rejected: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for hunk in self.hunks.iter().filter(|hunk| !hunk.accepted) {
+            rejected
+                .entry(hunk.path.clone())
+                .or_default()
+                .push(hunk.index);
+        }
+        rejected
+    }
+
+    fn accepted_count(&self) -> usize {
+        self.hunks.iter().filter(|hunk| hunk.accepted).count()
+    }
+
+    fn as_renderable(&self) -> ColumnRenderable<'_> {
+        let mut rows: Vec<Box<dyn Renderable>> = vec![
+            Box::new(Line::from("Review the edits hunk by hunk".bold())),
+            Box::new(Line::from("")),
+        ];
+
+        if let Some(hunk) = self.hunks.get(self.cursor) {
+            let mut title: Vec<Span<'static>> =
+                vec![display_path_for(&hunk.path, &self.cwd).bold()];
+            if let Some(dest) = self.move_paths.get(&hunk.path) {
+                title.push(" → ".dim());
+                title.push(display_path_for(dest, &self.cwd).bold());
+            }
+            if hunk.of > 1 {
+                title.push(format!("  hunk {} of {}", hunk.index + 1, hunk.of).dim());
+            }
+            title.push("  ".into());
+            title.push(if hunk.accepted {
+                "✔ accept".success()
+            } else {
+                "✗ reject".error()
+            });
+            rows.push(Box::new(Line::from(title)));
+            rows.push(Box::new(Line::from("")));
+            let body: Box<dyn Renderable> = match &hunk.change {
+                FileChange::Update { unified_diff, .. } if !unified_diff.starts_with("@@") => {
+                    let summary = unified_diff.trim();
+                    let summary = if summary.is_empty() {
+                        "rename only"
+                    } else {
+                        summary
+                    };
+                    Box::new(Line::from(summary.to_string().dim()))
+                }
+                change => Box::new(change.clone()),
+            };
+            rows.push(Box::new(InsetRenderable::new(
+                body,
+                Insets::tlbr(0, 2, 0, 0),
+            )));
+            rows.push(Box::new(Line::from("")));
+        }
+
+        rows.push(Box::new(Line::from(
+            format!(
+                "Hunk {} of {} · {} accepted",
+                (self.cursor + 1).min(self.hunks.len()),
+                self.hunks.len(),
+                self.accepted_count()
+            )
+            .dim(),
+        )));
+        rows.push(Box::new(Line::from(vec![
+            key_hint::action_span(KeyAction::Approve),
+            "/".into(),
+            key_hint::action_span(KeyAction::Deny),
+            " accept/reject · ".into(),
+            "space".into(),
+            " toggle · ".into(),
+            key_hint::plain(KeyCode::Up).into(),
+            "/".into(),
+            key_hint::plain(KeyCode::Down).into(),
+            " move · ".into(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " apply accepted · ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " back".into(),
+        ])));
+
+        ColumnRenderable::with(rows)
+    }
+}
+
+impl Renderable for PatchHunkReview {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.as_renderable().render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.as_renderable().desired_height(width)
+    }
+}
@@ -1,4 +1,3 @@
-use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -6,19 +5,24 @@ use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 
 use crate::key_hint;
+use crate::keymap::KeyAction;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 
 /// Widget that displays a list of user messages queued while a turn is in progress.
 pub(crate) struct QueuedUserMessages {
     pub messages: Vec<String>,
+    /// Message picked for editing, drawn with a marker.
+    pub selected: Option<usize>,
 }
 
 impl QueuedUserMessages {
     pub(crate) fn new() -> Self {
         Self {
             messages: Vec::new(),
+            selected: None,
         }
     }
 
@@ -29,11 +33,23 @@ impl QueuedUserMessages {
 
         let mut lines = vec![];
 
-        for message in &self.messages {
+        for (index, message) in self.messages.iter().enumerate() {
+            let selected = self.selected == Some(index);
+            let indent = if selected {
+                Line::from("  › ".accent())
+            } else {
+                Line::from("  ↳ ".dim())
+            };
             let wrapped = word_wrap_lines(
-                message.lines().map(|line| line.dim().italic()),
+                message.lines().map(|line| {
+                    if selected {
+                        line.italic()
+                    } else {
+                        line.dim().italic()
+                    }
+                }),
                 RtOptions::new(width as usize)
-                    .initial_indent(Line::from("  ↳ ".dim()))
+                    .initial_indent(indent)
                     .subsequent_indent(Line::from("    ")),
             );
             let len = wrapped.len();
@@ -48,7 +64,7 @@ impl QueuedUserMessages {
         lines.push(
             Line::from(vec![
                 "    ".into(),
-                key_hint::alt(KeyCode::Up).into(),
+                key_hint::action_span(KeyAction::EditQueuedMessage),
                 " edit".into(),
             ])
             .dim(),
@@ -154,4 +170,23 @@ mod tests {
         queue.render(Rect::new(0, 0, width, height), &mut buf);
         assert_snapshot!("render_many_line_message", format!("{buf:?}"));
     }
+
+    #[test]
+    fn selected_message_is_marked() {
+        let mut queue = QueuedUserMessages::new();
+        queue.messages.push("first".to_string());
+        queue.messages.push("second".to_string());
+        queue.selected = Some(1);
+        let width = 40;
+        let height = queue.desired_height(width);
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        queue.render(Rect::new(0, 0, width, height), &mut buf);
+        let row = |y: u16| {
+            (0..width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        };
+        assert!(row(0).starts_with("  ↳ first"));
+        assert!(row(1).starts_with("  › second"));
+    }
 }
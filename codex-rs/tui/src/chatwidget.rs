@@ -1,10 +1,13 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+use codex_core::BUILT_IN_OSS_MODEL_PROVIDER_ID;
 use codex_core::ModelProviderInfo;
 use codex_core::config::Config;
 use codex_core::config::types::Notifications;
@@ -35,6 +38,7 @@ use codex_core::protocol::McpListToolsResponseEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::MemoryPreviewEvent;
+use codex_core::protocol::ModelFailoverEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -47,6 +51,9 @@ use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
+use codex_core::protocol::UnifiedExecSessionInfo;
+use codex_core::protocol::UnifiedExecSessionsEvent;
+use codex_core::protocol::UsageCost;
 use codex_core::protocol::UserMessageEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
@@ -55,19 +62,18 @@ use codex_core::protocol::WebSearchEndEvent;
 use codex_protocol::ConversationId;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::user_input::UserInput;
-use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
-use crossterm::event::KeyModifiers;
 use rand::Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::unbounded_channel;
 use tracing::debug;
 
 use crate::app_event::AppEvent;
@@ -76,11 +82,13 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::ExecSessionsView;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
+use crate::bottom_pane::pinned_output_lines;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::diff_render::display_path_for;
@@ -93,6 +101,8 @@ use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::MemorySuggestionEntry;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::markdown::append_markdown;
 #[cfg(target_os = "windows")]
 use crate::onboarding::WSL_INSTRUCTIONS;
@@ -106,6 +116,7 @@ use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::status::compose_rate_limit_footer;
 use crate::text_formatting::truncate_text;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 mod interrupts;
 use self::interrupts::InterruptManager;
@@ -148,6 +159,9 @@ struct RunningCommand {
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
 const NUDGE_MODEL_SLUG: &str = "gpt-5-codex-mini";
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
+/// How often background exec sessions are re-listed while the panel is open
+/// or a session is pinned.
+const EXEC_SESSIONS_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Default)]
 struct RateLimitWarningState {
@@ -266,6 +280,10 @@ pub(crate) struct ChatWidget {
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
+    usage_cost: Option<UsageCost>,
+    /// Latest aggregated diff of the current (or most recent) turn; cleared
+    /// when a new turn starts so a turn without edits shows no stale diff.
+    last_turn_diff: Option<String>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_switch_prompt: RateLimitSwitchPromptState,
     // Stream lifecycle controller
@@ -291,6 +309,8 @@ pub(crate) struct ChatWidget {
     suppress_session_configured_redraw: bool,
     // User messages queued while a turn is in progress
     queued_user_messages: VecDeque<UserMessage>,
+    // Queued message picked with `select_queued_message`, newest first
+    selected_queued_message: Option<usize>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
     pending_context_notes: VecDeque<String>,
@@ -304,6 +324,11 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Latest listing of background exec sessions and the ones pinned to the footer.
+    exec_sessions: Vec<UnifiedExecSessionInfo>,
+    pinned_exec_sessions: HashSet<i32>,
+    // Whether a `PollExecSessions` tick is already on its way.
+    exec_sessions_poll_scheduled: bool,
 }
 
 struct UserMessage {
@@ -311,6 +336,14 @@ struct UserMessage {
     image_paths: Vec<PathBuf>,
 }
 
+/// What the external editor is editing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExternalEditTarget {
+    Composer,
+    /// The queued message at this index.
+    QueuedMessage(usize),
+}
+
 impl From<String> for UserMessage {
     fn from(text: String) -> Self {
         Self {
@@ -474,6 +507,7 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.last_turn_diff = None;
         self.request_redraw();
     }
 
@@ -631,6 +665,20 @@ impl ChatWidget {
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
+    fn on_model_failover(&mut self, event: ModelFailoverEvent) {
+        let ModelFailoverEvent {
+            from_provider,
+            from_model,
+            to_provider,
+            to_model,
+            reason,
+        } = event;
+        let reason = reason.to_string().replace('_', " ");
+        self.on_warning(format!(
+            "{from_model} ({from_provider}) failed with a {reason} error; answering with {to_model} ({to_provider}) instead."
+        ));
+    }
+
     fn on_memory_preview(&mut self, event: MemoryPreviewEvent) {
         self.app_event_tx
             .send(AppEvent::OpenMemoryPreview { preview: event });
@@ -737,6 +785,7 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        self.last_turn_diff = Some(unified_diff);
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -1040,6 +1089,50 @@ impl ChatWidget {
         let placeholder = EXAMPLE_PROMPTS[rng.random_range(0..EXAMPLE_PROMPTS.len())].to_string();
         let codex_op_tx = spawn_agent(config.clone(), app_event_tx.clone(), conversation_manager);
 
+        Self::with_op_sender(
+            ChatWidgetInit {
+                config,
+                frame_requester,
+                app_event_tx,
+                initial_prompt,
+                initial_images,
+                enhanced_keys_supported,
+                auth_manager,
+                feedback,
+            },
+            codex_op_tx,
+            placeholder,
+        )
+    }
+
+    /// Create a ChatWidget that is not attached to an agent. Ops it submits
+    /// are delivered to the returned receiver instead; session replay uses
+    /// this to drive the widget purely from recorded events.
+    pub(crate) fn new_detached(common: ChatWidgetInit) -> (Self, UnboundedReceiver<Op>) {
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        // Fixed placeholder so replays render identically every run.
+        let placeholder = EXAMPLE_PROMPTS[0].to_string();
+        (
+            Self::with_op_sender(common, codex_op_tx, placeholder),
+            codex_op_rx,
+        )
+    }
+
+    fn with_op_sender(
+        common: ChatWidgetInit,
+        codex_op_tx: UnboundedSender<Op>,
+        placeholder: String,
+    ) -> Self {
+        let ChatWidgetInit {
+            config,
+            frame_requester,
+            app_event_tx,
+            initial_prompt,
+            initial_images,
+            enhanced_keys_supported,
+            auth_manager,
+            feedback,
+        } = common;
         Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -1063,6 +1156,8 @@ impl ChatWidget {
             ),
             token_info: None,
             rate_limit_snapshot: None,
+            usage_cost: None,
+            last_turn_diff: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
             stream_controller: None,
@@ -1075,6 +1170,7 @@ impl ChatWidget {
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
+            selected_queued_message: None,
             show_welcome_banner: true,
             suppress_session_configured_redraw: false,
             pending_notification: None,
@@ -1084,6 +1180,9 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            exec_sessions: Vec::new(),
+            pinned_exec_sessions: HashSet::new(),
+            exec_sessions_poll_scheduled: false,
         }
     }
 
@@ -1132,6 +1231,8 @@ impl ChatWidget {
             ),
             token_info: None,
             rate_limit_snapshot: None,
+            usage_cost: None,
+            last_turn_diff: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
             stream_controller: None,
@@ -1144,6 +1245,7 @@ impl ChatWidget {
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
+            selected_queued_message: None,
             show_welcome_banner: true,
             suppress_session_configured_redraw: true,
             pending_notification: None,
@@ -1153,67 +1255,75 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            exec_sessions: Vec::new(),
+            pinned_exec_sessions: HashSet::new(),
+            exec_sessions_poll_scheduled: false,
         }
     }
 
     pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event {
-            KeyEvent {
-                code: KeyCode::Char(c),
-                modifiers,
-                kind: KeyEventKind::Press,
-                ..
-            } if modifiers.contains(KeyModifiers::CONTROL) && c.eq_ignore_ascii_case(&'c') => {
+        let keymap = keymap();
+        if key_event.kind == KeyEventKind::Press {
+            if keymap.matches(KeyAction::Interrupt, key_event) {
                 self.on_ctrl_c();
                 return;
             }
-            KeyEvent {
-                code: KeyCode::Char(c),
-                modifiers,
-                kind: KeyEventKind::Press,
-                ..
-            } if modifiers.contains(KeyModifiers::CONTROL) && c.eq_ignore_ascii_case(&'v') => {
+            if keymap.matches(KeyAction::PasteImage, key_event) {
                 if let Ok((path, info)) = paste_image_to_temp_png() {
                     self.attach_image(path, info.width, info.height, info.encoded_format.label());
                 }
                 return;
             }
-            other if other.kind == KeyEventKind::Press => {
-                self.bottom_pane.clear_ctrl_c_quit_hint();
+            self.bottom_pane.clear_ctrl_c_quit_hint();
+        }
+
+        if key_event.kind == KeyEventKind::Press
+            && keymap.matches(KeyAction::EditQueuedMessage, key_event)
+            && !self.queued_user_messages.is_empty()
+        {
+            // Prefer the selected item, then the most recently queued one.
+            let index = self
+                .selected_queued_message
+                .take()
+                .unwrap_or(self.queued_user_messages.len() - 1);
+            if let Some(user_message) = self.queued_user_messages.remove(index) {
+                self.bottom_pane.set_composer_text(user_message.text);
+                self.refresh_queued_user_messages();
+                self.request_redraw();
             }
-            _ => {}
+            return;
         }
 
-        match key_event {
-            KeyEvent {
-                code: KeyCode::Up,
-                modifiers: KeyModifiers::ALT,
-                kind: KeyEventKind::Press,
-                ..
-            } if !self.queued_user_messages.is_empty() => {
-                // Prefer the most recently queued item.
-                if let Some(user_message) = self.queued_user_messages.pop_back() {
-                    self.bottom_pane.set_composer_text(user_message.text);
-                    self.refresh_queued_user_messages();
-                    self.request_redraw();
-                }
+        if key_event.kind == KeyEventKind::Press
+            && keymap.matches(KeyAction::SelectQueuedMessage, key_event)
+            && !self.queued_user_messages.is_empty()
+        {
+            self.select_next_queued_message();
+            return;
+        }
+
+        // While the panel is open the key reaches it and closes it instead.
+        if key_event.kind == KeyEventKind::Press
+            && keymap.matches(KeyAction::ExecSessions, key_event)
+            && self.bottom_pane.composer_has_focus()
+        {
+            self.open_exec_sessions_panel();
+            return;
+        }
+
+        match self.bottom_pane.handle_key_event(key_event) {
+            InputResult::Submitted(text) => {
+                // If a task is running, queue the user input to be sent after the turn completes.
+                let user_message = UserMessage {
+                    text,
+                    image_paths: self.bottom_pane.take_recent_submission_images(),
+                };
+                self.queue_user_message(user_message);
             }
-            _ => {
-                match self.bottom_pane.handle_key_event(key_event) {
-                    InputResult::Submitted(text) => {
-                        // If a task is running, queue the user input to be sent after the turn completes.
-                        let user_message = UserMessage {
-                            text,
-                            image_paths: self.bottom_pane.take_recent_submission_images(),
-                        };
-                        self.queue_user_message(user_message);
-                    }
-                    InputResult::Command { command, args } => {
-                        self.dispatch_command(command, args);
-                    }
-                    InputResult::None => {}
-                }
+            InputResult::Command { command, args } => {
+                self.dispatch_command(command, args);
             }
+            InputResult::None => {}
         }
     }
 
@@ -1670,6 +1780,9 @@ impl ChatWidget {
             EventMsg::TokenCount(ev) => {
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
+                if ev.cost.is_some() {
+                    self.usage_cost = ev.cost;
+                }
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
             EventMsg::Error(ErrorEvent { message }) => self.on_error(message),
@@ -1683,6 +1796,9 @@ impl ChatWidget {
                 TurnAbortReason::ReviewEnded => {
                     self.on_interrupted_turn(ev.reason);
                 }
+                TurnAbortReason::BudgetExceeded => {
+                    self.on_error("Turn aborted: a token or cost budget was exhausted".to_owned())
+                }
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
             EventMsg::ExecApprovalRequest(ev) => {
@@ -1705,6 +1821,7 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::UnifiedExecSessions(ev) => self.on_unified_exec_sessions(ev),
             EventMsg::MemoryPreview(preview) => self.on_memory_preview(preview),
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
@@ -1715,6 +1832,7 @@ impl ChatWidget {
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
             EventMsg::StreamError(StreamErrorEvent { message }) => self.on_stream_error(message),
+            EventMsg::ModelFailover(ev) => self.on_model_failover(ev),
             EventMsg::UserMessage(ev) => {
                 if from_replay {
                     self.on_user_message_event(ev);
@@ -1789,6 +1907,15 @@ impl ChatWidget {
         }
     }
 
+    /// Show a prompt recovered from a recorded `UserInput` op. Live sessions
+    /// add the prompt cell on submit, so session replay has to do it here.
+    pub(crate) fn add_replayed_user_prompt(&mut self, message: String) {
+        self.on_user_message_event(UserMessageEvent {
+            message,
+            images: None,
+        });
+    }
+
     fn request_exit(&self) {
         self.app_event_tx.send(AppEvent::ExitRequest);
     }
@@ -1830,6 +1957,9 @@ impl ChatWidget {
             return;
         }
         if let Some(user_message) = self.queued_user_messages.pop_front() {
+            self.selected_queued_message = self
+                .selected_queued_message
+                .and_then(|index| index.checked_sub(1));
             self.submit_user_message(user_message);
         }
         // Update the list to reflect the remaining queued messages (if any).
@@ -1843,7 +1973,26 @@ impl ChatWidget {
             .iter()
             .map(|m| m.text.clone())
             .collect();
+        self.selected_queued_message = self
+            .selected_queued_message
+            .filter(|index| *index < messages.len());
         self.bottom_pane.set_queued_user_messages(messages);
+        self.bottom_pane
+            .select_queued_user_message(self.selected_queued_message);
+    }
+
+    /// Step the queued-message selection from the newest message towards the
+    /// oldest, then back to no selection.
+    fn select_next_queued_message(&mut self) {
+        let len = self.queued_user_messages.len();
+        self.selected_queued_message = match self.selected_queued_message {
+            _ if len == 0 => None,
+            None => Some(len - 1),
+            Some(0) => None,
+            Some(index) => Some(index - 1),
+        };
+        self.refresh_queued_user_messages();
+        self.request_redraw();
     }
 
     pub(crate) fn add_diff_in_progress(&mut self) {
@@ -1854,6 +2003,11 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// The diff of everything patched during the most recent turn, if any.
+    pub(crate) fn last_turn_diff(&self) -> Option<&str> {
+        self.last_turn_diff.as_deref()
+    }
+
     pub(crate) fn add_status_output(&mut self) {
         let default_usage = TokenUsage::default();
         let (total_usage, context_usage) = if let Some(ti) = &self.token_info {
@@ -1867,6 +2021,7 @@ impl ChatWidget {
             context_usage,
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
+            self.usage_cost.as_ref(),
             Local::now(),
         ));
     }
@@ -1980,6 +2135,7 @@ impl ChatWidget {
                 ..Default::default()
             });
         }
+        items.extend(self.ollama_pull_items());
 
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some("Select Model and Effort".to_string()),
@@ -1990,6 +2146,48 @@ impl ChatWidget {
         });
     }
 
+    /// "Pull a model…" entries for the active `--oss` provider and every
+    /// Ollama provider configured through BYOK.
+    fn ollama_pull_items(&self) -> Vec<SelectionItem> {
+        let mut targets: Vec<(String, String)> = Vec::new();
+        if self.config.model_provider_id == BUILT_IN_OSS_MODEL_PROVIDER_ID {
+            targets.push((
+                BUILT_IN_OSS_MODEL_PROVIDER_ID.to_string(),
+                self.config.model_provider.name.clone(),
+            ));
+        }
+        for (provider_id, provider) in &self.custom_providers {
+            if provider.provider_kind != ProviderKind::Ollama {
+                continue;
+            }
+            let display = if provider.name.trim().is_empty() {
+                provider_id.clone()
+            } else {
+                provider.name.clone()
+            };
+            targets.push((provider_id.clone(), display));
+        }
+
+        targets
+            .into_iter()
+            .map(|(provider_id, display)| {
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenOllamaPullPrompt {
+                        provider_id: provider_id.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name: format!("Pull a model… ({display})"),
+                    description: Some("Download a model from the Ollama library".to_string()),
+                    actions,
+                    dismiss_on_select: true,
+                    search_value: Some("pull".to_string()),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
     fn collect_model_presets(&self) -> Vec<ModelPreset> {
         let auth_mode = self.auth_manager.auth().map(|auth| auth.mode);
         let mut presets = builtin_model_presets(auth_mode);
@@ -2425,7 +2623,7 @@ impl ChatWidget {
             "When Codex runs with full access, it can edit any file on your computer and run commands with network, without your approval. "
                 .into(),
             "Exercise caution when enabling full access. This significantly increases the risk of data loss, leaks, or unexpected behavior."
-                .fg(Color::Red),
+                .error(),
         ]);
         header_children.push(Box::new(title_line));
         header_children.push(Box::new(
@@ -2493,8 +2691,7 @@ impl ChatWidget {
         let title_line = Line::from("Auto mode has unprotected directories").bold();
         let info_line = Line::from(vec![
             "Some important directories on this system are world-writable. ".into(),
-            "The Windows sandbox cannot protect writes to these locations in Auto mode."
-                .fg(Color::Red),
+            "The Windows sandbox cannot protect writes to these locations in Auto mode.".error(),
         ]);
         header_children.push(Box::new(title_line));
         header_children.push(Box::new(
@@ -2669,6 +2866,16 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn set_tab_status_line(&mut self, status: Option<String>) {
+        self.bottom_pane.set_tab_status_line(status);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_model_pull_status_line(&mut self, status: Option<String>) {
+        self.bottom_pane.set_model_pull_status_line(status);
+        self.request_redraw();
+    }
+
     pub(crate) fn push_bottom_view<F>(&mut self, f: F)
     where
         F: FnOnce(&mut BottomPane),
@@ -2727,6 +2934,11 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn add_warning_message(&mut self, message: String) {
+        self.add_to_history(history_cell::new_warning_event(message));
+        self.request_redraw();
+    }
+
     pub(crate) fn add_mcp_output(&mut self) {
         if self.config.mcp_servers.is_empty() {
             self.add_to_history(history_cell::empty_mcp_output());
@@ -2775,6 +2987,53 @@ impl ChatWidget {
         self.bottom_pane.set_composer_text(text);
     }
 
+    /// Picks what the external editor should open: the selected queued
+    /// message, else the composer draft, or the most recently queued message
+    /// when the composer is empty. Returns `None` while a modal view owns the
+    /// keyboard.
+    pub(crate) fn external_edit_seed(&self) -> Option<(ExternalEditTarget, String)> {
+        if !self.bottom_pane.composer_has_focus() {
+            return None;
+        }
+        let queued_index = match self.selected_queued_message {
+            Some(index) => Some(index),
+            None if self.bottom_pane.composer_is_empty() => {
+                self.queued_user_messages.len().checked_sub(1)
+            }
+            None => None,
+        };
+        if let Some(index) = queued_index
+            && let Some(message) = self.queued_user_messages.get(index)
+        {
+            return Some((
+                ExternalEditTarget::QueuedMessage(index),
+                message.text.clone(),
+            ));
+        }
+        Some((
+            ExternalEditTarget::Composer,
+            self.bottom_pane.composer_text(),
+        ))
+    }
+
+    /// Stores text returned from the external editor. An emptied queued
+    /// message is removed from the queue.
+    pub(crate) fn apply_external_edit(&mut self, target: ExternalEditTarget, text: String) {
+        match target {
+            ExternalEditTarget::Composer => self.bottom_pane.set_composer_text_from_editor(text),
+            ExternalEditTarget::QueuedMessage(index) => {
+                if text.trim().is_empty() {
+                    self.queued_user_messages.remove(index);
+                    self.selected_queued_message = None;
+                } else if let Some(message) = self.queued_user_messages.get_mut(index) {
+                    message.text = text;
+                }
+                self.refresh_queued_user_messages();
+            }
+        }
+        self.request_redraw();
+    }
+
     pub(crate) fn show_esc_backtrack_hint(&mut self) {
         self.bottom_pane.show_esc_backtrack_hint();
     }
@@ -2808,6 +3067,56 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn open_exec_sessions_panel(&mut self) {
+        let view = ExecSessionsView::new(self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.submit_op(Op::ListUnifiedExecSessions);
+    }
+
+    fn on_unified_exec_sessions(&mut self, ev: UnifiedExecSessionsEvent) {
+        self.exec_sessions = ev.sessions;
+        let live: HashSet<i32> = self
+            .exec_sessions
+            .iter()
+            .map(|session| session.session_id)
+            .collect();
+        self.pinned_exec_sessions.retain(|id| live.contains(id));
+        self.refresh_pinned_exec_output();
+        let panel_open = self
+            .bottom_pane
+            .update_exec_sessions(&self.exec_sessions, &self.pinned_exec_sessions);
+
+        if (panel_open || !self.pinned_exec_sessions.is_empty())
+            && !self.exec_sessions_poll_scheduled
+        {
+            self.exec_sessions_poll_scheduled = true;
+            let tx = self.app_event_tx.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(EXEC_SESSIONS_POLL_INTERVAL).await;
+                tx.send(AppEvent::PollExecSessions);
+            });
+        }
+    }
+
+    pub(crate) fn poll_exec_sessions(&mut self) {
+        self.exec_sessions_poll_scheduled = false;
+        self.submit_op(Op::ListUnifiedExecSessions);
+    }
+
+    pub(crate) fn toggle_exec_session_pin(&mut self, session_id: i32) {
+        if !self.pinned_exec_sessions.remove(&session_id) {
+            self.pinned_exec_sessions.insert(session_id);
+        }
+        self.refresh_pinned_exec_output();
+        self.bottom_pane
+            .update_exec_sessions(&self.exec_sessions, &self.pinned_exec_sessions);
+    }
+
+    fn refresh_pinned_exec_output(&mut self) {
+        let lines = pinned_output_lines(&self.exec_sessions, &self.pinned_exec_sessions);
+        self.bottom_pane.set_pinned_exec_output(lines);
+    }
+
     pub(crate) fn open_review_popup(&mut self) {
         let mut items: Vec<SelectionItem> = Vec::new();
 
@@ -3183,6 +3492,7 @@ pub(crate) fn refresh_model_metadata(config: &mut Config) {
         config.model_max_output_tokens = None;
         config.model_auto_compact_token_limit = None;
     }
+    codex_agentic_core::provider::apply_model_limits(config);
 }
 
 fn reasoning_presets_for_kind(kind: ProviderKind) -> Vec<ReasoningEffortPreset> {
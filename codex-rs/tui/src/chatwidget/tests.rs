@@ -43,10 +43,12 @@ use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
+use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_protocol::ConversationId;
+use codex_protocol::config_types::FailoverTrigger;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
@@ -76,61 +78,6 @@ fn test_config() -> Config {
     .expect("config")
 }
 
-// Backward-compat shim for older session logs that predate the
-// `formatted_output` field on ExecCommandEnd events.
-fn upgrade_event_payload_for_tests(mut payload: serde_json::Value) -> serde_json::Value {
-    if let Some(obj) = payload.as_object_mut()
-        && let Some(msg) = obj.get_mut("msg")
-        && let Some(m) = msg.as_object_mut()
-    {
-        let ty = m.get("type").and_then(|v| v.as_str()).unwrap_or("");
-        if ty == "exec_command_end" {
-            let stdout = m.get("stdout").and_then(|v| v.as_str()).unwrap_or("");
-            let stderr = m.get("stderr").and_then(|v| v.as_str()).unwrap_or("");
-            let aggregated = if stderr.is_empty() {
-                stdout.to_string()
-            } else {
-                format!("{stdout}{stderr}")
-            };
-            if !m.contains_key("formatted_output") {
-                m.insert(
-                    "formatted_output".to_string(),
-                    serde_json::Value::String(aggregated.clone()),
-                );
-            }
-            if !m.contains_key("aggregated_output") {
-                m.insert(
-                    "aggregated_output".to_string(),
-                    serde_json::Value::String(aggregated),
-                );
-            }
-        } else if ty == "exec_command_begin" {
-            let needs_parsed_cmd = m
-                .get("parsed_cmd")
-                .map(|value| !matches!(value, serde_json::Value::Array(_)))
-                .unwrap_or(true);
-            if needs_parsed_cmd {
-                m.insert(
-                    "parsed_cmd".to_string(),
-                    serde_json::Value::Array(Vec::new()),
-                );
-            }
-        } else if ty == "exec_approval_request" {
-            let needs_parsed_cmd = m
-                .get("parsed_cmd")
-                .map(|value| !matches!(value, serde_json::Value::Array(_)))
-                .unwrap_or(true);
-            if needs_parsed_cmd {
-                m.insert(
-                    "parsed_cmd".to_string(),
-                    serde_json::Value::Array(Vec::new()),
-                );
-            }
-        }
-    }
-    payload
-}
-
 fn snapshot(percent: f64) -> RateLimitSnapshot {
     RateLimitSnapshot {
         primary: Some(RateLimitWindow {
@@ -424,6 +371,8 @@ fn make_chatwidget_manual() -> (
         initial_user_message: None,
         token_info: None,
         rate_limit_snapshot: None,
+        usage_cost: None,
+        last_turn_diff: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
         stream_controller: None,
@@ -438,6 +387,7 @@ fn make_chatwidget_manual() -> (
         frame_requester: FrameRequester::test_dummy(),
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
+        selected_queued_message: None,
         suppress_session_configured_redraw: false,
         pending_notification: None,
         is_review_mode: false,
@@ -446,6 +396,9 @@ fn make_chatwidget_manual() -> (
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
+        exec_sessions: Vec::new(),
+        pinned_exec_sessions: HashSet::new(),
+        exec_sessions_poll_scheduled: false,
     };
     (widget, rx, op_rx)
 }
@@ -854,6 +807,47 @@ fn alt_up_edits_most_recent_queued_message() {
     );
 }
 
+#[test]
+fn external_editor_opens_the_selected_queued_message() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+    chat.bottom_pane.set_task_running(true);
+    chat.queued_user_messages
+        .push_back(UserMessage::from("first queued".to_string()));
+    chat.queued_user_messages
+        .push_back(UserMessage::from("second queued".to_string()));
+    chat.refresh_queued_user_messages();
+    chat.set_composer_text("draft".to_string());
+
+    // Without a selection the non-empty composer is edited.
+    assert_eq!(
+        chat.external_edit_seed(),
+        Some((ExternalEditTarget::Composer, "draft".to_string()))
+    );
+
+    // Alt+Down selects the newest message, then the next older one.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::ALT));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::ALT));
+    let (target, seed) = chat.external_edit_seed().expect("seed");
+    assert_eq!(
+        (target, seed.as_str()),
+        (ExternalEditTarget::QueuedMessage(0), "first queued")
+    );
+
+    chat.apply_external_edit(target, "first, edited".to_string());
+    let texts: Vec<&str> = chat
+        .queued_user_messages
+        .iter()
+        .map(|message| message.text.as_str())
+        .collect();
+    assert_eq!(texts, vec!["first, edited", "second queued"]);
+    assert_eq!(chat.bottom_pane.composer_text(), "draft");
+
+    // Emptying the message in the editor drops it from the queue.
+    chat.apply_external_edit(ExternalEditTarget::QueuedMessage(0), "  ".to_string());
+    assert_eq!(chat.queued_user_messages.len(), 1);
+    assert_eq!(chat.selected_queued_message, None);
+}
+
 /// Pressing Up to recall the most recent history entry and immediately queuing
 /// it while a task is running should always enqueue the same text, even when it
 /// is queued repeatedly.
@@ -1840,7 +1834,7 @@ async fn binary_size_transcript_snapshot() {
         match kind {
             "codex_event" => {
                 if let Some(payload) = v.get("payload") {
-                    let mut upgraded = upgrade_event_payload_for_tests(payload.clone());
+                    let mut upgraded = crate::replay::upgrade_event_payload(payload.clone());
                     if let Some(msg) = upgraded
                         .get_mut("msg")
                         .and_then(serde_json::Value::as_object_mut)
@@ -2189,6 +2183,28 @@ fn ui_snapshots_small_heights_idle() {
     }
 }
 
+#[test]
+fn turn_diff_is_cleared_when_the_next_turn_starts() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+    let task_started = || Event {
+        id: "task".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        }),
+    };
+    chat.handle_codex_event(task_started());
+    chat.handle_codex_event(Event {
+        id: "task".into(),
+        msg: EventMsg::TurnDiff(TurnDiffEvent {
+            unified_diff: "diff --git a/a.txt b/a.txt\n".into(),
+        }),
+    });
+    assert_eq!(chat.last_turn_diff(), Some("diff --git a/a.txt b/a.txt\n"));
+
+    chat.handle_codex_event(task_started());
+    assert_eq!(chat.last_turn_diff(), None);
+}
+
 // Snapshot test: ChatWidget at very small heights (task running)
 // Validates how status + composer are presented within tight space.
 #[test]
@@ -2765,6 +2781,35 @@ fn stream_error_updates_status_indicator() {
     );
 }
 
+#[test]
+fn model_failover_names_the_answering_model() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ModelFailover(ModelFailoverEvent {
+            from_provider: "openai".to_string(),
+            from_model: "gpt-5-codex".to_string(),
+            to_provider: "anthropic".to_string(),
+            to_model: "claude-sonnet-4-5".to_string(),
+            reason: FailoverTrigger::RateLimit,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let blob = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        blob.contains("rate limit"),
+        "expected failover reason in history: {blob:?}"
+    );
+    assert!(
+        blob.contains("claude-sonnet-4-5"),
+        "expected fallback model in history: {blob:?}"
+    );
+}
+
 #[test]
 fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
@@ -3104,3 +3149,38 @@ fn chatwidget_tall() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[tokio::test]
+async fn exec_sessions_panel_lists_sessions_and_pins_output() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    assert_matches!(op_rx.try_recv(), Ok(Op::ListUnifiedExecSessions));
+
+    let listing = |sessions: Vec<UnifiedExecSessionInfo>| Event {
+        id: "sessions".into(),
+        msg: EventMsg::UnifiedExecSessions(UnifiedExecSessionsEvent { sessions }),
+    };
+    chat.handle_codex_event(listing(vec![UnifiedExecSessionInfo {
+        session_id: 7,
+        command: "npm run dev".to_string(),
+        cwd: PathBuf::from("/repo"),
+        uptime_secs: 3,
+        output_tail: "ready on :3000\n".to_string(),
+    }]));
+    let popup = render_bottom_popup(&chat, 60);
+    assert!(popup.contains("› #7 npm run dev  3s"), "{popup}");
+
+    chat.toggle_exec_session_pin(7);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    let bottom = render_bottom_popup(&chat, 60);
+    assert!(
+        bottom.contains("#7 npm run dev › ready on :3000"),
+        "{bottom}"
+    );
+
+    // Pins are dropped once the session is gone.
+    chat.handle_codex_event(listing(Vec::new()));
+    let bottom = render_bottom_popup(&chat, 60);
+    assert!(!bottom.contains("npm run dev"), "{bottom}");
+}
@@ -28,6 +28,20 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_session_id: Option<String>,
 
+    /// Internal: fork the resumed session at this user turn (1-based) instead
+    /// of continuing it. Set by `codex resume --fork-at`.
+    #[clap(skip)]
+    pub resume_fork_at: Option<usize>,
+
+    /// Internal: open the transcript at this user turn (1-based) once the
+    /// resumed session has replayed. Set by `codex resume --turn`.
+    #[clap(skip)]
+    pub resume_turn: Option<usize>,
+
+    /// Internal: restore the ghost snapshot for the fork turn, if any.
+    #[clap(skip)]
+    pub resume_restore_snapshot: bool,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -80,3 +94,33 @@ pub struct Cli {
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
+
+/// Replay a session log recorded with `CODEX_TUI_RECORD_SESSION=1`.
+#[derive(Parser, Debug)]
+pub struct ReplayCli {
+    /// Session log (JSONL) to replay.
+    #[arg(value_name = "LOG", value_hint = ValueHint::FilePath)]
+    pub log: PathBuf,
+
+    /// Step through the log in a full-screen viewer instead of printing frames.
+    #[arg(long = "interactive", short = 'i', default_value_t = false)]
+    pub interactive: bool,
+
+    /// Width of the emulated terminal. Defaults to 80 columns, or the current
+    /// terminal width with `--interactive`.
+    #[arg(long = "width", value_name = "COLS")]
+    pub width: Option<u16>,
+
+    /// Height of the emulated terminal. Defaults to 40 rows, or the current
+    /// terminal height with `--interactive`.
+    #[arg(long = "height", value_name = "ROWS")]
+    pub height: Option<u16>,
+
+    /// Stop after applying this many steps of the log.
+    #[arg(long = "until", value_name = "STEP")]
+    pub until: Option<usize>,
+
+    /// Print a frame after every step instead of only the final one.
+    #[arg(long = "frames", default_value_t = false)]
+    pub frames: bool,
+}
@@ -0,0 +1,1382 @@
+//! Full-screen browser for unified diffs: a file tree on the left and the
+//! selected file's hunks, unified or side by side, on the right.
+//!
+//! Fed by `git diff` (via `/diff`) and by the aggregated diff of the last turn
+//! (`TurnDiffTracker::get_unified_diff`). Paths in both are relative to the
+//! repository root, which is where files are opened and reverted.
+
+use std::io::Result;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+
+use crate::diff_render::DiffLineType;
+use crate::diff_render::highlight_side;
+use crate::diff_render::line_number_width;
+use crate::diff_render::push_wrapped_diff_line;
+use crate::external_editor::editor_command;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
+use crate::pager_overlay::action_keys;
+use crate::pager_overlay::render_key_hints;
+use crate::render::highlight::Language;
+use crate::theme::ThemeStylize;
+use crate::tui;
+use crate::tui::TuiEvent;
+
+const KEY_CONFIRM: KeyBinding = key_hint::plain(KeyCode::Char('y'));
+const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
+const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+
+/// Narrowest terminal that still shows the file tree next to the diff.
+const MIN_WIDTH_FOR_TREE: u16 = 40;
+
+/// Where a diff shown in the browser came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DiffSource {
+    /// `git diff` of the working tree, including untracked files.
+    WorkingTree,
+    /// Everything changed by patches during the most recent turn.
+    LastTurn,
+}
+
+impl DiffSource {
+    fn label(self) -> &'static str {
+        match self {
+            DiffSource::WorkingTree => "working tree",
+            DiffSource::LastTurn => "last turn",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffLayout {
+    Unified,
+    SideBySide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileStatus {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+#[derive(Debug, PartialEq)]
+enum HunkLine {
+    Context(String),
+    Insert(String),
+    Delete(String),
+}
+
+#[derive(Debug, PartialEq)]
+struct DiffHunk {
+    header: String,
+    old_start: usize,
+    new_start: usize,
+    lines: Vec<HunkLine>,
+}
+
+/// One file's section of a unified diff.
+#[derive(Debug, PartialEq)]
+pub(crate) struct DiffFile {
+    /// `None` when the file was added.
+    old_path: Option<String>,
+    /// `None` when the file was deleted.
+    new_path: Option<String>,
+    hunks: Vec<DiffHunk>,
+    /// The raw text of this file's section, used to revert it.
+    patch: String,
+}
+
+impl DiffFile {
+    fn new(old_path: Option<String>, new_path: Option<String>) -> Self {
+        Self {
+            old_path,
+            new_path,
+            hunks: Vec::new(),
+            patch: String::new(),
+        }
+    }
+
+    fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+
+    fn status(&self) -> FileStatus {
+        match (&self.old_path, &self.new_path) {
+            (None, Some(_)) => FileStatus::Added,
+            (Some(_), None) => FileStatus::Deleted,
+            (Some(old), Some(new)) if old != new => FileStatus::Renamed,
+            _ => FileStatus::Modified,
+        }
+    }
+
+    fn line_counts(&self) -> (usize, usize) {
+        self.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .fold((0, 0), |(added, removed), line| match line {
+                HunkLine::Insert(_) => (added + 1, removed),
+                HunkLine::Delete(_) => (added, removed + 1),
+                HunkLine::Context(_) => (added, removed),
+            })
+    }
+
+    /// The extended header lines (mode changes, binary notices, ...) shown
+    /// when a file has no textual hunks.
+    fn header_lines(&self) -> impl Iterator<Item = &str> {
+        self.patch
+            .lines()
+            .filter(|line| !line.starts_with("diff --git ") && !line.starts_with("index "))
+    }
+}
+
+/// Splits a unified diff (as produced by `git diff` or the turn diff tracker)
+/// into per-file sections.
+pub(crate) fn parse_unified_diff(diff: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+    // Lines still expected in the current hunk, as (old, new).
+    let mut remaining = (0usize, 0usize);
+
+    for raw in diff.split_inclusive('\n') {
+        let line = raw.strip_suffix('\n').unwrap_or(raw);
+
+        if remaining != (0, 0)
+            && let Some(file) = files.last_mut()
+            && let Some(hunk) = file.hunks.last_mut()
+        {
+            let parsed = match line.chars().next() {
+                Some('+') => {
+                    remaining.1 = remaining.1.saturating_sub(1);
+                    Some(HunkLine::Insert(line[1..].to_string()))
+                }
+                Some('-') => {
+                    remaining.0 = remaining.0.saturating_sub(1);
+                    Some(HunkLine::Delete(line[1..].to_string()))
+                }
+                Some(' ') | None => {
+                    remaining.0 = remaining.0.saturating_sub(1);
+                    remaining.1 = remaining.1.saturating_sub(1);
+                    Some(HunkLine::Context(
+                        line.get(1..).unwrap_or_default().to_string(),
+                    ))
+                }
+                Some('\\') => None,
+                // Malformed hunk: fall through and treat the line as a header.
+                Some(_) => {
+                    remaining = (0, 0);
+                    None
+                }
+            };
+            if remaining != (0, 0) || parsed.is_some() || line.starts_with('\\') {
+                file.patch.push_str(raw);
+                hunk.lines.extend(parsed);
+                continue;
+            }
+        }
+
+        if let Some(names) = line.strip_prefix("diff --git ") {
+            let (old_path, new_path) = parse_git_header_paths(names);
+            files.push(DiffFile::new(old_path, new_path));
+        } else if line.starts_with("--- ") && files.last().is_none_or(|file| !file.hunks.is_empty())
+        {
+            // A plain unified diff without `diff --git` headers.
+            files.push(DiffFile::new(None, None));
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        file.patch.push_str(raw);
+
+        if let Some(path) = line.strip_prefix("--- ") {
+            file.old_path = parse_diff_path(path, "a/");
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            file.new_path = parse_diff_path(path, "b/");
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.old_path = Some(unquote_path(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.new_path = Some(unquote_path(path));
+        } else if line.starts_with("new file mode ") {
+            file.old_path = None;
+        } else if line.starts_with("deleted file mode ") {
+            file.new_path = None;
+        } else if let Some((hunk, counts)) = parse_hunk_header(line) {
+            file.hunks.push(hunk);
+            remaining = counts;
+        }
+    }
+
+    files
+}
+
+/// Parses `@@ -a,b +c,d @@ context`, returning the hunk and its (old, new)
+/// line counts.
+fn parse_hunk_header(line: &str) -> Option<(DiffHunk, (usize, usize))> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = parse_range(old)?;
+    let (new_start, new_count) = parse_range(new)?;
+    Some((
+        DiffHunk {
+            header: line.to_string(),
+            old_start,
+            new_start,
+            lines: Vec::new(),
+        },
+        (old_count, new_count),
+    ))
+}
+
+/// Splits the `a/<old> b/<new>` part of a `diff --git` line.
+fn parse_git_header_paths(names: &str) -> (Option<String>, Option<String>) {
+    if names.starts_with('"') {
+        let mut parts = split_quoted_names(names).into_iter();
+        let old = parts.next().map(|p| strip_diff_prefix(&p, "a/"));
+        let new = parts.next().map(|p| strip_diff_prefix(&p, "b/"));
+        return (old, new);
+    }
+    match names.rsplit_once(" b/") {
+        Some((old, new)) => (
+            Some(strip_diff_prefix(old, "a/")),
+            Some(new.trim_end().to_string()),
+        ),
+        None => (None, None),
+    }
+}
+
+fn split_quoted_names(names: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = names.trim();
+    while !rest.is_empty() {
+        if rest.starts_with('"') {
+            let end = quoted_len(rest);
+            out.push(unquote_path(&rest[..end]));
+            rest = rest[end..].trim_start();
+        } else {
+            let (name, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+            out.push(name.to_string());
+            rest = tail.trim_start();
+        }
+    }
+    out
+}
+
+/// Byte length of the quoted string at the start of `s`, including quotes.
+fn quoted_len(s: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return i + 1,
+            _ => escaped = false,
+        }
+    }
+    s.len()
+}
+
+/// Parses the path after `--- ` or `+++ `; `None` for `/dev/null`.
+fn parse_diff_path(path: &str, prefix: &str) -> Option<String> {
+    let path = path.split('\t').next().unwrap_or(path).trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(strip_diff_prefix(&unquote_path(path), prefix))
+}
+
+fn strip_diff_prefix(path: &str, prefix: &str) -> String {
+    path.strip_prefix(prefix).unwrap_or(path).to_string()
+}
+
+/// Undoes git's C-style quoting of paths with special characters.
+fn unquote_path(path: &str) -> String {
+    let Some(inner) = path
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return path.to_string();
+    };
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some(d @ '0'..='7') => {
+                let mut value = d.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    if let Some(digit) = chars.peek().and_then(|c| c.to_digit(8)) {
+                        value = value * 8 + digit;
+                        chars.next();
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+            }
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// A row of the file tree: a directory, or a file (by index).
+#[derive(Debug, PartialEq)]
+struct TreeRow {
+    depth: usize,
+    label: String,
+    file: Option<usize>,
+}
+
+/// Lays out `files` (sorted by path) as an indented directory tree.
+fn build_tree(files: &[DiffFile]) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    let mut previous_dirs: Vec<&str> = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let mut parts: Vec<&str> = file.path().split('/').filter(|p| !p.is_empty()).collect();
+        let name = parts.pop().unwrap_or_else(|| file.path());
+        let shared = previous_dirs
+            .iter()
+            .zip(&parts)
+            .take_while(|(a, b)| a == b)
+            .count();
+        for (depth, dir) in parts.iter().enumerate().skip(shared) {
+            rows.push(TreeRow {
+                depth,
+                label: format!("{dir}/"),
+                file: None,
+            });
+        }
+        rows.push(TreeRow {
+            depth: parts.len(),
+            label: name.to_string(),
+            file: Some(index),
+        });
+        previous_dirs = parts;
+    }
+    rows
+}
+
+/// The files of one diff source plus the browser's position within them.
+struct DiffView {
+    source: DiffSource,
+    files: Vec<DiffFile>,
+    tree: Vec<TreeRow>,
+    selected: usize,
+}
+
+impl DiffView {
+    fn new(source: DiffSource, diff: &str) -> Self {
+        let mut files = parse_unified_diff(diff);
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        let tree = build_tree(&files);
+        Self {
+            source,
+            files,
+            tree,
+            selected: 0,
+        }
+    }
+
+    fn remove_file(&mut self, index: usize) {
+        self.files.remove(index);
+        self.tree = build_tree(&self.files);
+        self.selected = self.selected.min(self.files.len().saturating_sub(1));
+    }
+}
+
+/// Right-hand pane contents for one file at one width.
+struct RenderedFile {
+    lines: Vec<Line<'static>>,
+    /// Index into `lines` of each hunk header.
+    hunk_offsets: Vec<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct RenderKey {
+    view: usize,
+    file: usize,
+    width: u16,
+    layout: DiffLayout,
+}
+
+pub(crate) struct DiffBrowserOverlay {
+    views: Vec<DiffView>,
+    active: usize,
+    root: PathBuf,
+    layout: DiffLayout,
+    scroll: usize,
+    tree_scroll: usize,
+    rendered: Option<(RenderKey, RenderedFile)>,
+    /// Size of the diff pane at the last draw; used to page and jump.
+    last_diff_area: Rect,
+    confirm_revert: bool,
+    status: Option<Line<'static>>,
+    is_done: bool,
+}
+
+impl DiffBrowserOverlay {
+    /// Builds the browser from `(source, unified diff)` pairs; sources whose
+    /// diff has no files are dropped. `root` is the directory the diff paths
+    /// are relative to.
+    pub(crate) fn new(diffs: Vec<(DiffSource, String)>, root: PathBuf) -> Self {
+        let views = diffs
+            .into_iter()
+            .map(|(source, diff)| DiffView::new(source, &diff))
+            .filter(|view| !view.files.is_empty())
+            .collect();
+        Self {
+            views,
+            active: 0,
+            root,
+            layout: DiffLayout::Unified,
+            scroll: 0,
+            tree_scroll: 0,
+            rendered: None,
+            last_diff_area: Rect::new(0, 0, 80, 24),
+            confirm_revert: false,
+            status: None,
+            is_done: false,
+        }
+    }
+
+    /// True when none of the sources contained any file changes.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn view(&self) -> Option<&DiffView> {
+        self.views.get(self.active)
+    }
+
+    fn selected_file(&self) -> Option<&DiffFile> {
+        self.view().and_then(|view| view.files.get(view.selected))
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                self.handle_key_event(tui, key_event);
+                tui.frame_requester()
+                    .schedule_frame_in(Duration::from_millis(16));
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        if std::mem::take(&mut self.confirm_revert) {
+            if KEY_CONFIRM.is_press(key_event) {
+                self.revert_selected_file();
+            } else {
+                self.status = Some("Revert cancelled".dim().into());
+            }
+            return;
+        }
+        self.status = None;
+        match key_event {
+            e if keymap().matches(KeyAction::Close, e)
+                || KEY_ESC.is_press(e)
+                || KEY_CTRL_C.is_press(e) =>
+            {
+                self.is_done = true;
+            }
+            e if keymap().matches(KeyAction::EditFile, e) => self.open_selected_in_editor(tui),
+            other => self.handle_navigation_key(other),
+        }
+    }
+
+    fn handle_navigation_key(&mut self, key_event: KeyEvent) {
+        let page = usize::from(self.last_diff_area.height.max(1));
+        let keymap = keymap();
+        match key_event {
+            e if keymap.matches(KeyAction::ScrollUp, e) => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            e if keymap.matches(KeyAction::ScrollDown, e) => {
+                self.scroll = self.scroll.saturating_add(1);
+            }
+            e if keymap.matches(KeyAction::PageUp, e) => {
+                self.scroll = self.scroll.saturating_sub(page);
+            }
+            e if keymap.matches(KeyAction::PageDown, e) => {
+                self.scroll = self.scroll.saturating_add(page);
+            }
+            e if keymap.matches(KeyAction::ScrollTop, e) => self.scroll = 0,
+            e if keymap.matches(KeyAction::ScrollBottom, e) => self.scroll = usize::MAX,
+            e if keymap.matches(KeyAction::NextFile, e) => {
+                self.select_relative_file(1);
+            }
+            e if keymap.matches(KeyAction::PreviousFile, e) => {
+                self.select_relative_file(-1);
+            }
+            e if keymap.matches(KeyAction::NextHunk, e) => self.next_hunk(),
+            e if keymap.matches(KeyAction::PreviousHunk, e) => self.previous_hunk(),
+            e if keymap.matches(KeyAction::ToggleLayout, e) => {
+                self.layout = match self.layout {
+                    DiffLayout::Unified => DiffLayout::SideBySide,
+                    DiffLayout::SideBySide => DiffLayout::Unified,
+                };
+                self.scroll = 0;
+            }
+            e if keymap.matches(KeyAction::SwitchSource, e) && self.views.len() > 1 => {
+                self.active = (self.active + 1) % self.views.len();
+                self.scroll = 0;
+            }
+            e if keymap.matches(KeyAction::RevertFile, e) => {
+                if let Some(file) = self.selected_file() {
+                    self.status = Some(
+                        format!("Revert {}? Press y to confirm.", file.path())
+                            .accent()
+                            .into(),
+                    );
+                    self.confirm_revert = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves the selection by `delta` files; returns false at either end.
+    fn select_relative_file(&mut self, delta: isize) -> bool {
+        let Some(view) = self.views.get_mut(self.active) else {
+            return false;
+        };
+        let Some(next) = view.selected.checked_add_signed(delta) else {
+            return false;
+        };
+        if next >= view.files.len() {
+            return false;
+        }
+        view.selected = next;
+        self.scroll = 0;
+        true
+    }
+
+    fn next_hunk(&mut self) {
+        let scroll = self.scroll;
+        let next = self
+            .rendered_file()
+            .and_then(|rendered| rendered.hunk_offsets.iter().copied().find(|&o| o > scroll));
+        match next {
+            Some(offset) => self.scroll = offset,
+            None => {
+                if self.select_relative_file(1) {
+                    self.scroll = self
+                        .rendered_file()
+                        .and_then(|rendered| rendered.hunk_offsets.first().copied())
+                        .unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    fn previous_hunk(&mut self) {
+        let scroll = self.scroll;
+        let previous = self.rendered_file().and_then(|rendered| {
+            rendered
+                .hunk_offsets
+                .iter()
+                .copied()
+                .rev()
+                .find(|&o| o < scroll)
+        });
+        match previous {
+            Some(offset) => self.scroll = offset,
+            None => {
+                if self.select_relative_file(-1) {
+                    self.scroll = self
+                        .rendered_file()
+                        .and_then(|rendered| rendered.hunk_offsets.last().copied())
+                        .unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    /// The new-file line at the top of the hunk currently in view.
+    fn current_line(&mut self) -> Option<usize> {
+        let scroll = self.scroll;
+        let index = self.rendered_file().map(|rendered| {
+            rendered
+                .hunk_offsets
+                .iter()
+                .rposition(|&o| o <= scroll)
+                .unwrap_or(0)
+        })?;
+        self.selected_file()
+            .and_then(|file| file.hunks.get(index))
+            .map(|hunk| hunk.new_start.max(1))
+    }
+
+    fn open_selected_in_editor(&mut self, tui: &mut tui::Tui) {
+        let line = self.current_line();
+        let Some(file) = self.selected_file() else {
+            return;
+        };
+        if file.status() == FileStatus::Deleted {
+            self.status = Some(format!("{} was deleted", file.path()).error().into());
+            return;
+        }
+        let path = self.root.join(file.path());
+        let display = file.path().to_string();
+        let status = editor_command(&path, line).and_then(|mut command| {
+            tui.run_interactive_command(&mut command)
+                .map_err(|err| format!("failed to launch editor: {err}"))
+        });
+        self.status = Some(match status {
+            Ok(status) if status.success() => {
+                format!("Edited {display}; run /diff again to refresh")
+                    .dim()
+                    .into()
+            }
+            Ok(status) => format!("Editor exited with {status}").error().into(),
+            Err(err) => err.error().into(),
+        });
+    }
+
+    fn revert_selected_file(&mut self) {
+        let Some(view) = self.views.get_mut(self.active) else {
+            return;
+        };
+        let index = view.selected;
+        let Some(file) = view.files.get(index) else {
+            return;
+        };
+        let path = file.path().to_string();
+        match reverse_apply(&self.root, &file.patch) {
+            Ok(()) => {
+                view.remove_file(index);
+                self.rendered = None;
+                self.scroll = 0;
+                self.status = Some(format!("Reverted {path}").success().into());
+            }
+            Err(err) => {
+                self.status = Some(format!("Could not revert {path}: {err}").error().into());
+            }
+        }
+    }
+
+    fn rendered_file(&mut self) -> Option<&RenderedFile> {
+        let view = self.view()?;
+        let file = view.files.get(view.selected)?;
+        let key = RenderKey {
+            view: self.active,
+            file: view.selected,
+            width: self.last_diff_area.width,
+            layout: self.layout,
+        };
+        if self.rendered.as_ref().map(|(k, _)| *k) != Some(key) {
+            let rendered = render_file(file, usize::from(key.width), key.layout);
+            self.rendered = Some((key, rendered));
+        }
+        self.rendered.as_ref().map(|(_, rendered)| rendered)
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        if area.height < 5 {
+            return;
+        }
+        let header = Rect::new(area.x, area.y, area.width, 1);
+        let body = Rect::new(area.x, area.y + 1, area.width, area.height - 4);
+        let separator = Rect::new(area.x, body.bottom(), area.width, 1);
+        let hints = Rect::new(area.x, separator.bottom(), area.width, 2);
+
+        self.render_header(header, buf);
+        let (tree_area, diff_area) = self.split_body(body);
+        if let Some(tree_area) = tree_area {
+            self.render_tree(tree_area, buf);
+            for y in body.top()..body.bottom() {
+                Span::from("│")
+                    .dim()
+                    .render_ref(Rect::new(tree_area.right(), y, 1, 1), buf);
+            }
+        }
+        self.last_diff_area = diff_area;
+        self.render_diff(diff_area, buf);
+        self.render_separator(separator, buf);
+        self.render_hints(hints, buf);
+    }
+
+    fn split_body(&self, body: Rect) -> (Option<Rect>, Rect) {
+        if body.width < MIN_WIDTH_FOR_TREE {
+            return (None, body);
+        }
+        let widest = self
+            .view()
+            .map(|view| {
+                view.tree
+                    .iter()
+                    .map(|row| row.depth * 2 + row.label.chars().count() + 3)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+        let tree_width = (widest as u16).clamp(12, body.width / 3);
+        let tree = Rect::new(body.x, body.y, tree_width, body.height);
+        let diff = Rect::new(
+            body.x + tree_width + 2,
+            body.y,
+            body.width.saturating_sub(tree_width + 2),
+            body.height,
+        );
+        (Some(tree), diff)
+    }
+
+    fn render_header(&self, area: Rect, buf: &mut Buffer) {
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(area, buf);
+        let source = self
+            .view()
+            .map(|view| format!(" · {}", view.source.label()))
+            .unwrap_or_default();
+        format!("/ D I F F{source} ").dim().render_ref(area, buf);
+    }
+
+    fn render_tree(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = self.views.get(self.active) else {
+            return;
+        };
+        let selected_row = view
+            .tree
+            .iter()
+            .position(|row| row.file == Some(view.selected))
+            .unwrap_or(0);
+        let height = usize::from(area.height.max(1));
+        if selected_row < self.tree_scroll {
+            self.tree_scroll = selected_row;
+        } else if selected_row >= self.tree_scroll + height {
+            self.tree_scroll = selected_row + 1 - height;
+        }
+
+        let lines: Vec<Line<'static>> = view
+            .tree
+            .iter()
+            .skip(self.tree_scroll)
+            .take(height)
+            .map(|row| {
+                let indent = "  ".repeat(row.depth);
+                let Some(file) = row.file.and_then(|index| view.files.get(index)) else {
+                    return Line::from(format!("{indent}{}", row.label).dim());
+                };
+                let marker = match file.status() {
+                    FileStatus::Added => "A ".success(),
+                    FileStatus::Deleted => "D ".error(),
+                    FileStatus::Modified => "M ".dim(),
+                    FileStatus::Renamed => "R ".dim(),
+                };
+                let label = if row.file == Some(view.selected) {
+                    row.label.clone().accent().bold()
+                } else {
+                    row.label.clone().into()
+                };
+                Line::from(vec![indent.into(), marker, label])
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn render_diff(&mut self, area: Rect, buf: &mut Buffer) {
+        let height = usize::from(area.height);
+        let requested = self.scroll;
+        let Some(rendered) = self.rendered_file() else {
+            Paragraph::new(Line::from("No changes.".italic())).render(area, buf);
+            return;
+        };
+        let max_scroll = rendered.lines.len().saturating_sub(height);
+        let scroll = requested.min(max_scroll);
+        let lines: Vec<Line<'static>> = rendered
+            .lines
+            .iter()
+            .skip(scroll)
+            .take(height)
+            .cloned()
+            .collect();
+        self.scroll = scroll;
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn render_separator(&self, area: Rect, buf: &mut Buffer) {
+        Span::from("─".repeat(area.width as usize))
+            .dim()
+            .render_ref(area, buf);
+        if let Some(status) = &self.status {
+            let status_area = Rect::new(area.x + 1, area.y, area.width.saturating_sub(2), 1);
+            let mut line = status.clone();
+            line.spans.insert(0, " ".into());
+            line.spans.push(" ".into());
+            line.render_ref(status_area, buf);
+        }
+        if let Some(view) = self.view() {
+            let position = format!(" file {} of {} ", view.selected + 1, view.files.len());
+            let width = position.chars().count() as u16;
+            if width + 2 <= area.width {
+                let x = area.right() - width - 1;
+                Span::from(position)
+                    .dim()
+                    .render_ref(Rect::new(x, area.y, width, 1), buf);
+            }
+        }
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        let scroll = action_keys(&[KeyAction::ScrollUp, KeyAction::ScrollDown]);
+        let file = action_keys(&[KeyAction::PreviousFile, KeyAction::NextFile]);
+        let hunk = action_keys(&[KeyAction::PreviousHunk, KeyAction::NextHunk]);
+        render_key_hints(
+            line1,
+            buf,
+            &[(&scroll, "to scroll"), (&file, "file"), (&hunk, "hunk")],
+        );
+        let layout_hint = match self.layout {
+            DiffLayout::Unified => "side by side",
+            DiffLayout::SideBySide => "unified",
+        };
+        let layout = action_keys(&[KeyAction::ToggleLayout]);
+        let edit = action_keys(&[KeyAction::EditFile]);
+        let revert = action_keys(&[KeyAction::RevertFile]);
+        let source = action_keys(&[KeyAction::SwitchSource]);
+        let close = action_keys(&[KeyAction::Close]);
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&layout, layout_hint),
+            (&edit, "edit"),
+            (&revert, "revert file"),
+        ];
+        if self.views.len() > 1 {
+            let other = self.views[(self.active + 1) % self.views.len()].source;
+            pairs.push((&source, other.label()));
+        }
+        pairs.push((&close, "to quit"));
+        render_key_hints(line2, buf, &pairs);
+    }
+}
+
+/// Reverts a file by applying its section of the diff in reverse.
+fn reverse_apply(root: &Path, patch: &str) -> std::result::Result<(), String> {
+    let mut child = Command::new("git")
+        .args(["apply", "-R", "--whitespace=nowarn", "-"])
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to run git apply: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(patch.as_bytes())
+            .map_err(|err| format!("failed to write patch: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("git apply failed: {err}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(stderr
+            .lines()
+            .next()
+            .unwrap_or("git apply failed")
+            .to_string())
+    }
+}
+
+fn render_file(file: &DiffFile, width: usize, layout: DiffLayout) -> RenderedFile {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let (added, removed) = file.line_counts();
+    let mut title: Vec<Span<'static>> = Vec::new();
+    if let (FileStatus::Renamed, Some(old)) = (file.status(), &file.old_path) {
+        title.push(old.clone().bold());
+        title.push(" → ".dim());
+    }
+    title.push(file.path().to_string().bold());
+    title.push(" (".into());
+    title.push(format!("+{added}").success());
+    title.push(" ".into());
+    title.push(format!("-{removed}").error());
+    title.push(")".into());
+    match file.status() {
+        FileStatus::Added => title.push(" new file".dim()),
+        FileStatus::Deleted => title.push(" deleted".dim()),
+        FileStatus::Modified | FileStatus::Renamed => {}
+    }
+    lines.push(Line::from(title));
+    lines.push(Line::from(""));
+
+    if file.hunks.is_empty() {
+        lines.extend(
+            file.header_lines()
+                .map(|line| Line::from(line.to_string().dim())),
+        );
+        return RenderedFile {
+            lines,
+            hunk_offsets: Vec::new(),
+        };
+    }
+
+    let language = Language::from_path(Path::new(file.path()));
+    let old_side = highlight_side(
+        file.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Delete(text) => Some(text.as_str()),
+                HunkLine::Insert(_) => None,
+            }),
+        language,
+    );
+    let new_side = highlight_side(
+        file.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Insert(text) => Some(text.as_str()),
+                HunkLine::Delete(_) => None,
+            }),
+        language,
+    );
+    let max_line_number = file
+        .hunks
+        .iter()
+        .map(|hunk| {
+            let (old, new) = hunk
+                .lines
+                .iter()
+                .fold((0, 0), |(old, new), line| match line {
+                    HunkLine::Context(_) => (old + 1, new + 1),
+                    HunkLine::Delete(_) => (old + 1, new),
+                    HunkLine::Insert(_) => (old, new + 1),
+                });
+            (hunk.old_start + old).max(hunk.new_start + new)
+        })
+        .max()
+        .unwrap_or(0);
+    let number_width = line_number_width(max_line_number);
+
+    let mut hunk_offsets = Vec::new();
+    let mut old_index = 0;
+    let mut new_index = 0;
+    for (i, hunk) in file.hunks.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        hunk_offsets.push(lines.len());
+        lines.push(Line::from(hunk.header.clone().dim()));
+
+        let mut old_ln = hunk.old_start;
+        let mut new_ln = hunk.new_start;
+        let mut rows = SideBySideRows::default();
+        for line in &hunk.lines {
+            let (old, new) = match line {
+                HunkLine::Context(text) => {
+                    let old = DiffCell::new(old_ln, DiffLineType::Context, text, old_index);
+                    let new = DiffCell::new(new_ln, DiffLineType::Context, text, new_index);
+                    old_ln += 1;
+                    new_ln += 1;
+                    old_index += 1;
+                    new_index += 1;
+                    (Some(old), Some(new))
+                }
+                HunkLine::Delete(text) => {
+                    let old = DiffCell::new(old_ln, DiffLineType::Delete, text, old_index);
+                    old_ln += 1;
+                    old_index += 1;
+                    (Some(old), None)
+                }
+                HunkLine::Insert(text) => {
+                    let new = DiffCell::new(new_ln, DiffLineType::Insert, text, new_index);
+                    new_ln += 1;
+                    new_index += 1;
+                    (None, Some(new))
+                }
+            };
+            match layout {
+                DiffLayout::Unified => {
+                    let (cell, side) = match (old, new) {
+                        (_, Some(new)) => (new, &new_side),
+                        (Some(old), None) => (old, &old_side),
+                        (None, None) => continue,
+                    };
+                    lines.extend(cell.render(side, width, number_width));
+                }
+                DiffLayout::SideBySide => rows.push(old, new),
+            }
+        }
+        if layout == DiffLayout::SideBySide {
+            for (old, new) in rows.finish() {
+                lines.extend(render_side_by_side_row(
+                    old.map(|cell| (cell, old_side.as_slice())),
+                    new.map(|cell| (cell, new_side.as_slice())),
+                    width,
+                    number_width,
+                ));
+            }
+        }
+    }
+
+    RenderedFile {
+        lines,
+        hunk_offsets,
+    }
+}
+
+/// One side of a diff line: its number, kind and index into the highlighted
+/// lines of that side.
+struct DiffCell<'a> {
+    line_number: usize,
+    kind: DiffLineType,
+    text: &'a str,
+    side_index: usize,
+}
+
+impl<'a> DiffCell<'a> {
+    fn new(line_number: usize, kind: DiffLineType, text: &'a str, side_index: usize) -> Self {
+        Self {
+            line_number,
+            kind,
+            text,
+            side_index,
+        }
+    }
+
+    fn render(
+        self,
+        side: &[Line<'static>],
+        width: usize,
+        number_width: usize,
+    ) -> Vec<Line<'static>> {
+        push_wrapped_diff_line(
+            self.line_number,
+            self.kind,
+            self.text,
+            side.get(self.side_index),
+            width,
+            number_width,
+        )
+    }
+}
+
+/// Pairs deletions with the insertions that follow them so replaced lines
+/// sit next to each other.
+#[derive(Default)]
+struct SideBySideRows<'a> {
+    rows: Vec<(Option<DiffCell<'a>>, Option<DiffCell<'a>>)>,
+    deletes: Vec<DiffCell<'a>>,
+    inserts: Vec<DiffCell<'a>>,
+}
+
+impl<'a> SideBySideRows<'a> {
+    fn push(&mut self, old: Option<DiffCell<'a>>, new: Option<DiffCell<'a>>) {
+        match (old, new) {
+            (Some(old), Some(new)) => {
+                self.flush();
+                self.rows.push((Some(old), Some(new)));
+            }
+            (Some(old), None) => {
+                if !self.inserts.is_empty() {
+                    self.flush();
+                }
+                self.deletes.push(old);
+            }
+            (None, Some(new)) => self.inserts.push(new),
+            (None, None) => {}
+        }
+    }
+
+    fn flush(&mut self) {
+        let mut deletes = std::mem::take(&mut self.deletes).into_iter();
+        let mut inserts = std::mem::take(&mut self.inserts).into_iter();
+        loop {
+            match (deletes.next(), inserts.next()) {
+                (None, None) => break,
+                pair => self.rows.push(pair),
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<(Option<DiffCell<'a>>, Option<DiffCell<'a>>)> {
+        self.flush();
+        self.rows
+    }
+}
+
+fn render_side_by_side_row(
+    old: Option<(DiffCell<'_>, &[Line<'static>])>,
+    new: Option<(DiffCell<'_>, &[Line<'static>])>,
+    width: usize,
+    number_width: usize,
+) -> Vec<Line<'static>> {
+    let left_width = width.saturating_sub(1) / 2;
+    let right_width = width.saturating_sub(left_width + 1);
+    let left = old
+        .map(|(cell, side)| cell.render(side, left_width, number_width))
+        .unwrap_or_default();
+    let right = new
+        .map(|(cell, side)| cell.render(side, right_width, number_width))
+        .unwrap_or_default();
+
+    (0..left.len().max(right.len()))
+        .map(|i| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut used = 0;
+            if let Some(line) = left.get(i) {
+                used = line.width();
+                spans.extend(line.spans.iter().cloned());
+            }
+            spans.push(" ".repeat(left_width.saturating_sub(used)).into());
+            spans.push("│".dim());
+            if let Some(line) = right.get(i) {
+                spans.extend(line.spans.iter().cloned());
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {}
+-fn b() {}
++fn b() -> u8 { 1 }
+ fn c() {}
+@@ -10,2 +10,3 @@ impl X {
+ let x = 1;
++let y = 2;
+ let z = 3;
+diff --git a/README.md b/README.md
+deleted file mode 100644
+index 3333333..0000000
+--- a/README.md
++++ /dev/null
+@@ -1 +0,0 @@
+--- not a header
+\\ No newline at end of file
+diff --git a/src/new.rs b/src/new.rs
+new file mode 100644
+index 0000000..4444444
+--- /dev/null
++++ b/src/new.rs
+@@ -0,0 +1 @@
++pub fn new() {}
+diff --git a/old name.txt b/docs/new name.txt
+similarity index 100%
+rename from old name.txt
+rename to docs/new name.txt
+";
+
+    fn browser() -> DiffBrowserOverlay {
+        DiffBrowserOverlay::new(
+            vec![(DiffSource::WorkingTree, DIFF.to_string())],
+            PathBuf::from("/repo"),
+        )
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, crossterm::event::KeyModifiers::NONE)
+    }
+
+    fn render_text(browser: &mut DiffBrowserOverlay, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        browser.render(area, &mut buf);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_files_hunks_and_statuses() {
+        let files = parse_unified_diff(DIFF);
+        let summary: Vec<(&str, FileStatus, usize, (usize, usize))> = files
+            .iter()
+            .map(|f| (f.path(), f.status(), f.hunks.len(), f.line_counts()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/lib.rs", FileStatus::Modified, 2, (2, 1)),
+                ("README.md", FileStatus::Deleted, 1, (0, 1)),
+                ("src/new.rs", FileStatus::Added, 1, (1, 0)),
+                ("docs/new name.txt", FileStatus::Renamed, 0, (0, 0)),
+            ]
+        );
+        // A removed line that looks like a file header stays in its hunk.
+        assert_eq!(
+            files[1].hunks[0].lines,
+            vec![HunkLine::Delete("-- not a header".to_string())]
+        );
+        assert_eq!(files[0].hunks[1].new_start, 10);
+        assert!(files[1].patch.ends_with("\\ No newline at end of file\n"));
+        assert!(files[3].patch.starts_with("diff --git a/old name.txt"));
+    }
+
+    #[test]
+    fn parses_quoted_paths() {
+        let diff = "\
+diff --git \"a/caf\\303\\251.txt\" \"b/caf\\303\\251.txt\"
+--- \"a/caf\\303\\251.txt\"
++++ \"b/caf\\303\\251.txt\"
+@@ -1 +1 @@
+-a
++b
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path(), "café.txt");
+        assert_eq!(files[0].status(), FileStatus::Modified);
+    }
+
+    #[test]
+    fn builds_tree_from_sorted_paths() {
+        let view = DiffView::new(DiffSource::WorkingTree, DIFF);
+        let rows: Vec<(usize, &str, bool)> = view
+            .tree
+            .iter()
+            .map(|row| (row.depth, row.label.as_str(), row.file.is_some()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (0, "README.md", true),
+                (0, "docs/", false),
+                (1, "new name.txt", true),
+                (0, "src/", false),
+                (1, "lib.rs", true),
+                (1, "new.rs", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn hunk_navigation_crosses_files() {
+        let mut browser = browser();
+        let view = &mut browser.views[0];
+        view.selected = view
+            .files
+            .iter()
+            .position(|f| f.path() == "src/lib.rs")
+            .unwrap_or_default();
+
+        browser.next_hunk();
+        assert_eq!(browser.scroll, 2);
+        browser.next_hunk();
+        let second = browser.scroll;
+        assert!(second > 2);
+        browser.next_hunk();
+        assert_eq!(
+            browser.selected_file().map(DiffFile::path),
+            Some("src/new.rs")
+        );
+        assert_eq!(browser.scroll, 2);
+        browser.previous_hunk();
+        assert_eq!(
+            browser.selected_file().map(DiffFile::path),
+            Some("src/lib.rs")
+        );
+        assert_eq!(browser.scroll, second);
+    }
+
+    #[test]
+    fn renders_tree_and_unified_diff() {
+        let mut browser = browser();
+        browser.select_relative_file(2);
+        let text = render_text(&mut browser, 80, 14);
+        assert!(text[0].starts_with("/ D I F F · working tree"));
+        assert!(text[3].contains("@@ -1,3 +1,3 @@"));
+        assert!(text[4].contains("src/"));
+        assert!(text[5].contains("M lib.rs") && text[5].contains("-fn b() {}"));
+        assert!(text.iter().any(|l| l.contains("+fn b() -> u8 { 1 }")));
+        assert!(text[11].contains("file 3 of 4"));
+    }
+
+    #[test]
+    fn side_by_side_pairs_replaced_lines() {
+        let mut browser = browser();
+        browser.select_relative_file(2);
+        browser.layout = DiffLayout::SideBySide;
+        let text = render_text(&mut browser, 100, 14);
+        let row = text
+            .iter()
+            .find(|l| l.contains("-fn b() {}"))
+            .cloned()
+            .unwrap_or_default();
+        assert!(row.contains("│ 2 +fn b() -> u8 { 1 }"), "{row}");
+    }
+
+    #[test]
+    fn revert_needs_confirmation() {
+        let mut browser = browser();
+        browser.handle_navigation_key(key(KeyCode::Char('r')));
+        assert!(browser.confirm_revert);
+        assert_eq!(browser.views[0].files.len(), 4);
+    }
+}
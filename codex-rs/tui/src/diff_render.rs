@@ -1,7 +1,6 @@
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -14,15 +13,18 @@ use std::path::PathBuf;
 
 use crate::exec_command::relativize_to_home;
 use crate::render::Insets;
+use crate::render::highlight::Language;
+use crate::render::highlight::highlight_code_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
 // Internal representation for diff line rendering
-enum DiffLineType {
+pub(crate) enum DiffLineType {
     Insert,
     Delete,
     Context,
@@ -42,13 +44,13 @@ impl DiffSummary {
 impl Renderable for FileChange {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let mut lines = vec![];
-        render_change(self, &mut lines, area.width as usize);
+        render_change(self, &mut lines, area.width as usize, None);
         Paragraph::new(lines).render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut lines = vec![];
-        render_change(self, &mut lines, width as usize);
+        render_change(self, &mut lines, width as usize, None);
         lines.len() as u16
     }
 }
@@ -126,9 +128,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
 fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
-    spans.push(format!("+{added}").green());
+    spans.push(format!("+{added}").success());
     spans.push(" ".into());
-    spans.push(format!("-{removed}").red());
+    spans.push(format!("-{removed}").error());
     spans.push(")".into());
     spans
 }
@@ -185,23 +187,31 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
             out.push(RtLine::from(header));
         }
 
+        let language = Language::from_path(r.move_path.as_deref().unwrap_or(&r.path));
         let mut lines = vec![];
-        render_change(&r.change, &mut lines, wrap_cols - 4);
+        render_change(&r.change, &mut lines, wrap_cols - 4, language);
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
 
     out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+fn render_change(
+    change: &FileChange,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+    language: Option<Language>,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
+            let highlighted = highlight_side(content.lines(), language);
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
                     i + 1,
                     DiffLineType::Insert,
                     raw,
+                    highlighted.get(i),
                     width,
                     line_number_width,
                 ));
@@ -209,11 +219,13 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
         }
         FileChange::Delete { content } => {
             let line_number_width = line_number_width(content.lines().count());
+            let highlighted = highlight_side(content.lines(), language);
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
                     i + 1,
                     DiffLineType::Delete,
                     raw,
+                    highlighted.get(i),
                     width,
                     line_number_width,
                 ));
@@ -244,6 +256,29 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     }
                 }
                 let line_number_width = line_number_width(max_line_number);
+                // Highlight each side of the diff as one source so tokens that
+                // span several lines (strings, comments) are classified right.
+                let lines = || patch.hunks().iter().flat_map(Hunk::lines);
+                let old_side = highlight_side(
+                    lines().filter_map(|l| match l {
+                        diffy::Line::Delete(text) | diffy::Line::Context(text) => {
+                            Some(text.trim_end_matches('\n'))
+                        }
+                        diffy::Line::Insert(_) => None,
+                    }),
+                    language,
+                );
+                let new_side = highlight_side(
+                    lines().filter_map(|l| match l {
+                        diffy::Line::Insert(text) | diffy::Line::Context(text) => {
+                            Some(text.trim_end_matches('\n'))
+                        }
+                        diffy::Line::Delete(_) => None,
+                    }),
+                    language,
+                );
+                let mut old_idx = 0;
+                let mut new_idx = 0;
                 let mut is_first_hunk = true;
                 for h in patch.hunks() {
                     if !is_first_hunk {
@@ -263,10 +298,12 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Insert,
                                     s,
+                                    new_side.get(new_idx),
                                     width,
                                     line_number_width,
                                 ));
                                 new_ln += 1;
+                                new_idx += 1;
                             }
                             diffy::Line::Delete(text) => {
                                 let s = text.trim_end_matches('\n');
@@ -274,10 +311,12 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     old_ln,
                                     DiffLineType::Delete,
                                     s,
+                                    old_side.get(old_idx),
                                     width,
                                     line_number_width,
                                 ));
                                 old_ln += 1;
+                                old_idx += 1;
                             }
                             diffy::Line::Context(text) => {
                                 let s = text.trim_end_matches('\n');
@@ -285,11 +324,14 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Context,
                                     s,
+                                    new_side.get(new_idx),
                                     width,
                                     line_number_width,
                                 ));
                                 old_ln += 1;
                                 new_ln += 1;
+                                old_idx += 1;
+                                new_idx += 1;
                             }
                         }
                     }
@@ -331,68 +373,115 @@ fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
     }
 }
 
-fn push_wrapped_diff_line(
+/// Highlights the given lines as a single source, returning one styled line
+/// per input line, or nothing when the language is unknown.
+pub(crate) fn highlight_side<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    language: Option<Language>,
+) -> Vec<RtLine<'static>> {
+    let Some(language) = language else {
+        return Vec::new();
+    };
+    let source = lines.collect::<Vec<_>>().join("\n");
+    highlight_code_to_lines(&source, language)
+}
+
+pub(crate) fn push_wrapped_diff_line(
     line_number: usize,
     kind: DiffLineType,
     text: &str,
+    highlighted: Option<&RtLine<'static>>,
     width: usize,
     line_number_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
-    let mut remaining_text: &str = text;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
     let gutter_width = line_number_width.max(1);
     let prefix_cols = gutter_width + 1;
 
-    let mut first = true;
     let (sign_char, line_style) = match kind {
         DiffLineType::Insert => ('+', style_add()),
         DiffLineType::Delete => ('-', style_del()),
         DiffLineType::Context => (' ', style_context()),
     };
-    let mut lines: Vec<RtLine<'static>> = Vec::new();
 
+    // Syntax colors win over the diff color; tokens the highlighter leaves
+    // unstyled keep the success/error color of the diff kind.
+    let mut segments: Vec<(String, Style)> = match highlighted {
+        Some(line)
+            if line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+                == text =>
+        {
+            line.spans
+                .iter()
+                .filter(|span| !span.content.is_empty())
+                .map(|span| (span.content.to_string(), line_style.patch(span.style)))
+                .collect()
+        }
+        _ => vec![(text.to_string(), line_style)],
+    };
+    segments.reverse();
+
+    let mut lines: Vec<RtLine<'static>> = Vec::new();
+    let mut first = true;
     loop {
-        // Fit the content for the current terminal row:
-        // compute how many columns are available after the prefix, then split
-        // at a UTF-8 character boundary so this row's chunk fits exactly.
+        // Fit the content for the current terminal row: compute how many
+        // columns are available after the prefix, then take that many
+        // characters across the styled segments.
         let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-        let split_at_byte_index = remaining_text
-            .char_indices()
-            .nth(available_content_cols)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| remaining_text.len());
-        let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
-        remaining_text = rest;
-
+        let mut row: Vec<RtSpan<'static>> = Vec::new();
         if first {
-            // Build gutter (right-aligned line number plus spacer) as a dimmed span
-            let gutter = format!("{ln_str:>gutter_width$} ");
-            // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
+            // Build gutter (right-aligned line number plus spacer) as a dimmed span,
+            // followed by the sign ('+'/'-'/' ') styled per diff kind.
+            row.push(RtSpan::styled(
+                format!("{ln_str:>gutter_width$} "),
+                style_gutter(),
+            ));
+            row.push(RtSpan::styled(sign_char.to_string(), line_style));
             first = false;
         } else {
             // Continuation lines keep a space for the sign column so content aligns
-            let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
+            row.push(RtSpan::styled(
+                format!("{:gutter_width$}  ", ""),
+                style_gutter(),
+            ));
         }
-        if remaining_text.is_empty() {
+
+        let mut remaining_cols = available_content_cols;
+        while remaining_cols > 0 {
+            let Some((segment, style)) = segments.pop() else {
+                break;
+            };
+            let split_at_byte_index = segment
+                .char_indices()
+                .nth(remaining_cols)
+                .map(|(i, _)| i)
+                .unwrap_or(segment.len());
+            let (chunk, rest) = segment.split_at(split_at_byte_index);
+            remaining_cols -= chunk.chars().count();
+            if !chunk.is_empty() {
+                row.push(RtSpan::styled(chunk.to_string(), style));
+            }
+            if !rest.is_empty() {
+                segments.push((rest.to_string(), style));
+            }
+        }
+        lines.push(RtLine::from(row));
+
+        if segments.is_empty() {
             break;
         }
     }
     lines
 }
 
-fn line_number_width(max_line_number: usize) -> usize {
+pub(crate) fn line_number_width(max_line_number: usize) -> usize {
     if max_line_number == 0 {
         1
     } else {
@@ -409,11 +498,11 @@ fn style_context() -> Style {
 }
 
 fn style_add() -> Style {
-    Style::default().fg(Color::Green)
+    Style::default().success()
 }
 
 fn style_del() -> Style {
-    Style::default().fg(Color::Red)
+    Style::default().error()
 }
 
 #[cfg(test)]
@@ -670,4 +759,51 @@ mod tests {
 
         snapshot_lines("apply_update_block_relativizes_path", lines, 80, 10);
     }
+
+    #[test]
+    fn highlighted_lines_split_tokens_and_keep_wrapping() {
+        let original = "fn main() {}\n";
+        let modified = "fn main() { println!(\"a long string literal that wraps\"); }\n";
+        let patch = diffy::create_patch(original, modified).to_string();
+
+        let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        changes.insert(
+            PathBuf::from("main.rs"),
+            FileChange::Update {
+                unified_diff: patch.clone(),
+                move_path: None,
+            },
+        );
+        let highlighted = create_diff_summary(&changes, &PathBuf::from("/"), 40);
+
+        let mut plain_changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        plain_changes.insert(
+            PathBuf::from("main.txt"),
+            FileChange::Update {
+                unified_diff: patch,
+                move_path: None,
+            },
+        );
+        let plain = create_diff_summary(&plain_changes, &PathBuf::from("/"), 40);
+
+        let text = |lines: &[RtLine<'static>]| -> Vec<String> {
+            lines
+                .iter()
+                .skip(1)
+                .map(|l| {
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect()
+        };
+        assert_eq!(text(&highlighted), text(&plain));
+        assert!(
+            highlighted
+                .iter()
+                .any(|line| line.spans.iter().any(|span| span.content == "fn")),
+            "expected `fn` as its own span: {highlighted:?}"
+        );
+    }
 }
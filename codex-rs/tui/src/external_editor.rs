@@ -0,0 +1,107 @@
+//! Resolve the user's preferred editor (`$VISUAL`, then `$EDITOR`) into a
+//! command that can be handed the terminal via
+//! [`crate::tui::Tui::run_interactive_command`].
+
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use crate::tui::Tui;
+
+/// Editors known to accept `+<line>` before the file name.
+const PLUS_LINE_EDITORS: &[&str] = &[
+    "emacs",
+    "emacsclient",
+    "hx",
+    "kak",
+    "micro",
+    "nano",
+    "nvim",
+    "vi",
+    "vim",
+];
+
+/// Builds the command that opens `path` in the user's editor, jumping to
+/// `line` when the editor is known to support it.
+pub(crate) fn editor_command(path: &Path, line: Option<usize>) -> Result<Command, String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .ok_or_else(|| "set $VISUAL or $EDITOR to open files".to_string())?;
+    let mut parts = shlex::split(&editor)
+        .filter(|parts| !parts.is_empty())
+        .ok_or_else(|| format!("could not parse editor command `{editor}`"))?;
+    let program = parts.remove(0);
+
+    let mut command = Command::new(&program);
+    command.args(parts);
+    if let Some(line) = line
+        && supports_plus_line(&program)
+    {
+        command.arg(format!("+{line}"));
+    }
+    command.arg(path);
+    Ok(command)
+}
+
+fn supports_plus_line(program: &str) -> bool {
+    Path::new(program)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| PLUS_LINE_EDITORS.contains(&stem))
+}
+
+/// Lets the user edit `initial` in their editor through a temporary Markdown
+/// file and returns the saved text. Fails without touching anything if the
+/// editor cannot be started or exits unsuccessfully.
+pub(crate) fn edit_text(tui: &mut Tui, initial: &str) -> Result<String, String> {
+    let mut file = tempfile::Builder::new()
+        .prefix("codex-prompt-")
+        .suffix(".md")
+        .tempfile()
+        .map_err(|err| format!("could not create a temporary file: {err}"))?;
+    file.write_all(initial.as_bytes())
+        .and_then(|()| file.flush())
+        .map_err(|err| format!("could not write {}: {err}", file.path().display()))?;
+
+    let mut command = editor_command(file.path(), None)?;
+    let status = tui
+        .run_interactive_command(&mut command)
+        .map_err(|err| format!("failed to launch editor: {err}"))?;
+    if !status.success() {
+        return Err(format!(
+            "editor exited with {status}; keeping the previous text"
+        ));
+    }
+
+    let edited = std::fs::read_to_string(file.path())
+        .map_err(|err| format!("could not read {}: {err}", file.path().display()))?;
+    Ok(strip_final_newline(edited))
+}
+
+/// Editors usually terminate the last line; drop that one newline so the
+/// text round-trips unchanged.
+fn strip_final_newline(mut text: String) -> String {
+    if text.ends_with('\n') {
+        text.pop();
+        if text.ends_with('\r') {
+            text.pop();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn strips_only_the_final_newline() {
+        assert_eq!(strip_final_newline("fix it\n".to_string()), "fix it");
+        assert_eq!(strip_final_newline("a\r\n".to_string()), "a");
+        assert_eq!(strip_final_newline("a\n\n".to_string()), "a\n");
+        assert_eq!(strip_final_newline("a".to_string()), "a");
+    }
+}
@@ -4,6 +4,9 @@
 //! `codex-cli`: it returns the diff for tracked changes as well as any
 //! untracked files. When the current directory is not inside a Git
 //! repository, the function returns `Ok((false, String::new()))`.
+//!
+//! The diff is plain text (no ANSI colours) and every path in it is relative
+//! to the repository root, so it can be parsed and re-applied with `git apply`.
 
 use std::io;
 use std::path::Path;
@@ -20,10 +23,19 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
         return Ok((false, String::new()));
     }
 
+    let toplevel = run_git_capture_stdout(&["rev-parse", "--show-toplevel"]).await?;
+    let toplevel = toplevel.trim_end_matches(['\n', '\r']).to_string();
+
     // Run tracked diff and untracked file listing in parallel.
     let (tracked_diff_res, untracked_output_res) = tokio::join!(
-        run_git_capture_diff(&["diff", "--color"]),
-        run_git_capture_stdout(&["ls-files", "--others", "--exclude-standard"]),
+        run_git_capture_diff(&["-C", &toplevel, "diff", "--no-color"]),
+        run_git_capture_stdout(&[
+            "-C",
+            &toplevel,
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+        ]),
     );
     let tracked_diff = tracked_diff_res?;
     let untracked_output = untracked_output_res?;
@@ -43,9 +55,19 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
         .filter(|s| !s.is_empty())
     {
         let null_path = null_path.clone();
+        let toplevel = toplevel.clone();
         let file = file.to_string();
         join_set.spawn(async move {
-            let args = ["diff", "--color", "--no-index", "--", &null_path, &file];
+            let args = [
+                "-C",
+                &toplevel,
+                "diff",
+                "--no-color",
+                "--no-index",
+                "--",
+                &null_path,
+                &file,
+            ];
             run_git_capture_diff(&args).await
         });
     }
@@ -15,6 +15,8 @@ use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
 use crate::text_formatting::truncate_text;
+use crate::theme::ThemeStylize;
+use crate::theme::theme;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::updates::UpdateAction;
 use crate::version::CODEX_CLI_VERSION;
@@ -178,10 +180,7 @@ impl ReasoningSummaryCell {
             &mut lines,
         );
 
-        let reasoning_style = Style::default()
-            .fg(Color::Gray)
-            .add_modifier(Modifier::ITALIC)
-            .add_modifier(Modifier::DIM);
+        let reasoning_style = theme().muted.add_modifier(Modifier::ITALIC);
         let summary_lines = lines
             .into_iter()
             .map(|mut line| {
@@ -307,19 +306,19 @@ impl HistoryCell for UpdateAvailableHistoryCell {
         use ratatui_macros::line;
         use ratatui_macros::text;
         let update_instruction = if let Some(update_action) = self.update_action {
-            line!["Run ", update_action.command_str().cyan(), " to update."]
+            line!["Run ", update_action.command_str().accent(), " to update."]
         } else {
             line![
                 "See ",
-                "https://github.com/openai/codex".cyan().underlined(),
+                "https://github.com/openai/codex".accent().underlined(),
                 " for installation options."
             ]
         };
 
         let content = text![
             line![
-                padded_emoji("✨").bold().cyan(),
-                "Update available!".bold().cyan(),
+                padded_emoji("✨").bold().accent(),
+                "Update available!".bold().accent(),
                 " ",
                 format!("{CODEX_CLI_VERSION} -> {}", self.latest_version).bold(),
             ],
@@ -327,7 +326,7 @@ impl HistoryCell for UpdateAvailableHistoryCell {
             "",
             "See full release notes:",
             "https://github.com/openai/codex/releases/latest"
-                .cyan()
+                .accent()
                 .underlined(),
         ];
 
@@ -403,7 +402,7 @@ pub fn new_approval_decision_cell(
         Approved => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                "✔ ".success(),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -416,7 +415,7 @@ pub fn new_approval_decision_cell(
         ApprovedForSession => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                "✔ ".success(),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -429,7 +428,7 @@ pub fn new_approval_decision_cell(
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✗ ".red(),
+                "✗ ".error(),
                 vec![
                     "You ".into(),
                     "did not approve".bold(),
@@ -441,7 +440,7 @@ pub fn new_approval_decision_cell(
         Abort => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✗ ".red(),
+                "✗ ".error(),
                 vec![
                     "You ".into(),
                     "canceled".bold(),
@@ -459,10 +458,10 @@ pub fn new_approval_decision_cell(
     ))
 }
 
-/// Cyan history cell line showing the current review status.
+/// Accent-coloured history cell line showing the current review status.
 pub(crate) fn new_review_status_line(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
-        lines: vec![Line::from(message.cyan())],
+        lines: vec![Line::from(message.accent())],
     }
 }
 
@@ -630,7 +629,7 @@ pub(crate) fn new_session_info(
         CompositeHistoryCell { parts: vec![] }
     } else {
         let lines = vec![
-            "model changed:".magenta().bold().into(),
+            "model changed:".brand().bold().into(),
             format!("requested: {}", config.model).into(),
             format!("used: {model}").into(),
         ];
@@ -744,7 +743,7 @@ impl HistoryCell for SessionHeaderHistoryCell {
             model_spans.push(Span::from(reasoning));
         }
         model_spans.push("   ".dim());
-        model_spans.push(CHANGE_MODEL_HINT_COMMAND.cyan());
+        model_spans.push(CHANGE_MODEL_HINT_COMMAND.accent());
         model_spans.push(CHANGE_MODEL_HINT_EXPLANATION.dim());
 
         let dir_label = format!("{DIR_LABEL:<label_width$}");
@@ -870,8 +869,8 @@ impl HistoryCell for McpToolCallCell {
         let mut lines: Vec<Line<'static>> = Vec::new();
         let status = self.success();
         let bullet = match status {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => "•".success().bold(),
+            Some(false) => "•".error().bold(),
             None => spinner(Some(self.start_time)),
         };
         let header_text = if status.is_some() {
@@ -1009,10 +1008,9 @@ fn try_new_completed_mcp_tool_call_with_image_output(
     }
 }
 
-#[allow(clippy::disallowed_methods)]
 pub(crate) fn new_warning_event(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
-        lines: vec![vec![format!("⚠ {message}").yellow()].into()],
+        lines: vec![vec![format!("⚠ {message}").warning()].into()],
     }
 }
 
@@ -1032,7 +1030,7 @@ pub(crate) fn new_deprecation_notice(
 impl HistoryCell for DeprecationNoticeCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut lines: Vec<Line<'static>> = Vec::new();
-        lines.push(vec!["⚠ ".red().bold(), self.summary.clone().red()].into());
+        lines.push(vec!["⚠ ".error().bold(), self.summary.clone().error()].into());
 
         let wrap_width = width.saturating_sub(4).max(1) as usize;
 
@@ -1051,7 +1049,7 @@ impl HistoryCell for DeprecationNoticeCell {
 /// Render a summary of configured MCP servers from the current `Config`.
 pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     let lines: Vec<Line<'static>> = vec![
-        "/mcp".magenta().into(),
+        "/mcp".brand().into(),
         "".into(),
         vec!["🔌  ".into(), "MCP Tools".bold()].into(),
         "".into(),
@@ -1076,7 +1074,7 @@ pub(crate) fn new_mcp_tools_output(
     auth_statuses: &HashMap<String, McpAuthStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
-        "/mcp".magenta().into(),
+        "/mcp".brand().into(),
         "".into(),
         vec!["🔌  ".into(), "MCP Tools".bold()].into(),
         "".into(),
@@ -1107,13 +1105,13 @@ pub(crate) fn new_mcp_tools_output(
         let mut header: Vec<Span<'static>> = vec!["  • ".into(), server.clone().into()];
         if !cfg.enabled {
             header.push(" ".into());
-            header.push("(disabled)".red());
+            header.push("(disabled)".error());
             lines.push(header.into());
             lines.push(Line::from(""));
             continue;
         }
         lines.push(header.into());
-        lines.push(vec!["    • Status: ".into(), "enabled".green()].into());
+        lines.push(vec!["    • Status: ".into(), "enabled".success()].into());
         lines.push(vec!["    • Auth: ".into(), auth_status.to_string().into()].into());
 
         match &cfg.transport {
@@ -1245,7 +1243,7 @@ pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     // Use a hair space (U+200A) to create a subtle, near-invisible separation
     // before the text. VS16 is intentionally omitted to keep spacing tighter
     // in terminals like Ghostty.
-    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").red()].into()];
+    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").error()].into()];
     PlainHistoryCell { lines }
 }
 
@@ -1279,7 +1277,7 @@ impl HistoryCell for PlanUpdateCell {
         let render_step = |status: &StepStatus, text: &str| -> Vec<Line<'static>> {
             let (box_str, step_style) = match status {
                 StepStatus::Completed => ("✔ ", Style::default().crossed_out().dim()),
-                StepStatus::InProgress => ("□ ", Style::default().cyan().bold()),
+                StepStatus::InProgress => ("□ ", Style::default().accent().bold()),
                 StepStatus::Pending => ("□ ", Style::default().dim()),
             };
             let wrap_width = (width as usize)
@@ -1337,7 +1335,7 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
     // Failure title
-    lines.push(Line::from("✘ Failed to apply patch".magenta().bold()));
+    lines.push(Line::from("✘ Failed to apply patch".brand().bold()));
 
     if !stderr.trim().is_empty() {
         let raw_output = output_lines(
@@ -1448,9 +1446,9 @@ fn format_mcp_invocation<'a>(invocation: McpInvocation) -> Line<'a> {
         .unwrap_or_default();
 
     let invocation_spans = vec![
-        invocation.server.clone().cyan(),
+        invocation.server.clone().accent(),
         ".".into(),
-        invocation.tool.cyan(),
+        invocation.tool.accent(),
         "(".into(),
         args_str.dim(),
         ")".into(),
@@ -1533,9 +1531,9 @@ impl HistoryCell for SearchResultsCell {
             let spans = vec![
                 format!("  {}.", entry.rank).dim(),
                 " ".into(),
-                format!("{:>3}%", entry.score_percent).cyan(),
+                format!("{:>3}%", entry.score_percent).accent(),
                 " ".into(),
-                entry.file_path.clone().cyan(),
+                entry.file_path.clone().accent(),
                 format!(":{}-{}", entry.start_line, entry.end_line).dim(),
             ];
             lines.push(Line::from(spans));
@@ -1605,9 +1603,9 @@ impl HistoryCell for MemorySuggestionsCell {
             lines.push(Line::from(vec![
                 "-".into(),
                 " ".into(),
-                format!("[{}]", entry.record_id).cyan(),
+                format!("[{}]", entry.record_id).accent(),
                 " ".into(),
-                format!("{:>3}%", entry.confidence_percent).cyan(),
+                format!("{:>3}%", entry.confidence_percent).accent(),
                 " ".into(),
                 format!("score {:.2}", entry.score).dim(),
             ]));
@@ -6,6 +6,9 @@ use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
+
 #[cfg(test)]
 const ALT_PREFIX: &str = "⌥ + ";
 #[cfg(all(not(test), target_os = "macos"))]
@@ -49,6 +52,25 @@ pub(crate) const fn ctrl(key: KeyCode) -> KeyBinding {
     KeyBinding::new(key, KeyModifiers::CONTROL)
 }
 
+/// The binding hints should show for `action`, honoring `[tui.keymap]`
+/// overrides. `None` when the user unbound the action.
+pub(crate) fn action(action: KeyAction) -> Option<KeyBinding> {
+    keymap().primary(action)
+}
+
+/// Hint span for `action`'s effective binding.
+pub(crate) fn action_span(action: KeyAction) -> Span<'static> {
+    match keymap().primary(action) {
+        Some(binding) => binding.into(),
+        None => Span::styled("unbound", key_hint_style()),
+    }
+}
+
+/// Every effective binding for `action`, for hints that list alternatives.
+pub(crate) fn actions(action: KeyAction) -> &'static [KeyBinding] {
+    keymap().bindings(action)
+}
+
 fn modifiers_to_string(modifiers: KeyModifiers) -> String {
     let mut result = String::new();
     if modifiers.contains(KeyModifiers::CONTROL) {
@@ -80,6 +102,7 @@ impl From<&KeyBinding> for Span<'static> {
             KeyCode::Right => "→".to_string(),
             KeyCode::PageUp => "pgup".to_string(),
             KeyCode::PageDown => "pgdn".to_string(),
+            KeyCode::BackTab => "tab".to_string(),
             _ => format!("{key}").to_ascii_lowercase(),
         };
         Span::styled(format!("{modifiers}{key}"), key_hint_style())
@@ -0,0 +1,625 @@
+//! Named key actions and their effective bindings.
+//!
+//! Every rebindable shortcut in the TUI is a [`KeyAction`]. Widgets ask the
+//! process-wide [`Keymap`] whether a key event triggers an action instead of
+//! matching key codes directly, and footers/hints render the bindings returned
+//! by [`Keymap::bindings`] so they always reflect the user's `[tui.keymap]`
+//! overrides from `config.toml`.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use codex_core::config::types::KeymapBinding;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::text::Span;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Where an action is active. Actions in overlapping contexts must not share
+/// a binding, otherwise one of them becomes unreachable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum KeyContext {
+    /// Handled by the app/chat widget before the bottom pane sees the key.
+    Global,
+    Composer,
+    Approval,
+    Pager,
+    /// The diff browser also uses the pager scrolling actions.
+    DiffBrowser,
+    /// Search in the transcript overlay, on top of the pager actions.
+    TranscriptSearch,
+    /// The background terminals panel, which selects with the pager
+    /// scrolling actions.
+    ExecPanel,
+}
+
+impl KeyContext {
+    fn overlaps(self, other: KeyContext) -> bool {
+        use KeyContext::*;
+        self == other
+            || matches!(
+                (self, other),
+                (Global, Composer | Approval | ExecPanel)
+                    | (Composer | Approval | ExecPanel, Global)
+                    | (Pager, DiffBrowser | TranscriptSearch | ExecPanel)
+                    | (DiffBrowser | TranscriptSearch | ExecPanel, Pager)
+            )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum KeyAction {
+    Transcript,
+    Interrupt,
+    PasteImage,
+    EditQueuedMessage,
+    SelectQueuedMessage,
+    OpenEditor,
+    NewTab,
+    NextTab,
+    PreviousTab,
+    CloseTab,
+    ExecSessions,
+    Submit,
+    Newline,
+    HistoryPrevious,
+    HistoryNext,
+    Quit,
+    ShowShortcuts,
+    Approve,
+    ApproveForSession,
+    Deny,
+    ReviewHunks,
+    ExpandApproval,
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    ScrollTop,
+    ScrollBottom,
+    Close,
+    NextFile,
+    PreviousFile,
+    NextHunk,
+    PreviousHunk,
+    ToggleLayout,
+    EditFile,
+    RevertFile,
+    SwitchSource,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    ToggleSearchRegex,
+    ToggleSearchCase,
+    EditFromMatch,
+    TypeIntoSession,
+    KillSession,
+    PinSession,
+}
+
+impl KeyAction {
+    pub(crate) const ALL: &'static [KeyAction] = &[
+        KeyAction::Transcript,
+        KeyAction::Interrupt,
+        KeyAction::PasteImage,
+        KeyAction::EditQueuedMessage,
+        KeyAction::SelectQueuedMessage,
+        KeyAction::OpenEditor,
+        KeyAction::NewTab,
+        KeyAction::NextTab,
+        KeyAction::PreviousTab,
+        KeyAction::CloseTab,
+        KeyAction::ExecSessions,
+        KeyAction::Submit,
+        KeyAction::Newline,
+        KeyAction::HistoryPrevious,
+        KeyAction::HistoryNext,
+        KeyAction::Quit,
+        KeyAction::ShowShortcuts,
+        KeyAction::Approve,
+        KeyAction::ApproveForSession,
+        KeyAction::Deny,
+        KeyAction::ReviewHunks,
+        KeyAction::ExpandApproval,
+        KeyAction::ScrollUp,
+        KeyAction::ScrollDown,
+        KeyAction::PageUp,
+        KeyAction::PageDown,
+        KeyAction::ScrollTop,
+        KeyAction::ScrollBottom,
+        KeyAction::Close,
+        KeyAction::NextFile,
+        KeyAction::PreviousFile,
+        KeyAction::NextHunk,
+        KeyAction::PreviousHunk,
+        KeyAction::ToggleLayout,
+        KeyAction::EditFile,
+        KeyAction::RevertFile,
+        KeyAction::SwitchSource,
+        KeyAction::Search,
+        KeyAction::NextMatch,
+        KeyAction::PreviousMatch,
+        KeyAction::ToggleSearchRegex,
+        KeyAction::ToggleSearchCase,
+        KeyAction::EditFromMatch,
+        KeyAction::TypeIntoSession,
+        KeyAction::KillSession,
+        KeyAction::PinSession,
+    ];
+
+    /// Name used for this action under `[tui.keymap]`.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            KeyAction::Transcript => "transcript",
+            KeyAction::Interrupt => "interrupt",
+            KeyAction::PasteImage => "paste_image",
+            KeyAction::EditQueuedMessage => "edit_queued_message",
+            KeyAction::SelectQueuedMessage => "select_queued_message",
+            KeyAction::OpenEditor => "open_editor",
+            KeyAction::NewTab => "new_tab",
+            KeyAction::NextTab => "next_tab",
+            KeyAction::PreviousTab => "previous_tab",
+            KeyAction::CloseTab => "close_tab",
+            KeyAction::ExecSessions => "exec_sessions",
+            KeyAction::Submit => "submit",
+            KeyAction::Newline => "newline",
+            KeyAction::HistoryPrevious => "history_previous",
+            KeyAction::HistoryNext => "history_next",
+            KeyAction::Quit => "quit",
+            KeyAction::ShowShortcuts => "show_shortcuts",
+            KeyAction::Approve => "approve",
+            KeyAction::ApproveForSession => "approve_for_session",
+            KeyAction::Deny => "deny",
+            KeyAction::ReviewHunks => "review_hunks",
+            KeyAction::ExpandApproval => "expand_approval",
+            KeyAction::ScrollUp => "scroll_up",
+            KeyAction::ScrollDown => "scroll_down",
+            KeyAction::PageUp => "page_up",
+            KeyAction::PageDown => "page_down",
+            KeyAction::ScrollTop => "scroll_top",
+            KeyAction::ScrollBottom => "scroll_bottom",
+            KeyAction::Close => "close",
+            KeyAction::NextFile => "next_file",
+            KeyAction::PreviousFile => "previous_file",
+            KeyAction::NextHunk => "next_hunk",
+            KeyAction::PreviousHunk => "previous_hunk",
+            KeyAction::ToggleLayout => "toggle_layout",
+            KeyAction::EditFile => "edit_file",
+            KeyAction::RevertFile => "revert_file",
+            KeyAction::SwitchSource => "switch_source",
+            KeyAction::Search => "search",
+            KeyAction::NextMatch => "next_match",
+            KeyAction::PreviousMatch => "previous_match",
+            KeyAction::ToggleSearchRegex => "toggle_search_regex",
+            KeyAction::ToggleSearchCase => "toggle_search_case",
+            KeyAction::EditFromMatch => "edit_from_match",
+            KeyAction::TypeIntoSession => "type_into_session",
+            KeyAction::KillSession => "kill_session",
+            KeyAction::PinSession => "pin_session",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<KeyAction> {
+        KeyAction::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+    }
+
+    fn context(self) -> KeyContext {
+        match self {
+            KeyAction::Transcript
+            | KeyAction::Interrupt
+            | KeyAction::PasteImage
+            | KeyAction::EditQueuedMessage
+            | KeyAction::SelectQueuedMessage
+            | KeyAction::OpenEditor
+            | KeyAction::NewTab
+            | KeyAction::NextTab
+            | KeyAction::PreviousTab
+            | KeyAction::CloseTab
+            | KeyAction::ExecSessions => KeyContext::Global,
+            KeyAction::Submit
+            | KeyAction::Newline
+            | KeyAction::HistoryPrevious
+            | KeyAction::HistoryNext
+            | KeyAction::Quit
+            | KeyAction::ShowShortcuts => KeyContext::Composer,
+            KeyAction::Approve
+            | KeyAction::ApproveForSession
+            | KeyAction::Deny
+            | KeyAction::ReviewHunks
+            | KeyAction::ExpandApproval => KeyContext::Approval,
+            KeyAction::ScrollUp
+            | KeyAction::ScrollDown
+            | KeyAction::PageUp
+            | KeyAction::PageDown
+            | KeyAction::ScrollTop
+            | KeyAction::ScrollBottom
+            | KeyAction::Close => KeyContext::Pager,
+            KeyAction::NextFile
+            | KeyAction::PreviousFile
+            | KeyAction::NextHunk
+            | KeyAction::PreviousHunk
+            | KeyAction::ToggleLayout
+            | KeyAction::EditFile
+            | KeyAction::RevertFile
+            | KeyAction::SwitchSource => KeyContext::DiffBrowser,
+            KeyAction::Search
+            | KeyAction::NextMatch
+            | KeyAction::PreviousMatch
+            | KeyAction::ToggleSearchRegex
+            | KeyAction::ToggleSearchCase
+            | KeyAction::EditFromMatch => KeyContext::TranscriptSearch,
+            KeyAction::TypeIntoSession | KeyAction::KillSession | KeyAction::PinSession => {
+                KeyContext::ExecPanel
+            }
+        }
+    }
+
+    fn default_bindings(self) -> Vec<KeyBinding> {
+        use key_hint::alt;
+        use key_hint::ctrl;
+        use key_hint::plain;
+        use key_hint::shift;
+        match self {
+            KeyAction::Transcript => vec![ctrl(KeyCode::Char('t'))],
+            KeyAction::Interrupt => vec![ctrl(KeyCode::Char('c'))],
+            KeyAction::PasteImage => vec![ctrl(KeyCode::Char('v'))],
+            KeyAction::EditQueuedMessage => vec![alt(KeyCode::Up)],
+            KeyAction::SelectQueuedMessage => vec![alt(KeyCode::Down)],
+            KeyAction::OpenEditor => vec![ctrl(KeyCode::Char('g'))],
+            KeyAction::NewTab => vec![ctrl(KeyCode::Char('o'))],
+            KeyAction::NextTab => vec![ctrl(KeyCode::Char('n'))],
+            KeyAction::PreviousTab => vec![ctrl(KeyCode::Char('p'))],
+            KeyAction::CloseTab => vec![alt(KeyCode::Char('w'))],
+            KeyAction::ExecSessions => vec![ctrl(KeyCode::Char('x'))],
+            KeyAction::Submit => vec![plain(KeyCode::Enter)],
+            KeyAction::Newline => vec![shift(KeyCode::Enter), ctrl(KeyCode::Char('j'))],
+            KeyAction::HistoryPrevious => vec![plain(KeyCode::Up)],
+            KeyAction::HistoryNext => vec![plain(KeyCode::Down)],
+            KeyAction::Quit => vec![ctrl(KeyCode::Char('d'))],
+            KeyAction::ShowShortcuts => vec![plain(KeyCode::Char('?'))],
+            KeyAction::Approve => vec![plain(KeyCode::Char('y'))],
+            KeyAction::ApproveForSession => vec![plain(KeyCode::Char('a'))],
+            KeyAction::Deny => vec![plain(KeyCode::Char('n'))],
+            KeyAction::ReviewHunks => vec![plain(KeyCode::Char('h'))],
+            KeyAction::ExpandApproval => vec![ctrl(KeyCode::Char('a'))],
+            KeyAction::ScrollUp => vec![plain(KeyCode::Up), plain(KeyCode::Char('k'))],
+            KeyAction::ScrollDown => vec![plain(KeyCode::Down), plain(KeyCode::Char('j'))],
+            KeyAction::PageUp => vec![plain(KeyCode::PageUp)],
+            KeyAction::PageDown => vec![plain(KeyCode::PageDown), plain(KeyCode::Char(' '))],
+            KeyAction::ScrollTop => vec![plain(KeyCode::Home)],
+            KeyAction::ScrollBottom => vec![plain(KeyCode::End)],
+            KeyAction::Close => vec![plain(KeyCode::Char('q'))],
+            KeyAction::NextFile => vec![plain(KeyCode::Char(']')), plain(KeyCode::Tab)],
+            KeyAction::PreviousFile => {
+                vec![plain(KeyCode::Char('[')), shift(KeyCode::BackTab)]
+            }
+            KeyAction::NextHunk => vec![plain(KeyCode::Char('n'))],
+            KeyAction::PreviousHunk => vec![plain(KeyCode::Char('p'))],
+            KeyAction::ToggleLayout => vec![plain(KeyCode::Char('v'))],
+            KeyAction::EditFile => vec![plain(KeyCode::Char('e'))],
+            KeyAction::RevertFile => vec![plain(KeyCode::Char('r'))],
+            KeyAction::SwitchSource => vec![plain(KeyCode::Char('t'))],
+            KeyAction::Search => vec![plain(KeyCode::Char('/'))],
+            KeyAction::NextMatch => vec![plain(KeyCode::Char('n'))],
+            KeyAction::PreviousMatch => vec![shift(KeyCode::Char('N'))],
+            KeyAction::ToggleSearchRegex => vec![alt(KeyCode::Char('r'))],
+            KeyAction::ToggleSearchCase => vec![alt(KeyCode::Char('c'))],
+            KeyAction::EditFromMatch => vec![plain(KeyCode::Enter)],
+            KeyAction::TypeIntoSession => {
+                vec![plain(KeyCode::Char('i')), plain(KeyCode::Enter)]
+            }
+            KeyAction::KillSession => vec![plain(KeyCode::Char('x'))],
+            KeyAction::PinSession => vec![plain(KeyCode::Char('p'))],
+        }
+    }
+}
+
+/// Effective bindings for every [`KeyAction`].
+#[derive(Clone, Debug)]
+pub(crate) struct Keymap {
+    bindings: HashMap<KeyAction, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KeyAction::ALL
+                .iter()
+                .map(|action| (*action, action.default_bindings()))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Applies `[tui.keymap]` overrides on top of the defaults. Returns the
+    /// resulting keymap together with human-readable problems (unknown
+    /// actions, unparsable keys, conflicting bindings) to show at startup.
+    pub(crate) fn from_config(overrides: &BTreeMap<String, KeymapBinding>) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let mut problems = Vec::new();
+
+        for (name, binding) in overrides {
+            let Some(action) = KeyAction::from_name(name) else {
+                problems.push(format!("Unknown keymap action `{name}` in [tui.keymap]."));
+                continue;
+            };
+            let mut parsed = Vec::new();
+            for spec in binding.specs() {
+                match parse_key_spec(spec) {
+                    Ok(key) if !parsed.contains(&key) => parsed.push(key),
+                    Ok(_) => {}
+                    Err(err) => problems.push(format!(
+                        "Ignoring keymap binding `{spec}` for `{name}`: {err}."
+                    )),
+                }
+            }
+            keymap.bindings.insert(action, parsed);
+        }
+
+        problems.extend(keymap.conflicts());
+        (keymap, problems)
+    }
+
+    pub(crate) fn bindings(&self, action: KeyAction) -> &[KeyBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The binding shown in hints for `action`, if it is bound at all.
+    pub(crate) fn primary(&self, action: KeyAction) -> Option<KeyBinding> {
+        self.bindings(action).first().copied()
+    }
+
+    pub(crate) fn matches(&self, action: KeyAction, event: KeyEvent) -> bool {
+        let event = normalize_event(event);
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.is_press(event))
+    }
+
+    fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for (i, first) in KeyAction::ALL.iter().enumerate() {
+            for second in &KeyAction::ALL[i + 1..] {
+                if !first.context().overlaps(second.context()) {
+                    continue;
+                }
+                for binding in self.bindings(*first) {
+                    if self.bindings(*second).contains(binding) {
+                        conflicts.push(format!(
+                            "Key `{}` is bound to both `{}` and `{}`.",
+                            Span::from(binding).content,
+                            first.name(),
+                            second.name(),
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// Installs the keymap built from `overrides` for the rest of the process and
+/// returns any problems found while building it.
+pub(crate) fn install(overrides: &BTreeMap<String, KeymapBinding>) -> Vec<String> {
+    let (keymap, problems) = Keymap::from_config(overrides);
+    let _ = KEYMAP.set(keymap);
+    problems
+}
+
+/// The installed keymap, or the defaults when none was installed (tests).
+pub(crate) fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
+
+/// Terminals disagree on how they report shifted letters and control
+/// combinations; fold events into the shape produced by [`parse_key_spec`].
+fn normalize_event(mut event: KeyEvent) -> KeyEvent {
+    if let KeyCode::Char(c) = event.code {
+        if event.modifiers.contains(KeyModifiers::CONTROL) {
+            event.code = KeyCode::Char(c.to_ascii_lowercase());
+            event.modifiers.remove(KeyModifiers::SHIFT);
+        } else if c.is_ascii_uppercase() {
+            event.modifiers.insert(KeyModifiers::SHIFT);
+        }
+    }
+    event
+}
+
+/// Parses specs such as `ctrl-enter`, `alt+up`, `shift-tab`, `f5` or `?`.
+fn parse_key_spec(spec: &str) -> Result<KeyBinding, String> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err("empty key".to_string());
+    }
+    // A trailing separator is the key itself, as in `alt--` or `ctrl-+`.
+    let (prefix, key) = if spec.len() > 1
+        && spec.ends_with(['-', '+'])
+        && spec[..spec.len() - 1].ends_with(['-', '+'])
+    {
+        (&spec[..spec.len() - 2], &spec[spec.len() - 1..])
+    } else {
+        match spec.rfind(['-', '+']) {
+            Some(idx) if idx + 1 < spec.len() => (&spec[..idx], &spec[idx + 1..]),
+            _ => ("", spec),
+        }
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for modifier in prefix.split(['-', '+']).filter(|m| !m.is_empty()) {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "meta" | "option" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            other => return Err(format!("unknown modifier `{other}`")),
+        };
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => {
+            if c.is_ascii_uppercase() {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            if modifiers.contains(KeyModifiers::CONTROL) {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_lowercase())
+            } else if modifiers.contains(KeyModifiers::SHIFT) {
+                KeyCode::Char(c.to_ascii_uppercase())
+            } else {
+                KeyCode::Char(c)
+            }
+        }
+        _ => named_key(&key.to_ascii_lowercase(), &mut modifiers)
+            .ok_or_else(|| format!("unknown key `{key}`"))?,
+    };
+    Ok(KeyBinding::new(code, modifiers))
+}
+
+fn named_key(name: &str, modifiers: &mut KeyModifiers) -> Option<KeyCode> {
+    let code = match name {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "tab" => KeyCode::Tab,
+        "backtab" => {
+            *modifiers |= KeyModifiers::SHIFT;
+            KeyCode::BackTab
+        }
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let number = name.strip_prefix('f')?.parse::<u8>().ok()?;
+            if !(1..=12).contains(&number) {
+                return None;
+            }
+            KeyCode::F(number)
+        }
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn overrides(entries: &[(&str, KeymapBinding)]) -> BTreeMap<String, KeymapBinding> {
+        entries
+            .iter()
+            .map(|(name, binding)| (name.to_string(), binding.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        assert_eq!(Keymap::default().conflicts(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn parses_key_specs() {
+        assert_eq!(
+            parse_key_spec("ctrl-enter"),
+            Ok(key_hint::ctrl(KeyCode::Enter))
+        );
+        assert_eq!(parse_key_spec("Alt+Up"), Ok(key_hint::alt(KeyCode::Up)));
+        assert_eq!(
+            parse_key_spec("shift-tab"),
+            Ok(key_hint::shift(KeyCode::BackTab))
+        );
+        assert_eq!(
+            parse_key_spec("ctrl-K"),
+            Ok(key_hint::ctrl(KeyCode::Char('k')))
+        );
+        assert_eq!(
+            parse_key_spec("shift-k"),
+            Ok(key_hint::shift(KeyCode::Char('K')))
+        );
+        assert_eq!(parse_key_spec("f5"), Ok(key_hint::plain(KeyCode::F(5))));
+        assert_eq!(parse_key_spec("?"), Ok(key_hint::plain(KeyCode::Char('?'))));
+        assert_eq!(
+            parse_key_spec("alt--"),
+            Ok(key_hint::alt(KeyCode::Char('-')))
+        );
+        assert!(parse_key_spec("hyper-x").is_err());
+        assert!(parse_key_spec("ctrl-nope").is_err());
+    }
+
+    #[test]
+    fn overrides_replace_defaults_and_report_problems() {
+        let (keymap, problems) = Keymap::from_config(&overrides(&[
+            ("submit", KeymapBinding::One("ctrl-enter".to_string())),
+            (
+                "newline",
+                KeymapBinding::Many(vec!["enter".to_string(), "bogus-key".to_string()]),
+            ),
+            ("teleport", KeymapBinding::One("ctrl-x".to_string())),
+        ]));
+
+        assert_eq!(
+            keymap.bindings(KeyAction::Submit),
+            &[key_hint::ctrl(KeyCode::Enter)]
+        );
+        assert_eq!(
+            keymap.bindings(KeyAction::Newline),
+            &[key_hint::plain(KeyCode::Enter)]
+        );
+        assert_eq!(
+            problems,
+            vec![
+                "Ignoring keymap binding `bogus-key` for `newline`: unknown modifier `bogus`."
+                    .to_string(),
+                "Unknown keymap action `teleport` in [tui.keymap].".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_overrides_are_reported() {
+        let (_, problems) = Keymap::from_config(&overrides(&[(
+            "quit",
+            KeymapBinding::One("ctrl-t".to_string()),
+        )]));
+
+        assert_eq!(
+            problems,
+            vec!["Key `ctrl + t` is bound to both `transcript` and `quit`."]
+        );
+    }
+
+    #[test]
+    fn matching_tolerates_terminal_variations() {
+        let keymap = Keymap::default();
+        assert!(keymap.matches(
+            KeyAction::Interrupt,
+            KeyEvent::new(
+                KeyCode::Char('C'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )
+        ));
+        assert!(!keymap.matches(
+            KeyAction::Interrupt,
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)
+        ));
+    }
+}
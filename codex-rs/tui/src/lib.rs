@@ -45,6 +45,7 @@ mod app;
 mod app_backtrack;
 mod app_event;
 mod app_event_sender;
+mod app_tabs;
 mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
@@ -52,9 +53,11 @@ mod cli;
 mod clipboard_paste;
 mod color;
 pub mod custom_terminal;
+mod diff_browser;
 mod diff_render;
 mod exec_cell;
 mod exec_command;
+mod external_editor;
 mod file_search;
 mod frames;
 mod get_git_diff;
@@ -64,6 +67,7 @@ mod index_status;
 mod index_worker;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -74,6 +78,7 @@ pub mod onboarding;
 mod pager_overlay;
 pub mod public_widgets;
 mod render;
+mod replay;
 mod resume_picker;
 mod session_log;
 mod shimmer;
@@ -83,15 +88,15 @@ mod status_indicator_widget;
 mod streaming;
 mod style;
 mod terminal_palette;
+mod test_backend;
 mod text_formatting;
+mod theme;
+mod transcript_search;
 mod tui;
 mod ui_consts;
 mod version;
 mod wrapping;
 
-#[cfg(test)]
-pub mod test_backend;
-
 pub mod updates;
 pub use updates::UpdateAction;
 
@@ -101,9 +106,11 @@ use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
 use crate::tui::Tui;
 pub use cli::Cli;
+pub use cli::ReplayCli;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+pub use replay::run_replay;
 use std::io::Write as _;
 
 // (tests access modules directly within the crate)
@@ -237,6 +244,7 @@ pub async fn run_main(
     refresh_model_metadata(&mut config);
     codex_agentic_core::provider::sanitize_reasoning_overrides(&mut config);
     codex_agentic_core::provider::sanitize_tool_overrides(&mut config);
+    codex_agentic_core::provider::apply_provider_fallbacks(&mut config);
 
     apply_overlay_to_config(&mut config, &overlay_prompt);
 
@@ -332,6 +340,7 @@ pub async fn run_main(
             .await
             .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
     }
+    codex_agentic_core::provider::discover_model_limits(&mut config).await;
 
     let otel = codex_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION"));
 
@@ -360,6 +369,8 @@ pub async fn run_main(
         }
     };
 
+    codex_core::spawn_startup_retention(&config);
+
     run_ratatui_app(
         cli,
         config,
@@ -390,6 +401,9 @@ async fn run_ratatui_app(
         tracing::error!("panic: {info}");
         prev_hook(info);
     }));
+    // Widgets read the theme on every render, so install it before the first draw.
+    let theme_problems = theme::install(config.tui_theme.as_deref(), &config.codex_home);
+
     let mut terminal = tui::init()?;
     terminal.clear()?;
 
@@ -405,6 +419,7 @@ async fn run_ratatui_app(
     if let Some(upgrade) = updates::get_upgrade_version(&config, &update_config) {
         use crate::history_cell::padded_emoji;
         use crate::history_cell::with_border_with_inner_width;
+        use crate::theme::ThemeStylize as _;
         use ratatui::style::Stylize as _;
         use ratatui::text::Line;
         use ratatui::text::Span;
@@ -419,29 +434,29 @@ async fn run_ratatui_app(
 
         let mut content_lines: Vec<Line<'static>> = vec![
             Line::from(vec![
-                padded_emoji("✨").bold().cyan(),
-                "Update available!".bold().cyan(),
+                padded_emoji("✨").bold().accent(),
+                "Update available!".bold().accent(),
                 " ".into(),
                 format!("{current_version} -> {}.", upgrade.latest_version).bold(),
             ]),
             Line::from(""),
             Line::from("See full release notes:"),
             Line::from(""),
-            Line::from(Span::raw(release_url.clone()).cyan().underlined()),
+            Line::from(Span::raw(release_url.clone()).accent().underlined()),
             Line::from(""),
         ];
 
         if let Some(custom_cmd) = upgrade.upgrade_cmd.as_deref() {
             content_lines.push(Line::from(vec![
                 "Run ".into(),
-                Span::raw(custom_cmd.to_owned()).cyan(),
+                Span::raw(custom_cmd.to_owned()).accent(),
                 " to update.".into(),
             ]));
         } else if managed_by_npm {
             let npm_cmd = "npm install -g @openai/codex@latest";
             content_lines.push(Line::from(vec![
                 "Run ".into(),
-                npm_cmd.cyan(),
+                npm_cmd.accent(),
                 " to update.".into(),
             ]));
         } else if cfg!(target_os = "macos")
@@ -450,13 +465,13 @@ async fn run_ratatui_app(
             let brew_cmd = "brew upgrade codex";
             content_lines.push(Line::from(vec![
                 "Run ".into(),
-                brew_cmd.cyan(),
+                brew_cmd.accent(),
                 " to update.".into(),
             ]));
         } else {
             content_lines.push(Line::from(vec![
                 "See ".into(),
-                Span::raw(release_url.clone()).cyan().underlined(),
+                Span::raw(release_url.clone()).accent().underlined(),
                 " for installation options.".into(),
             ]));
         }
@@ -583,6 +598,19 @@ async fn run_ratatui_app(
     } else {
         resume_picker::ResumeSelection::StartFresh
     };
+    let resume_selection = match (resume_selection, cli.resume_fork_at, cli.resume_turn) {
+        (resume_picker::ResumeSelection::Resume(path), Some(turn), _) => {
+            resume_picker::ResumeSelection::Fork {
+                path,
+                turn,
+                restore_snapshot: cli.resume_restore_snapshot,
+            }
+        }
+        (resume_picker::ResumeSelection::Resume(path), None, Some(turn)) => {
+            resume_picker::ResumeSelection::ResumeAtTurn { path, turn }
+        }
+        (selection, _, _) => selection,
+    };
 
     let Cli { prompt, images, .. } = cli;
 
@@ -595,6 +623,7 @@ async fn run_ratatui_app(
         images,
         resume_selection,
         feedback,
+        theme_problems,
     )
     .await;
 
@@ -2,20 +2,35 @@ use clap::Parser;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_common::CliConfigOverrides;
 use codex_tui::Cli;
+use codex_tui::ReplayCli;
 use codex_tui::run_main;
+use codex_tui::run_replay;
 
 #[derive(Parser, Debug)]
 struct TopCli {
     #[clap(flatten)]
     config_overrides: CliConfigOverrides,
 
+    #[clap(subcommand)]
+    command: Option<TopCommand>,
+
     #[clap(flatten)]
     inner: Cli,
 }
 
+#[derive(Debug, clap::Subcommand)]
+enum TopCommand {
+    /// Replay a session log recorded with `CODEX_TUI_RECORD_SESSION=1`.
+    Replay(ReplayCli),
+}
+
 fn main() -> anyhow::Result<()> {
     arg0_dispatch_or_else(|codex_linux_sandbox_exe| async move {
         let top_cli = TopCli::parse();
+        if let Some(TopCommand::Replay(replay_cli)) = top_cli.command {
+            run_replay(replay_cli)?;
+            return Ok(());
+        }
         let mut inner = top_cli.inner;
         inner
             .config_overrides
@@ -8,6 +8,22 @@ pub(crate) fn append_markdown(
     crate::render::line_utils::push_owned_lines(&rendered.lines, lines);
 }
 
+/// [`append_markdown`] for a document that is re-rendered as it streams in;
+/// `cache` carries code block highlighting between renders.
+pub(crate) fn append_markdown_with_highlight_cache(
+    markdown_source: &str,
+    width: Option<usize>,
+    lines: &mut Vec<Line<'static>>,
+    cache: &mut crate::render::highlight::CodeHighlightCache,
+) {
+    let rendered = crate::markdown_render::render_markdown_text_with_highlight_cache(
+        markdown_source,
+        width,
+        cache,
+    );
+    crate::render::line_utils::push_owned_lines(&rendered.lines, lines);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,9 @@
+use crate::render::highlight::CodeHighlightCache;
+use crate::render::highlight::Language;
+use crate::render::highlight::highlight_code_to_lines;
 use crate::render::line_utils::line_to_static;
+use crate::theme::ThemeStylize;
+use crate::theme::theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use pulldown_cmark::CodeBlockKind;
@@ -45,6 +50,23 @@ pub(crate) fn render_markdown_text_with_width(input: &str, width: Option<usize>)
     w.text
 }
 
+/// Like [`render_markdown_text_with_width`], but reuses `cache` to highlight
+/// code blocks incrementally when the same growing document is re-rendered.
+pub(crate) fn render_markdown_text_with_highlight_cache(
+    input: &str,
+    width: Option<usize>,
+    cache: &mut CodeHighlightCache,
+) -> Text<'static> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    let parser = Parser::new_ext(input, options);
+    let mut w = Writer::new(parser, width);
+    w.highlight_cache = Some(std::mem::take(cache));
+    w.run();
+    *cache = w.highlight_cache.take().unwrap_or_default();
+    w.text
+}
+
 struct Writer<'a, I>
 where
     I: Iterator<Item = Event<'a>>,
@@ -59,6 +81,12 @@ where
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
+    /// Language of the current fenced block; its text is buffered in
+    /// `code_block_source` and highlighted when the block ends.
+    code_block_language: Option<Language>,
+    code_block_source: String,
+    highlighted_block_count: usize,
+    highlight_cache: Option<CodeHighlightCache>,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -83,6 +111,10 @@ where
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
+            code_block_language: None,
+            code_block_source: String::new(),
+            highlighted_block_count: 0,
+            highlight_cache: None,
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -233,6 +265,19 @@ where
     }
 
     fn text(&mut self, text: CowStr<'a>) {
+        if self.in_code_block && self.code_block_language.is_some() {
+            self.code_block_source.push_str(&text);
+            return;
+        }
+        let style = self.inline_styles.last().copied().unwrap_or_default();
+        let lines = text
+            .lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), style)))
+            .collect();
+        self.push_text_lines(lines);
+    }
+
+    fn push_text_lines(&mut self, lines: Vec<Line<'static>>) {
         if self.pending_marker_line {
             self.push_line(Line::default());
         }
@@ -253,7 +298,7 @@ where
                 self.push_line(Line::default());
             }
         }
-        for (i, line) in text.lines().enumerate() {
+        for (i, line) in lines.into_iter().enumerate() {
             if self.needs_newline {
                 self.push_line(Line::default());
                 self.needs_newline = false;
@@ -261,12 +306,9 @@ where
             if i > 0 {
                 self.push_line(Line::default());
             }
-            let content = line.to_string();
-            let span = Span::styled(
-                content,
-                self.inline_styles.last().copied().unwrap_or_default(),
-            );
-            self.push_span(span);
+            for span in line.spans {
+                self.push_span(span);
+            }
         }
         self.needs_newline = false;
     }
@@ -330,7 +372,9 @@ where
                 None => Some(vec![Span::from(" ".repeat(width - 1) + "- ")]),
                 Some(index) => {
                     *index += 1;
-                    Some(vec![format!("{:width$}. ", *index - 1).light_blue()])
+                    Some(vec![
+                        format!("{:width$}. ", *index - 1).themed(theme().list_marker),
+                    ])
                 }
             }
         } else {
@@ -347,7 +391,9 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
+        self.code_block_language = lang.as_deref().and_then(Language::from_fence_info);
+        self.code_block_source.clear();
         self.flush_current_line();
         if !self.text.lines.is_empty() {
             self.push_blank_line();
@@ -362,6 +408,22 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some(language) = self.code_block_language.take() {
+            let source = std::mem::take(&mut self.code_block_source);
+            let block_index = self.highlighted_block_count;
+            self.highlighted_block_count += 1;
+            let mut lines = match self.highlight_cache.as_mut() {
+                Some(cache) => cache.highlight(block_index, &source, language),
+                None => highlight_code_to_lines(&source, language),
+            };
+            // Match `str::lines`, which drops the empty line after a final newline.
+            if source.ends_with('\n') {
+                lines.pop();
+            }
+            if !lines.is_empty() {
+                self.push_text_lines(lines);
+            }
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
@@ -384,7 +446,7 @@ where
     fn pop_link(&mut self) {
         if let Some(link) = self.link.take() {
             self.push_span(" (".into());
-            self.push_span(link.cyan().underlined());
+            self.push_span(link.accent().underlined());
             self.push_span(")".into());
         }
     }
@@ -422,7 +484,7 @@ where
             .iter()
             .any(|ctx| ctx.prefix.iter().any(|s| s.content.contains('>')));
         let style = if blockquote_active {
-            Style::new().green()
+            theme().quote
         } else {
             line.style
         };
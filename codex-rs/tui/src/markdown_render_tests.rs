@@ -1,4 +1,5 @@
 use pretty_assertions::assert_eq;
+use ratatui::style::Modifier;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -652,12 +653,40 @@ fn link() {
 }
 
 #[test]
-fn code_block_unhighlighted() {
-    let text = render_markdown_text("```rust\nfn main() {}\n```\n");
+fn code_block_unknown_language_unhighlighted() {
+    let text = render_markdown_text("```text\nfn main() {}\n```\n");
     let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
     assert_eq!(text, expected);
 }
 
+#[test]
+fn code_block_highlighted_by_fence_language() {
+    let text = render_markdown_text("```rust\n// entry\nfn main() {}\n```\n");
+    let contents: Vec<Vec<String>> = text
+        .lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect()
+        })
+        .collect();
+    assert_eq!(contents.len(), 2);
+    assert_eq!(contents[0].concat(), "// entry");
+    assert_eq!(contents[1].concat(), "fn main() {}");
+    assert!(
+        contents[1].iter().any(|span| span == "fn"),
+        "expected `fn` as its own highlighted span, got {contents:?}"
+    );
+    let comment = text.lines[0]
+        .spans
+        .iter()
+        .find(|span| span.content == "// entry")
+        .expect("comment span");
+    assert!(comment.style.add_modifier.contains(Modifier::DIM));
+}
+
 #[test]
 fn code_block_multiple_lines_root() {
     let md = "```\nfirst\nsecond\n```\n";
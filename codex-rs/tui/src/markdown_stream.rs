@@ -1,6 +1,7 @@
 use ratatui::text::Line;
 
 use crate::markdown;
+use crate::render::highlight::CodeHighlightCache;
 
 /// Newline-gated accumulator that renders markdown and commits only fully
 /// completed logical lines.
@@ -8,6 +9,8 @@ pub(crate) struct MarkdownStreamCollector {
     buffer: String,
     committed_line_count: usize,
     width: Option<usize>,
+    /// Keeps code block highlighting incremental across re-renders.
+    highlight_cache: CodeHighlightCache,
 }
 
 impl MarkdownStreamCollector {
@@ -16,12 +19,14 @@ impl MarkdownStreamCollector {
             buffer: String::new(),
             committed_line_count: 0,
             width,
+            highlight_cache: CodeHighlightCache::default(),
         }
     }
 
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.committed_line_count = 0;
+        self.highlight_cache = CodeHighlightCache::default();
     }
 
     pub fn push_delta(&mut self, delta: &str) {
@@ -41,7 +46,12 @@ impl MarkdownStreamCollector {
             return Vec::new();
         };
         let mut rendered: Vec<Line<'static>> = Vec::new();
-        markdown::append_markdown(&source, self.width, &mut rendered);
+        markdown::append_markdown_with_highlight_cache(
+            &source,
+            self.width,
+            &mut rendered,
+            &mut self.highlight_cache,
+        );
         let mut complete_line_count = rendered.len();
         if complete_line_count > 0
             && crate::render::line_utils::is_blank_line_spaces_only(
@@ -82,7 +92,12 @@ impl MarkdownStreamCollector {
         tracing::trace!("markdown finalize (raw source):\n---\n{source}\n---");
 
         let mut rendered: Vec<Line<'static>> = Vec::new();
-        markdown::append_markdown(&source, self.width, &mut rendered);
+        markdown::append_markdown_with_highlight_cache(
+            &source,
+            self.width,
+            &mut rendered,
+            &mut self.highlight_cache,
+        );
 
         let out = if self.committed_line_count >= rendered.len() {
             Vec::new()
@@ -46,6 +46,7 @@ use uuid::Uuid;
 
 use crate::custom_terminal::Frame as TerminalFrame;
 use crate::text_formatting::truncate_text;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -860,7 +861,7 @@ impl MemoryManagerState {
         if !self.enabled {
             return Line::from(vec![
                 Span::from("MiniCPM: ").dim(),
-                "runtime disabled".red(),
+                "runtime disabled".error(),
             ]);
         }
         let mut spans: Vec<Span<'static>> = vec![Span::from("MiniCPM: ").dim()];
@@ -873,21 +874,22 @@ impl MemoryManagerState {
                     .with_timezone(&Local)
                     .format("%Y-%m-%d %H:%M")
                     .to_string();
-                spans.push(format!("ready ({version}, updated {ts})").green());
+                spans.push(format!("ready ({version}, updated {ts})").success());
             }
             Some(MiniCpmStatus::Missing {
                 version, missing, ..
             }) => {
                 if let Some(message) = self.format_download_progress() {
-                    spans.push(message.cyan());
+                    spans.push(message.accent());
                 } else if missing.is_empty() {
-                    spans.push(format!("verifying cache (version {version})").cyan());
+                    spans.push(format!("verifying cache (version {version})").accent());
                 } else {
-                    spans
-                        .push(format!("missing {} (version {version})", missing.join(", ")).cyan());
+                    spans.push(
+                        format!("missing {} (version {version})", missing.join(", ")).accent(),
+                    );
                 }
             }
-            None => spans.push("status unavailable".red()),
+            None => spans.push("status unavailable".error()),
         }
 
         if let Some(failure) = self.diagnostics.last_failure.as_ref() {
@@ -898,7 +900,7 @@ impl MemoryManagerState {
                 .to_string();
             let message = truncate_text(&failure.message, 40);
             spans.push("   ".into());
-            spans.push(format!("last error {message} @ {when}").red());
+            spans.push(format!("last error {message} @ {when}").error());
         }
 
         Line::from(spans)
@@ -1268,7 +1270,7 @@ impl MemoryManagerState {
     ) {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title("Memory Manager".cyan().bold());
+            .title("Memory Manager".accent().bold());
         let inner = block.inner(area);
         block.render(area, frame.buffer_mut());
 
@@ -1295,13 +1297,13 @@ impl MemoryManagerState {
         if editing {
             query_display.push('▌');
         }
-        let search_line = Line::from(vec!["[S]earch: ".cyan().bold(), query_display.into()]);
+        let search_line = Line::from(vec!["[S]earch: ".accent().bold(), query_display.into()]);
         Paragraph::new(search_line).render(columns[0], frame.buffer_mut());
 
         let runtime_line = if self.enabled {
-            "Runtime: on".green().bold()
+            "Runtime: on".success().bold()
         } else {
-            "Runtime: off".red().bold()
+            "Runtime: off".error().bold()
         };
         let toggle_hint = if self.enabled {
             " [e] disable".dim()
@@ -1315,7 +1317,7 @@ impl MemoryManagerState {
                 MemoryPreviewMode::Disabled => "auto",
             }
         )
-        .cyan()
+        .accent()
         .bold();
         Paragraph::new(Line::from(vec![
             runtime_line,
@@ -1330,7 +1332,7 @@ impl MemoryManagerState {
 
         let confidence_line = Line::from(vec![
             format!("CF ≥ {:>3}%", (min_conf * 100.0).round() as i32)
-                .cyan()
+                .accent()
                 .bold(),
             " [+/-]".dim(),
         ]);
@@ -1359,7 +1361,7 @@ impl MemoryManagerState {
     ) {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title("Recent Memories".cyan().bold());
+            .title("Recent Memories".accent().bold());
         let inner = block.inner(area);
         block.render(area, frame.buffer_mut());
         if inner.width == 0 || inner.height == 0 {
@@ -1398,7 +1400,7 @@ impl MemoryManagerState {
                 }
                 let summary = truncate_text(&summary_text, summary_width);
                 let tool_marker: Span<'static> = if record.tool_last_fetched_at.is_some() {
-                    "[t]".cyan()
+                    "[t]".accent()
                 } else {
                     "[ ]".dim()
                 };
@@ -1522,8 +1524,8 @@ impl MemoryManagerState {
         if let Some(status) = status {
             status_spans.push("   ".into());
             let styled = match status.level {
-                StatusLevel::Success => status.text.clone().green(),
-                StatusLevel::Error => status.text.clone().red(),
+                StatusLevel::Success => status.text.clone().success(),
+                StatusLevel::Error => status.text.clone().error(),
             };
             status_spans.push(styled);
         }
@@ -1568,7 +1570,7 @@ impl MemoryManagerState {
             title,
             (form.confidence * 100.0).round() as i32
         ))
-        .cyan()
+        .accent()
         .bold();
         let block = Block::default().borders(Borders::ALL).title(title_line);
         let inner = block.inner(area);
@@ -1624,7 +1626,7 @@ impl MemoryManagerState {
         ];
         if let Some(error) = &form.error {
             footer.push("   ".into());
-            footer.push(error.clone().red());
+            footer.push(error.clone().error());
         }
         Paragraph::new(Line::from(footer))
             .wrap(ratatui::widgets::Wrap { trim: true })
@@ -1638,7 +1640,7 @@ impl MemoryManagerState {
         label: &str,
         active: bool,
     ) {
-        let mut spans = vec![label.cyan().bold()];
+        let mut spans = vec![label.accent().bold()];
         if active {
             spans.push("  (editing)".dim());
         }
@@ -1665,7 +1667,7 @@ impl MemoryManagerState {
                 let (before, after) = text.split_at(split_idx);
                 let spans: Vec<Span<'static>> = vec![
                     before.to_string().into(),
-                    "▌".cyan().bold(),
+                    "▌".accent().bold(),
                     after.to_string().into(),
                 ];
                 rendered.push(Line::from(spans));
@@ -1699,7 +1701,7 @@ impl MemoryManagerState {
             let (before, after) = value.split_at(split_idx);
             vec![
                 before.to_string().into(),
-                "▌".cyan().bold(),
+                "▌".accent().bold(),
                 after.to_string().into(),
             ]
         } else {
@@ -1717,7 +1719,7 @@ impl MemoryManagerState {
         };
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(title.cyan().bold());
+            .title(title.accent().bold());
         let inner = block.inner(area);
         block.render(area, frame.buffer_mut());
         let layout = Layout::default()
@@ -1749,7 +1751,7 @@ impl MemoryManagerState {
         Clear.render(popup_area, frame.buffer_mut());
         let block = Block::default()
             .borders(Borders::ALL)
-            .title("Save Changes?".cyan().bold());
+            .title("Save Changes?".accent().bold());
         let inner = block.inner(popup_area);
         block.render(popup_area, frame.buffer_mut());
         let lines = vec![
@@ -1,5 +1,7 @@
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::tui;
 use crate::tui::TuiEvent;
 use codex_core::protocol::MemoryPreviewEntry;
@@ -123,29 +125,19 @@ impl MemoryPreviewOverlay {
 
     fn handle_key_event(&mut self, tui: &mut tui::Tui, key: KeyEvent) {
         if self.rows.is_empty() {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.send_decision(Vec::new());
-                }
-                _ => {}
+            if key.code == KeyCode::Esc || keymap().matches(KeyAction::Close, key) {
+                self.send_decision(Vec::new());
             }
             return;
         }
+        let keymap = keymap();
         match key {
-            KeyEvent {
-                code: KeyCode::Up,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if keymap.matches(KeyAction::ScrollUp, key) => {
                 if self.cursor > 0 {
                     self.cursor -= 1;
                 }
             }
-            KeyEvent {
-                code: KeyCode::Down,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if keymap.matches(KeyAction::ScrollDown, key) => {
                 if self.cursor + 1 < self.rows.len() {
                     self.cursor += 1;
                 }
@@ -187,14 +179,12 @@ impl MemoryPreviewOverlay {
                 code: KeyCode::Esc,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('q'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
             } => {
                 self.send_decision(Vec::new());
             }
+            _ if keymap.matches(KeyAction::Close, key) => {
+                self.send_decision(Vec::new());
+            }
             KeyEvent {
                 code: KeyCode::Char('m'),
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
@@ -8,7 +8,6 @@ use crossterm::event::KeyEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::prelude::Widget;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -18,6 +17,7 @@ use ratatui::widgets::Wrap;
 
 use crate::onboarding::onboarding_screen::KeyboardHandler;
 use crate::onboarding::onboarding_screen::StepStateProvider;
+use crate::theme::ThemeStylize;
 
 use super::onboarding_screen::StepState;
 
@@ -64,7 +64,7 @@ impl WidgetRef for &TrustDirectoryWidget {
             |idx: usize, option: TrustDirectorySelection, text: &str| -> Line<'static> {
                 let is_selected = self.highlighted == option;
                 if is_selected {
-                    Line::from(format!("> {}. {text}", idx + 1)).cyan()
+                    Line::from(format!("> {}. {text}", idx + 1)).accent()
                 } else {
                     Line::from(format!("  {}. {}", idx + 1, text))
                 }
@@ -95,7 +95,7 @@ impl WidgetRef for &TrustDirectoryWidget {
         }
         lines.push("".into());
         if let Some(error) = &self.error {
-            lines.push(Line::from(format!("  {error}")).fg(Color::Red));
+            lines.push(Line::from(format!("  {error}")).error());
             lines.push("".into());
         }
         // AE: Following styles.md, this should probably be Cyan because it's a user input tip.
@@ -7,7 +7,6 @@ use crossterm::event::KeyEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::prelude::Widget;
-use ratatui::style::Color;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
@@ -16,6 +15,7 @@ use ratatui::widgets::Wrap;
 
 use crate::onboarding::onboarding_screen::KeyboardHandler;
 use crate::onboarding::onboarding_screen::StepStateProvider;
+use crate::theme::ThemeStylize;
 
 use super::onboarding_screen::StepState;
 
@@ -109,7 +109,7 @@ impl WidgetRef for &WindowsSetupWidget {
         let create_option =
             |idx: usize, option: WindowsSetupSelection, text: &str| -> Line<'static> {
                 if self.highlighted == option {
-                    Line::from(format!("> {}. {text}", idx + 1)).cyan()
+                    Line::from(format!("> {}. {text}", idx + 1)).accent()
                 } else {
                     Line::from(format!("  {}. {}", idx + 1, text))
                 }
@@ -128,7 +128,7 @@ impl WidgetRef for &WindowsSetupWidget {
         lines.push("".into());
 
         if let Some(error) = &self.error {
-            lines.push(Line::from(format!("  {error}")).fg(Color::Red));
+            lines.push(Line::from(format!("  {error}")).error());
             lines.push("".into());
         }
 
@@ -3,15 +3,24 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::app_event_sender::AppEventSender;
+use crate::diff_browser::DiffBrowserOverlay;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::memory_preview_overlay::MemoryPreviewOverlay;
 use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
+use crate::theme::ThemeStylize;
+use crate::transcript_search::LineHighlight;
+use crate::transcript_search::SearchInput;
+use crate::transcript_search::SearchMatch;
+use crate::transcript_search::TranscriptSearch;
+use crate::transcript_search::apply_highlights;
 use crate::tui;
 use crate::tui::TuiEvent;
 use codex_core::protocol::MemoryPreviewEvent;
@@ -34,6 +43,7 @@ pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
     MemoryPreview(MemoryPreviewOverlay),
+    DiffBrowser(DiffBrowserOverlay),
 }
 
 impl Overlay {
@@ -67,6 +77,7 @@ impl Overlay {
                 o.handle_event(tui, event);
                 Ok(())
             }
+            Overlay::DiffBrowser(o) => o.handle_event(tui, event),
         }
     }
 
@@ -75,32 +86,41 @@ impl Overlay {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
             Overlay::MemoryPreview(o) => o.is_done(),
+            Overlay::DiffBrowser(o) => o.is_done(),
         }
     }
 }
 
-const KEY_UP: KeyBinding = key_hint::plain(KeyCode::Up);
-const KEY_DOWN: KeyBinding = key_hint::plain(KeyCode::Down);
-const KEY_PAGE_UP: KeyBinding = key_hint::plain(KeyCode::PageUp);
-const KEY_PAGE_DOWN: KeyBinding = key_hint::plain(KeyCode::PageDown);
-const KEY_SPACE: KeyBinding = key_hint::plain(KeyCode::Char(' '));
-const KEY_HOME: KeyBinding = key_hint::plain(KeyCode::Home);
-const KEY_END: KeyBinding = key_hint::plain(KeyCode::End);
-const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
 const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
-const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
 
+/// Hint keys for `actions`: the effective primary binding of each bound one.
+pub(crate) fn action_keys(actions: &[KeyAction]) -> Vec<KeyBinding> {
+    actions
+        .iter()
+        .filter_map(|action| key_hint::action(*action))
+        .collect()
+}
+
 // Common pager navigation hints rendered on the first line
-const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
-    (&[KEY_UP, KEY_DOWN], "to scroll"),
-    (&[KEY_PAGE_UP, KEY_PAGE_DOWN], "to page"),
-    (&[KEY_HOME, KEY_END], "to jump"),
-];
+fn render_pager_key_hints(area: Rect, buf: &mut Buffer) {
+    let scroll = action_keys(&[KeyAction::ScrollUp, KeyAction::ScrollDown]);
+    let page = action_keys(&[KeyAction::PageUp, KeyAction::PageDown]);
+    let jump = action_keys(&[KeyAction::ScrollTop, KeyAction::ScrollBottom]);
+    render_key_hints(
+        area,
+        buf,
+        &[
+            (&scroll, "to scroll"),
+            (&page, "to page"),
+            (&jump, "to jump"),
+        ],
+    );
+}
 
 // Render a single line of key hints from (key(s), description) pairs.
-fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
+pub(crate) fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (keys, desc) in pairs {
@@ -129,6 +149,8 @@ struct PagerView {
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// If set, on next render ensure this row of this chunk is visible.
+    pending_scroll_row: Option<(usize, usize)>,
 }
 
 impl PagerView {
@@ -140,6 +162,7 @@ impl PagerView {
             last_content_height: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
+            pending_scroll_row: None,
         }
     }
 
@@ -162,6 +185,9 @@ impl PagerView {
         if let Some(idx) = self.pending_scroll_chunk.take() {
             self.ensure_chunk_visible(idx, content_area);
         }
+        if let Some((idx, row)) = self.pending_scroll_row.take() {
+            self.ensure_row_visible(idx, row, content_area);
+        }
         self.scroll_offset = self
             .scroll_offset
             .min(content_height.saturating_sub(content_area.height as usize));
@@ -248,25 +274,26 @@ impl PagerView {
     }
 
     fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
+        let keymap = keymap();
         match key_event {
-            e if KEY_UP.is_press(e) => {
+            e if keymap.matches(KeyAction::ScrollUp, e) => {
                 self.scroll_offset = self.scroll_offset.saturating_sub(1);
             }
-            e if KEY_DOWN.is_press(e) => {
+            e if keymap.matches(KeyAction::ScrollDown, e) => {
                 self.scroll_offset = self.scroll_offset.saturating_add(1);
             }
-            e if KEY_PAGE_UP.is_press(e) => {
+            e if keymap.matches(KeyAction::PageUp, e) => {
                 let area = self.content_area(tui.terminal.viewport_area);
                 self.scroll_offset = self.scroll_offset.saturating_sub(area.height as usize);
             }
-            e if KEY_PAGE_DOWN.is_press(e) || KEY_SPACE.is_press(e) => {
+            e if keymap.matches(KeyAction::PageDown, e) => {
                 let area = self.content_area(tui.terminal.viewport_area);
                 self.scroll_offset = self.scroll_offset.saturating_add(area.height as usize);
             }
-            e if KEY_HOME.is_press(e) => {
+            e if keymap.matches(KeyAction::ScrollTop, e) => {
                 self.scroll_offset = 0;
             }
-            e if KEY_END.is_press(e) => {
+            e if keymap.matches(KeyAction::ScrollBottom, e) => {
                 self.scroll_offset = usize::MAX;
             }
             _ => {
@@ -316,16 +343,33 @@ impl PagerView {
         self.pending_scroll_chunk = Some(chunk_index);
     }
 
+    /// Request that `row` (relative to the chunk's top) be scrolled into view
+    /// on next render.
+    fn scroll_row_into_view(&mut self, chunk_index: usize, row: usize) {
+        self.pending_scroll_row = Some((chunk_index, row));
+    }
+
+    /// Offset of the first row of chunk `idx` within the content.
+    fn chunk_top(&self, idx: usize, width: u16) -> usize {
+        self.renderables
+            .iter()
+            .take(idx)
+            .map(|r| r.desired_height(width) as usize)
+            .sum()
+    }
+
+    /// The first content row currently on screen.
+    fn top_row(&self, width: u16) -> usize {
+        let height = self.last_content_height.unwrap_or(0);
+        self.scroll_offset
+            .min(self.content_height(width).saturating_sub(height))
+    }
+
     fn ensure_chunk_visible(&mut self, idx: usize, area: Rect) {
         if area.height == 0 || idx >= self.renderables.len() {
             return;
         }
-        let first = self
-            .renderables
-            .iter()
-            .take(idx)
-            .map(|r| r.desired_height(area.width) as usize)
-            .sum();
+        let first = self.chunk_top(idx, area.width);
         let last = first + self.renderables[idx].desired_height(area.width) as usize;
         let current_top = self.scroll_offset;
         let current_bottom = current_top.saturating_add(area.height.saturating_sub(1) as usize);
@@ -335,6 +379,24 @@ impl PagerView {
             self.scroll_offset = last.saturating_sub(area.height.saturating_sub(1) as usize);
         }
     }
+
+    /// Scroll so `row` of chunk `idx` is on screen, placing it a third of the
+    /// way down when the view has to move.
+    fn ensure_row_visible(&mut self, idx: usize, row: usize, area: Rect) {
+        if area.height == 0 || idx >= self.renderables.len() {
+            return;
+        }
+        let target = self.chunk_top(idx, area.width) + row;
+        let height = area.height as usize;
+        let top = self
+            .scroll_offset
+            .min(self.content_height(area.width).saturating_sub(height));
+        self.scroll_offset = if target < top || target >= top + height {
+            target.saturating_sub(height / 3)
+        } else {
+            top
+        };
+    }
 }
 
 /// A renderable that caches its desired height.
@@ -371,12 +433,15 @@ impl Renderable for CachedRenderable {
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
     style: Style,
+    /// Search matches to paint over the cell's lines.
+    highlights: Vec<LineHighlight>,
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let p =
-            Paragraph::new(Text::from(self.cell.transcript_lines(area.width))).style(self.style);
+        let mut lines = self.cell.transcript_lines(area.width);
+        apply_highlights(&mut lines, &self.highlights);
+        let p = Paragraph::new(Text::from(lines)).style(self.style);
         p.render(area, buf);
     }
 
@@ -389,19 +454,28 @@ pub(crate) struct TranscriptOverlay {
     view: PagerView,
     cells: Vec<Arc<dyn HistoryCell>>,
     highlight_cell: Option<usize>,
+    search: TranscriptSearch,
+    /// Width of the last render; search matches are computed at this width.
+    last_width: Option<u16>,
+    /// Cell of the match the user asked to edit from, for the app to pick up.
+    backtrack_request: Option<usize>,
     is_done: bool,
 }
 
 impl TranscriptOverlay {
     pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
+        let search = TranscriptSearch::default();
         Self {
             view: PagerView::new(
-                Self::render_cells(&transcript_cells, None),
+                Self::render_cells(&transcript_cells, None, &search),
                 "T R A N S C R I P T".to_string(),
                 usize::MAX,
             ),
             cells: transcript_cells,
             highlight_cell: None,
+            search,
+            last_width: None,
+            backtrack_request: None,
             is_done: false,
         }
     }
@@ -409,6 +483,7 @@ impl TranscriptOverlay {
     fn render_cells(
         cells: &[Arc<dyn HistoryCell>],
         highlight_cell: Option<usize>,
+        search: &TranscriptSearch,
     ) -> Vec<Box<dyn Renderable>> {
         cells
             .iter()
@@ -423,14 +498,16 @@ impl TranscriptOverlay {
                         } else {
                             user_message_style()
                         },
+                        highlights: search.highlights_for_cell(i),
                     }))) as Box<dyn Renderable>
                 } else {
                     Box::new(CachedRenderable::new(Box::new(CellRenderable {
                         cell: c.clone(),
                         style: Style::default(),
+                        highlights: search.highlights_for_cell(i),
                     }))) as Box<dyn Renderable>
                 };
-                if !c.is_stream_continuation() && i > 0 {
+                if Self::has_top_inset(cells, i) {
                     cell_renderable = Box::new(InsetRenderable::new(
                         cell_renderable,
                         Insets::tlbr(1, 0, 0, 0),
@@ -442,10 +519,25 @@ impl TranscriptOverlay {
             .collect()
     }
 
+    /// Cells after the first are separated by a blank row unless they
+    /// continue a stream.
+    fn has_top_inset(cells: &[Arc<dyn HistoryCell>], idx: usize) -> bool {
+        idx > 0 && cells.get(idx).is_some_and(|c| !c.is_stream_continuation())
+    }
+
+    fn rebuild_renderables(&mut self) {
+        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell, &self.search);
+    }
+
     pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>) {
         let follow_bottom = self.view.is_scrolled_to_bottom();
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        if self.search.is_active()
+            && let Some(width) = self.last_width
+        {
+            self.search.refresh(&self.cells, width);
+        }
+        self.rebuild_renderables();
         if follow_bottom {
             self.view.scroll_offset = usize::MAX;
         }
@@ -453,26 +545,144 @@ impl TranscriptOverlay {
 
     pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
         self.highlight_cell = cell;
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.rebuild_renderables();
         if let Some(idx) = self.highlight_cell {
             self.view.scroll_chunk_into_view(idx);
         }
     }
 
+    /// True while the search query is being typed; every key belongs to it.
+    pub(crate) fn is_editing_search(&self) -> bool {
+        self.search.is_editing()
+    }
+
+    /// The transcript cell the user chose to edit from, if they just did.
+    pub(crate) fn take_backtrack_request(&mut self) -> Option<usize> {
+        self.backtrack_request.take()
+    }
+
+    /// Row of `m` relative to the top of its cell's renderable.
+    fn match_row(&self, m: &SearchMatch) -> usize {
+        usize::from(Self::has_top_inset(&self.cells, m.cell)) + m.line
+    }
+
+    fn refresh_search(&mut self) {
+        let width = self.last_width.unwrap_or(u16::MAX);
+        self.search.refresh(&self.cells, width);
+    }
+
+    /// After the query changes, jump to the first match at or below the top
+    /// of the view so typing does not lose the reader's place.
+    fn select_match_from_view(&mut self) {
+        let width = self.last_width.unwrap_or(u16::MAX);
+        let top = self.view.top_row(width);
+        let tops: Vec<usize> = (0..self.cells.len())
+            .map(|idx| self.view.chunk_top(idx, width))
+            .collect();
+        let cells = &self.cells;
+        self.search.select_first_where(|m| {
+            let inset = usize::from(Self::has_top_inset(cells, m.cell));
+            tops.get(m.cell).copied().unwrap_or(0) + inset + m.line >= top
+        });
+        self.show_current_match();
+    }
+
+    fn show_current_match(&mut self) {
+        self.rebuild_renderables();
+        if let Some(m) = self.search.current_match() {
+            let row = self.match_row(&m);
+            self.view.scroll_row_into_view(m.cell, row);
+        }
+    }
+
+    fn handle_search_input(&mut self, key_event: KeyEvent) {
+        match self.search.handle_input(key_event) {
+            SearchInput::Changed => {
+                self.refresh_search();
+                self.select_match_from_view();
+            }
+            SearchInput::Committed | SearchInput::Cancelled => self.rebuild_renderables(),
+            SearchInput::Ignored => {}
+        }
+    }
+
+    fn render_search_bar(&self, area: Rect, buf: &mut Buffer) {
+        if !self.search.is_active() {
+            let search = action_keys(&[KeyAction::Search]);
+            render_key_hints(area, buf, &[(&search, "to search")]);
+            return;
+        }
+        let mut spans: Vec<Span<'static>> =
+            vec![" /".accent(), self.search.query().to_string().into()];
+        if self.search.is_editing() {
+            spans.push(" ".reversed());
+        }
+        if let Some(error) = self.search.error() {
+            spans.push(format!("  {error}").error());
+        } else if !self.search.query().is_empty() {
+            let count = self.search.match_count();
+            let status = match (count, self.search.current_index()) {
+                (0, _) => "no matches".to_string(),
+                (count, Some(idx)) => format!("{}/{count}", idx + 1),
+                (count, None) => format!("{count} matches"),
+            };
+            spans.push(format!("  {status}").dim());
+        }
+        if self.search.is_editing() {
+            for (action, label, enabled) in [
+                (
+                    KeyAction::ToggleSearchRegex,
+                    "regex",
+                    self.search.is_regex(),
+                ),
+                (
+                    KeyAction::ToggleSearchCase,
+                    "match case",
+                    self.search.is_case_sensitive(),
+                ),
+            ] {
+                spans.push("   ".into());
+                spans.push(key_hint::action_span(action));
+                spans.push(" ".into());
+                spans.push(if enabled { label.accent() } else { label.dim() });
+            }
+        } else if self.search.match_count() > 0 {
+            spans.push("   ".into());
+            spans.push(key_hint::action_span(KeyAction::NextMatch));
+            spans.push("/".dim());
+            spans.push(key_hint::action_span(KeyAction::PreviousMatch));
+            spans.push(" to navigate   ".dim());
+            spans.push(key_hint::action_span(KeyAction::EditFromMatch));
+            spans.push(" to edit from match".dim());
+        }
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        render_pager_key_hints(line1, buf);
 
+        let close = action_keys(&[KeyAction::Close]);
         let mut pairs: Vec<(&[KeyBinding], &str)> =
-            vec![(&[KEY_Q], "to quit"), (&[KEY_ESC], "to edit prev")];
+            vec![(&close, "to quit"), (&[KEY_ESC], "to edit prev")];
         if self.highlight_cell.is_some() {
             pairs.push((&[KEY_ENTER], "to edit message"));
         }
         render_key_hints(line2, buf, &pairs);
+
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
+        self.render_search_bar(line3, buf);
     }
 
     pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if self.last_width != Some(area.width) {
+            self.last_width = Some(area.width);
+            if self.search.is_active() {
+                self.refresh_search();
+                self.rebuild_renderables();
+            }
+        }
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
@@ -484,13 +694,49 @@ impl TranscriptOverlay {
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
-            TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
-                    self.is_done = true;
-                    Ok(())
+            TuiEvent::Key(key_event) if self.search.is_editing() => {
+                self.handle_search_input(key_event);
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Key(key_event) => {
+                let keymap = keymap();
+                match key_event {
+                    e if keymap.matches(KeyAction::Close, e)
+                        || keymap.matches(KeyAction::Transcript, e)
+                        || KEY_CTRL_C.is_press(e) =>
+                    {
+                        self.is_done = true;
+                    }
+                    e if keymap.matches(KeyAction::Search, e) => self.search.start_editing(),
+                    e if keymap.matches(KeyAction::NextMatch, e) => {
+                        self.search.select_next();
+                        self.show_current_match();
+                    }
+                    e if keymap.matches(KeyAction::PreviousMatch, e) => {
+                        self.search.select_previous();
+                        self.show_current_match();
+                    }
+                    e if keymap.matches(KeyAction::ToggleSearchRegex, e) => {
+                        self.search.toggle_regex();
+                        self.refresh_search();
+                        self.select_match_from_view();
+                    }
+                    e if keymap.matches(KeyAction::ToggleSearchCase, e) => {
+                        self.search.toggle_case();
+                        self.refresh_search();
+                        self.select_match_from_view();
+                    }
+                    e if keymap.matches(KeyAction::EditFromMatch, e)
+                        && self.search.current_match().is_some() =>
+                    {
+                        self.backtrack_request = self.search.current_match().map(|m| m.cell);
+                    }
+                    other => return self.view.handle_key_event(tui, other),
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -530,8 +776,9 @@ impl StaticOverlay {
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        render_pager_key_hints(line1, buf);
+        let close = action_keys(&[KeyAction::Close]);
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&close, "to quit")];
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -548,7 +795,7 @@ impl StaticOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                e if keymap().matches(KeyAction::Close, e) || KEY_CTRL_C.is_press(e) => {
                     self.is_done = true;
                     Ok(())
                 }
@@ -611,6 +858,7 @@ mod tests {
     use codex_protocol::parse_command::ParsedCommand;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
+    use ratatui::style::Modifier;
     use ratatui::text::Text;
 
     #[derive(Debug)]
@@ -815,6 +1063,53 @@ mod tests {
         assert_eq!(overlay.view.scroll_offset, 0);
     }
 
+    #[test]
+    fn transcript_overlay_search_highlights_and_navigates_matches() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(if i % 10 == 3 {
+                            format!("needle {i}")
+                        } else {
+                            format!("line{i}")
+                        })],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        overlay.view.scroll_offset = 0;
+
+        overlay.search.start_editing();
+        for c in "NEEDLE".chars() {
+            overlay.handle_search_input(KeyEvent::from(KeyCode::Char(c)));
+        }
+        overlay.handle_search_input(KeyEvent::from(KeyCode::Enter));
+        assert!(!overlay.is_editing_search());
+        assert_eq!(overlay.search.current_match().map(|m| m.cell), Some(3));
+
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("/NEEDLE  1/2"), "{text}");
+        let row = (0..area.height)
+            .find(|&y| buf[(0, y)].symbol() == "n")
+            .expect("match row");
+        assert!(buf[(0, row)].modifier.contains(Modifier::REVERSED));
+
+        overlay.search.select_next();
+        overlay.show_current_match();
+        assert_eq!(overlay.search.current_match().map(|m| m.cell), Some(13));
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("needle 13"), "{text}");
+        assert!(text.contains("2/2"), "{text}");
+    }
+
     #[test]
     fn static_overlay_snapshot_basic() {
         // Prepare a static overlay with a few lines and a title
@@ -1,145 +1,484 @@
+use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
+use std::path::Path;
 use std::sync::OnceLock;
 use tree_sitter_highlight::Highlight;
 use tree_sitter_highlight::HighlightConfiguration;
 use tree_sitter_highlight::HighlightEvent;
 use tree_sitter_highlight::Highlighter;
 
-// Ref: https://github.com/tree-sitter/tree-sitter-bash/blob/master/queries/highlights.scm
-#[derive(Copy, Clone)]
-enum BashHighlight {
+use crate::terminal_palette::best_color;
+use crate::theme::theme;
+
+/// Languages with a bundled tree-sitter grammar and highlight query.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Language {
+    Bash,
+    Go,
+    JavaScript,
+    Json,
+    Python,
+    Rust,
+    Toml,
+    Tsx,
+    TypeScript,
+    Yaml,
+}
+
+impl Language {
+    const ALL: [Self; 10] = [
+        Self::Bash,
+        Self::Go,
+        Self::JavaScript,
+        Self::Json,
+        Self::Python,
+        Self::Rust,
+        Self::Toml,
+        Self::Tsx,
+        Self::TypeScript,
+        Self::Yaml,
+    ];
+
+    /// Fence info strings (the first word after ```) naming this language.
+    const fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Self::Bash => &["bash", "sh", "shell", "zsh", "console", "shellscript"],
+            Self::Go => &["go", "golang"],
+            Self::JavaScript => &["javascript", "js", "jsx", "mjs", "cjs", "node"],
+            Self::Json => &["json", "jsonc", "json5", "jsonl"],
+            Self::Python => &["python", "py", "python3", "py3"],
+            Self::Rust => &["rust", "rs"],
+            Self::Toml => &["toml"],
+            Self::Tsx => &["tsx"],
+            Self::TypeScript => &["typescript", "ts", "mts", "cts"],
+            Self::Yaml => &["yaml", "yml"],
+        }
+    }
+
+    const fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Bash => &["sh", "bash", "zsh"],
+            Self::Go => &["go"],
+            Self::JavaScript => &["js", "jsx", "mjs", "cjs"],
+            Self::Json => &["json", "jsonc", "jsonl"],
+            Self::Python => &["py", "pyi"],
+            Self::Rust => &["rs"],
+            Self::Toml => &["toml"],
+            Self::Tsx => &["tsx"],
+            Self::TypeScript => &["ts", "mts", "cts"],
+            Self::Yaml => &["yaml", "yml"],
+        }
+    }
+
+    /// Resolve a markdown fence info string such as `rust` or `ts title=x.ts`.
+    pub(crate) fn from_fence_info(info: &str) -> Option<Self> {
+        let token = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()?
+            .trim_start_matches('.')
+            .to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.aliases().contains(&token.as_str()))
+    }
+
+    /// Resolve a file path by its extension or a well-known file name.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        match file_name {
+            "Cargo.lock" | "Pipfile" | "poetry.lock" | "uv.lock" => return Some(Self::Toml),
+            ".bashrc" | ".zshrc" | ".profile" | ".bash_profile" => return Some(Self::Bash),
+            _ => {}
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.extensions().contains(&extension.as_str()))
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|language| *language == self)
+            .unwrap_or_default()
+    }
+
+    fn load(self) -> Result<HighlightConfiguration, tree_sitter::QueryError> {
+        let (language, name, query): (tree_sitter::Language, &str, String) = match self {
+            Self::Bash => (
+                tree_sitter_bash::LANGUAGE.into(),
+                "bash",
+                tree_sitter_bash::HIGHLIGHT_QUERY.to_string(),
+            ),
+            Self::Go => (
+                tree_sitter_go::LANGUAGE.into(),
+                "go",
+                tree_sitter_go::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::JavaScript => (
+                tree_sitter_javascript::LANGUAGE.into(),
+                "javascript",
+                [
+                    tree_sitter_javascript::HIGHLIGHT_QUERY,
+                    tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+                ]
+                .join("\n"),
+            ),
+            Self::Json => (
+                tree_sitter_json::LANGUAGE.into(),
+                "json",
+                tree_sitter_json::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Python => (
+                tree_sitter_python::LANGUAGE.into(),
+                "python",
+                tree_sitter_python::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Rust => (
+                tree_sitter_rust::LANGUAGE.into(),
+                "rust",
+                tree_sitter_rust::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Toml => (
+                tree_sitter_toml_ng::LANGUAGE.into(),
+                "toml",
+                tree_sitter_toml_ng::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            // The TypeScript queries extend the JavaScript ones.
+            Self::Tsx => (
+                tree_sitter_typescript::LANGUAGE_TSX.into(),
+                "tsx",
+                [
+                    tree_sitter_javascript::HIGHLIGHT_QUERY,
+                    tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+                    tree_sitter_typescript::HIGHLIGHTS_QUERY,
+                ]
+                .join("\n"),
+            ),
+            Self::TypeScript => (
+                tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+                "typescript",
+                [
+                    tree_sitter_javascript::HIGHLIGHT_QUERY,
+                    tree_sitter_typescript::HIGHLIGHTS_QUERY,
+                ]
+                .join("\n"),
+            ),
+            Self::Yaml => (
+                tree_sitter_yaml::LANGUAGE.into(),
+                "yaml",
+                tree_sitter_yaml::HIGHLIGHTS_QUERY.to_string(),
+            ),
+        };
+        let mut config = HighlightConfiguration::new(language, name, &query, "", "")?;
+        config.configure(&CAPTURE_NAMES.map(|(name, _)| name));
+        Ok(config)
+    }
+
+    /// Highlight configuration, loaded on first use. `None` when the bundled
+    /// query fails to compile, in which case code renders unhighlighted.
+    fn config(self) -> Option<&'static HighlightConfiguration> {
+        static CONFIGS: [OnceLock<Option<HighlightConfiguration>>; Language::ALL.len()] =
+            [const { OnceLock::new() }; Language::ALL.len()];
+        CONFIGS[self.index()]
+            .get_or_init(|| match self.load() {
+                Ok(config) => Some(config),
+                Err(err) => {
+                    tracing::warn!("failed to load {self:?} highlight query: {err}");
+                    None
+                }
+            })
+            .as_ref()
+    }
+}
+
+/// Coarse classes that tree-sitter capture names are folded into for styling.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Capture {
+    Attribute,
     Comment,
     Constant,
     Embedded,
+    Escape,
     Function,
     Keyword,
     Number,
     Operator,
     Property,
+    Punctuation,
     String,
+    Type,
+    Variable,
 }
 
-impl BashHighlight {
-    const ALL: [Self; 9] = [
-        Self::Comment,
-        Self::Constant,
-        Self::Embedded,
-        Self::Function,
-        Self::Keyword,
-        Self::Number,
-        Self::Operator,
-        Self::Property,
-        Self::String,
-    ];
+// Ref: https://tree-sitter.github.io/tree-sitter/3-syntax-highlighting.html#highlights
+// Captures match the longest dotted prefix listed here.
+const CAPTURE_NAMES: [(&str, Capture); 24] = [
+    ("attribute", Capture::Attribute),
+    ("boolean", Capture::Constant),
+    ("comment", Capture::Comment),
+    ("constant", Capture::Constant),
+    ("constructor", Capture::Type),
+    ("embedded", Capture::Embedded),
+    ("escape", Capture::Escape),
+    ("function", Capture::Function),
+    ("keyword", Capture::Keyword),
+    ("label", Capture::Property),
+    ("module", Capture::Type),
+    ("number", Capture::Number),
+    ("operator", Capture::Operator),
+    ("property", Capture::Property),
+    ("punctuation", Capture::Punctuation),
+    ("string", Capture::String),
+    ("string.escape", Capture::Escape),
+    ("string.special", Capture::Escape),
+    ("tag", Capture::Attribute),
+    ("type", Capture::Type),
+    ("variable", Capture::Variable),
+    ("variable.builtin", Capture::Constant),
+    ("variable.parameter", Capture::Variable),
+    ("character", Capture::String),
+];
 
-    const fn as_str(self) -> &'static str {
-        match self {
-            Self::Comment => "comment",
-            Self::Constant => "constant",
-            Self::Embedded => "embedded",
-            Self::Function => "function",
-            Self::Keyword => "keyword",
-            Self::Number => "number",
-            Self::Operator => "operator",
-            Self::Property => "property",
-            Self::String => "string",
-        }
+impl Capture {
+    fn from_highlight(highlight: Highlight) -> Capture {
+        CAPTURE_NAMES
+            .get(highlight.0)
+            .map_or(Capture::Variable, |(_, capture)| *capture)
     }
 
-    fn style(self) -> Style {
+    /// Subdued styling used for shell commands in approvals and exec cells.
+    fn monochrome_style(self) -> Style {
         match self {
-            Self::Comment | Self::Operator | Self::String => Style::default().dim(),
+            Self::Comment | Self::Operator | Self::String | Self::Escape => Style::default().dim(),
             _ => Style::default(),
         }
     }
-}
 
-static HIGHLIGHT_CONFIG: OnceLock<HighlightConfiguration> = OnceLock::new();
-
-fn highlight_names() -> &'static [&'static str] {
-    static NAMES: OnceLock<[&'static str; BashHighlight::ALL.len()]> = OnceLock::new();
-    NAMES
-        .get_or_init(|| BashHighlight::ALL.map(BashHighlight::as_str))
-        .as_slice()
-}
+    /// Color styling used for code blocks and diffs.
+    fn color_style(self) -> Style {
+        // One palette per background brightness, indexed by `Theme::light`.
+        static PALETTES: [OnceLock<Vec<(Capture, Option<Color>)>>; 2] =
+            [OnceLock::new(), OnceLock::new()];
+        if self == Self::Comment {
+            return Style::default().dim().italic();
+        }
+        let light = theme().light;
+        let palette = PALETTES[usize::from(light)].get_or_init(|| {
+            [
+                Self::Attribute,
+                Self::Constant,
+                Self::Escape,
+                Self::Function,
+                Self::Keyword,
+                Self::Number,
+                Self::Property,
+                Self::String,
+                Self::Type,
+            ]
+            .into_iter()
+            .map(|capture| (capture, capture.target_color(light).map(palette_color)))
+            .collect()
+        });
+        palette
+            .iter()
+            .find(|(capture, _)| *capture == self)
+            .and_then(|(_, color)| *color)
+            .map_or_else(Style::default, |color| Style::default().fg(color))
+    }
 
-fn highlight_config() -> &'static HighlightConfiguration {
-    HIGHLIGHT_CONFIG.get_or_init(|| {
-        let language = tree_sitter_bash::LANGUAGE.into();
-        #[expect(clippy::expect_used)]
-        let mut config = HighlightConfiguration::new(
-            language,
-            "bash",
-            tree_sitter_bash::HIGHLIGHT_QUERY,
-            "",
-            "",
-        )
-        .expect("load bash highlight query");
-        config.configure(highlight_names());
-        config
-    })
+    /// Target RGB per class, tuned separately for dark and light backgrounds.
+    fn target_color(self, light: bool) -> Option<(u8, u8, u8)> {
+        let (dark, light_variant) = match self {
+            Self::Keyword => ((198, 120, 221), (166, 38, 164)),
+            Self::String => ((152, 195, 121), (80, 161, 79)),
+            Self::Escape => ((86, 182, 194), (1, 132, 188)),
+            Self::Constant | Self::Number => ((209, 154, 102), (152, 104, 1)),
+            Self::Function => ((97, 175, 239), (64, 120, 242)),
+            Self::Type => ((229, 192, 123), (193, 132, 1)),
+            Self::Attribute | Self::Property => ((224, 108, 117), (228, 86, 73)),
+            _ => return None,
+        };
+        Some(if light { light_variant } else { dark })
+    }
 }
 
-fn highlight_for(highlight: Highlight) -> BashHighlight {
-    BashHighlight::ALL[highlight.0]
+/// `best_color` falls back to the terminal default when colors are not
+/// supported; leave such spans unstyled rather than resetting their color.
+fn palette_color(target: (u8, u8, u8)) -> Option<Color> {
+    Some(best_color(target)).filter(|color| *color != Color::default())
 }
 
-fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Style>) {
-    for (i, part) in segment.split('\n').enumerate() {
-        if i > 0 {
-            lines.push(Line::from(""));
-        }
-        if part.is_empty() {
-            continue;
-        }
-        let span = match style {
-            Some(style) => Span::styled(part.to_string(), style),
-            None => part.to_string().into(),
-        };
-        if let Some(last) = lines.last_mut() {
-            last.spans.push(span);
-        }
-    }
+/// Result of highlighting a source string.
+struct Highlighted {
+    lines: Vec<Line<'static>>,
+    /// Byte offset and line index of the last blank line that starts outside
+    /// any capture. Text before it highlights the same regardless of what is
+    /// appended later, so streaming can resume from there.
+    checkpoint: Option<(usize, usize)>,
 }
 
-/// Convert a bash script into per-line styled content using tree-sitter's
-/// bash highlight query. The highlighter is streamed so multi-line content is
-/// split into `Line`s while preserving style boundaries.
-pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+fn highlight_source(
+    source: &str,
+    language: Language,
+    style_for: fn(Capture) -> Style,
+) -> Option<Highlighted> {
+    let config = language.config()?;
     let mut highlighter = Highlighter::new();
-    let iterator =
-        match highlighter.highlight(highlight_config(), script.as_bytes(), None, |_| None) {
-            Ok(iter) => iter,
-            Err(_) => return vec![script.to_string().into()],
-        };
+    let iterator = highlighter
+        .highlight(config, source.as_bytes(), None, |_| None)
+        .ok()?;
 
     let mut lines: Vec<Line<'static>> = vec![Line::from("")];
     let mut highlight_stack: Vec<Highlight> = Vec::new();
+    let mut checkpoint = None;
 
     for event in iterator {
-        match event {
-            Ok(HighlightEvent::HighlightStart(highlight)) => highlight_stack.push(highlight),
-            Ok(HighlightEvent::HighlightEnd) => {
+        match event.ok()? {
+            HighlightEvent::HighlightStart(highlight) => highlight_stack.push(highlight),
+            HighlightEvent::HighlightEnd => {
                 highlight_stack.pop();
             }
-            Ok(HighlightEvent::Source { start, end }) => {
+            HighlightEvent::Source { start, end } => {
                 if start == end {
                     continue;
                 }
-                let style = highlight_stack.last().map(|h| highlight_for(*h).style());
-                push_segment(&mut lines, &script[start..end], style);
+                let style = highlight_stack
+                    .last()
+                    .map(|h| style_for(Capture::from_highlight(*h)));
+                let mut offset = start;
+                for (i, part) in source[start..end].split('\n').enumerate() {
+                    if i > 0 {
+                        let previous_blank = lines.last().is_none_or(|line| {
+                            line.spans.iter().all(|s| s.content.trim().is_empty())
+                        });
+                        lines.push(Line::from(""));
+                        if highlight_stack.is_empty() && previous_blank {
+                            checkpoint = Some((offset, lines.len() - 1));
+                        }
+                    }
+                    offset += part.len() + 1;
+                    if part.is_empty() {
+                        continue;
+                    }
+                    let span = match style {
+                        Some(style) => Span::styled(part.to_string(), style),
+                        None => part.to_string().into(),
+                    };
+                    if let Some(last) = lines.last_mut() {
+                        last.spans.push(span);
+                    }
+                }
             }
-            Err(_) => return vec![script.to_string().into()],
         }
     }
 
-    if lines.is_empty() {
-        vec![Line::from("")]
-    } else {
-        lines
+    Some(Highlighted { lines, checkpoint })
+}
+
+/// Convert a bash script into per-line styled content using tree-sitter's
+/// bash highlight query. The highlighter is streamed so multi-line content is
+/// split into `Line`s while preserving style boundaries.
+pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+    match highlight_source(script, Language::Bash, Capture::monochrome_style) {
+        Some(highlighted) => highlighted.lines,
+        None => vec![script.to_string().into()],
+    }
+}
+
+/// Highlight `code` with the palette used for code blocks and diffs. Returns
+/// one `Line` per `\n`-separated line of input, so a trailing newline yields a
+/// trailing empty line.
+pub(crate) fn highlight_code_to_lines(code: &str, language: Language) -> Vec<Line<'static>> {
+    match highlight_source(code, language, Capture::color_style) {
+        Some(highlighted) => highlighted.lines,
+        None => code
+            .split('\n')
+            .map(|line| line.to_string().into())
+            .collect(),
+    }
+}
+
+/// Highlights a code block that grows as a response streams in. Each update
+/// re-highlights only the text after the last checkpoint (a blank line outside
+/// any capture) instead of the whole block.
+#[derive(Debug)]
+pub(crate) struct IncrementalHighlighter {
+    language: Language,
+    source: String,
+    lines: Vec<Line<'static>>,
+    checkpoint: (usize, usize),
+}
+
+impl IncrementalHighlighter {
+    pub(crate) fn new(language: Language) -> Self {
+        Self {
+            language,
+            source: String::new(),
+            lines: Vec::new(),
+            checkpoint: (0, 0),
+        }
+    }
+
+    pub(crate) fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Highlight `code`, which normally extends the previous update's code.
+    pub(crate) fn update(&mut self, code: &str) -> &[Line<'static>] {
+        if code == self.source && !self.lines.is_empty() {
+            return &self.lines;
+        }
+        if !code.starts_with(&self.source) || self.checkpoint.0 > code.len() {
+            self.lines.clear();
+            self.checkpoint = (0, 0);
+        }
+        let (offset, line_index) = self.checkpoint;
+        self.lines.truncate(line_index);
+        match highlight_source(&code[offset..], self.language, Capture::color_style) {
+            Some(highlighted) => {
+                if let Some((next_offset, next_line)) = highlighted.checkpoint {
+                    self.checkpoint = (offset + next_offset, line_index + next_line);
+                }
+                self.lines.extend(highlighted.lines);
+            }
+            None => self.lines.extend(
+                code[offset..]
+                    .split('\n')
+                    .map(|line| Line::from(line.to_string())),
+            ),
+        }
+        self.source = code.to_string();
+        &self.lines
+    }
+}
+
+/// Per-document cache of code block highlighters, indexed by the order in
+/// which fenced blocks appear. Reused across renders of a streaming message.
+#[derive(Debug, Default)]
+pub(crate) struct CodeHighlightCache {
+    blocks: Vec<IncrementalHighlighter>,
+}
+
+impl CodeHighlightCache {
+    pub(crate) fn highlight(
+        &mut self,
+        block_index: usize,
+        code: &str,
+        language: Language,
+    ) -> Vec<Line<'static>> {
+        if block_index > self.blocks.len() {
+            return highlight_code_to_lines(code, language);
+        }
+        if block_index == self.blocks.len() {
+            self.blocks.push(IncrementalHighlighter::new(language));
+        } else if self.blocks[block_index].language() != language {
+            self.blocks[block_index] = IncrementalHighlighter::new(language);
+        }
+        self.blocks[block_index].update(code).to_vec()
     }
 }
 
@@ -173,6 +512,37 @@ mod tests {
             .collect()
     }
 
+    /// Capture class of each token, independent of terminal color support.
+    fn captures(source: &str, language: Language) -> Vec<(String, Capture)> {
+        let highlighted =
+            highlight_source(source, language, capture_marker).expect("highlight source");
+        highlighted
+            .lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .filter_map(|span| {
+                let index = span.style.underline_color.and_then(|color| match color {
+                    Color::Indexed(index) => Some(index as usize),
+                    _ => None,
+                })?;
+                Some((
+                    span.content.trim().to_string(),
+                    CAPTURE_NAMES.iter().map(|(_, c)| *c).nth(index)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Encodes the capture in an otherwise unused style field.
+    #[allow(clippy::disallowed_methods)]
+    fn capture_marker(capture: Capture) -> Style {
+        let index = CAPTURE_NAMES
+            .iter()
+            .position(|(_, c)| *c == capture)
+            .unwrap_or_default();
+        Style::default().underline_color(Color::Indexed(index as u8))
+    }
+
     #[test]
     fn dims_expected_bash_operators() {
         let s = "echo foo && bar || baz | qux & (echo hi)";
@@ -233,4 +603,87 @@ mod tests {
         let body_style = body_style.expect("missing heredoc span");
         assert!(body_style.add_modifier.contains(Modifier::DIM));
     }
+
+    #[test]
+    fn resolves_languages_from_fences_and_paths() {
+        assert_eq!(Language::from_fence_info("rust"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_fence_info("TS title=a.ts"),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(Language::from_fence_info("py3"), Some(Language::Python));
+        assert_eq!(Language::from_fence_info("yml"), Some(Language::Yaml));
+        assert_eq!(Language::from_fence_info("brainfuck"), None);
+        assert_eq!(Language::from_fence_info(""), None);
+
+        assert_eq!(
+            Language::from_path(Path::new("src/main.go")),
+            Some(Language::Go)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("web/App.tsx")),
+            Some(Language::Tsx)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("Cargo.lock")),
+            Some(Language::Toml)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("package.json")),
+            Some(Language::Json)
+        );
+        assert_eq!(Language::from_path(Path::new("README")), None);
+    }
+
+    #[test]
+    fn every_bundled_query_loads() {
+        for language in Language::ALL {
+            assert!(
+                language.config().is_some(),
+                "{language:?} query failed to load"
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_tokens_across_languages() {
+        let rust = captures("fn main() { let s = \"hi\"; }", Language::Rust);
+        assert!(rust.contains(&("fn".to_string(), Capture::Keyword)));
+        assert!(rust.contains(&("main".to_string(), Capture::Function)));
+        assert!(rust.contains(&("\"hi\"".to_string(), Capture::String)));
+
+        let python = captures("# note\ndef f():\n    return 1\n", Language::Python);
+        assert!(python.contains(&("# note".to_string(), Capture::Comment)));
+        assert!(python.contains(&("def".to_string(), Capture::Keyword)));
+        assert!(python.contains(&("1".to_string(), Capture::Number)));
+
+        let json = captures("{\"a\": true}", Language::Json);
+        assert!(json.contains(&("true".to_string(), Capture::Constant)));
+
+        let code = "const x: number = 1;\n";
+        let lines = highlight_code_to_lines(code, Language::TypeScript);
+        assert_eq!(reconstructed(&lines), code);
+    }
+
+    #[test]
+    fn incremental_updates_match_full_highlight() {
+        let full = "fn a() {}\n\nfn b() {\n    let s = \"x\";\n}\n\nfn c() {}\n";
+        let mut incremental = IncrementalHighlighter::new(Language::Rust);
+        let mut end = 0;
+        while end < full.len() {
+            end = (end + 7).min(full.len());
+            incremental.update(&full[..end]);
+        }
+        assert_eq!(incremental.checkpoint.1, 6);
+        assert_eq!(
+            incremental.update(full).to_vec(),
+            highlight_code_to_lines(full, Language::Rust)
+        );
+
+        // A non-extending update starts over.
+        assert_eq!(
+            reconstructed(incremental.update("let y = 2;")),
+            "let y = 2;"
+        );
+    }
 }
@@ -0,0 +1,642 @@
+//! Deterministic replay of session logs written by [`crate::session_log`].
+//!
+//! A recording (`CODEX_TUI_RECORD_SESSION=1`) is turned into a list of steps
+//! that are fed into a [`ChatWidget`] with no agent behind it. Each frame is
+//! rendered from scratch into a [`VT100Backend`], so the same log always
+//! produces the same screens regardless of the host terminal. Frames can be
+//! printed headlessly (for bug reports and snapshot tests) or browsed in a
+//! full-screen viewer with step/seek controls.
+
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_agentic_core::settings;
+use codex_agentic_core::settings::Settings;
+use codex_core::AuthManager;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::config::ConfigToml;
+use codex_core::config::find_codex_home;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use crossterm::cursor::Hide;
+use crossterm::cursor::MoveTo;
+use crossterm::cursor::Show;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::execute;
+use crossterm::queue;
+use crossterm::style::Attribute;
+use crossterm::style::Print;
+use crossterm::style::SetAttribute;
+use crossterm::terminal::Clear;
+use crossterm::terminal::ClearType;
+use crossterm::terminal::EnterAlternateScreen;
+use crossterm::terminal::LeaveAlternateScreen;
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::chatwidget::ChatWidget;
+use crate::chatwidget::ChatWidgetInit;
+use crate::cli::ReplayCli;
+use crate::custom_terminal::Terminal;
+use crate::render::renderable::Renderable;
+use crate::test_backend::VT100Backend;
+use crate::tui::FrameRequester;
+
+const DEFAULT_WIDTH: u16 = 80;
+const DEFAULT_HEIGHT: u16 = 40;
+
+/// Replay a session log as described by `cli`, either printing frames to
+/// stdout or opening the interactive viewer.
+pub fn run_replay(cli: ReplayCli) -> io::Result<()> {
+    let contents = std::fs::read_to_string(&cli.log).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("failed to read {}: {err}", cli.log.display()),
+        )
+    })?;
+    let log = SessionLog::parse(&contents);
+    {
+        let mut stderr = io::stderr().lock();
+        for warning in &log.warnings {
+            writeln!(stderr, "replay: {warning}")?;
+        }
+    }
+    if cli.interactive {
+        run_interactive(log, &cli)
+    } else {
+        run_headless(log, &cli)
+    }
+}
+
+fn run_headless(log: SessionLog, cli: &ReplayCli) -> io::Result<()> {
+    let width = cli.width.unwrap_or(DEFAULT_WIDTH).max(1);
+    let height = cli.height.unwrap_or(DEFAULT_HEIGHT).max(1);
+    let total = log.steps.len();
+    let end = cli.until.map_or(total, |until| until.min(total));
+    let mut replay = Replay::new(log, width)?;
+    let mut stdout = io::stdout().lock();
+    if cli.frames {
+        while replay.position() < end {
+            replay.step();
+            writeln!(
+                stdout,
+                "── step {}/{total}: {} ──",
+                replay.position(),
+                replay.current_label()
+            )?;
+            writeln!(stdout, "{}", replay.render_text(height)?)?;
+        }
+    } else {
+        replay.seek(end);
+        writeln!(stdout, "{}", replay.render_text(height)?)?;
+    }
+    stdout.flush()
+}
+
+fn run_interactive(log: SessionLog, cli: &ReplayCli) -> io::Result<()> {
+    let (cols, rows) = crossterm::terminal::size()?;
+    let width = cli.width.unwrap_or(cols).max(1);
+    let mut replay = Replay::new(log, width)?;
+    replay.seek(cli.until.unwrap_or(0));
+
+    crossterm::terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+    let result = interactive_loop(&mut replay, cli, rows);
+    let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    let _ = crossterm::terminal::disable_raw_mode();
+    result
+}
+
+fn interactive_loop(replay: &mut Replay, cli: &ReplayCli, mut rows: u16) -> io::Result<()> {
+    loop {
+        // The bottom row of the real terminal is reserved for the status bar.
+        let height = cli.height.unwrap_or(rows.saturating_sub(1)).max(1);
+        draw_interactive(replay, height, rows)?;
+        match crossterm::event::read()? {
+            crossterm::event::Event::Key(key)
+                if matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) =>
+            {
+                let end = replay.len();
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    }
+                    KeyCode::Right | KeyCode::Char('n' | 'l' | ' ') => {
+                        replay.step();
+                    }
+                    KeyCode::Left | KeyCode::Char('p' | 'h') => {
+                        replay.seek(replay.position().saturating_sub(1));
+                    }
+                    KeyCode::Char(']') => replay.seek(replay.next_user_turn().unwrap_or(end)),
+                    KeyCode::Char('[') => replay.seek(replay.previous_user_turn().unwrap_or(0)),
+                    KeyCode::Home | KeyCode::Char('g') => replay.seek(0),
+                    KeyCode::End | KeyCode::Char('G') => replay.seek(end),
+                    _ => {}
+                }
+            }
+            crossterm::event::Event::Resize(new_cols, new_rows) => {
+                rows = new_rows;
+                if cli.width.is_none() {
+                    replay.set_width(new_cols.max(1));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw_interactive(replay: &Replay, height: u16, rows: u16) -> io::Result<()> {
+    let terminal = replay.render(height)?;
+    let screen = terminal.backend().vt100().screen();
+    let status = format!(
+        " step {}/{} · {}  │  ←/→ step  [/] turn  g/G start/end  q quit",
+        replay.position(),
+        replay.len(),
+        replay.current_label()
+    );
+
+    let mut stdout = io::stdout().lock();
+    queue!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
+    stdout.write_all(&screen.contents_formatted())?;
+    queue!(
+        stdout,
+        MoveTo(0, rows.saturating_sub(1)),
+        Clear(ClearType::CurrentLine),
+        SetAttribute(Attribute::Reverse),
+        Print(status),
+        SetAttribute(Attribute::Reset),
+    )?;
+    stdout.flush()
+}
+
+/// A parsed session log: the replayable steps plus anything that was skipped.
+pub(crate) struct SessionLog {
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    steps: Vec<ReplayStep>,
+    warnings: Vec<String>,
+}
+
+#[derive(Clone)]
+struct ReplayStep {
+    label: String,
+    action: StepAction,
+}
+
+#[derive(Clone)]
+enum StepAction {
+    Event(Box<Event>),
+    CommitTick,
+    /// A prompt the user submitted. Live sessions add the prompt cell on
+    /// submit rather than from an event, so it is rebuilt from the `Op`.
+    UserPrompt(String),
+    /// The user answered an approval prompt; the modal is dismissed.
+    ApprovalDecision,
+}
+
+impl SessionLog {
+    /// Parse JSONL records. Lines that cannot be replayed (unknown record
+    /// kinds, events from incompatible versions) are skipped with a warning
+    /// rather than aborting the whole replay.
+    pub(crate) fn parse(contents: &str) -> Self {
+        let mut log = SessionLog {
+            cwd: None,
+            model: None,
+            steps: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for (idx, line) in contents.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let record: serde_json::Value = match serde_json::from_str(line) {
+                Ok(record) => record,
+                Err(err) => {
+                    log.warnings
+                        .push(format!("line {line_no}: invalid JSON ({err})"));
+                    continue;
+                }
+            };
+            let dir = record.get("dir").and_then(serde_json::Value::as_str);
+            let kind = record.get("kind").and_then(serde_json::Value::as_str);
+            match (dir, kind) {
+                (Some("meta"), Some("session_start")) => {
+                    log.cwd = record
+                        .get("cwd")
+                        .and_then(serde_json::Value::as_str)
+                        .map(PathBuf::from);
+                    log.model = record
+                        .get("model")
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::to_string);
+                }
+                (Some("to_tui"), Some("codex_event")) => {
+                    let Some(payload) = record.get("payload") else {
+                        continue;
+                    };
+                    match parse_event(payload.clone()) {
+                        Ok(step) => log.steps.push(step),
+                        Err(err) => log
+                            .warnings
+                            .push(format!("line {line_no}: skipped event ({err})")),
+                    }
+                }
+                (Some("to_tui"), Some("app_event")) => {
+                    if record.get("variant").and_then(serde_json::Value::as_str)
+                        == Some("CommitTick")
+                    {
+                        log.steps.push(ReplayStep {
+                            label: "commit_tick".to_string(),
+                            action: StepAction::CommitTick,
+                        });
+                    }
+                }
+                (Some("from_tui"), Some("op")) => {
+                    let Some(payload) = record.get("payload") else {
+                        continue;
+                    };
+                    if let Ok(op) = serde_json::from_value::<Op>(payload.clone())
+                        && let Some(step) = op_step(op)
+                    {
+                        log.steps.push(step);
+                    }
+                }
+                _ => {}
+            }
+        }
+        log
+    }
+}
+
+fn parse_event(payload: serde_json::Value) -> Result<ReplayStep, serde_json::Error> {
+    let payload = upgrade_event_payload(payload);
+    let label = payload
+        .get("msg")
+        .and_then(|msg| msg.get("type"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("codex_event")
+        .to_string();
+    let mut event: Event = serde_json::from_value(payload)?;
+    // Older logs did not record `parsed_cmd`; derive it the same way core does
+    // so exec cells render with their summarized form.
+    if let EventMsg::ExecCommandBegin(begin) = &mut event.msg
+        && begin.parsed_cmd.is_empty()
+    {
+        begin.parsed_cmd = codex_core::parse_command::parse_command(&begin.command);
+    }
+    Ok(ReplayStep {
+        label,
+        action: StepAction::Event(Box::new(event)),
+    })
+}
+
+fn op_step(op: Op) -> Option<ReplayStep> {
+    match op {
+        Op::UserInput { items } | Op::UserTurn { items, .. } => {
+            let text = items
+                .into_iter()
+                .filter_map(|item| match item {
+                    UserInput::Text { text } => Some(text),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(ReplayStep {
+                label: "user_input".to_string(),
+                action: StepAction::UserPrompt(text),
+            })
+        }
+        Op::ExecApproval { .. } | Op::PatchApproval { .. } => Some(ReplayStep {
+            label: "approval_decision".to_string(),
+            action: StepAction::ApprovalDecision,
+        }),
+        _ => None,
+    }
+}
+
+/// Fill in fields that older session logs predate so their events still
+/// deserialize: `formatted_output`/`aggregated_output` on `exec_command_end`
+/// and `parsed_cmd` on exec begin/approval events.
+pub(crate) fn upgrade_event_payload(mut payload: serde_json::Value) -> serde_json::Value {
+    if let Some(obj) = payload.as_object_mut()
+        && let Some(msg) = obj.get_mut("msg")
+        && let Some(m) = msg.as_object_mut()
+    {
+        let ty = m.get("type").and_then(|v| v.as_str()).unwrap_or("");
+        if ty == "exec_command_end" {
+            let stdout = m.get("stdout").and_then(|v| v.as_str()).unwrap_or("");
+            let stderr = m.get("stderr").and_then(|v| v.as_str()).unwrap_or("");
+            let aggregated = if stderr.is_empty() {
+                stdout.to_string()
+            } else {
+                format!("{stdout}{stderr}")
+            };
+            if !m.contains_key("formatted_output") {
+                m.insert(
+                    "formatted_output".to_string(),
+                    serde_json::Value::String(aggregated.clone()),
+                );
+            }
+            if !m.contains_key("aggregated_output") {
+                m.insert(
+                    "aggregated_output".to_string(),
+                    serde_json::Value::String(aggregated),
+                );
+            }
+        } else if ty == "exec_command_begin" || ty == "exec_approval_request" {
+            let needs_parsed_cmd = m
+                .get("parsed_cmd")
+                .map(|value| !matches!(value, serde_json::Value::Array(_)))
+                .unwrap_or(true);
+            if needs_parsed_cmd {
+                m.insert(
+                    "parsed_cmd".to_string(),
+                    serde_json::Value::Array(Vec::new()),
+                );
+            }
+        }
+    }
+    payload
+}
+
+/// A `ChatWidget` driven by a session log, positioned after some prefix of
+/// its steps. Seeking backwards replays from the start.
+pub(crate) struct Replay {
+    config: Config,
+    auth_manager: Arc<AuthManager>,
+    steps: Vec<ReplayStep>,
+    width: u16,
+    position: usize,
+    chat: ChatWidget,
+    app_event_rx: UnboundedReceiver<AppEvent>,
+    op_rx: UnboundedReceiver<Op>,
+    history: Vec<Line<'static>>,
+}
+
+impl Replay {
+    pub(crate) fn new(log: SessionLog, width: u16) -> io::Result<Self> {
+        // Base defaults rather than the user's config.toml so a log renders
+        // the same on every machine; only the recorded cwd and model apply.
+        settings::init_global(Settings::default());
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides {
+                model: log.model,
+                cwd: log.cwd,
+                ..Default::default()
+            },
+            find_codex_home()?,
+        )?;
+        let auth_manager = AuthManager::shared(
+            config.codex_home.clone(),
+            false,
+            config.cli_auth_credentials_store_mode,
+        );
+        let (chat, app_event_rx, op_rx) = new_widget(&config, &auth_manager);
+        Ok(Self {
+            config,
+            auth_manager,
+            steps: log.steps,
+            width,
+            position: 0,
+            chat,
+            app_event_rx,
+            op_rx,
+            history: Vec::new(),
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Number of steps applied so far.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Label of the most recently applied step.
+    pub(crate) fn current_label(&self) -> &str {
+        self.position
+            .checked_sub(1)
+            .and_then(|idx| self.steps.get(idx))
+            .map_or("start", |step| step.label.as_str())
+    }
+
+    /// Apply the next step. Returns `false` at the end of the log.
+    pub(crate) fn step(&mut self) -> bool {
+        let Some(step) = self.steps.get(self.position) else {
+            return false;
+        };
+        match step.action.clone() {
+            StepAction::Event(event) => self.chat.handle_codex_event(*event),
+            StepAction::CommitTick => self.chat.on_commit_tick(),
+            StepAction::UserPrompt(text) => self.chat.add_replayed_user_prompt(text),
+            StepAction::ApprovalDecision => self.chat.pop_bottom_view(),
+        }
+        self.position += 1;
+        self.drain_events();
+        true
+    }
+
+    /// Move to the state after exactly `target` steps (clamped to the log).
+    pub(crate) fn seek(&mut self, target: usize) {
+        let target = target.min(self.steps.len());
+        if target < self.position {
+            self.reset();
+        }
+        while self.position < target && self.step() {}
+    }
+
+    /// Re-render history at a new width, keeping the current position.
+    pub(crate) fn set_width(&mut self, width: u16) {
+        if width == self.width {
+            return;
+        }
+        self.width = width;
+        let position = self.position;
+        self.reset();
+        self.seek(position);
+    }
+
+    /// Position just after the next user prompt, if any.
+    pub(crate) fn next_user_turn(&self) -> Option<usize> {
+        self.steps
+            .iter()
+            .enumerate()
+            .skip(self.position)
+            .find(|(_, step)| matches!(step.action, StepAction::UserPrompt(_)))
+            .map(|(idx, _)| idx + 1)
+    }
+
+    /// Position just after the user prompt preceding the current one, if any.
+    pub(crate) fn previous_user_turn(&self) -> Option<usize> {
+        self.steps
+            .iter()
+            .enumerate()
+            .take(self.position.saturating_sub(1))
+            .rev()
+            .find(|(_, step)| matches!(step.action, StepAction::UserPrompt(_)))
+            .map(|(idx, _)| idx + 1)
+    }
+
+    /// Render the current state into a fresh emulated terminal of
+    /// `self.width` x `height`: history above, the live viewport below.
+    pub(crate) fn render(&self, height: u16) -> io::Result<Terminal<VT100Backend>> {
+        let width = self.width;
+        let ui_height = self.chat.desired_height(width).min(height);
+        let mut terminal = Terminal::with_options(VT100Backend::new(width, height))?;
+        terminal.set_viewport_area(Rect::new(0, height - ui_height, width, ui_height));
+        crate::insert_history::insert_history_lines(&mut terminal, self.history.clone())?;
+        terminal.draw(|frame| {
+            let area = frame.area();
+            self.chat.render(area, frame.buffer_mut());
+            if let Some((x, y)) = self.chat.cursor_pos(area) {
+                frame.set_cursor_position((x, y));
+            }
+        })?;
+        Ok(terminal)
+    }
+
+    /// Plain-text contents of [`Replay::render`], trailing blanks trimmed.
+    pub(crate) fn render_text(&self, height: u16) -> io::Result<String> {
+        let terminal = self.render(height)?;
+        let contents = terminal.backend().vt100().screen().contents();
+        Ok(contents
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_string())
+    }
+
+    fn reset(&mut self) {
+        let (chat, app_event_rx, op_rx) = new_widget(&self.config, &self.auth_manager);
+        self.chat = chat;
+        self.app_event_rx = app_event_rx;
+        self.op_rx = op_rx;
+        self.history.clear();
+        self.position = 0;
+    }
+
+    fn drain_events(&mut self) {
+        while let Ok(event) = self.app_event_rx.try_recv() {
+            if let AppEvent::InsertHistoryCell(cell) = event {
+                let mut lines = cell.display_lines(self.width);
+                if !self.history.is_empty() && !cell.is_stream_continuation() && !lines.is_empty() {
+                    lines.insert(0, Line::from(""));
+                }
+                self.history.extend(lines);
+            }
+        }
+        // Ops the widget submits (e.g. follow-ups to replayed events) have no
+        // agent to go to.
+        while self.op_rx.try_recv().is_ok() {}
+    }
+}
+
+fn new_widget(
+    config: &Config,
+    auth_manager: &Arc<AuthManager>,
+) -> (
+    ChatWidget,
+    UnboundedReceiver<AppEvent>,
+    UnboundedReceiver<Op>,
+) {
+    let (app_event_tx, app_event_rx) = unbounded_channel();
+    let (chat, op_rx) = ChatWidget::new_detached(ChatWidgetInit {
+        config: config.clone(),
+        frame_requester: FrameRequester::detached(),
+        app_event_tx: AppEventSender::new(app_event_tx),
+        initial_prompt: None,
+        initial_images: Vec::new(),
+        enhanced_keys_supported: false,
+        auth_manager: Arc::clone(auth_manager),
+        feedback: codex_feedback::CodexFeedback::new(),
+    });
+    (chat, app_event_rx, op_rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn log_line(dir: &str, kind: &str, payload: serde_json::Value) -> String {
+        serde_json::json!({
+            "ts": "2025-01-01T00:00:00.000Z",
+            "dir": dir,
+            "kind": kind,
+            "payload": payload,
+        })
+        .to_string()
+    }
+
+    fn sample_log() -> String {
+        [
+            log_line(
+                "from_tui",
+                "op",
+                serde_json::json!({
+                    "type": "user_input",
+                    "items": [{ "type": "text", "text": "list the files" }],
+                }),
+            ),
+            log_line(
+                "to_tui",
+                "codex_event",
+                serde_json::json!({
+                    "id": "1",
+                    "msg": { "type": "agent_message", "message": "Here are the files." },
+                }),
+            ),
+            "not json".to_string(),
+            log_line("to_tui", "file_search_start", serde_json::json!({})),
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn parse_keeps_replayable_records_and_reports_bad_lines() {
+        let log = SessionLog::parse(&sample_log());
+        let labels: Vec<&str> = log.steps.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["user_input", "agent_message"]);
+        assert_eq!(log.warnings.len(), 1);
+        assert!(log.warnings[0].starts_with("line 3:"));
+    }
+
+    #[test]
+    fn replay_renders_prompt_and_reply_and_seeks_back() {
+        let mut replay = Replay::new(SessionLog::parse(&sample_log()), 60).expect("replay");
+        replay.seek(replay.len());
+        let end = replay.render_text(20).expect("render");
+        assert!(end.contains("list the files"), "{end}");
+        assert!(end.contains("Here are the files."), "{end}");
+
+        replay.seek(1);
+        let after_prompt = replay.render_text(20).expect("render");
+        assert!(after_prompt.contains("list the files"), "{after_prompt}");
+        assert!(
+            !after_prompt.contains("Here are the files."),
+            "{after_prompt}"
+        );
+
+        replay.seek(replay.len());
+        assert_eq!(replay.render_text(20).expect("render"), end);
+    }
+}
@@ -28,6 +28,7 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::key_hint;
 use crate::text_formatting::truncate_text;
+use crate::theme::ThemeStylize as _;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -40,6 +41,19 @@ const LOAD_NEAR_THRESHOLD: usize = 5;
 pub enum ResumeSelection {
     StartFresh,
     Resume(PathBuf),
+    /// Resume the session at `path` and open the transcript at user turn
+    /// `turn` (1-based).
+    ResumeAtTurn {
+        path: PathBuf,
+        turn: usize,
+    },
+    /// Branch a new session from user turn `turn` (1-based) of the recorded
+    /// session at `path`.
+    Fork {
+        path: PathBuf,
+        turn: usize,
+        restore_snapshot: bool,
+    },
     Exit,
 }
 
@@ -658,7 +672,7 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
 
         // Header
         frame.render_widget_ref(
-            Line::from(vec!["Resume a previous session".bold().cyan()]),
+            Line::from(vec!["Resume a previous session".bold().accent()]),
             header,
         );
 
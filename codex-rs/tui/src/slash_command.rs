@@ -55,7 +55,7 @@ impl SlashCommand {
                 "run semantic code search and adjust the confidence threshold"
             }
             SlashCommand::MemorySuggest => "list stored memories related to the current question",
-            SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Diff => "browse git diff (including untracked files)",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Model => "choose what model and reasoning effort to use",
@@ -2,6 +2,7 @@ use crate::history_cell::CompositeHistoryCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::with_border_with_inner_width;
+use crate::theme::ThemeStylize;
 use crate::version::CODEX_CLI_VERSION;
 use chrono::DateTime;
 use chrono::Local;
@@ -9,6 +10,7 @@ use codex_common::create_config_summary_entries;
 use codex_core::config::Config;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
+use codex_core::protocol::UsageCost;
 use codex_protocol::ConversationId;
 use ratatui::prelude::*;
 use ratatui::style::Stylize;
@@ -62,6 +64,7 @@ struct StatusHistoryCell {
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
+    cost: Option<UsageCost>,
     rate_limits: StatusRateLimitData,
 }
 
@@ -71,15 +74,17 @@ pub(crate) fn new_status_output(
     context_usage: Option<&TokenUsage>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
+    cost: Option<&UsageCost>,
     now: DateTime<Local>,
 ) -> CompositeHistoryCell {
-    let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
+    let command = PlainHistoryCell::new(vec!["/status".brand().into()]);
     let card = StatusHistoryCell::new(
         config,
         total_usage,
         context_usage,
         session_id,
         rate_limits,
+        cost,
         now,
     );
 
@@ -93,6 +98,7 @@ impl StatusHistoryCell {
         context_usage: Option<&TokenUsage>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
+        cost: Option<&UsageCost>,
         now: DateTime<Local>,
     ) -> Self {
         let config_entries = create_config_summary_entries(config);
@@ -137,6 +143,7 @@ impl StatusHistoryCell {
             account,
             session_id,
             token_usage,
+            cost: cost.copied(),
             rate_limits,
             prompt_summary,
         }
@@ -160,6 +167,17 @@ impl StatusHistoryCell {
         ]
     }
 
+    fn cost_spans(&self) -> Option<Vec<Span<'static>>> {
+        let cost = self.cost.as_ref()?;
+        Some(vec![
+            Span::from(format!("${:.2}", cost.session_usd)),
+            Span::from(" this session").dim(),
+            Span::from(" · ").dim(),
+            Span::from(format!("${:.2}", cost.daily_usd)),
+            Span::from(" today").dim(),
+        ])
+    }
+
     fn context_window_spans(&self) -> Option<Vec<Span<'static>>> {
         let context = self.token_usage.context_window.as_ref()?;
         let percent = context.percent_remaining;
@@ -313,6 +331,9 @@ impl HistoryCell for StatusHistoryCell {
             push_label(&mut labels, &mut seen, "Session");
         }
         push_label(&mut labels, &mut seen, "Token usage");
+        if self.cost.is_some() {
+            push_label(&mut labels, &mut seen, "Cost");
+        }
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
@@ -322,12 +343,12 @@ impl HistoryCell for StatusHistoryCell {
         let value_width = formatter.value_width(available_inner_width);
 
         let note_first_line = Line::from(vec![
-            Span::from("Visit ").cyan(),
-            "chatgpt.com/codex/settings/usage".cyan().underlined(),
-            Span::from(" for up-to-date").cyan(),
+            Span::from("Visit ").accent(),
+            "chatgpt.com/codex/settings/usage".accent().underlined(),
+            Span::from(" for up-to-date").accent(),
         ]);
         let note_second_line = Line::from(vec![
-            Span::from("information on rate limits and credits").cyan(),
+            Span::from("information on rate limits and credits").accent(),
         ]);
         let note_lines = word_wrap_lines(
             [note_first_line, note_second_line],
@@ -369,6 +390,9 @@ impl HistoryCell for StatusHistoryCell {
         // Hide token usage only for ChatGPT subscribers
         if !matches!(self.account, Some(StatusAccountDisplay::ChatGpt { .. })) {
             lines.push(formatter.line("Token usage", self.token_usage_spans()));
+            if let Some(spans) = self.cost_spans() {
+                lines.push(formatter.line("Cost", spans));
+            }
         }
 
         if let Some(spans) = self.context_window_spans() {
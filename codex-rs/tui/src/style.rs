@@ -2,11 +2,15 @@ use crate::color::blend;
 use crate::color::is_light;
 use crate::terminal_palette::best_color;
 use crate::terminal_palette::default_bg;
+use crate::theme::theme;
 use ratatui::style::Color;
 use ratatui::style::Style;
 
 pub fn user_message_style() -> Style {
-    user_message_style_for(default_bg())
+    match theme().user_message_bg {
+        Some(bg) => Style::default().bg(bg),
+        None => user_message_style_for(default_bg()),
+    }
 }
 
 /// Returns the style for a user-authored message using the provided terminal background.
@@ -0,0 +1,489 @@
+//! Named color themes and the semantic roles widgets style text with.
+//!
+//! Widgets do not pick ANSI colors directly. They style text with a role
+//! from [`ThemeStylize`] (`accent`, `success`, `error`, `warning`, `brand`,
+//! `muted`, or [`ThemeStylize::themed`] for the markdown roles) and the active
+//! [`Theme`] decides what each role looks like. The built-in themes are
+//! `dark`, `light` and `high-contrast`; users can add their own as TOML files
+//! in `$CODEX_HOME/themes/`. With the default `auto` setting the theme follows
+//! the terminal background reported by [`default_bg`], so it switches when
+//! that is re-queried on focus.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::sync::OnceLock;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Styled;
+use serde::Deserialize;
+
+use crate::color::is_light;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
+
+/// Directory under `$CODEX_HOME` holding user theme files.
+pub(crate) const THEMES_DIR: &str = "themes";
+
+const AUTO: &str = "auto";
+
+static SELECTION: OnceLock<Selection> = OnceLock::new();
+static DARK: LazyLock<Theme> = LazyLock::new(Theme::dark);
+static LIGHT: LazyLock<Theme> = LazyLock::new(Theme::light);
+
+#[cfg(test)]
+thread_local! {
+    static TEST_THEME: std::cell::Cell<Option<&'static Theme>> =
+        const { std::cell::Cell::new(None) };
+}
+
+/// The style of each semantic role. See `tui/styles.md` for when to use which.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Theme {
+    pub(crate) name: String,
+    /// Whether the theme is designed for a light terminal background. Code
+    /// highlighting picks its palette from this.
+    pub(crate) light: bool,
+    /// User input tips, selection, and status indicators.
+    pub(crate) accent: Style,
+    /// Success and additions.
+    pub(crate) success: Style,
+    /// Errors, failures and deletions.
+    pub(crate) error: Style,
+    /// Warnings that do not stop the turn.
+    pub(crate) warning: Style,
+    /// Codex itself.
+    pub(crate) brand: Style,
+    /// Text that should recede but stay readable, such as reasoning.
+    pub(crate) muted: Style,
+    /// Markdown block quotes.
+    pub(crate) quote: Style,
+    /// Markdown ordered-list numbers.
+    pub(crate) list_marker: Style,
+    /// Background behind user messages. `None` derives one from the terminal
+    /// background.
+    pub(crate) user_message_bg: Option<Color>,
+}
+
+impl Theme {
+    pub(crate) const BUILTIN: [&'static str; 3] = ["dark", "light", "high-contrast"];
+
+    pub(crate) fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            light: false,
+            accent: Style::new().fg(Color::Cyan),
+            success: Style::new().fg(Color::Green),
+            error: Style::new().fg(Color::Red),
+            warning: Style::new().fg(Color::Yellow),
+            brand: Style::new().fg(Color::Magenta),
+            muted: Style::new().fg(Color::Gray).add_modifier(Modifier::DIM),
+            quote: Style::new().fg(Color::Green),
+            list_marker: Style::new().fg(Color::LightBlue),
+            user_message_bg: None,
+        }
+    }
+
+    /// ANSI cyan, yellow and the light colors wash out on light backgrounds,
+    /// so accents use blue, warnings magenta, and muted text dark gray
+    /// without dimming.
+    pub(crate) fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            light: true,
+            accent: Style::new().fg(Color::Blue),
+            success: Style::new().fg(Color::Green),
+            error: Style::new().fg(Color::Red),
+            warning: Style::new().fg(Color::Magenta),
+            brand: Style::new().fg(Color::Magenta),
+            muted: Style::new().fg(Color::DarkGray),
+            quote: Style::new().fg(Color::Green),
+            list_marker: Style::new().fg(Color::Blue),
+            user_message_bg: None,
+        }
+    }
+
+    /// Bright, bold colors and no dimming, for dark backgrounds.
+    pub(crate) fn high_contrast() -> Self {
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        Self {
+            name: "high-contrast".to_string(),
+            light: false,
+            accent: bold.fg(Color::LightCyan),
+            success: bold.fg(Color::LightGreen),
+            error: bold.fg(Color::LightRed),
+            warning: bold.fg(Color::LightYellow),
+            brand: bold.fg(Color::LightMagenta),
+            muted: Style::new(),
+            quote: Style::new().fg(Color::LightGreen),
+            list_marker: bold.fg(Color::LightBlue),
+            user_message_bg: None,
+        }
+    }
+
+    pub(crate) fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Reads `<dir>/<name>.toml`. Roles the file leaves out come from its
+    /// `base` theme (default `dark`).
+    pub(crate) fn load(name: &str, dir: &Path) -> Result<Self, String> {
+        let path = dir.join(format!("{name}.toml"));
+        let contents =
+            fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let file: ThemeFile =
+            toml::from_str(&contents).map_err(|err| format!("{}: {err}", path.display()))?;
+        file.into_theme(name)
+            .map_err(|err| format!("{}: {err}", path.display()))
+    }
+}
+
+/// On-disk theme format. Each role is a color (`"blue"`, `"light-red"`,
+/// `"#d70000"`) or a table such as `{ fg = "red", bold = true }`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    light: Option<bool>,
+    accent: Option<RoleSpec>,
+    success: Option<RoleSpec>,
+    error: Option<RoleSpec>,
+    warning: Option<RoleSpec>,
+    brand: Option<RoleSpec>,
+    muted: Option<RoleSpec>,
+    quote: Option<RoleSpec>,
+    list_marker: Option<RoleSpec>,
+    user_message_bg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RoleSpec {
+    Color(String),
+    Style(StyleSpec),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleSpec {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    dim: bool,
+    #[serde(default)]
+    underlined: bool,
+}
+
+impl ThemeFile {
+    fn into_theme(self, name: &str) -> Result<Theme, String> {
+        let base = self.base.as_deref().unwrap_or("dark");
+        let mut theme = Theme::builtin(base).ok_or_else(|| {
+            format!(
+                "unknown base theme `{base}` (expected one of {})",
+                Theme::BUILTIN.join(", ")
+            )
+        })?;
+        theme.name = name.to_string();
+        if let Some(light) = self.light {
+            theme.light = light;
+        }
+        for (slot, spec) in [
+            (&mut theme.accent, self.accent),
+            (&mut theme.success, self.success),
+            (&mut theme.error, self.error),
+            (&mut theme.warning, self.warning),
+            (&mut theme.brand, self.brand),
+            (&mut theme.muted, self.muted),
+            (&mut theme.quote, self.quote),
+            (&mut theme.list_marker, self.list_marker),
+        ] {
+            if let Some(spec) = spec {
+                *slot = spec.to_style()?;
+            }
+        }
+        if let Some(bg) = self.user_message_bg {
+            theme.user_message_bg = Some(parse_color(&bg)?);
+        }
+        Ok(theme)
+    }
+}
+
+impl RoleSpec {
+    fn to_style(&self) -> Result<Style, String> {
+        match self {
+            Self::Color(color) => Ok(Style::new().fg(parse_color(color)?)),
+            Self::Style(spec) => {
+                let mut style = Style::new();
+                if let Some(fg) = &spec.fg {
+                    style = style.fg(parse_color(fg)?);
+                }
+                if let Some(bg) = &spec.bg {
+                    style = style.bg(parse_color(bg)?);
+                }
+                for (enabled, modifier) in [
+                    (spec.bold, Modifier::BOLD),
+                    (spec.italic, Modifier::ITALIC),
+                    (spec.dim, Modifier::DIM),
+                    (spec.underlined, Modifier::UNDERLINED),
+                ] {
+                    if enabled {
+                        style = style.add_modifier(modifier);
+                    }
+                }
+                Ok(style)
+            }
+        }
+    }
+}
+
+/// Parses an ANSI color name or `#rrggbb`. Hex colors are approximated to
+/// what the terminal can display.
+fn parse_color(spec: &str) -> Result<Color, String> {
+    if let Some(hex) = spec.strip_prefix('#') {
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        };
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(best_color((r, g, b))),
+            _ => Err(format!("invalid color `{spec}` (expected #rrggbb)")),
+        };
+    }
+    Color::from_str(spec).map_err(|_| format!("unknown color `{spec}`"))
+}
+
+/// The configured theme; `Auto` follows the terminal background.
+enum Selection {
+    Auto,
+    Fixed(Box<Theme>),
+}
+
+impl Selection {
+    fn from_config(name: Option<&str>, dir: &Path) -> (Self, Vec<String>) {
+        let name = name.unwrap_or(AUTO);
+        if name == AUTO {
+            return (Self::Auto, Vec::new());
+        }
+        if let Some(theme) = Theme::builtin(name) {
+            return (Self::Fixed(Box::new(theme)), Vec::new());
+        }
+        match Theme::load(name, dir) {
+            Ok(theme) => (Self::Fixed(Box::new(theme)), Vec::new()),
+            Err(err) => (
+                Self::Auto,
+                vec![format!(
+                    "Could not load theme `{name}` from [tui].theme, using `auto`: {err}."
+                )],
+            ),
+        }
+    }
+}
+
+/// Installs the theme named by `tui.theme` for the rest of the process and
+/// returns any problems found while loading it. Must run before the first
+/// frame is drawn.
+pub(crate) fn install(name: Option<&str>, codex_home: &Path) -> Vec<String> {
+    let (selection, problems) = Selection::from_config(name, &codex_home.join(THEMES_DIR));
+    let _ = SELECTION.set(selection);
+    problems
+}
+
+/// The active theme, or the `auto` choice when none was installed (tests).
+pub(crate) fn theme() -> &'static Theme {
+    #[cfg(test)]
+    if let Some(theme) = TEST_THEME.with(std::cell::Cell::get) {
+        return theme;
+    }
+    match SELECTION.get_or_init(|| Selection::Auto) {
+        Selection::Auto if default_bg().is_some_and(is_light) => &LIGHT,
+        Selection::Auto => &DARK,
+        Selection::Fixed(theme) => theme,
+    }
+}
+
+/// Renders `f` with `theme` active on this thread.
+#[cfg(test)]
+pub(crate) fn with_theme<R>(theme: Theme, f: impl FnOnce() -> R) -> R {
+    let theme: &'static Theme = Box::leak(Box::new(theme));
+    let previous = TEST_THEME.with(|cell| cell.replace(Some(theme)));
+    let result = f();
+    TEST_THEME.with(|cell| cell.set(previous));
+    result
+}
+
+/// Styles anything ratatui can style with a role from the active theme, in
+/// the same chaining form as [`ratatui::style::Stylize`]:
+/// `"Error".bold().error()`.
+pub(crate) trait ThemeStylize: Styled + Sized {
+    fn accent(self) -> Self::Item {
+        self.themed(theme().accent)
+    }
+
+    fn success(self) -> Self::Item {
+        self.themed(theme().success)
+    }
+
+    fn error(self) -> Self::Item {
+        self.themed(theme().error)
+    }
+
+    fn warning(self) -> Self::Item {
+        self.themed(theme().warning)
+    }
+
+    fn brand(self) -> Self::Item {
+        self.themed(theme().brand)
+    }
+
+    fn muted(self) -> Self::Item {
+        self.themed(theme().muted)
+    }
+
+    fn themed(self, role: Style) -> Self::Item {
+        let style = self.style().patch(role);
+        self.set_style(style)
+    }
+}
+
+impl<T: Styled> ThemeStylize for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::Stylize;
+    use ratatui::text::Line;
+    use ratatui::text::Span;
+    use ratatui::widgets::Paragraph;
+    use ratatui::widgets::Widget;
+
+    /// Every role, styled through [`ThemeStylize`] as widgets do.
+    fn render_swatch(theme_under_test: Theme) -> String {
+        with_theme(theme_under_test, || {
+            let lines: Vec<Line<'static>> = vec![
+                vec![
+                    "accent ".accent(),
+                    "success ".success(),
+                    "error ".error(),
+                    "warning ".warning(),
+                    "brand".brand(),
+                ]
+                .into(),
+                vec![
+                    "muted ".muted(),
+                    "> quote ".themed(theme().quote),
+                    "1. ".themed(theme().list_marker),
+                    "plain".into(),
+                ]
+                .into(),
+            ];
+            let area = Rect::new(0, 0, 40, 2);
+            let mut buf = Buffer::empty(area);
+            Paragraph::new(lines).render(area, &mut buf);
+            format!("{buf:?}")
+        })
+    }
+
+    #[test]
+    fn dark_theme_snapshot() {
+        assert_snapshot!(render_swatch(Theme::dark()));
+    }
+
+    #[test]
+    fn light_theme_snapshot() {
+        assert_snapshot!(render_swatch(Theme::light()));
+    }
+
+    #[test]
+    fn high_contrast_theme_snapshot() {
+        assert_snapshot!(render_swatch(Theme::high_contrast()));
+    }
+
+    fn write_theme(dir: &Path, name: &str, contents: &str) {
+        fs::write(dir.join(format!("{name}.toml")), contents).expect("write theme");
+    }
+
+    #[test]
+    fn theme_file_overrides_roles_on_top_of_base() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_theme(
+            dir.path(),
+            "solar",
+            r#"
+base = "light"
+accent = "magenta"
+error = { fg = "light-red", bold = true }
+user_message_bg = "gray"
+"#,
+        );
+
+        let theme = Theme::load("solar", dir.path()).expect("theme loads");
+
+        assert_eq!(
+            theme,
+            Theme {
+                name: "solar".to_string(),
+                accent: Style::new().fg(Color::Magenta),
+                error: Style::new()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+                user_message_bg: Some(Color::Gray),
+                ..Theme::light()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_theme_falls_back_to_auto_with_a_problem() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_theme(dir.path(), "typo", "accent = \"not-a-color\"\n");
+
+        let (selection, problems) = Selection::from_config(Some("typo"), dir.path());
+        assert!(matches!(selection, Selection::Auto));
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("unknown color `not-a-color`"),
+            "{problems:?}"
+        );
+
+        let (selection, problems) = Selection::from_config(Some("missing"), dir.path());
+        assert!(matches!(selection, Selection::Auto));
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn builtin_names_win_over_theme_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_theme(dir.path(), "light", "accent = \"red\"\n");
+
+        let (selection, problems) = Selection::from_config(Some("light"), dir.path());
+        assert!(problems.is_empty());
+        assert!(matches!(selection, Selection::Fixed(theme) if *theme == Theme::light()));
+    }
+
+    #[test]
+    fn roles_patch_existing_styles() {
+        let span = with_theme(Theme::high_contrast(), || Span::from("x").italic().error());
+        assert_eq!(
+            span.style,
+            Style::new()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+    }
+}
@@ -0,0 +1,472 @@
+//! Incremental `/`-style search over the transcript overlay.
+//!
+//! Matches are found in the plain text of each cell's `transcript_lines` at
+//! the overlay's current width, so a match's `(cell, line)` lines up with
+//! what the pager draws. The pattern is always compiled as a regex; literal
+//! queries are escaped first and case-insensitive ones get an `(?i)` prefix.
+
+use std::sync::Arc;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use regex_lite::Regex;
+
+use crate::history_cell::HistoryCell;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
+use crate::theme::ThemeStylize;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct SearchMatch {
+    /// Index into the transcript cells.
+    pub(crate) cell: usize,
+    /// Line within the cell's `transcript_lines`.
+    pub(crate) line: usize,
+    /// Byte range within that line's text.
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// A match range to paint within one line of a cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct LineHighlight {
+    pub(crate) line: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) current: bool,
+}
+
+/// What a key did while the query was being edited.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum SearchInput {
+    /// The query or a toggle changed; matches need recomputing.
+    Changed,
+    /// Editing finished, keeping the query.
+    Committed,
+    /// Editing was abandoned and the search cleared.
+    Cancelled,
+    Ignored,
+}
+
+#[derive(Default)]
+pub(crate) struct TranscriptSearch {
+    query: String,
+    editing: bool,
+    regex: bool,
+    case_sensitive: bool,
+    pattern: Option<Regex>,
+    error: Option<String>,
+    matches: Vec<SearchMatch>,
+    current: Option<usize>,
+}
+
+impl TranscriptSearch {
+    pub(crate) fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// True while there is a query to show, whether or not it is being edited.
+    pub(crate) fn is_active(&self) -> bool {
+        self.editing || !self.query.is_empty()
+    }
+
+    pub(crate) fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub(crate) fn is_regex(&self) -> bool {
+        self.regex
+    }
+
+    pub(crate) fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub(crate) fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub(crate) fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// 0-based index of the current match.
+    pub(crate) fn current_index(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub(crate) fn current_match(&self) -> Option<SearchMatch> {
+        self.current.and_then(|idx| self.matches.get(idx).copied())
+    }
+
+    /// Begin editing a new query; the previous one is kept as a starting point.
+    pub(crate) fn start_editing(&mut self) {
+        self.editing = true;
+    }
+
+    pub(crate) fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.compile();
+    }
+
+    pub(crate) fn toggle_case(&mut self) {
+        self.case_sensitive = !self.case_sensitive;
+        self.compile();
+    }
+
+    /// Route a key pressed while editing the query.
+    pub(crate) fn handle_input(&mut self, key: KeyEvent) -> SearchInput {
+        let keymap = keymap();
+        if keymap.matches(KeyAction::ToggleSearchRegex, key) {
+            self.toggle_regex();
+            return SearchInput::Changed;
+        }
+        if keymap.matches(KeyAction::ToggleSearchCase, key) {
+            self.toggle_case();
+            return SearchInput::Changed;
+        }
+        match key.code {
+            KeyCode::Enter => {
+                self.editing = false;
+                SearchInput::Committed
+            }
+            KeyCode::Esc => {
+                self.clear();
+                SearchInput::Cancelled
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.clear();
+                SearchInput::Cancelled
+            }
+            KeyCode::Backspace if self.query.is_empty() => {
+                self.clear();
+                SearchInput::Cancelled
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.compile();
+                SearchInput::Changed
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.compile();
+                SearchInput::Changed
+            }
+            _ => SearchInput::Ignored,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.query.clear();
+        self.editing = false;
+        self.pattern = None;
+        self.error = None;
+        self.matches.clear();
+        self.current = None;
+    }
+
+    fn compile(&mut self) {
+        self.pattern = None;
+        self.error = None;
+        if self.query.is_empty() {
+            return;
+        }
+        let mut pattern = if self.regex {
+            self.query.clone()
+        } else {
+            regex_lite::escape(&self.query)
+        };
+        if !self.case_sensitive {
+            pattern.insert_str(0, "(?i)");
+        }
+        match Regex::new(&pattern) {
+            Ok(regex) => self.pattern = Some(regex),
+            Err(err) => {
+                let message = err.to_string();
+                self.error = Some(
+                    message
+                        .lines()
+                        .last()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                );
+            }
+        }
+    }
+
+    /// Recompute matches against `cells` rendered at `width`, keeping the
+    /// current match on the same position when it still exists.
+    pub(crate) fn refresh(&mut self, cells: &[Arc<dyn HistoryCell>], width: u16) {
+        let previous = self.current_match();
+        self.matches.clear();
+        self.current = None;
+        let Some(pattern) = &self.pattern else {
+            return;
+        };
+        for (cell_idx, cell) in cells.iter().enumerate() {
+            for (line_idx, line) in cell.transcript_lines(width).iter().enumerate() {
+                let text = line_text(line);
+                for found in pattern.find_iter(&text) {
+                    if found.start() == found.end() {
+                        continue;
+                    }
+                    self.matches.push(SearchMatch {
+                        cell: cell_idx,
+                        line: line_idx,
+                        start: found.start(),
+                        end: found.end(),
+                    });
+                }
+            }
+        }
+        if let Some(previous) = previous {
+            self.current = self.matches.iter().position(|m| *m == previous);
+        }
+    }
+
+    /// Make the first match accepted by `at_or_after` current, wrapping to
+    /// the first match overall.
+    pub(crate) fn select_first_where(&mut self, at_or_after: impl Fn(&SearchMatch) -> bool) {
+        if self.matches.is_empty() {
+            self.current = None;
+            return;
+        }
+        self.current = Some(self.matches.iter().position(at_or_after).unwrap_or(0));
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        self.current = Some(match self.current {
+            Some(idx) => (idx + 1) % self.matches.len(),
+            None => 0,
+        });
+    }
+
+    pub(crate) fn select_previous(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() - 1;
+        self.current = Some(match self.current {
+            Some(0) | None => last,
+            Some(idx) => idx - 1,
+        });
+    }
+
+    /// Ranges to paint in `cell`, in line order.
+    pub(crate) fn highlights_for_cell(&self, cell: usize) -> Vec<LineHighlight> {
+        let current = self.current_match();
+        self.matches
+            .iter()
+            .filter(|m| m.cell == cell)
+            .map(|m| LineHighlight {
+                line: m.line,
+                start: m.start,
+                end: m.end,
+                current: Some(*m) == current,
+            })
+            .collect()
+    }
+}
+
+fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// Paint `highlights` onto `lines`, splitting spans at match boundaries.
+pub(crate) fn apply_highlights(lines: &mut [Line<'static>], highlights: &[LineHighlight]) {
+    for highlight in highlights {
+        let Some(line) = lines.get_mut(highlight.line) else {
+            continue;
+        };
+        let style = if highlight.current {
+            Style::new().accent().bold().reversed()
+        } else {
+            Style::new().reversed()
+        };
+        let spans = std::mem::take(&mut line.spans);
+        line.spans = highlight_spans(spans, highlight.start, highlight.end, style);
+    }
+}
+
+fn highlight_spans(
+    spans: Vec<Span<'static>>,
+    start: usize,
+    end: usize,
+    style: Style,
+) -> Vec<Span<'static>> {
+    let mut out = Vec::with_capacity(spans.len() + 2);
+    let mut offset = 0;
+    for span in spans {
+        let len = span.content.len();
+        let (span_start, span_end) = (offset, offset + len);
+        offset = span_end;
+        if span_end <= start || span_start >= end {
+            out.push(span);
+            continue;
+        }
+        let content = span.content.as_ref();
+        let from = start.saturating_sub(span_start).min(len);
+        let to = end.saturating_sub(span_start).min(len);
+        if from > 0 {
+            out.push(Span::styled(content[..from].to_string(), span.style));
+        }
+        out.push(Span::styled(
+            content[from..to].to_string(),
+            span.style.patch(style),
+        ));
+        if to < len {
+            out.push(Span::styled(content[to..].to_string(), span.style));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug)]
+    struct TextCell(Vec<&'static str>);
+
+    impl HistoryCell for TextCell {
+        fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+            self.0.iter().map(|line| Line::from(*line)).collect()
+        }
+    }
+
+    fn cells() -> Vec<Arc<dyn HistoryCell>> {
+        vec![
+            Arc::new(TextCell(vec!["ran cargo test", "Cargo.toml updated"])),
+            Arc::new(TextCell(vec!["see src/lib.rs:10"])),
+        ]
+    }
+
+    fn type_query(search: &mut TranscriptSearch, query: &str) {
+        search.start_editing();
+        for c in query.chars() {
+            search.handle_input(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn literal_search_is_case_insensitive_by_default() {
+        let mut search = TranscriptSearch::default();
+        type_query(&mut search, "cargo");
+        search.refresh(&cells(), 80);
+        assert_eq!(search.match_count(), 2);
+
+        search.toggle_case();
+        search.refresh(&cells(), 80);
+        assert_eq!(
+            search.matches,
+            vec![SearchMatch {
+                cell: 0,
+                line: 0,
+                start: 4,
+                end: 9,
+            }]
+        );
+    }
+
+    #[test]
+    fn regex_toggle_and_invalid_patterns() {
+        let mut search = TranscriptSearch::default();
+        type_query(&mut search, r"\w+\.rs");
+        search.refresh(&cells(), 80);
+        assert_eq!(search.match_count(), 0);
+
+        search.toggle_regex();
+        search.refresh(&cells(), 80);
+        assert_eq!(search.match_count(), 1);
+        assert_eq!(search.current_match(), None);
+
+        type_query(&mut search, "(");
+        search.refresh(&cells(), 80);
+        assert!(search.error().is_some());
+        assert_eq!(search.match_count(), 0);
+    }
+
+    #[test]
+    fn navigation_wraps_and_esc_clears() {
+        let mut search = TranscriptSearch::default();
+        type_query(&mut search, "r");
+        search.refresh(&cells(), 80);
+        let count = search.match_count();
+        assert!(count > 2);
+
+        search.select_first_where(|m| m.cell >= 1);
+        assert_eq!(search.current_match().map(|m| m.cell), Some(1));
+        search.select_previous();
+        assert_eq!(search.current_match().map(|m| m.cell), Some(0));
+        search.select_first_where(|_| false);
+        search.select_previous();
+        assert_eq!(search.current_index(), Some(count - 1));
+        search.select_next();
+        assert_eq!(search.current_index(), Some(0));
+
+        assert_eq!(
+            search.handle_input(KeyEvent::from(KeyCode::Enter)),
+            SearchInput::Committed
+        );
+        assert!(search.is_active() && !search.is_editing());
+        search.start_editing();
+        assert_eq!(
+            search.handle_input(KeyEvent::from(KeyCode::Esc)),
+            SearchInput::Cancelled
+        );
+        assert!(!search.is_active());
+    }
+
+    #[test]
+    fn highlights_split_spans_at_match_boundaries() {
+        let mut lines = vec![Line::from(vec!["ab".into(), "cd".dim(), "ef".into()])];
+        apply_highlights(
+            &mut lines,
+            &[LineHighlight {
+                line: 0,
+                start: 1,
+                end: 5,
+                current: false,
+            }],
+        );
+        let parts: Vec<(&str, bool)> = lines[0]
+            .spans
+            .iter()
+            .map(|span| {
+                (
+                    span.content.as_ref(),
+                    span.style
+                        .add_modifier
+                        .contains(ratatui::style::Modifier::REVERSED),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("a", false),
+                ("b", true),
+                ("cd", true),
+                ("e", true),
+                ("f", false),
+            ]
+        );
+    }
+}
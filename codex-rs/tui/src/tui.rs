@@ -10,6 +10,8 @@ use std::sync::atomic::AtomicU8;
 #[cfg(unix)]
 use std::sync::atomic::AtomicU16;
 use std::sync::atomic::Ordering;
+use std::task::Poll;
+use std::task::Waker;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -26,6 +28,8 @@ use crossterm::event::KeyEvent;
 use crossterm::event::KeyboardEnhancementFlags;
 use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
+use crossterm::terminal::Clear;
+use crossterm::terminal::ClearType;
 use crossterm::terminal::EnterAlternateScreen;
 use crossterm::terminal::LeaveAlternateScreen;
 use crossterm::terminal::supports_keyboard_enhancement;
@@ -166,6 +170,7 @@ pub struct Tui {
     // True when terminal/tab is focused; updated internally from crossterm events
     terminal_focused: Arc<AtomicBool>,
     enhanced_keys_supported: bool,
+    terminal_input: TerminalInput,
 }
 
 #[cfg(unix)]
@@ -192,6 +197,58 @@ fn take_resume_action(pending: &AtomicU8) -> ResumeAction {
     }
 }
 
+/// Key, paste and focus events from the terminal. Crossterm's `EventStream`
+/// reads stdin on a background thread for as long as it exists, so it is
+/// dropped while a child process owns the terminal and recreated afterwards.
+#[derive(Clone, Default)]
+struct TerminalInput {
+    state: Arc<std::sync::Mutex<TerminalInputState>>,
+}
+
+#[derive(Default)]
+struct TerminalInputState {
+    events: Option<crossterm::event::EventStream>,
+    paused: bool,
+    waker: Option<Waker>,
+}
+
+impl TerminalInput {
+    async fn next(&self) -> Option<std::io::Result<Event>> {
+        std::future::poll_fn(|cx| {
+            let Ok(mut state) = self.state.lock() else {
+                return Poll::Ready(None);
+            };
+            if state.paused {
+                state.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            Pin::new(
+                state
+                    .events
+                    .get_or_insert_with(crossterm::event::EventStream::new),
+            )
+            .poll_next(cx)
+        })
+        .await
+    }
+
+    fn pause(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.paused = true;
+            state.events = None;
+        }
+    }
+
+    fn resume(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.paused = false;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct FrameRequester {
     frame_schedule_tx: tokio::sync::mpsc::UnboundedSender<Instant>,
@@ -203,16 +260,22 @@ impl FrameRequester {
     pub fn schedule_frame_in(&self, dur: Duration) {
         let _ = self.frame_schedule_tx.send(Instant::now() + dur);
     }
+
+    /// Create a frame requester whose requests are dropped, for callers that
+    /// draw on their own schedule (e.g. session replay).
+    pub(crate) fn detached() -> Self {
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        FrameRequester {
+            frame_schedule_tx: tx,
+        }
+    }
 }
 
 #[cfg(test)]
 impl FrameRequester {
     /// Create a no-op frame requester for tests.
     pub(crate) fn test_dummy() -> Self {
-        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
-        FrameRequester {
-            frame_schedule_tx: tx,
-        }
+        Self::detached()
     }
 }
 
@@ -292,6 +355,7 @@ impl Tui {
             alt_screen_active: Arc::new(AtomicBool::new(false)),
             terminal_focused: Arc::new(AtomicBool::new(true)),
             enhanced_keys_supported,
+            terminal_input: TerminalInput::default(),
         }
     }
 
@@ -306,8 +370,7 @@ impl Tui {
     }
 
     pub fn event_stream(&self) -> Pin<Box<dyn Stream<Item = TuiEvent> + Send + 'static>> {
-        use tokio_stream::StreamExt;
-        let mut crossterm_events = crossterm::event::EventStream::new();
+        let terminal_input = self.terminal_input.clone();
         let mut draw_rx = self.draw_tx.subscribe();
         #[cfg(unix)]
         let resume_pending = self.resume_pending.clone();
@@ -319,7 +382,7 @@ impl Tui {
         let event_stream = async_stream::stream! {
             loop {
                 select! {
-                    Some(Ok(event)) = crossterm_events.next() => {
+                    Some(Ok(event)) = terminal_input.next() => {
                         match event {
                             crossterm::event::Event::Key(key_event) => {
                                 #[cfg(unix)]
@@ -480,6 +543,48 @@ impl Tui {
         Ok(())
     }
 
+    /// Hand the terminal to an interactive child process (such as `$EDITOR`)
+    /// and take it back, redrawing from scratch, once the process exits.
+    pub fn run_interactive_command(
+        &mut self,
+        command: &mut std::process::Command,
+    ) -> Result<std::process::ExitStatus> {
+        let alt_screen_active = self.alt_screen_active.load(Ordering::Relaxed);
+        if alt_screen_active {
+            let _ = execute!(self.terminal.backend_mut(), DisableAlternateScroll);
+        }
+        restore()?;
+        // Stop reading stdin so keystrokes reach the child, not the TUI.
+        self.terminal_input.pause();
+        let status = command.status();
+        self.terminal_input.resume();
+        set_modes()?;
+        if alt_screen_active {
+            // Editors usually leave the alternate screen on exit; re-enter it.
+            let _ = execute!(self.terminal.backend_mut(), EnterAlternateScreen);
+            let _ = execute!(self.terminal.backend_mut(), EnableAlternateScroll);
+        }
+        self.terminal.clear()?;
+        status
+    }
+
+    /// Wipe the screen and the terminal's scrollback and move the inline
+    /// viewport back to the top, so history can be re-inserted from scratch.
+    pub fn clear_history(&mut self) -> Result<()> {
+        self.pending_history_lines.clear();
+        execute!(
+            self.terminal.backend_mut(),
+            Clear(ClearType::Purge),
+            Clear(ClearType::All)
+        )?;
+        let mut area = self.terminal.viewport_area;
+        area.y = 0;
+        self.terminal.set_viewport_area(area);
+        self.terminal.clear()?;
+        self.frame_requester().schedule_frame();
+        Ok(())
+    }
+
     pub fn insert_history_lines(&mut self, lines: Vec<Line<'static>>) {
         self.pending_history_lines.extend(lines);
         self.frame_requester().schedule_frame();
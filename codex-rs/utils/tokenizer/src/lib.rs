@@ -1,4 +1,6 @@
 use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Error as AnyhowError;
@@ -30,6 +32,12 @@ pub enum TokenizerError {
         #[source]
         source: AnyhowError,
     },
+    #[error("failed to load tokenizer from {}", path.display())]
+    LoadFile {
+        path: PathBuf,
+        #[source]
+        source: AnyhowError,
+    },
     #[error("failed to decode tokens")]
     Decode {
         #[source]
@@ -37,10 +45,17 @@ pub enum TokenizerError {
     },
 }
 
-/// Thin wrapper around a `tiktoken_rs::CoreBPE` tokenizer.
+/// Thin wrapper around a `tiktoken_rs::CoreBPE` tokenizer, or a Hugging Face
+/// tokenizer for models that do not use a tiktoken encoding.
 #[derive(Clone)]
 pub struct Tokenizer {
-    inner: CoreBPE,
+    inner: Inner,
+}
+
+#[derive(Clone)]
+enum Inner {
+    Tiktoken(CoreBPE),
+    HuggingFace(Box<tokenizers::Tokenizer>),
 }
 
 impl Tokenizer {
@@ -52,7 +67,21 @@ impl Tokenizer {
         };
 
         let inner = loader().map_err(|source| TokenizerError::LoadEncoding { kind, source })?;
-        Ok(Self { inner })
+        Ok(Self {
+            inner: Inner::Tiktoken(inner),
+        })
+    }
+
+    /// Load a Hugging Face `tokenizer.json` file.
+    pub fn from_file(path: &Path) -> Result<Self, TokenizerError> {
+        let inner =
+            tokenizers::Tokenizer::from_file(path).map_err(|source| TokenizerError::LoadFile {
+                path: path.to_path_buf(),
+                source: anyhow::anyhow!(source),
+            })?;
+        Ok(Self {
+            inner: Inner::HuggingFace(Box::new(inner)),
+        })
     }
 
     /// Default to `O200kBase`
@@ -64,7 +93,9 @@ impl Tokenizer {
     /// Falls back to the `O200kBase` encoding when the model is unknown.
     pub fn for_model(model: &str) -> Result<Self, TokenizerError> {
         match tiktoken_rs::get_bpe_from_model(model) {
-            Ok(inner) => Ok(Self { inner }),
+            Ok(inner) => Ok(Self {
+                inner: Inner::Tiktoken(inner),
+            }),
             Err(model_error) => {
                 let inner = tiktoken_rs::o200k_base()
                     .with_context(|| {
@@ -74,7 +105,9 @@ impl Tokenizer {
                         kind: EncodingKind::O200kBase,
                         source,
                     })?;
-                Ok(Self { inner })
+                Ok(Self {
+                    inner: Inner::Tiktoken(inner),
+                })
             }
         }
     }
@@ -83,10 +116,13 @@ impl Tokenizer {
     /// tokens are allowed and may appear in the result.
     #[must_use]
     pub fn encode(&self, text: &str, with_special_tokens: bool) -> Vec<i32> {
-        let raw = if with_special_tokens {
-            self.inner.encode_with_special_tokens(text)
-        } else {
-            self.inner.encode_ordinary(text)
+        let raw = match &self.inner {
+            Inner::Tiktoken(bpe) if with_special_tokens => bpe.encode_with_special_tokens(text),
+            Inner::Tiktoken(bpe) => bpe.encode_ordinary(text),
+            Inner::HuggingFace(tokenizer) => tokenizer
+                .encode(text, with_special_tokens)
+                .map(|encoding| encoding.get_ids().to_vec())
+                .unwrap_or_default(),
         };
         raw.into_iter().map(|t| t as i32).collect()
     }
@@ -94,16 +130,34 @@ impl Tokenizer {
     /// Count tokens in `text` as a signed integer.
     #[must_use]
     pub fn count(&self, text: &str) -> i64 {
+        let len = match &self.inner {
+            Inner::Tiktoken(bpe) => bpe.encode_ordinary(text).len(),
+            Inner::HuggingFace(tokenizer) => match tokenizer.encode(text, false) {
+                Ok(encoding) => encoding.len(),
+                // Roughly four bytes per token when the tokenizer rejects
+                // the input.
+                Err(_) => text.len().div_ceil(4),
+            },
+        };
         // Signed length to satisfy our style preference.
-        i64::try_from(self.inner.encode_ordinary(text).len()).unwrap_or(i64::MAX)
+        i64::try_from(len).unwrap_or(i64::MAX)
     }
 
     /// Decode token IDs back to text.
     pub fn decode(&self, tokens: &[i32]) -> Result<String, TokenizerError> {
         let raw: Vec<u32> = tokens.iter().map(|t| *t as u32).collect();
-        self.inner
-            .decode(raw)
-            .map_err(|source| TokenizerError::Decode { source })
+        match &self.inner {
+            Inner::Tiktoken(bpe) => bpe
+                .decode(raw)
+                .map_err(|source| TokenizerError::Decode { source }),
+            Inner::HuggingFace(tokenizer) => {
+                tokenizer
+                    .decode(&raw, false)
+                    .map_err(|source| TokenizerError::Decode {
+                        source: anyhow::anyhow!(source),
+                    })
+            }
+        }
     }
 }
 
@@ -150,6 +204,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn loads_hugging_face_tokenizer_json() -> Result<(), TokenizerError> {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("tokenizer.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "version": "1.0",
+                "truncation": null,
+                "padding": null,
+                "added_tokens": [],
+                "normalizer": null,
+                "pre_tokenizer": { "type": "Whitespace" },
+                "post_processor": null,
+                "decoder": null,
+                "model": {
+                    "type": "WordLevel",
+                    "vocab": { "[UNK]": 0, "hello": 1, "world": 2 },
+                    "unk_token": "[UNK]"
+                }
+            }"#,
+        )
+        .expect("write tokenizer.json");
+
+        let tok = Tokenizer::from_file(&path)?;
+        assert_eq!(tok.encode("hello world again", false), vec![1, 2, 0]);
+        assert_eq!(tok.count("hello world again"), 3);
+
+        let missing = Tokenizer::from_file(&dir.path().join("missing.json"));
+        assert!(matches!(missing, Err(TokenizerError::LoadFile { .. })));
+        Ok(())
+    }
+
     #[test]
     fn unknown_model_defaults_to_o200k_base() -> Result<(), TokenizerError> {
         let fallback = Tokenizer::new(EncodingKind::O200kBase)?;
@@ -25,6 +25,8 @@ export type Usage = {
   cached_input_tokens: number;
   /** The number of output tokens used during the turn. */
   output_tokens: number;
+  /** Estimated cost of the session so far, in USD, when the model is priced. */
+  cost_usd?: number;
 };
 
 /** Emitted when a turn is completed. Typically right after the assistant's response. */
@@ -68,6 +70,25 @@ export type ThreadErrorEvent = {
   message: string;
 };
 
+/** One way in which the final message fails `--output-schema`. */
+export type OutputSchemaViolation = {
+  /** Location of the offending value, e.g. `$.items[2].name`. */
+  path: string;
+  message: string;
+};
+
+/** The final message did not match `--output-schema`. */
+export type OutputSchemaInvalidEvent = {
+  type: "output_schema.invalid";
+  /** 1-based attempt whose final message failed validation. */
+  attempt: number;
+  /** Attempts allowed in total: the original turn plus repair turns. */
+  max_attempts: number;
+  /** Whether a repair turn is being sent. When false, exec exits with status 3. */
+  will_retry: boolean;
+  violations: OutputSchemaViolation[];
+};
+
 /** Top-level JSONL events emitted by codex exec. */
 export type ThreadEvent =
   | ThreadStartedEvent
@@ -77,4 +98,5 @@ export type ThreadEvent =
   | ItemStartedEvent
   | ItemUpdatedEvent
   | ItemCompletedEvent
-  | ThreadErrorEvent;
+  | ThreadErrorEvent
+  | OutputSchemaInvalidEvent;
@@ -9,6 +9,8 @@ export type {
   ItemCompletedEvent,
   ThreadError,
   ThreadErrorEvent,
+  OutputSchemaInvalidEvent,
+  OutputSchemaViolation,
   Usage,
 } from "./events";
 export type {